@@ -19,6 +19,11 @@ typedef struct wire_cst_list_prim_u_8_strict {
   int32_t len;
 } wire_cst_list_prim_u_8_strict;
 
+typedef struct wire_cst_list_String {
+  struct wire_cst_list_prim_u_8_strict **ptr;
+  int32_t len;
+} wire_cst_list_String;
+
 typedef struct wire_cst_AttestationIdentity_Fixed {
   struct wire_cst_list_prim_u_8_strict *id;
 } wire_cst_AttestationIdentity_Fixed;
@@ -224,11 +229,18 @@ typedef struct wire_cst_WalletEvent_Disclosure {
 typedef struct wire_cst_WalletEvent_Issuance {
   struct wire_cst_list_prim_u_8_strict *date_time;
   struct wire_cst_attestation *attestation;
+  bool renewed;
 } wire_cst_WalletEvent_Issuance;
 
+typedef struct wire_cst_WalletEvent_Deletion {
+  struct wire_cst_list_prim_u_8_strict *date_time;
+  struct wire_cst_attestation *attestation;
+} wire_cst_WalletEvent_Deletion;
+
 typedef union WalletEventKind {
   struct wire_cst_WalletEvent_Disclosure Disclosure;
   struct wire_cst_WalletEvent_Issuance Issuance;
+  struct wire_cst_WalletEvent_Deletion Deletion;
 } WalletEventKind;
 
 typedef struct wire_cst_wallet_event {
@@ -356,6 +368,12 @@ void frbgen_wallet_core_wire__crate__api__full__continue_pid_issuance(int64_t po
 
 void frbgen_wallet_core_wire__crate__api__full__create_pid_issuance_redirect_uri(int64_t port_);
 
+void frbgen_wallet_core_wire__crate__api__full__get_account_provider_state(int64_t port_);
+
+void frbgen_wallet_core_wire__crate__api__full__get_config_environments(int64_t port_);
+
+void frbgen_wallet_core_wire__crate__api__full__get_current_config_environment(int64_t port_);
+
 void frbgen_wallet_core_wire__crate__api__full__get_history(int64_t port_);
 
 void frbgen_wallet_core_wire__crate__api__full__get_history_for_card(int64_t port_,
@@ -388,6 +406,9 @@ void frbgen_wallet_core_wire__crate__api__full__register(int64_t port_,
 
 void frbgen_wallet_core_wire__crate__api__full__reset_wallet(int64_t port_);
 
+void frbgen_wallet_core_wire__crate__api__full__select_config_environment(int64_t port_,
+                                                                          struct wire_cst_list_prim_u_8_strict *environment);
+
 void frbgen_wallet_core_wire__crate__api__full__set_attestations_stream(int64_t port_,
                                                                         struct wire_cst_list_prim_u_8_strict *sink);
 
@@ -414,6 +435,8 @@ void frbgen_wallet_core_wire__crate__api__full__unlock_wallet(int64_t port_,
 
 void frbgen_wallet_core_wire__crate__api__full__unlock_wallet_with_biometrics(int64_t port_);
 
+void frbgen_wallet_core_wire__crate__api__full__verify_attested_key_continuity(int64_t port_);
+
 struct wire_cst_attestation *frbgen_wallet_core_cst_new_box_autoadd_attestation(void);
 
 struct wire_cst_image *frbgen_wallet_core_cst_new_box_autoadd_image(void);
@@ -430,6 +453,8 @@ uint64_t *frbgen_wallet_core_cst_new_box_autoadd_u_64(uint64_t value);
 
 struct wire_cst_wallet_instruction_error *frbgen_wallet_core_cst_new_box_autoadd_wallet_instruction_error(void);
 
+struct wire_cst_list_String *frbgen_wallet_core_cst_new_list_String(int32_t len);
+
 struct wire_cst_list_attestation *frbgen_wallet_core_cst_new_list_attestation(int32_t len);
 
 struct wire_cst_list_attestation_attribute *frbgen_wallet_core_cst_new_list_attestation_attribute(int32_t len);
@@ -455,6 +480,7 @@ static int64_t dummy_method_to_enforce_bundling(void) {
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_box_autoadd_request_policy);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_box_autoadd_u_64);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_box_autoadd_wallet_instruction_error);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_list_String);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_list_attestation);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_list_attestation_attribute);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_cst_new_list_disclosure_card);
@@ -477,6 +503,9 @@ static int64_t dummy_method_to_enforce_bundling(void) {
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__continue_change_pin);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__continue_pid_issuance);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__create_pid_issuance_redirect_uri);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_account_provider_state);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_config_environments);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_current_config_environment);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_history);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_history_for_card);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__get_version_string);
@@ -491,6 +520,7 @@ static int64_t dummy_method_to_enforce_bundling(void) {
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__lock_wallet);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__register);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__reset_wallet);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__select_config_environment);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__set_attestations_stream);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__set_biometric_unlock);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__set_configuration_stream);
@@ -500,6 +530,7 @@ static int64_t dummy_method_to_enforce_bundling(void) {
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__start_disclosure);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__unlock_wallet);
     dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__unlock_wallet_with_biometrics);
+    dummy_var ^= ((int64_t) (void*) frbgen_wallet_core_wire__crate__api__full__verify_attested_key_continuity);
     dummy_var ^= ((int64_t) (void*) store_dart_post_cobject);
     return dummy_var;
 }
@@ -2,6 +2,7 @@ part of './wallet_event.dart';
 
 class IssuanceEvent extends WalletEvent {
   final WalletCard card;
+  final IssuanceEventType eventType;
 
   @override
   List<DataAttribute> get attributes => card.attributes;
@@ -10,8 +11,11 @@ class IssuanceEvent extends WalletEvent {
     required super.dateTime,
     required super.status,
     required this.card,
+    this.eventType = IssuanceEventType.cardIssued,
   });
 
   @override
-  List<Object?> get props => [dateTime, status, card];
+  List<Object?> get props => [dateTime, status, card, eventType];
 }
+
+enum IssuanceEventType { cardIssued, cardRenewed, cardRemoved }
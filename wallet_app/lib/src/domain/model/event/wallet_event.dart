@@ -38,6 +38,7 @@ sealed class WalletEvent extends Equatable {
     required DateTime dateTime,
     required EventStatus status,
     required WalletCard card,
+    IssuanceEventType eventType,
   }) = IssuanceEvent;
 
   const factory WalletEvent.sign({
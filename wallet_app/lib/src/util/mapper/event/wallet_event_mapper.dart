@@ -46,6 +46,16 @@ class WalletEventMapper extends Mapper<core.WalletEvent, WalletEvent> {
           dateTime: DateTime.parse(issuance.dateTime).toLocal(),
           status: EventStatus.success,
           card: card,
+          eventType: issuance.renewed ? IssuanceEventType.cardRenewed : IssuanceEventType.cardIssued,
+        );
+      },
+      deletion: (deletion) {
+        final card = _cardMapper.map(deletion.attestation);
+        return WalletEvent.issuance(
+          dateTime: DateTime.parse(deletion.dateTime).toLocal(),
+          status: EventStatus.success,
+          card: card,
+          eventType: IssuanceEventType.cardRemoved,
         );
       },
     );
@@ -38,10 +38,13 @@ class WalletEventStatusDescriptionMapper extends ContextMapper<WalletEvent, Stri
   }
 
   String mapIssuanceEvent(BuildContext context, IssuanceEvent event) {
-    return context.l10n.historyDetailScreenIssuanceSuccessDescription;
-    // In the future, I imagine we re-introduce renewal/expiry through separate events.
-    // For reference keeping the correct translations here:
-    // renewal --> context.l10n.historyDetailScreenOperationStatusRenewedDescription;
+    return switch (event.eventType) {
+      IssuanceEventType.cardIssued => context.l10n.historyDetailScreenIssuanceSuccessDescription,
+      IssuanceEventType.cardRenewed => context.l10n.historyDetailScreenOperationStatusRenewedDescription,
+      IssuanceEventType.cardRemoved => context.l10n.historyDetailScreenOperationStatusRemovedDescription,
+    };
+    // In the future, I imagine we re-introduce expiry through a separate event.
+    // For reference keeping the correct translation here:
     // expiry --> context.l10n.historyDetailScreenOperationStatusExpiredDescription;
   }
 
@@ -41,10 +41,13 @@ class WalletEventStatusTextMapper extends ContextMapper<WalletEvent, String> {
   }
 
   String mapIssuanceEvent(BuildContext context, IssuanceEvent event) {
-    return context.l10n.cardHistoryIssuanceSuccess;
-    // In the future, I imagine we re-introduce renewal/expiry through separate events.
-    // For reference keeping the correct translations here:
-    // renewal --> context.l10n.cardHistoryTimelineOperationRenewed;
+    return switch (event.eventType) {
+      IssuanceEventType.cardIssued => context.l10n.cardHistoryIssuanceSuccess,
+      IssuanceEventType.cardRenewed => context.l10n.cardHistoryTimelineOperationRenewed,
+      IssuanceEventType.cardRemoved => context.l10n.cardHistoryTimelineOperationRemoved,
+    };
+    // In the future, I imagine we re-introduce expiry through a separate event.
+    // For reference keeping the correct translation here:
     // expiry --> context.l10n.cardHistoryTimelineOperationExpired;
   }
 
@@ -5,7 +5,9 @@ import 'src/api/full.dart' as core;
 
 export 'src/api/full.dart';
 export 'src/frb_generated.dart';
+export 'src/models/account_provider.dart';
 export 'src/models/attestation.dart';
+export 'src/models/attested_key.dart';
 export 'src/models/config.dart';
 export 'src/models/disclosure.dart';
 export 'src/models/instruction.dart';
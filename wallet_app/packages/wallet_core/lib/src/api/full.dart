@@ -4,7 +4,9 @@
 // ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
 
 import '../frb_generated.dart';
+import '../models/account_provider.dart';
 import '../models/attestation.dart';
+import '../models/attested_key.dart';
 import '../models/config.dart';
 import '../models/disclosure.dart';
 import '../models/instruction.dart';
@@ -58,8 +60,14 @@ Future<WalletInstructionResult> continueChangePin({required String pin}) =>
 
 Future<bool> hasRegistration() => WalletCore.instance.api.crateApiFullHasRegistration();
 
+Future<FlutterAccountProviderState> getAccountProviderState() =>
+    WalletCore.instance.api.crateApiFullGetAccountProviderState();
+
 Future<void> register({required String pin}) => WalletCore.instance.api.crateApiFullRegister(pin: pin);
 
+Future<FlutterAttestedKeyContinuity> verifyAttestedKeyContinuity() =>
+    WalletCore.instance.api.crateApiFullVerifyAttestedKeyContinuity();
+
 Future<IdentifyUriResult> identifyUri({required String uri}) =>
     WalletCore.instance.api.crateApiFullIdentifyUri(uri: uri);
 
@@ -100,3 +108,10 @@ Future<List<WalletEvent>> getHistoryForCard({required String docType}) =>
 Future<void> resetWallet() => WalletCore.instance.api.crateApiFullResetWallet();
 
 Future<String> getVersionString() => WalletCore.instance.api.crateApiFullGetVersionString();
+
+Future<List<String>> getConfigEnvironments() => WalletCore.instance.api.crateApiFullGetConfigEnvironments();
+
+Future<String> getCurrentConfigEnvironment() => WalletCore.instance.api.crateApiFullGetCurrentConfigEnvironment();
+
+Future<void> selectConfigEnvironment({required String environment}) =>
+    WalletCore.instance.api.crateApiFullSelectConfigEnvironment(environment: environment);
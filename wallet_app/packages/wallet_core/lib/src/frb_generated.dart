@@ -8,7 +8,9 @@ import 'dart:async';
 import 'dart:convert';
 import 'frb_generated.dart';
 import 'frb_generated.io.dart' if (dart.library.js_interop) 'frb_generated.web.dart';
+import 'models/account_provider.dart';
 import 'models/attestation.dart';
+import 'models/attested_key.dart';
 import 'models/config.dart';
 import 'models/disclosure.dart';
 import 'models/instruction.dart';
@@ -73,7 +75,7 @@ class WalletCore extends BaseEntrypoint<WalletCoreApi, WalletCoreApiImpl, Wallet
   String get codegenVersion => '2.7.1';
 
   @override
-  int get rustContentHash => 1458509282;
+  int get rustContentHash => 1630211874;
 
   static const kDefaultExternalLibraryLoaderConfig = ExternalLibraryLoaderConfig(
     stem: 'wallet_core',
@@ -111,6 +113,12 @@ abstract class WalletCoreApi extends BaseApi {
 
   Future<String> crateApiFullCreatePidIssuanceRedirectUri();
 
+  Future<FlutterAccountProviderState> crateApiFullGetAccountProviderState();
+
+  Future<List<String>> crateApiFullGetConfigEnvironments();
+
+  Future<String> crateApiFullGetCurrentConfigEnvironment();
+
   Future<List<WalletEvent>> crateApiFullGetHistory();
 
   Future<List<WalletEvent>> crateApiFullGetHistoryForCard({required String docType});
@@ -139,6 +147,8 @@ abstract class WalletCoreApi extends BaseApi {
 
   Future<void> crateApiFullResetWallet();
 
+  Future<void> crateApiFullSelectConfigEnvironment({required String environment});
+
   Stream<List<Attestation>> crateApiFullSetAttestationsStream();
 
   Future<void> crateApiFullSetBiometricUnlock({required bool enable});
@@ -156,6 +166,8 @@ abstract class WalletCoreApi extends BaseApi {
   Future<WalletInstructionResult> crateApiFullUnlockWallet({required String pin});
 
   Future<void> crateApiFullUnlockWalletWithBiometrics();
+
+  Future<FlutterAttestedKeyContinuity> crateApiFullVerifyAttestedKeyContinuity();
 }
 
 class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreApi {
@@ -467,6 +479,69 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
         argNames: [],
       );
 
+  @override
+  Future<FlutterAccountProviderState> crateApiFullGetAccountProviderState() {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        return wire.wire__crate__api__full__get_account_provider_state(port_);
+      },
+      codec: DcoCodec(
+        decodeSuccessData: dco_decode_flutter_account_provider_state,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiFullGetAccountProviderStateConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFullGetAccountProviderStateConstMeta => const TaskConstMeta(
+        debugName: "get_account_provider_state",
+        argNames: [],
+      );
+
+  @override
+  Future<List<String>> crateApiFullGetConfigEnvironments() {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        return wire.wire__crate__api__full__get_config_environments(port_);
+      },
+      codec: DcoCodec(
+        decodeSuccessData: dco_decode_list_String,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiFullGetConfigEnvironmentsConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFullGetConfigEnvironmentsConstMeta => const TaskConstMeta(
+        debugName: "get_config_environments",
+        argNames: [],
+      );
+
+  @override
+  Future<String> crateApiFullGetCurrentConfigEnvironment() {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        return wire.wire__crate__api__full__get_current_config_environment(port_);
+      },
+      codec: DcoCodec(
+        decodeSuccessData: dco_decode_String,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiFullGetCurrentConfigEnvironmentConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFullGetCurrentConfigEnvironmentConstMeta => const TaskConstMeta(
+        debugName: "get_current_config_environment",
+        argNames: [],
+      );
+
   @override
   Future<List<WalletEvent>> crateApiFullGetHistory() {
     return handler.executeNormal(NormalTask(
@@ -765,6 +840,28 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
         argNames: [],
       );
 
+  @override
+  Future<void> crateApiFullSelectConfigEnvironment({required String environment}) {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        var arg0 = cst_encode_String(environment);
+        return wire.wire__crate__api__full__select_config_environment(port_, arg0);
+      },
+      codec: DcoCodec(
+        decodeSuccessData: dco_decode_unit,
+        decodeErrorData: dco_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiFullSelectConfigEnvironmentConstMeta,
+      argValues: [environment],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFullSelectConfigEnvironmentConstMeta => const TaskConstMeta(
+        debugName: "select_config_environment",
+        argNames: ["environment"],
+      );
+
   @override
   Stream<List<Attestation>> crateApiFullSetAttestationsStream() {
     final sink = RustStreamSink<List<Attestation>>();
@@ -973,6 +1070,27 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
         argNames: [],
       );
 
+  @override
+  Future<FlutterAttestedKeyContinuity> crateApiFullVerifyAttestedKeyContinuity() {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        return wire.wire__crate__api__full__verify_attested_key_continuity(port_);
+      },
+      codec: DcoCodec(
+        decodeSuccessData: dco_decode_flutter_attested_key_continuity,
+        decodeErrorData: dco_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiFullVerifyAttestedKeyContinuityConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFullVerifyAttestedKeyContinuityConstMeta => const TaskConstMeta(
+        debugName: "verify_attested_key_continuity",
+        argNames: [],
+      );
+
   @protected
   AnyhowException dco_decode_AnyhowException(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -1192,6 +1310,18 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     );
   }
 
+  @protected
+  FlutterAccountProviderState dco_decode_flutter_account_provider_state(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return FlutterAccountProviderState.values[raw as int];
+  }
+
+  @protected
+  FlutterAttestedKeyContinuity dco_decode_flutter_attested_key_continuity(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return FlutterAttestedKeyContinuity.values[raw as int];
+  }
+
   @protected
   FlutterConfiguration dco_decode_flutter_configuration(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -1268,6 +1398,12 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     }
   }
 
+  @protected
+  List<String> dco_decode_list_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_String).toList();
+  }
+
   @protected
   List<Attestation> dco_decode_list_attestation(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -1518,6 +1654,12 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
         return WalletEvent_Issuance(
           dateTime: dco_decode_String(raw[1]),
           attestation: dco_decode_box_autoadd_attestation(raw[2]),
+          renewed: dco_decode_bool(raw[3]),
+        );
+      case 2:
+        return WalletEvent_Deletion(
+          dateTime: dco_decode_String(raw[1]),
+          attestation: dco_decode_box_autoadd_attestation(raw[2]),
         );
       default:
         throw Exception("unreachable");
@@ -1777,6 +1919,20 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     return DisplayMetadata(lang: var_lang, name: var_name, description: var_description, rendering: var_rendering);
   }
 
+  @protected
+  FlutterAccountProviderState sse_decode_flutter_account_provider_state(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return FlutterAccountProviderState.values[inner];
+  }
+
+  @protected
+  FlutterAttestedKeyContinuity sse_decode_flutter_attested_key_continuity(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return FlutterAttestedKeyContinuity.values[inner];
+  }
+
   @protected
   FlutterConfiguration sse_decode_flutter_configuration(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -1853,6 +2009,18 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     }
   }
 
+  @protected
+  List<String> sse_decode_list_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <String>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_String(deserializer));
+    }
+    return ans_;
+  }
+
   @protected
   List<Attestation> sse_decode_list_attestation(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -2203,7 +2371,12 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
       case 1:
         var var_dateTime = sse_decode_String(deserializer);
         var var_attestation = sse_decode_box_autoadd_attestation(deserializer);
-        return WalletEvent_Issuance(dateTime: var_dateTime, attestation: var_attestation);
+        var var_renewed = sse_decode_bool(deserializer);
+        return WalletEvent_Issuance(dateTime: var_dateTime, attestation: var_attestation, renewed: var_renewed);
+      case 2:
+        var var_dateTime = sse_decode_String(deserializer);
+        var var_attestation = sse_decode_box_autoadd_attestation(deserializer);
+        return WalletEvent_Deletion(dateTime: var_dateTime, attestation: var_attestation);
       default:
         throw UnimplementedError('');
     }
@@ -2270,6 +2443,18 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     return cst_encode_i_32(raw.index);
   }
 
+  @protected
+  int cst_encode_flutter_account_provider_state(FlutterAccountProviderState raw) {
+    // Codec=Cst (C-struct based), see doc to use other codecs
+    return cst_encode_i_32(raw.index);
+  }
+
+  @protected
+  int cst_encode_flutter_attested_key_continuity(FlutterAttestedKeyContinuity raw) {
+    // Codec=Cst (C-struct based), see doc to use other codecs
+    return cst_encode_i_32(raw.index);
+  }
+
   @protected
   int cst_encode_i_32(int raw) {
     // Codec=Cst (C-struct based), see doc to use other codecs
@@ -2529,6 +2714,18 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     sse_encode_opt_box_autoadd_rendering_metadata(self.rendering, serializer);
   }
 
+  @protected
+  void sse_encode_flutter_account_provider_state(FlutterAccountProviderState self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
+  @protected
+  void sse_encode_flutter_attested_key_continuity(FlutterAttestedKeyContinuity self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
   @protected
   void sse_encode_flutter_configuration(FlutterConfiguration self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -2592,6 +2789,15 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
     }
   }
 
+  @protected
+  void sse_encode_list_String(List<String> self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_String(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_attestation(List<Attestation> self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -2887,10 +3093,15 @@ class WalletCoreApiImpl extends WalletCoreApiImplPlatform implements WalletCoreA
         sse_encode_box_autoadd_request_policy(requestPolicy, serializer);
         sse_encode_disclosure_status(status, serializer);
         sse_encode_disclosure_type(typ, serializer);
-      case WalletEvent_Issuance(dateTime: final dateTime, attestation: final attestation):
+      case WalletEvent_Issuance(dateTime: final dateTime, attestation: final attestation, renewed: final renewed):
         sse_encode_i_32(1, serializer);
         sse_encode_String(dateTime, serializer);
         sse_encode_box_autoadd_attestation(attestation, serializer);
+        sse_encode_bool(renewed, serializer);
+      case WalletEvent_Deletion(dateTime: final dateTime, attestation: final attestation):
+        sse_encode_i_32(2, serializer);
+        sse_encode_String(dateTime, serializer);
+        sse_encode_box_autoadd_attestation(attestation, serializer);
     }
   }
 
@@ -8,7 +8,9 @@ import 'dart:async';
 import 'dart:convert';
 import 'dart:ffi' as ffi;
 import 'frb_generated.dart';
+import 'models/account_provider.dart';
 import 'models/attestation.dart';
+import 'models/attested_key.dart';
 import 'models/config.dart';
 import 'models/disclosure.dart';
 import 'models/instruction.dart';
@@ -105,6 +107,12 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   DisplayMetadata dco_decode_display_metadata(dynamic raw);
 
+  @protected
+  FlutterAccountProviderState dco_decode_flutter_account_provider_state(dynamic raw);
+
+  @protected
+  FlutterAttestedKeyContinuity dco_decode_flutter_attested_key_continuity(dynamic raw);
+
   @protected
   FlutterConfiguration dco_decode_flutter_configuration(dynamic raw);
 
@@ -123,6 +131,9 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   Image dco_decode_image(dynamic raw);
 
+  @protected
+  List<String> dco_decode_list_String(dynamic raw);
+
   @protected
   List<Attestation> dco_decode_list_attestation(dynamic raw);
 
@@ -291,6 +302,12 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   DisplayMetadata sse_decode_display_metadata(SseDeserializer deserializer);
 
+  @protected
+  FlutterAccountProviderState sse_decode_flutter_account_provider_state(SseDeserializer deserializer);
+
+  @protected
+  FlutterAttestedKeyContinuity sse_decode_flutter_attested_key_continuity(SseDeserializer deserializer);
+
   @protected
   FlutterConfiguration sse_decode_flutter_configuration(SseDeserializer deserializer);
 
@@ -309,6 +326,9 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   Image sse_decode_image(SseDeserializer deserializer);
 
+  @protected
+  List<String> sse_decode_list_String(SseDeserializer deserializer);
+
   @protected
   List<Attestation> sse_decode_list_attestation(SseDeserializer deserializer);
 
@@ -534,6 +554,16 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
     return raw.toInt();
   }
 
+  @protected
+  ffi.Pointer<wire_cst_list_String> cst_encode_list_String(List<String> raw) {
+    // Codec=Cst (C-struct based), see doc to use other codecs
+    final ans = wire.cst_new_list_String(raw.length);
+    for (var i = 0; i < raw.length; ++i) {
+      ans.ref.ptr[i] = cst_encode_String(raw[i]);
+    }
+    return ans;
+  }
+
   @protected
   ffi.Pointer<wire_cst_list_attestation> cst_encode_list_attestation(List<Attestation> raw) {
     // Codec=Cst (C-struct based), see doc to use other codecs
@@ -970,9 +1000,19 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
     if (apiObj is WalletEvent_Issuance) {
       var pre_date_time = cst_encode_String(apiObj.dateTime);
       var pre_attestation = cst_encode_box_autoadd_attestation(apiObj.attestation);
+      var pre_renewed = cst_encode_bool(apiObj.renewed);
       wireObj.tag = 1;
       wireObj.kind.Issuance.date_time = pre_date_time;
       wireObj.kind.Issuance.attestation = pre_attestation;
+      wireObj.kind.Issuance.renewed = pre_renewed;
+      return;
+    }
+    if (apiObj is WalletEvent_Deletion) {
+      var pre_date_time = cst_encode_String(apiObj.dateTime);
+      var pre_attestation = cst_encode_box_autoadd_attestation(apiObj.attestation);
+      wireObj.tag = 2;
+      wireObj.kind.Deletion.date_time = pre_date_time;
+      wireObj.kind.Deletion.attestation = pre_attestation;
       return;
     }
   }
@@ -1027,6 +1067,12 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   int cst_encode_disclosure_type(DisclosureType raw);
 
+  @protected
+  int cst_encode_flutter_account_provider_state(FlutterAccountProviderState raw);
+
+  @protected
+  int cst_encode_flutter_attested_key_continuity(FlutterAttestedKeyContinuity raw);
+
   @protected
   int cst_encode_i_32(int raw);
 
@@ -1125,6 +1171,12 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   void sse_encode_display_metadata(DisplayMetadata self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_flutter_account_provider_state(FlutterAccountProviderState self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_flutter_attested_key_continuity(FlutterAttestedKeyContinuity self, SseSerializer serializer);
+
   @protected
   void sse_encode_flutter_configuration(FlutterConfiguration self, SseSerializer serializer);
 
@@ -1143,6 +1195,9 @@ abstract class WalletCoreApiImplPlatform extends BaseApiImpl<WalletCoreWire> {
   @protected
   void sse_encode_image(Image self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_String(List<String> self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_attestation(List<Attestation> self, SseSerializer serializer);
 
@@ -1477,6 +1532,48 @@ class WalletCoreWire implements BaseWire {
   late final _wire__crate__api__full__create_pid_issuance_redirect_uri =
       _wire__crate__api__full__create_pid_issuance_redirect_uriPtr.asFunction<void Function(int)>();
 
+  void wire__crate__api__full__get_account_provider_state(
+    int port_,
+  ) {
+    return _wire__crate__api__full__get_account_provider_state(
+      port_,
+    );
+  }
+
+  late final _wire__crate__api__full__get_account_provider_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'frbgen_wallet_core_wire__crate__api__full__get_account_provider_state');
+  late final _wire__crate__api__full__get_account_provider_state =
+      _wire__crate__api__full__get_account_provider_statePtr.asFunction<void Function(int)>();
+
+  void wire__crate__api__full__get_config_environments(
+    int port_,
+  ) {
+    return _wire__crate__api__full__get_config_environments(
+      port_,
+    );
+  }
+
+  late final _wire__crate__api__full__get_config_environmentsPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'frbgen_wallet_core_wire__crate__api__full__get_config_environments');
+  late final _wire__crate__api__full__get_config_environments =
+      _wire__crate__api__full__get_config_environmentsPtr.asFunction<void Function(int)>();
+
+  void wire__crate__api__full__get_current_config_environment(
+    int port_,
+  ) {
+    return _wire__crate__api__full__get_current_config_environment(
+      port_,
+    );
+  }
+
+  late final _wire__crate__api__full__get_current_config_environmentPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'frbgen_wallet_core_wire__crate__api__full__get_current_config_environment');
+  late final _wire__crate__api__full__get_current_config_environment =
+      _wire__crate__api__full__get_current_config_environmentPtr.asFunction<void Function(int)>();
+
   void wire__crate__api__full__get_history(
     int port_,
   ) {
@@ -1673,6 +1770,22 @@ class WalletCoreWire implements BaseWire {
   late final _wire__crate__api__full__reset_wallet =
       _wire__crate__api__full__reset_walletPtr.asFunction<void Function(int)>();
 
+  void wire__crate__api__full__select_config_environment(
+    int port_,
+    ffi.Pointer<wire_cst_list_prim_u_8_strict> environment,
+  ) {
+    return _wire__crate__api__full__select_config_environment(
+      port_,
+      environment,
+    );
+  }
+
+  late final _wire__crate__api__full__select_config_environmentPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Pointer<wire_cst_list_prim_u_8_strict>)>>(
+          'frbgen_wallet_core_wire__crate__api__full__select_config_environment');
+  late final _wire__crate__api__full__select_config_environment = _wire__crate__api__full__select_config_environmentPtr
+      .asFunction<void Function(int, ffi.Pointer<wire_cst_list_prim_u_8_strict>)>();
+
   void wire__crate__api__full__set_attestations_stream(
     int port_,
     ffi.Pointer<wire_cst_list_prim_u_8_strict> sink,
@@ -1817,6 +1930,20 @@ class WalletCoreWire implements BaseWire {
   late final _wire__crate__api__full__unlock_wallet_with_biometrics =
       _wire__crate__api__full__unlock_wallet_with_biometricsPtr.asFunction<void Function(int)>();
 
+  void wire__crate__api__full__verify_attested_key_continuity(
+    int port_,
+  ) {
+    return _wire__crate__api__full__verify_attested_key_continuity(
+      port_,
+    );
+  }
+
+  late final _wire__crate__api__full__verify_attested_key_continuityPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'frbgen_wallet_core_wire__crate__api__full__verify_attested_key_continuity');
+  late final _wire__crate__api__full__verify_attested_key_continuity =
+      _wire__crate__api__full__verify_attested_key_continuityPtr.asFunction<void Function(int)>();
+
   ffi.Pointer<wire_cst_attestation> cst_new_box_autoadd_attestation() {
     return _cst_new_box_autoadd_attestation();
   }
@@ -1899,6 +2026,20 @@ class WalletCoreWire implements BaseWire {
   late final _cst_new_box_autoadd_wallet_instruction_error = _cst_new_box_autoadd_wallet_instruction_errorPtr
       .asFunction<ffi.Pointer<wire_cst_wallet_instruction_error> Function()>();
 
+  ffi.Pointer<wire_cst_list_String> cst_new_list_String(
+    int len,
+  ) {
+    return _cst_new_list_String(
+      len,
+    );
+  }
+
+  late final _cst_new_list_StringPtr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<wire_cst_list_String> Function(ffi.Int32)>>(
+          'frbgen_wallet_core_cst_new_list_String');
+  late final _cst_new_list_String =
+      _cst_new_list_StringPtr.asFunction<ffi.Pointer<wire_cst_list_String> Function(int)>();
+
   ffi.Pointer<wire_cst_list_attestation> cst_new_list_attestation(
     int len,
   ) {
@@ -2031,6 +2172,13 @@ final class wire_cst_list_prim_u_8_strict extends ffi.Struct {
   external int len;
 }
 
+final class wire_cst_list_String extends ffi.Struct {
+  external ffi.Pointer<ffi.Pointer<wire_cst_list_prim_u_8_strict>> ptr;
+
+  @ffi.Int32()
+  external int len;
+}
+
 final class wire_cst_AttestationIdentity_Fixed extends ffi.Struct {
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> id;
 }
@@ -2311,12 +2459,23 @@ final class wire_cst_WalletEvent_Issuance extends ffi.Struct {
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> date_time;
 
   external ffi.Pointer<wire_cst_attestation> attestation;
+
+  @ffi.Bool()
+  external bool renewed;
+}
+
+final class wire_cst_WalletEvent_Deletion extends ffi.Struct {
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> date_time;
+
+  external ffi.Pointer<wire_cst_attestation> attestation;
 }
 
 final class WalletEventKind extends ffi.Union {
   external wire_cst_WalletEvent_Disclosure Disclosure;
 
   external wire_cst_WalletEvent_Issuance Issuance;
+
+  external wire_cst_WalletEvent_Deletion Deletion;
 }
 
 final class wire_cst_wallet_event extends ffi.Struct {
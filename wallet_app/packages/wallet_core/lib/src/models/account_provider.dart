@@ -0,0 +1,15 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.7.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+/// The availability of the Wallet Provider, as tracked by the circuit breaker in front of it.
+enum FlutterAccountProviderState {
+  Closed,
+  Open,
+  HalfOpen,
+  ;
+}
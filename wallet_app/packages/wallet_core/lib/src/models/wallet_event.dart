@@ -27,5 +27,10 @@ sealed class WalletEvent with _$WalletEvent {
   const factory WalletEvent.issuance({
     required String dateTime,
     required Attestation attestation,
+    required bool renewed,
   }) = WalletEvent_Issuance;
+  const factory WalletEvent.deletion({
+    required String dateTime,
+    required Attestation attestation,
+  }) = WalletEvent_Deletion;
 }
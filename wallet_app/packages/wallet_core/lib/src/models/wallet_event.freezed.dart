@@ -28,7 +28,8 @@ mixin _$WalletEvent {
             DisclosureStatus status,
             DisclosureType typ)
         disclosure,
-    required TResult Function(String dateTime, Attestation attestation) issuance,
+    required TResult Function(String dateTime, Attestation attestation, bool renewed) issuance,
+    required TResult Function(String dateTime, Attestation attestation) deletion,
   }) =>
       throw _privateConstructorUsedError;
   @optionalTypeArgs
@@ -42,7 +43,8 @@ mixin _$WalletEvent {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult? Function(String dateTime, Attestation attestation)? issuance,
+    TResult? Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult? Function(String dateTime, Attestation attestation)? deletion,
   }) =>
       throw _privateConstructorUsedError;
   @optionalTypeArgs
@@ -56,7 +58,8 @@ mixin _$WalletEvent {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult Function(String dateTime, Attestation attestation)? issuance,
+    TResult Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult Function(String dateTime, Attestation attestation)? deletion,
     required TResult orElse(),
   }) =>
       throw _privateConstructorUsedError;
@@ -64,18 +67,21 @@ mixin _$WalletEvent {
   TResult map<TResult extends Object?>({
     required TResult Function(WalletEvent_Disclosure value) disclosure,
     required TResult Function(WalletEvent_Issuance value) issuance,
+    required TResult Function(WalletEvent_Deletion value) deletion,
   }) =>
       throw _privateConstructorUsedError;
   @optionalTypeArgs
   TResult? mapOrNull<TResult extends Object?>({
     TResult? Function(WalletEvent_Disclosure value)? disclosure,
     TResult? Function(WalletEvent_Issuance value)? issuance,
+    TResult? Function(WalletEvent_Deletion value)? deletion,
   }) =>
       throw _privateConstructorUsedError;
   @optionalTypeArgs
   TResult maybeMap<TResult extends Object?>({
     TResult Function(WalletEvent_Disclosure value)? disclosure,
     TResult Function(WalletEvent_Issuance value)? issuance,
+    TResult Function(WalletEvent_Deletion value)? deletion,
     required TResult orElse(),
   }) =>
       throw _privateConstructorUsedError;
@@ -277,7 +283,8 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
             DisclosureStatus status,
             DisclosureType typ)
         disclosure,
-    required TResult Function(String dateTime, Attestation attestation) issuance,
+    required TResult Function(String dateTime, Attestation attestation, bool renewed) issuance,
+    required TResult Function(String dateTime, Attestation attestation) deletion,
   }) {
     return disclosure(dateTime, relyingParty, purpose, requestedCards, requestPolicy, status, typ);
   }
@@ -294,7 +301,8 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult? Function(String dateTime, Attestation attestation)? issuance,
+    TResult? Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult? Function(String dateTime, Attestation attestation)? deletion,
   }) {
     return disclosure?.call(dateTime, relyingParty, purpose, requestedCards, requestPolicy, status, typ);
   }
@@ -311,7 +319,8 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult Function(String dateTime, Attestation attestation)? issuance,
+    TResult Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult Function(String dateTime, Attestation attestation)? deletion,
     required TResult orElse(),
   }) {
     if (disclosure != null) {
@@ -325,6 +334,7 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
   TResult map<TResult extends Object?>({
     required TResult Function(WalletEvent_Disclosure value) disclosure,
     required TResult Function(WalletEvent_Issuance value) issuance,
+    required TResult Function(WalletEvent_Deletion value) deletion,
   }) {
     return disclosure(this);
   }
@@ -334,6 +344,7 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
   TResult? mapOrNull<TResult extends Object?>({
     TResult? Function(WalletEvent_Disclosure value)? disclosure,
     TResult? Function(WalletEvent_Issuance value)? issuance,
+    TResult? Function(WalletEvent_Deletion value)? deletion,
   }) {
     return disclosure?.call(this);
   }
@@ -343,6 +354,7 @@ class _$WalletEvent_DisclosureImpl extends WalletEvent_Disclosure {
   TResult maybeMap<TResult extends Object?>({
     TResult Function(WalletEvent_Disclosure value)? disclosure,
     TResult Function(WalletEvent_Issuance value)? issuance,
+    TResult Function(WalletEvent_Deletion value)? deletion,
     required TResult orElse(),
   }) {
     if (disclosure != null) {
@@ -387,7 +399,7 @@ abstract class _$$WalletEvent_IssuanceImplCopyWith<$Res> implements $WalletEvent
       __$$WalletEvent_IssuanceImplCopyWithImpl<$Res>;
   @override
   @useResult
-  $Res call({String dateTime, Attestation attestation});
+  $Res call({String dateTime, Attestation attestation, bool renewed});
 }
 
 /// @nodoc
@@ -404,6 +416,7 @@ class __$$WalletEvent_IssuanceImplCopyWithImpl<$Res> extends _$WalletEventCopyWi
   $Res call({
     Object? dateTime = null,
     Object? attestation = null,
+    Object? renewed = null,
   }) {
     return _then(_$WalletEvent_IssuanceImpl(
       dateTime: null == dateTime
@@ -414,6 +427,10 @@ class __$$WalletEvent_IssuanceImplCopyWithImpl<$Res> extends _$WalletEventCopyWi
           ? _value.attestation
           : attestation // ignore: cast_nullable_to_non_nullable
               as Attestation,
+      renewed: null == renewed
+          ? _value.renewed
+          : renewed // ignore: cast_nullable_to_non_nullable
+              as bool,
     ));
   }
 }
@@ -421,16 +438,19 @@ class __$$WalletEvent_IssuanceImplCopyWithImpl<$Res> extends _$WalletEventCopyWi
 /// @nodoc
 
 class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
-  const _$WalletEvent_IssuanceImpl({required this.dateTime, required this.attestation}) : super._();
+  const _$WalletEvent_IssuanceImpl({required this.dateTime, required this.attestation, required this.renewed})
+      : super._();
 
   @override
   final String dateTime;
   @override
   final Attestation attestation;
+  @override
+  final bool renewed;
 
   @override
   String toString() {
-    return 'WalletEvent.issuance(dateTime: $dateTime, attestation: $attestation)';
+    return 'WalletEvent.issuance(dateTime: $dateTime, attestation: $attestation, renewed: $renewed)';
   }
 
   @override
@@ -439,11 +459,12 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
         (other.runtimeType == runtimeType &&
             other is _$WalletEvent_IssuanceImpl &&
             (identical(other.dateTime, dateTime) || other.dateTime == dateTime) &&
-            (identical(other.attestation, attestation) || other.attestation == attestation));
+            (identical(other.attestation, attestation) || other.attestation == attestation) &&
+            (identical(other.renewed, renewed) || other.renewed == renewed));
   }
 
   @override
-  int get hashCode => Object.hash(runtimeType, dateTime, attestation);
+  int get hashCode => Object.hash(runtimeType, dateTime, attestation, renewed);
 
   /// Create a copy of WalletEvent
   /// with the given fields replaced by the non-null parameter values.
@@ -465,9 +486,10 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
             DisclosureStatus status,
             DisclosureType typ)
         disclosure,
-    required TResult Function(String dateTime, Attestation attestation) issuance,
+    required TResult Function(String dateTime, Attestation attestation, bool renewed) issuance,
+    required TResult Function(String dateTime, Attestation attestation) deletion,
   }) {
-    return issuance(dateTime, attestation);
+    return issuance(dateTime, attestation, renewed);
   }
 
   @override
@@ -482,9 +504,10 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult? Function(String dateTime, Attestation attestation)? issuance,
+    TResult? Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult? Function(String dateTime, Attestation attestation)? deletion,
   }) {
-    return issuance?.call(dateTime, attestation);
+    return issuance?.call(dateTime, attestation, renewed);
   }
 
   @override
@@ -499,11 +522,12 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
             DisclosureStatus status,
             DisclosureType typ)?
         disclosure,
-    TResult Function(String dateTime, Attestation attestation)? issuance,
+    TResult Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult Function(String dateTime, Attestation attestation)? deletion,
     required TResult orElse(),
   }) {
     if (issuance != null) {
-      return issuance(dateTime, attestation);
+      return issuance(dateTime, attestation, renewed);
     }
     return orElse();
   }
@@ -513,6 +537,7 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
   TResult map<TResult extends Object?>({
     required TResult Function(WalletEvent_Disclosure value) disclosure,
     required TResult Function(WalletEvent_Issuance value) issuance,
+    required TResult Function(WalletEvent_Deletion value) deletion,
   }) {
     return issuance(this);
   }
@@ -522,6 +547,7 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
   TResult? mapOrNull<TResult extends Object?>({
     TResult? Function(WalletEvent_Disclosure value)? disclosure,
     TResult? Function(WalletEvent_Issuance value)? issuance,
+    TResult? Function(WalletEvent_Deletion value)? deletion,
   }) {
     return issuance?.call(this);
   }
@@ -531,6 +557,7 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
   TResult maybeMap<TResult extends Object?>({
     TResult Function(WalletEvent_Disclosure value)? disclosure,
     TResult Function(WalletEvent_Issuance value)? issuance,
+    TResult Function(WalletEvent_Deletion value)? deletion,
     required TResult orElse(),
   }) {
     if (issuance != null) {
@@ -541,13 +568,16 @@ class _$WalletEvent_IssuanceImpl extends WalletEvent_Issuance {
 }
 
 abstract class WalletEvent_Issuance extends WalletEvent {
-  const factory WalletEvent_Issuance({required final String dateTime, required final Attestation attestation}) =
-      _$WalletEvent_IssuanceImpl;
+  const factory WalletEvent_Issuance(
+      {required final String dateTime,
+      required final Attestation attestation,
+      required final bool renewed}) = _$WalletEvent_IssuanceImpl;
   const WalletEvent_Issuance._() : super._();
 
   @override
   String get dateTime;
   Attestation get attestation;
+  bool get renewed;
 
   /// Create a copy of WalletEvent
   /// with the given fields replaced by the non-null parameter values.
@@ -555,3 +585,185 @@ abstract class WalletEvent_Issuance extends WalletEvent {
   @JsonKey(includeFromJson: false, includeToJson: false)
   _$$WalletEvent_IssuanceImplCopyWith<_$WalletEvent_IssuanceImpl> get copyWith => throw _privateConstructorUsedError;
 }
+
+/// @nodoc
+abstract class _$$WalletEvent_DeletionImplCopyWith<$Res> implements $WalletEventCopyWith<$Res> {
+  factory _$$WalletEvent_DeletionImplCopyWith(
+          _$WalletEvent_DeletionImpl value, $Res Function(_$WalletEvent_DeletionImpl) then) =
+      __$$WalletEvent_DeletionImplCopyWithImpl<$Res>;
+  @override
+  @useResult
+  $Res call({String dateTime, Attestation attestation});
+}
+
+/// @nodoc
+class __$$WalletEvent_DeletionImplCopyWithImpl<$Res> extends _$WalletEventCopyWithImpl<$Res, _$WalletEvent_DeletionImpl>
+    implements _$$WalletEvent_DeletionImplCopyWith<$Res> {
+  __$$WalletEvent_DeletionImplCopyWithImpl(
+      _$WalletEvent_DeletionImpl _value, $Res Function(_$WalletEvent_DeletionImpl) _then)
+      : super(_value, _then);
+
+  /// Create a copy of WalletEvent
+  /// with the given fields replaced by the non-null parameter values.
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? dateTime = null,
+    Object? attestation = null,
+  }) {
+    return _then(_$WalletEvent_DeletionImpl(
+      dateTime: null == dateTime
+          ? _value.dateTime
+          : dateTime // ignore: cast_nullable_to_non_nullable
+              as String,
+      attestation: null == attestation
+          ? _value.attestation
+          : attestation // ignore: cast_nullable_to_non_nullable
+              as Attestation,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$WalletEvent_DeletionImpl extends WalletEvent_Deletion {
+  const _$WalletEvent_DeletionImpl({required this.dateTime, required this.attestation}) : super._();
+
+  @override
+  final String dateTime;
+  @override
+  final Attestation attestation;
+
+  @override
+  String toString() {
+    return 'WalletEvent.deletion(dateTime: $dateTime, attestation: $attestation)';
+  }
+
+  @override
+  bool operator ==(Object other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$WalletEvent_DeletionImpl &&
+            (identical(other.dateTime, dateTime) || other.dateTime == dateTime) &&
+            (identical(other.attestation, attestation) || other.attestation == attestation));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, dateTime, attestation);
+
+  /// Create a copy of WalletEvent
+  /// with the given fields replaced by the non-null parameter values.
+  @JsonKey(includeFromJson: false, includeToJson: false)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$WalletEvent_DeletionImplCopyWith<_$WalletEvent_DeletionImpl> get copyWith =>
+      __$$WalletEvent_DeletionImplCopyWithImpl<_$WalletEvent_DeletionImpl>(this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(
+            String dateTime,
+            Organization relyingParty,
+            List<LocalizedString> purpose,
+            List<DisclosureCard>? requestedCards,
+            RequestPolicy requestPolicy,
+            DisclosureStatus status,
+            DisclosureType typ)
+        disclosure,
+    required TResult Function(String dateTime, Attestation attestation, bool renewed) issuance,
+    required TResult Function(String dateTime, Attestation attestation) deletion,
+  }) {
+    return deletion(dateTime, attestation);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(
+            String dateTime,
+            Organization relyingParty,
+            List<LocalizedString> purpose,
+            List<DisclosureCard>? requestedCards,
+            RequestPolicy requestPolicy,
+            DisclosureStatus status,
+            DisclosureType typ)?
+        disclosure,
+    TResult? Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult? Function(String dateTime, Attestation attestation)? deletion,
+  }) {
+    return deletion?.call(dateTime, attestation);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(
+            String dateTime,
+            Organization relyingParty,
+            List<LocalizedString> purpose,
+            List<DisclosureCard>? requestedCards,
+            RequestPolicy requestPolicy,
+            DisclosureStatus status,
+            DisclosureType typ)?
+        disclosure,
+    TResult Function(String dateTime, Attestation attestation, bool renewed)? issuance,
+    TResult Function(String dateTime, Attestation attestation)? deletion,
+    required TResult orElse(),
+  }) {
+    if (deletion != null) {
+      return deletion(dateTime, attestation);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(WalletEvent_Disclosure value) disclosure,
+    required TResult Function(WalletEvent_Issuance value) issuance,
+    required TResult Function(WalletEvent_Deletion value) deletion,
+  }) {
+    return deletion(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(WalletEvent_Disclosure value)? disclosure,
+    TResult? Function(WalletEvent_Issuance value)? issuance,
+    TResult? Function(WalletEvent_Deletion value)? deletion,
+  }) {
+    return deletion?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(WalletEvent_Disclosure value)? disclosure,
+    TResult Function(WalletEvent_Issuance value)? issuance,
+    TResult Function(WalletEvent_Deletion value)? deletion,
+    required TResult orElse(),
+  }) {
+    if (deletion != null) {
+      return deletion(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class WalletEvent_Deletion extends WalletEvent {
+  const factory WalletEvent_Deletion({required final String dateTime, required final Attestation attestation}) =
+      _$WalletEvent_DeletionImpl;
+  const WalletEvent_Deletion._() : super._();
+
+  @override
+  String get dateTime;
+  Attestation get attestation;
+
+  /// Create a copy of WalletEvent
+  /// with the given fields replaced by the non-null parameter values.
+  @override
+  @JsonKey(includeFromJson: false, includeToJson: false)
+  _$$WalletEvent_DeletionImplCopyWith<_$WalletEvent_DeletionImpl> get copyWith => throw _privateConstructorUsedError;
+}
@@ -8,5 +8,6 @@ export 'src/wallet_core_for_signing.dart';
 
 const kMockPidIssuanceRedirectUri = 'initiate_mock_digid_flow';
 const kDrivingLicenseDocType = 'com.example.drivinglicense';
+const kMockConfigEnvironment = 'mock';
 const kMockVersionString =
     '0.2.3-dev (linux/x86_64, debug-mode, built: Tue, 3 Dec 2024 10:26:24 +0000, commit: x0y1234+modifications)';
@@ -23,6 +23,7 @@ class WalletEventLog {
             return disclosure.requestedCards!.any((card) => card.docType == docType);
           },
           issuance: (WalletEvent_Issuance issuance) => issuance.attestation.attestationType == docType,
+          deletion: (WalletEvent_Deletion deletion) => deletion.attestation.attestationType == docType,
         ),
       )
       .toList();
@@ -79,6 +80,7 @@ class WalletEventLog {
     final event = WalletEvent.issuance(
       dateTime: DateTime.now().toIso8601String(),
       attestation: attestation,
+      renewed: false,
     );
     _logEvent(event);
   }
@@ -99,6 +101,9 @@ class WalletEventLog {
           issuance: (issuance) {
             return issuance.attestation.issuer == organization;
           },
+          deletion: (deletion) {
+            return deletion.attestation.issuer == organization;
+          },
         );
       },
     );
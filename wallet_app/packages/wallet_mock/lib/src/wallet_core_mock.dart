@@ -201,6 +201,14 @@ class WalletCoreMock implements WalletCoreApi {
     _wallet.unlock();
   }
 
+  @override
+  Future<FlutterAccountProviderState> crateApiFullGetAccountProviderState({hint}) async =>
+      FlutterAccountProviderState.Closed;
+
+  @override
+  Future<FlutterAttestedKeyContinuity> crateApiFullVerifyAttestedKeyContinuity({hint}) async =>
+      FlutterAttestedKeyContinuity.Intact;
+
   @override
   Future<void> crateApiFullResetWallet({hint}) async {
     await _pinManager.resetPin();
@@ -289,4 +297,15 @@ class WalletCoreMock implements WalletCoreApi {
 
   @override
   Future<String> crateApiFullGetVersionString({hint}) async => kMockVersionString;
+
+  @override
+  Future<List<String>> crateApiFullGetConfigEnvironments({hint}) async => [kMockConfigEnvironment];
+
+  @override
+  Future<String> crateApiFullGetCurrentConfigEnvironment({hint}) async => kMockConfigEnvironment;
+
+  @override
+  Future<void> crateApiFullSelectConfigEnvironment({required String environment, hint}) async {
+    if (environment != kMockConfigEnvironment) throw StateError('Unknown configuration environment: $environment');
+  }
 }
@@ -57,6 +57,30 @@ void main() {
     expect(event, isNot(equals(differentEvent)));
   });
 
+  test('IssuanceEvent with different event type', () {
+    final event = WalletEvent.issuance(
+      dateTime: DateTime(2024),
+      status: EventStatus.success,
+      card: WalletMockData.card,
+    );
+    final removedEvent = WalletEvent.issuance(
+      dateTime: DateTime(2024),
+      status: EventStatus.success,
+      card: WalletMockData.card,
+      eventType: IssuanceEventType.cardRemoved,
+    );
+    final renewedEvent = WalletEvent.issuance(
+      dateTime: DateTime(2024),
+      status: EventStatus.success,
+      card: WalletMockData.card,
+      eventType: IssuanceEventType.cardRenewed,
+    );
+    expect((event as IssuanceEvent).eventType, IssuanceEventType.cardIssued);
+    expect(event, isNot(equals(removedEvent)));
+    expect(event, isNot(equals(renewedEvent)));
+    expect(renewedEvent, isNot(equals(removedEvent)));
+  });
+
   test('SignEvent', () {
     final event = WalletEvent.sign(
       dateTime: DateTime(2024),
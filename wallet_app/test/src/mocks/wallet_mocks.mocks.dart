@@ -3874,6 +3874,41 @@ class MockWalletCoreApi extends _i1.Mock implements _i18.WalletCoreApi {
         ),
       ) as _i8.Future<String>);
 
+  @override
+  _i8.Future<_i18.FlutterAccountProviderState> crateApiFullGetAccountProviderState() => (super.noSuchMethod(
+        Invocation.method(#crateApiFullGetAccountProviderState, []),
+        returnValue: _i8.Future<_i18.FlutterAccountProviderState>.value(
+          _i18.FlutterAccountProviderState.Closed,
+        ),
+        returnValueForMissingStub: _i8.Future<_i18.FlutterAccountProviderState>.value(
+          _i18.FlutterAccountProviderState.Closed,
+        ),
+      ) as _i8.Future<_i18.FlutterAccountProviderState>);
+
+  @override
+  _i8.Future<List<String>> crateApiFullGetConfigEnvironments() => (super.noSuchMethod(
+        Invocation.method(#crateApiFullGetConfigEnvironments, []),
+        returnValue: _i8.Future<List<String>>.value(<String>[]),
+        returnValueForMissingStub: _i8.Future<List<String>>.value(<String>[]),
+      ) as _i8.Future<List<String>>);
+
+  @override
+  _i8.Future<String> crateApiFullGetCurrentConfigEnvironment() => (super.noSuchMethod(
+        Invocation.method(#crateApiFullGetCurrentConfigEnvironment, []),
+        returnValue: _i8.Future<String>.value(
+          _i12.dummyValue<String>(
+            this,
+            Invocation.method(#crateApiFullGetCurrentConfigEnvironment, []),
+          ),
+        ),
+        returnValueForMissingStub: _i8.Future<String>.value(
+          _i12.dummyValue<String>(
+            this,
+            Invocation.method(#crateApiFullGetCurrentConfigEnvironment, []),
+          ),
+        ),
+      ) as _i8.Future<String>);
+
   @override
   _i8.Future<List<_i18.WalletEvent>> crateApiFullGetHistory() => (super.noSuchMethod(
         Invocation.method(#crateApiFullGetHistory, []),
@@ -4009,6 +4044,15 @@ class MockWalletCoreApi extends _i1.Mock implements _i18.WalletCoreApi {
         returnValueForMissingStub: _i8.Future<void>.value(),
       ) as _i8.Future<void>);
 
+  @override
+  _i8.Future<void> crateApiFullSelectConfigEnvironment({required String? environment}) => (super.noSuchMethod(
+        Invocation.method(#crateApiFullSelectConfigEnvironment, [], {
+          #environment: environment,
+        }),
+        returnValue: _i8.Future<void>.value(),
+        returnValueForMissingStub: _i8.Future<void>.value(),
+      ) as _i8.Future<void>);
+
   @override
   _i8.Stream<List<_i18.Attestation>> crateApiFullSetAttestationsStream() => (super.noSuchMethod(
         Invocation.method(#crateApiFullSetAttestationsStream, []),
@@ -4111,4 +4155,15 @@ class MockWalletCoreApi extends _i1.Mock implements _i18.WalletCoreApi {
         returnValue: _i8.Future<void>.value(),
         returnValueForMissingStub: _i8.Future<void>.value(),
       ) as _i8.Future<void>);
+
+  @override
+  _i8.Future<_i18.FlutterAttestedKeyContinuity> crateApiFullVerifyAttestedKeyContinuity() => (super.noSuchMethod(
+        Invocation.method(#crateApiFullVerifyAttestedKeyContinuity, []),
+        returnValue: _i8.Future<_i18.FlutterAttestedKeyContinuity>.value(
+          _i18.FlutterAttestedKeyContinuity.Unregistered,
+        ),
+        returnValueForMissingStub: _i8.Future<_i18.FlutterAttestedKeyContinuity>.value(
+          _i18.FlutterAttestedKeyContinuity.Unregistered,
+        ),
+      ) as _i8.Future<_i18.FlutterAttestedKeyContinuity>);
 }
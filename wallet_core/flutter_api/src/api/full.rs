@@ -13,7 +13,9 @@ use wallet::Wallet;
 
 use crate::frb_generated::StreamSink;
 use crate::logging::init_logging;
+use crate::models::account_provider::FlutterAccountProviderState;
 use crate::models::attestation::Attestation;
+use crate::models::attested_key::FlutterAttestedKeyContinuity;
 use crate::models::config::FlutterConfiguration;
 use crate::models::disclosure::AcceptDisclosureResult;
 use crate::models::disclosure::StartDisclosureResult;
@@ -159,7 +161,7 @@ pub async fn unlock_wallet(pin: String) -> anyhow::Result<WalletInstructionResul
 pub async fn lock_wallet() {
     let mut wallet = wallet().write().await;
 
-    wallet.lock();
+    wallet.lock().await;
 }
 
 #[flutter_api_error]
@@ -193,6 +195,10 @@ pub async fn has_registration() -> bool {
     wallet().read().await.has_registration()
 }
 
+pub async fn get_account_provider_state() -> FlutterAccountProviderState {
+    wallet().read().await.account_provider_state().into()
+}
+
 #[flutter_api_error]
 pub async fn register(pin: String) -> anyhow::Result<()> {
     let mut wallet = wallet().write().await;
@@ -202,6 +208,15 @@ pub async fn register(pin: String) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[flutter_api_error]
+pub async fn verify_attested_key_continuity() -> anyhow::Result<FlutterAttestedKeyContinuity> {
+    let wallet = wallet().read().await;
+
+    let continuity = wallet.verify_attested_key_continuity().await?.into();
+
+    Ok(continuity)
+}
+
 #[flutter_api_error]
 pub async fn identify_uri(uri: String) -> anyhow::Result<IdentifyUriResult> {
     let wallet = wallet().read().await;
@@ -350,7 +365,9 @@ pub async fn get_history_for_card(doc_type: String) -> anyhow::Result<Vec<Wallet
         .flat_map(WalletEvents::from)
         .filter(|e| match e {
             WalletEvent::Disclosure { .. } => true,
-            WalletEvent::Issuance { attestation, .. } => attestation.attestation_type == doc_type,
+            WalletEvent::Issuance { attestation, .. } | WalletEvent::Deletion { attestation, .. } => {
+                attestation.attestation_type == doc_type
+            }
         })
         .collect();
     Ok(history)
@@ -367,6 +384,21 @@ pub fn get_version_string() -> String {
     version_string()
 }
 
+pub fn get_config_environments() -> Vec<String> {
+    wallet::config_environments().into_iter().map(String::from).collect()
+}
+
+pub fn get_current_config_environment() -> String {
+    wallet::current_config_environment().to_string()
+}
+
+#[flutter_api_error]
+pub async fn select_config_environment(environment: String) -> anyhow::Result<()> {
+    wallet::select_config_environment(&environment).await?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,12 +6,15 @@ use serde::Serialize;
 use serde_with::skip_serializing_none;
 use url::Url;
 
+use wallet::errors::openid4vc::CredentialRequestFailure;
+use wallet::errors::openid4vc::IssuanceRecovery;
 use wallet::errors::openid4vc::IssuanceSessionError;
 use wallet::errors::openid4vc::OidcError;
 use wallet::errors::openid4vc::VpClientError;
 use wallet::errors::openid4vc::VpMessageClientErrorType;
 use wallet::errors::reqwest;
 use wallet::errors::AccountProviderError;
+use wallet::errors::AttestedKeyContinuityError;
 use wallet::errors::ChangePinError;
 use wallet::errors::DigidSessionError;
 use wallet::errors::DisclosureError;
@@ -26,6 +29,7 @@ use wallet::errors::WalletInitError;
 use wallet::errors::WalletRegistrationError;
 use wallet::errors::WalletUnlockError;
 use wallet::openid4vc::SessionType;
+use wallet::LocalizedString;
 
 /// A type encapsulating data about a Flutter error that
 /// is to be serialized to JSON and sent to Flutter.
@@ -114,6 +118,7 @@ impl TryFrom<anyhow::Error> for FlutterApiError {
             .downcast::<WalletInitError>()
             .map(Self::from)
             .or_else(|e| e.downcast::<WalletRegistrationError>().map(Self::from))
+            .or_else(|e| e.downcast::<AttestedKeyContinuityError>().map(Self::from))
             .or_else(|e| e.downcast::<WalletUnlockError>().map(Self::from))
             .or_else(|e| e.downcast::<UriIdentificationError>().map(Self::from))
             .or_else(|e| e.downcast::<PidIssuanceError>().map(Self::from))
@@ -161,6 +166,8 @@ impl FlutterApiErrorFields for WalletRegistrationError {
     }
 }
 
+impl FlutterApiErrorFields for AttestedKeyContinuityError {}
+
 impl FlutterApiErrorFields for WalletUnlockError {
     fn typ(&self) -> FlutterApiErrorType {
         match self {
@@ -200,9 +207,10 @@ impl FlutterApiErrorFields for PidIssuanceError {
 
         match self {
             PidIssuanceError::VersionBlocked => FlutterApiErrorType::VersionBlocked,
-            PidIssuanceError::NotRegistered | PidIssuanceError::Locked | PidIssuanceError::SessionState => {
-                FlutterApiErrorType::WalletState
-            }
+            PidIssuanceError::NotRegistered
+            | PidIssuanceError::Locked
+            | PidIssuanceError::SessionState
+            | PidIssuanceError::SessionAbandoned => FlutterApiErrorType::WalletState,
             PidIssuanceError::DigidSessionFinish(DigidSessionError::Oidc(OidcError::RedirectUriError(_))) => {
                 FlutterApiErrorType::RedirectUri
             }
@@ -215,6 +223,7 @@ impl FlutterApiErrorFields for PidIssuanceError {
             | PidIssuanceError::DigidSessionFinish(DigidSessionError::Oidc(OidcError::RequestingUserInfo(_))) => {
                 FlutterApiErrorType::Server
             }
+            PidIssuanceError::Timeout => FlutterApiErrorType::Networking,
             PidIssuanceError::UpdatePolicy(e) => FlutterApiErrorType::from(e),
             _ => FlutterApiErrorType::Generic,
         }
@@ -229,26 +238,43 @@ impl FlutterApiErrorFields for PidIssuanceError {
                     .into_iter()
                     .collect::<serde_json::Value>()
             }
+            Self::PidIssuer(IssuanceSessionError::CredentialRequest(error_response)) => {
+                // The raw error response of the issuer is only logged, the UI receives the mapped failure reason.
+                let failure = CredentialRequestFailure::from(error_response.error);
+                serde_json::to_value(CredentialRequestFailureData {
+                    credential_request_failure: failure,
+                    recovery: failure.recovery(),
+                })
+                .expect("CredentialRequestFailureData should always serialize")
+            }
             _ => serde_json::Value::Null,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize)]
+struct CredentialRequestFailureData {
+    credential_request_failure: CredentialRequestFailure,
+    recovery: IssuanceRecovery,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize)]
 struct DisclosureErrorData<'a> {
     session_type: Option<SessionType>,
     can_retry: Option<bool>,
     return_url: Option<&'a Url>,
+    disabled_reason: Option<&'a [LocalizedString]>,
 }
 
 impl FlutterApiErrorFields for DisclosureError {
     fn typ(&self) -> FlutterApiErrorType {
         match self {
             DisclosureError::VersionBlocked => FlutterApiErrorType::VersionBlocked,
-            DisclosureError::NotRegistered | DisclosureError::Locked | DisclosureError::SessionState => {
-                FlutterApiErrorType::WalletState
-            }
+            DisclosureError::NotRegistered
+            | DisclosureError::Locked
+            | DisclosureError::SessionState
+            | DisclosureError::SessionAbandoned => FlutterApiErrorType::WalletState,
             DisclosureError::VpDisclosureSession(VpClientError::DisclosureUriSourceMismatch(_, _)) => {
                 FlutterApiErrorType::DisclosureSourceMismatch
             }
@@ -281,12 +307,17 @@ impl FlutterApiErrorFields for DisclosureError {
             _ => None,
         };
         let return_url = self.return_url();
+        let disabled_reason = match self {
+            DisclosureError::DisclosureDisabled { reason } => Some(reason.as_slice()),
+            _ => None,
+        };
 
-        if session_type.is_some() || can_retry.is_some() || return_url.is_some() {
+        if session_type.is_some() || can_retry.is_some() || return_url.is_some() || disabled_reason.is_some() {
             serde_json::to_value(DisclosureErrorData {
                 session_type,
                 can_retry,
                 return_url,
+                disabled_reason,
             })
             .unwrap() // This conversion should never fail.
         } else {
@@ -316,6 +347,7 @@ impl From<&AccountProviderError> for FlutterApiErrorType {
         match value {
             AccountProviderError::Response(_) => FlutterApiErrorType::Server,
             AccountProviderError::Networking(e) => FlutterApiErrorType::from(e),
+            AccountProviderError::CircuitOpen => FlutterApiErrorType::Server,
             _ => FlutterApiErrorType::Generic,
         }
     }
@@ -397,7 +429,9 @@ mod tests {
 
     use serde_json::json;
     use wallet::errors::openid4vc::AuthorizationErrorCode;
+    use wallet::errors::openid4vc::CredentialErrorCode;
     use wallet::errors::openid4vc::ErrorResponse;
+    use wallet::errors::openid4vc::IssuanceSessionError;
     use wallet::errors::openid4vc::OidcError;
     use wallet::errors::DigidSessionError;
     use wallet::errors::PidIssuanceError;
@@ -454,6 +488,24 @@ mod tests {
         FlutterApiErrorType::Server,
         serde_json::Value::Null
     )]
+    #[case(
+        PidIssuanceError::PidIssuer(IssuanceSessionError::CredentialRequest(ErrorResponse {
+            error: CredentialErrorCode::InvalidToken,
+            error_description: None,
+            error_uri: None,
+        })),
+        FlutterApiErrorType::Server,
+        json!({"credential_request_failure": "access_token_rejected", "recovery": "reauthenticate"})
+    )]
+    #[case(
+        PidIssuanceError::PidIssuer(IssuanceSessionError::CredentialRequest(ErrorResponse {
+            error: CredentialErrorCode::InvalidProof,
+            error_description: None,
+            error_uri: None,
+        })),
+        FlutterApiErrorType::Server,
+        json!({"credential_request_failure": "invalid_proof", "recovery": "reauthenticate"})
+    )]
     #[case(
         PidIssuanceError::MissingSignature,
         FlutterApiErrorType::Generic,
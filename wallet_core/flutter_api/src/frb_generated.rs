@@ -37,7 +37,7 @@ flutter_rust_bridge::frb_generated_boilerplate!(
     default_rust_auto_opaque = RustAutoOpaqueNom,
 );
 pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_VERSION: &str = "2.7.1";
-pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 1458509282;
+pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 1630211874;
 
 // Section: executor
 
@@ -363,6 +363,60 @@ fn wire__crate__api__full__create_pid_issuance_redirect_uri_impl(
         },
     )
 }
+fn wire__crate__api__full__get_account_provider_state_impl(port_: flutter_rust_bridge::for_generated::MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::DcoCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_account_provider_state",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            move |context| async move {
+                transform_result_dco::<_, _, ()>(
+                    (move || async move {
+                        let output_ok = Result::<_, ()>::Ok(crate::api::full::get_account_provider_state().await)?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__full__get_config_environments_impl(port_: flutter_rust_bridge::for_generated::MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::DcoCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_config_environments",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            move |context| {
+                transform_result_dco::<_, _, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::full::get_config_environments())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__full__get_current_config_environment_impl(port_: flutter_rust_bridge::for_generated::MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::DcoCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_current_config_environment",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            move |context| {
+                transform_result_dco::<_, _, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::full::get_current_config_environment())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
 fn wire__crate__api__full__get_history_impl(port_: flutter_rust_bridge::for_generated::MessagePort) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::DcoCodec, _, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
@@ -654,6 +708,30 @@ fn wire__crate__api__full__reset_wallet_impl(port_: flutter_rust_bridge::for_gen
         },
     )
 }
+fn wire__crate__api__full__select_config_environment_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    environment: impl CstDecode<String>,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::DcoCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "select_config_environment",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let api_environment = environment.cst_decode();
+            move |context| async move {
+                transform_result_dco::<_, _, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || async move {
+                        let output_ok = crate::api::full::select_config_environment(api_environment).await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
 fn wire__crate__api__full__set_attestations_stream_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     sink: impl CstDecode<
@@ -882,6 +960,26 @@ fn wire__crate__api__full__unlock_wallet_with_biometrics_impl(port_: flutter_rus
         },
     )
 }
+fn wire__crate__api__full__verify_attested_key_continuity_impl(port_: flutter_rust_bridge::for_generated::MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::DcoCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "verify_attested_key_continuity",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            move |context| async move {
+                transform_result_dco::<_, _, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || async move {
+                        let output_ok = crate::api::full::verify_attested_key_continuity().await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
 
 // Section: dart2rust
 
@@ -922,6 +1020,28 @@ impl CstDecode<crate::models::disclosure::DisclosureType> for i32 {
         }
     }
 }
+impl CstDecode<crate::models::account_provider::FlutterAccountProviderState> for i32 {
+    // Codec=Cst (C-struct based), see doc to use other codecs
+    fn cst_decode(self) -> crate::models::account_provider::FlutterAccountProviderState {
+        match self {
+            0 => crate::models::account_provider::FlutterAccountProviderState::Closed,
+            1 => crate::models::account_provider::FlutterAccountProviderState::Open,
+            2 => crate::models::account_provider::FlutterAccountProviderState::HalfOpen,
+            _ => unreachable!("Invalid variant for FlutterAccountProviderState: {}", self),
+        }
+    }
+}
+impl CstDecode<crate::models::attested_key::FlutterAttestedKeyContinuity> for i32 {
+    // Codec=Cst (C-struct based), see doc to use other codecs
+    fn cst_decode(self) -> crate::models::attested_key::FlutterAttestedKeyContinuity {
+        match self {
+            0 => crate::models::attested_key::FlutterAttestedKeyContinuity::Unregistered,
+            1 => crate::models::attested_key::FlutterAttestedKeyContinuity::Intact,
+            2 => crate::models::attested_key::FlutterAttestedKeyContinuity::Lost,
+            _ => unreachable!("Invalid variant for FlutterAttestedKeyContinuity: {}", self),
+        }
+    }
+}
 impl CstDecode<i32> for i32 {
     // Codec=Cst (C-struct based), see doc to use other codecs
     fn cst_decode(self) -> i32 {
@@ -1211,6 +1331,32 @@ impl SseDecode for crate::models::attestation::DisplayMetadata {
     }
 }
 
+impl SseDecode for crate::models::account_provider::FlutterAccountProviderState {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::models::account_provider::FlutterAccountProviderState::Closed,
+            1 => crate::models::account_provider::FlutterAccountProviderState::Open,
+            2 => crate::models::account_provider::FlutterAccountProviderState::HalfOpen,
+            _ => unreachable!("Invalid variant for FlutterAccountProviderState: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::models::attested_key::FlutterAttestedKeyContinuity {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::models::attested_key::FlutterAttestedKeyContinuity::Unregistered,
+            1 => crate::models::attested_key::FlutterAttestedKeyContinuity::Intact,
+            2 => crate::models::attested_key::FlutterAttestedKeyContinuity::Lost,
+            _ => unreachable!("Invalid variant for FlutterAttestedKeyContinuity: {}", inner),
+        };
+    }
+}
+
 impl SseDecode for crate::models::config::FlutterConfiguration {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -1309,6 +1455,18 @@ impl SseDecode for crate::models::disclosure::Image {
     }
 }
 
+impl SseDecode for Vec<String> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<String>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
 impl SseDecode for Vec<crate::models::attestation::Attestation> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -1711,9 +1869,19 @@ impl SseDecode for crate::models::wallet_event::WalletEvent {
             1 => {
                 let mut var_dateTime = <String>::sse_decode(deserializer);
                 let mut var_attestation = <crate::models::attestation::Attestation>::sse_decode(deserializer);
+                let mut var_renewed = <bool>::sse_decode(deserializer);
                 return crate::models::wallet_event::WalletEvent::Issuance {
                     date_time: var_dateTime,
                     attestation: var_attestation,
+                    renewed: var_renewed,
+                };
+            }
+            2 => {
+                let mut var_dateTime = <String>::sse_decode(deserializer);
+                let mut var_attestation = <crate::models::attestation::Attestation>::sse_decode(deserializer);
+                return crate::models::wallet_event::WalletEvent::Deletion {
+                    date_time: var_dateTime,
+                    attestation: var_attestation,
                 };
             }
             _ => {
@@ -2007,6 +2175,50 @@ impl flutter_rust_bridge::IntoIntoDart<crate::models::attestation::DisplayMetada
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::account_provider::FlutterAccountProviderState {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Closed => 0.into_dart(),
+            Self::Open => 1.into_dart(),
+            Self::HalfOpen => 2.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::models::account_provider::FlutterAccountProviderState
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::account_provider::FlutterAccountProviderState>
+    for crate::models::account_provider::FlutterAccountProviderState
+{
+    fn into_into_dart(self) -> crate::models::account_provider::FlutterAccountProviderState {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::attested_key::FlutterAttestedKeyContinuity {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Unregistered => 0.into_dart(),
+            Self::Intact => 1.into_dart(),
+            Self::Lost => 2.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::models::attested_key::FlutterAttestedKeyContinuity
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::attested_key::FlutterAttestedKeyContinuity>
+    for crate::models::attested_key::FlutterAttestedKeyContinuity
+{
+    fn into_into_dart(self) -> crate::models::attested_key::FlutterAttestedKeyContinuity {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::models::config::FlutterConfiguration {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         [
@@ -2323,10 +2535,21 @@ impl flutter_rust_bridge::IntoDart for crate::models::wallet_event::WalletEvent
                 typ.into_into_dart().into_dart(),
             ]
             .into_dart(),
-            crate::models::wallet_event::WalletEvent::Issuance { date_time, attestation } => [
+            crate::models::wallet_event::WalletEvent::Issuance {
+                date_time,
+                attestation,
+                renewed,
+            } => [
                 1.into_dart(),
                 date_time.into_into_dart().into_dart(),
                 attestation.into_into_dart().into_dart(),
+                renewed.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::wallet_event::WalletEvent::Deletion { date_time, attestation } => [
+                2.into_dart(),
+                date_time.into_into_dart().into_dart(),
+                attestation.into_into_dart().into_dart(),
             ]
             .into_dart(),
             _ => {
@@ -2616,6 +2839,40 @@ impl SseEncode for crate::models::attestation::DisplayMetadata {
     }
 }
 
+impl SseEncode for crate::models::account_provider::FlutterAccountProviderState {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::account_provider::FlutterAccountProviderState::Closed => 0,
+                crate::models::account_provider::FlutterAccountProviderState::Open => 1,
+                crate::models::account_provider::FlutterAccountProviderState::HalfOpen => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::models::attested_key::FlutterAttestedKeyContinuity {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::attested_key::FlutterAttestedKeyContinuity::Unregistered => 0,
+                crate::models::attested_key::FlutterAttestedKeyContinuity::Intact => 1,
+                crate::models::attested_key::FlutterAttestedKeyContinuity::Lost => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
 impl SseEncode for crate::models::config::FlutterConfiguration {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -2709,6 +2966,16 @@ impl SseEncode for crate::models::disclosure::Image {
     }
 }
 
+impl SseEncode for Vec<String> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <String>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<crate::models::attestation::Attestation> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -3047,10 +3314,20 @@ impl SseEncode for crate::models::wallet_event::WalletEvent {
                 <crate::models::disclosure::DisclosureStatus>::sse_encode(status, serializer);
                 <crate::models::disclosure::DisclosureType>::sse_encode(typ, serializer);
             }
-            crate::models::wallet_event::WalletEvent::Issuance { date_time, attestation } => {
+            crate::models::wallet_event::WalletEvent::Issuance {
+                date_time,
+                attestation,
+                renewed,
+            } => {
                 <i32>::sse_encode(1, serializer);
                 <String>::sse_encode(date_time, serializer);
                 <crate::models::attestation::Attestation>::sse_encode(attestation, serializer);
+                <bool>::sse_encode(renewed, serializer);
+            }
+            crate::models::wallet_event::WalletEvent::Deletion { date_time, attestation } => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(date_time, serializer);
+                <crate::models::attestation::Attestation>::sse_encode(attestation, serializer);
             }
             _ => {
                 unimplemented!("");
@@ -3416,6 +3693,16 @@ mod io {
             }
         }
     }
+    impl CstDecode<Vec<String>> for *mut wire_cst_list_String {
+        // Codec=Cst (C-struct based), see doc to use other codecs
+        fn cst_decode(self) -> Vec<String> {
+            let vec = unsafe {
+                let wrap = flutter_rust_bridge::for_generated::box_from_leak_ptr(self);
+                flutter_rust_bridge::for_generated::vec_from_leak_ptr(wrap.ptr, wrap.len)
+            };
+            vec.into_iter().map(CstDecode::cst_decode).collect()
+        }
+    }
     impl CstDecode<Vec<crate::models::attestation::Attestation>> for *mut wire_cst_list_attestation {
         // Codec=Cst (C-struct based), see doc to use other codecs
         fn cst_decode(self) -> Vec<crate::models::attestation::Attestation> {
@@ -3621,6 +3908,14 @@ mod io {
                     crate::models::wallet_event::WalletEvent::Issuance {
                         date_time: ans.date_time.cst_decode(),
                         attestation: ans.attestation.cst_decode(),
+                        renewed: ans.renewed.cst_decode(),
+                    }
+                }
+                2 => {
+                    let ans = unsafe { self.kind.Deletion };
+                    crate::models::wallet_event::WalletEvent::Deletion {
+                        date_time: ans.date_time.cst_decode(),
+                        attestation: ans.attestation.cst_decode(),
                     }
                 }
                 _ => unreachable!(),
@@ -4034,6 +4329,21 @@ mod io {
         wire__crate__api__full__create_pid_issuance_redirect_uri_impl(port_)
     }
 
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__get_account_provider_state(port_: i64) {
+        wire__crate__api__full__get_account_provider_state_impl(port_)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__get_config_environments(port_: i64) {
+        wire__crate__api__full__get_config_environments_impl(port_)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__get_current_config_environment(port_: i64) {
+        wire__crate__api__full__get_current_config_environment_impl(port_)
+    }
+
     #[no_mangle]
     pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__get_history(port_: i64) {
         wire__crate__api__full__get_history_impl(port_)
@@ -4116,6 +4426,14 @@ mod io {
         wire__crate__api__full__reset_wallet_impl(port_)
     }
 
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__select_config_environment(
+        port_: i64,
+        environment: *mut wire_cst_list_prim_u_8_strict,
+    ) {
+        wire__crate__api__full__select_config_environment_impl(port_, environment)
+    }
+
     #[no_mangle]
     pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__set_attestations_stream(
         port_: i64,
@@ -4183,6 +4501,11 @@ mod io {
         wire__crate__api__full__unlock_wallet_with_biometrics_impl(port_)
     }
 
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_wire__crate__api__full__verify_attested_key_continuity(port_: i64) {
+        wire__crate__api__full__verify_attested_key_continuity_impl(port_)
+    }
+
     #[no_mangle]
     pub extern "C" fn frbgen_wallet_core_cst_new_box_autoadd_attestation() -> *mut wire_cst_attestation {
         flutter_rust_bridge::for_generated::new_leak_box_ptr(wire_cst_attestation::new_with_null_ptr())
@@ -4224,6 +4547,18 @@ mod io {
         flutter_rust_bridge::for_generated::new_leak_box_ptr(wire_cst_wallet_instruction_error::new_with_null_ptr())
     }
 
+    #[no_mangle]
+    pub extern "C" fn frbgen_wallet_core_cst_new_list_String(len: i32) -> *mut wire_cst_list_String {
+        let wrap = wire_cst_list_String {
+            ptr: flutter_rust_bridge::for_generated::new_leak_vec_ptr(
+                <*mut wire_cst_list_prim_u_8_strict>::new_with_null_ptr(),
+                len,
+            ),
+            len,
+        };
+        flutter_rust_bridge::for_generated::new_leak_box_ptr(wrap)
+    }
+
     #[no_mangle]
     pub extern "C" fn frbgen_wallet_core_cst_new_list_attestation(len: i32) -> *mut wire_cst_list_attestation {
         let wrap = wire_cst_list_attestation {
@@ -4484,6 +4819,12 @@ mod io {
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
+    pub struct wire_cst_list_String {
+        ptr: *mut *mut wire_cst_list_prim_u_8_strict,
+        len: i32,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct wire_cst_list_attestation {
         ptr: *mut wire_cst_attestation,
         len: i32,
@@ -4636,6 +4977,7 @@ mod io {
     pub union WalletEventKind {
         Disclosure: wire_cst_WalletEvent_Disclosure,
         Issuance: wire_cst_WalletEvent_Issuance,
+        Deletion: wire_cst_WalletEvent_Deletion,
         nil__: (),
     }
     #[repr(C)]
@@ -4654,6 +4996,13 @@ mod io {
     pub struct wire_cst_WalletEvent_Issuance {
         date_time: *mut wire_cst_list_prim_u_8_strict,
         attestation: *mut wire_cst_attestation,
+        renewed: bool,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct wire_cst_WalletEvent_Deletion {
+        date_time: *mut wire_cst_list_prim_u_8_strict,
+        attestation: *mut wire_cst_attestation,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -0,0 +1,18 @@
+use wallet::CircuitState;
+
+/// The availability of the Wallet Provider, as tracked by the circuit breaker in front of it.
+pub enum FlutterAccountProviderState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl From<CircuitState> for FlutterAccountProviderState {
+    fn from(value: CircuitState) -> Self {
+        match value {
+            CircuitState::Closed => FlutterAccountProviderState::Closed,
+            CircuitState::Open => FlutterAccountProviderState::Open,
+            CircuitState::HalfOpen => FlutterAccountProviderState::HalfOpen,
+        }
+    }
+}
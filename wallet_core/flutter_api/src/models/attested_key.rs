@@ -0,0 +1,17 @@
+use wallet::AttestedKeyContinuity;
+
+pub enum FlutterAttestedKeyContinuity {
+    Unregistered,
+    Intact,
+    Lost,
+}
+
+impl From<AttestedKeyContinuity> for FlutterAttestedKeyContinuity {
+    fn from(value: AttestedKeyContinuity) -> Self {
+        match value {
+            AttestedKeyContinuity::Unregistered => FlutterAttestedKeyContinuity::Unregistered,
+            AttestedKeyContinuity::Intact => FlutterAttestedKeyContinuity::Intact,
+            AttestedKeyContinuity::Lost => FlutterAttestedKeyContinuity::Lost,
+        }
+    }
+}
@@ -10,7 +10,9 @@
 //! models. The former option places severe limitions on how Rust can be used in `wallet-core`. The latter option is
 //! chosen here, even though it causes a lot duplication.
 
+pub mod account_provider;
 pub mod attestation;
+pub mod attested_key;
 pub mod config;
 pub mod disclosure;
 pub mod instruction;
@@ -25,6 +25,12 @@ pub enum WalletEvent {
         // ISO8601
         date_time: String,
         attestation: Attestation,
+        renewed: bool,
+    },
+    Deletion {
+        // ISO8601
+        date_time: String,
+        attestation: Attestation,
     },
 }
 
@@ -41,11 +47,16 @@ impl IntoIterator for WalletEvents {
 impl From<HistoryEvent> for WalletEvents {
     fn from(source: HistoryEvent) -> Self {
         let result = match source {
-            HistoryEvent::Issuance { timestamp, mdocs } => mdocs
+            HistoryEvent::Issuance {
+                timestamp,
+                mdocs,
+                renewed,
+            } => mdocs
                 .into_iter()
                 .map(|document| WalletEvent::Issuance {
                     date_time: timestamp.to_rfc3339(),
                     attestation: document.into(),
+                    renewed,
                 })
                 .collect(),
             HistoryEvent::Disclosure {
@@ -66,6 +77,13 @@ impl From<HistoryEvent> for WalletEvents {
                     typ: r#type.into(),
                 }]
             }
+            HistoryEvent::Deletion { timestamp, mdocs } => mdocs
+                .into_iter()
+                .map(|document| WalletEvent::Deletion {
+                    date_time: timestamp.to_rfc3339(),
+                    attestation: document.into(),
+                })
+                .collect(),
         };
         WalletEvents(result)
     }
@@ -16,7 +16,7 @@ use crate::iso::device_retrieval::ReaderAuthenticationBytes;
 use crate::iso::disclosure::DeviceResponse;
 use crate::iso::engagement::DeviceAuthenticationBytes;
 use crate::test;
-use crate::test::DebugCollapseBts;
+use crate::utils::debug::DebugCollapseBts;
 use crate::utils::serialization::CborSeq;
 use crate::utils::serialization::TaggedBytes;
 use crate::SessionTranscript;
@@ -1,5 +1,9 @@
 use derive_more::Debug;
+use error_category::ErrorCategory;
 use indexmap::IndexSet;
+use itertools::Itertools;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::holder::Mdoc;
 use crate::iso::device_retrieval::DeviceRequest;
@@ -10,7 +14,7 @@ use crate::iso::mdocs::NameSpace;
 use crate::utils::serialization::TaggedBytes;
 use crate::Document;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 #[debug("{credential_type}/{namespace}/{attribute}")]
 pub struct AttributeIdentifier {
     pub credential_type: String,
@@ -18,6 +22,87 @@ pub struct AttributeIdentifier {
     pub attribute: DataElementIdentifier,
 }
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq, ErrorCategory)]
+#[category(critical)]
+pub enum AttributeIdentifierError {
+    #[error("expected attribute identifier with 3 parts separated by '/', got {0} parts")]
+    InvalidPartsCount(usize),
+    #[error("{0} of attribute identifier is empty")]
+    EmptyPart(AttributeIdentifierPart),
+    #[error("{part} of attribute identifier contains whitespace or control characters: {value:?}")]
+    InvalidCharacters {
+        part: AttributeIdentifierPart,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum AttributeIdentifierPart {
+    CredentialType,
+    Namespace,
+    Attribute,
+}
+
+impl AttributeIdentifier {
+    /// Parse an identifier of the form `credential_type/namespace/attribute` and normalize it, see
+    /// [`Self::normalize()`]. Note that this is only suitable for identifiers of which none of the parts contain a '/',
+    /// which is officially allowed, so it should only be used for identifiers that are known not to contain one.
+    pub fn parse_normalized(source: &str) -> Result<Self, AttributeIdentifierError> {
+        let parts = source.split('/').collect_vec();
+        let [credential_type, namespace, attribute] = parts.as_slice() else {
+            return Err(AttributeIdentifierError::InvalidPartsCount(parts.len()));
+        };
+
+        Self {
+            credential_type: credential_type.to_string(),
+            namespace: namespace.to_string(),
+            attribute: attribute.to_string(),
+        }
+        .normalize()
+    }
+
+    /// Normalize this identifier by trimming surrounding whitespace from each of the parts and by converting the
+    /// namespace to lowercase, after which it is validated using [`Self::validate()`]. Namespaces are reverse domain
+    /// names, which are case insensitive and lowercase by convention, while the casing of the credential type and
+    /// attribute is significant and therefore preserved.
+    pub fn normalize(self) -> Result<Self, AttributeIdentifierError> {
+        let identifier = Self {
+            credential_type: self.credential_type.trim().to_string(),
+            namespace: self.namespace.trim().to_ascii_lowercase(),
+            attribute: self.attribute.trim().to_string(),
+        };
+        identifier.validate()?;
+
+        Ok(identifier)
+    }
+
+    /// Check that none of the parts of this identifier are empty or contain whitespace or control characters, as such
+    /// an identifier will never match the attributes of a credential.
+    pub fn validate(&self) -> Result<(), AttributeIdentifierError> {
+        [
+            (AttributeIdentifierPart::CredentialType, &self.credential_type),
+            (AttributeIdentifierPart::Namespace, &self.namespace),
+            (AttributeIdentifierPart::Attribute, &self.attribute),
+        ]
+        .into_iter()
+        .try_for_each(|(part, value)| {
+            if value.is_empty() {
+                return Err(AttributeIdentifierError::EmptyPart(part));
+            }
+
+            if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err(AttributeIdentifierError::InvalidCharacters {
+                    part,
+                    value: value.clone(),
+                });
+            }
+
+            Ok(())
+        })
+    }
+}
+
 impl IssuerSigned {
     fn attribute_identifiers(&self, doc_type: &str) -> IndexSet<AttributeIdentifier> {
         self.name_spaces
@@ -118,30 +203,109 @@ mod examples {
 #[cfg(any(test, feature = "test"))]
 mod tests {
     use super::AttributeIdentifier;
+    use super::AttributeIdentifierError;
 
-    #[derive(Debug, thiserror::Error, PartialEq, Eq)]
-    pub enum AttributeIdParsingError {
-        #[error("Expected string with 3 parts separated by '/', got {0} parts")]
-        InvalidPartsCount(usize),
-    }
-
-    // This implementation is solely intended for unit testing purposes to easily construct AttributeIdentifiers.
-    // This implementation should never end up in production code, because the use of '/' is officially allowed in the
-    // various parts.
+    // This implementation is solely intended for testing purposes to easily construct AttributeIdentifiers. It should
+    // never end up in production code, because the use of '/' is officially allowed in the various parts.
     impl std::str::FromStr for AttributeIdentifier {
-        type Err = AttributeIdParsingError;
+        type Err = AttributeIdentifierError;
 
         fn from_str(source: &str) -> Result<Self, Self::Err> {
-            let parts = source.split('/').collect::<Vec<&str>>();
-            if parts.len() != 3 {
-                return Err(AttributeIdParsingError::InvalidPartsCount(parts.len()));
+            Self::parse_normalized(source)
+        }
+    }
+}
+
+#[cfg(test)]
+mod normalization_tests {
+    use rstest::rstest;
+
+    use super::AttributeIdentifier;
+    use super::AttributeIdentifierError;
+    use super::AttributeIdentifierPart;
+
+    #[rstest]
+    #[case("com.example.pid/com.example.pid/given_name")]
+    #[case(" com.example.pid/com.example.pid/given_name ")]
+    #[case("com.example.pid /\tcom.example.pid/ given_name\n")]
+    #[case("com.example.pid/Com.Example.PID/given_name")]
+    fn test_attribute_identifier_parse_normalized(#[case] source: &str) {
+        let identifier = AttributeIdentifier::parse_normalized(source).unwrap();
+
+        assert_eq!(
+            identifier,
+            AttributeIdentifier {
+                credential_type: "com.example.pid".to_string(),
+                namespace: "com.example.pid".to_string(),
+                attribute: "given_name".to_string(),
             }
-            let result = Self {
-                credential_type: parts[0].to_owned(),
-                namespace: parts[1].to_owned(),
-                attribute: parts[2].to_owned(),
-            };
-            Ok(result)
+        );
+    }
+
+    #[test]
+    fn test_attribute_identifier_parse_normalized_preserves_casing() {
+        let identifier = AttributeIdentifier::parse_normalized("org.iso.18013.5.1.mDL/org.iso.18013.5.1/Name").unwrap();
+
+        assert_eq!(identifier.credential_type, "org.iso.18013.5.1.mDL");
+        assert_eq!(identifier.attribute, "Name");
+    }
+
+    #[test]
+    fn test_attribute_identifier_normalize() {
+        let identifier = AttributeIdentifier {
+            credential_type: " com.example.pid".to_string(),
+            namespace: "Com.Example.PID ".to_string(),
+            attribute: "\tgiven_name".to_string(),
         }
+        .normalize()
+        .unwrap();
+
+        assert_eq!(
+            identifier,
+            "com.example.pid/com.example.pid/given_name".parse().unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("", AttributeIdentifierError::InvalidPartsCount(1))]
+    #[case("com.example.pid", AttributeIdentifierError::InvalidPartsCount(1))]
+    #[case("com.example.pid/given_name", AttributeIdentifierError::InvalidPartsCount(2))]
+    #[case(
+        "com.example.pid/com.example.pid/address/street",
+        AttributeIdentifierError::InvalidPartsCount(4)
+    )]
+    #[case(
+        "/com.example.pid/given_name",
+        AttributeIdentifierError::EmptyPart(AttributeIdentifierPart::CredentialType)
+    )]
+    #[case(
+        "com.example.pid/  /given_name",
+        AttributeIdentifierError::EmptyPart(AttributeIdentifierPart::Namespace)
+    )]
+    #[case(
+        "com.example.pid/com.example.pid/",
+        AttributeIdentifierError::EmptyPart(AttributeIdentifierPart::Attribute)
+    )]
+    #[case(
+        "com.example.pid/com.example pid/given_name",
+        AttributeIdentifierError::InvalidCharacters {
+            part: AttributeIdentifierPart::Namespace,
+            value: "com.example pid".to_string(),
+        }
+    )]
+    #[case(
+        "com.example.pid/com.example.pid/given\u{0}name",
+        AttributeIdentifierError::InvalidCharacters {
+            part: AttributeIdentifierPart::Attribute,
+            value: "given\u{0}name".to_string(),
+        }
+    )]
+    fn test_attribute_identifier_parse_normalized_error(
+        #[case] source: &str,
+        #[case] expected_error: AttributeIdentifierError,
+    ) {
+        let error = AttributeIdentifier::parse_normalized(source).expect_err("parsing should fail");
+
+        assert_eq!(error, expected_error);
     }
 }
@@ -100,12 +100,24 @@ impl SessionTranscript {
     }
 
     pub fn new_oid4vp(response_uri: &BaseUrl, client_id: &str, nonce: String, mdoc_nonce: &str) -> Self {
+        Self::new_for_openid4vp(client_id, response_uri, &nonce, mdoc_nonce)
+    }
+
+    /// Construct the [`SessionTranscript`] for a disclosure over OpenID4VP, which both the wallet and the verifier
+    /// compute independently: the wallet signs it in its [`DeviceAuthentication`] and the verifier uses it when
+    /// verifying the disclosed documents. Its CBOR encoding is
+    /// `[null, null, [SHA256(CBOR([client_id, mdoc_generated_nonce])), SHA256(CBOR([response_uri,
+    /// mdoc_generated_nonce])), nonce]]`, per ISO 18013-7 (B.4.4).
+    ///
+    /// Here `nonce` is the nonce from the Authorization Request and `mdoc_generated_nonce` is the nonce generated by
+    /// the wallet, which it sends to the verifier as the `apu` parameter of the encrypted Authorization Response.
+    pub fn new_for_openid4vp(client_id: &str, response_uri: &BaseUrl, nonce: &str, mdoc_generated_nonce: &str) -> Self {
         let handover = OID4VPHandover {
-            client_id_hash: ByteBuf::from(sha256(&cbor_serialize(&[client_id, mdoc_nonce]).unwrap())),
+            client_id_hash: ByteBuf::from(sha256(&cbor_serialize(&[client_id, mdoc_generated_nonce]).unwrap())),
             response_uri_hash: ByteBuf::from(sha256(
-                &cbor_serialize(&[&response_uri.to_string(), mdoc_nonce]).unwrap(),
+                &cbor_serialize(&[&response_uri.to_string(), mdoc_generated_nonce]).unwrap(),
             )),
-            nonce,
+            nonce: nonce.to_string(),
         };
 
         SessionTranscriptKeyed {
@@ -207,6 +219,9 @@ mod test {
 
 #[cfg(test)]
 mod tests {
+    use ciborium::Value;
+    use hex_literal::hex;
+
     use crate::examples::Example;
     use crate::examples::EXAMPLE_DOC_TYPE;
     use crate::utils::serialization;
@@ -225,4 +240,59 @@ mod tests {
             DeviceAuthenticationBytes::example_bts()
         );
     }
+
+    #[test]
+    fn test_session_transcript_new_for_openid4vp() {
+        let session_transcript = SessionTranscript::new_for_openid4vp(
+            "example.com",
+            &"https://example.com/response".parse().unwrap(),
+            "nonce_1234",
+            "mdoc_nonce_1234",
+        );
+
+        let client_id_hash = sha256(&cbor_serialize(&["example.com", "mdoc_nonce_1234"]).unwrap());
+        let response_uri_hash = sha256(&cbor_serialize(&["https://example.com/response", "mdoc_nonce_1234"]).unwrap());
+        let expected = Value::Array(vec![
+            Value::Null,
+            Value::Null,
+            Value::Array(vec![
+                Value::Bytes(client_id_hash),
+                Value::Bytes(response_uri_hash),
+                Value::Text("nonce_1234".to_string()),
+            ]),
+        ]);
+
+        let encoded = cbor_serialize(&session_transcript).unwrap();
+
+        assert_eq!(encoded, cbor_serialize(&expected).unwrap());
+
+        // Pin the exact bytes, so that any change to the encoding is noticed.
+        assert_eq!(
+            encoded,
+            hex!(
+                "83f6f683582035a3e979f58a2c12f5fa66f7491ac4bdc034c9ec470042332a641905651e0b1f582099c9bd361d806089740e44"
+                "a8501dfa8dacf6c1a2daad593fcfa4b0b1443166b16a6e6f6e63655f31323334"
+            )
+        );
+    }
+
+    #[test]
+    fn test_session_transcript_new_for_openid4vp_mdoc_generated_nonce() {
+        let response_uri = "https://example.com/response".parse().unwrap();
+        let session_transcript =
+            SessionTranscript::new_for_openid4vp("example.com", &response_uri, "nonce_1234", "mdoc_nonce_1234");
+        let other_session_transcript =
+            SessionTranscript::new_for_openid4vp("example.com", &response_uri, "nonce_1234", "mdoc_nonce_5678");
+
+        // Both hashes depend on the nonce generated by the wallet.
+        let (Handover::Oid4vpHandover(CborSeq(handover)), Handover::Oid4vpHandover(CborSeq(other_handover))) =
+            (&session_transcript.0.handover, &other_session_transcript.0.handover)
+        else {
+            panic!("should be OpenID4VP handover");
+        };
+
+        assert_ne!(handover.client_id_hash, other_handover.client_id_hash);
+        assert_ne!(handover.response_uri_hash, other_handover.response_uri_hash);
+        assert_eq!(handover.nonce, other_handover.nonce);
+    }
 }
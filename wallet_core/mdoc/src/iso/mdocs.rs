@@ -20,6 +20,7 @@ use serde::Serialize;
 use serde_bytes::ByteBuf;
 use serde_with::skip_serializing_none;
 
+use wallet_common::redact::Redacted;
 use wallet_common::utils::random_bytes;
 
 use crate::unsigned::Entry;
@@ -282,13 +283,14 @@ pub type IssuerSignedItemBytes = TaggedBytes<IssuerSignedItem>;
 /// See also
 /// - [`Entry`], which contains just the name and value of the attribute,
 /// - [`Digest`] and [`DigestIDs`]: the digests (hashes) of [`IssuerSignedItem`]s, contained in the MSO.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, derive_more::Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct IssuerSignedItem {
     #[serde(rename = "digestID")]
     pub digest_id: u64,
     pub random: ByteBuf,
     pub element_identifier: DataElementIdentifier,
+    #[debug("{:?}", Redacted(element_value))]
     pub element_value: DataElementValue,
 }
 
@@ -5,6 +5,8 @@ use nutype::nutype;
 use serde::Deserialize;
 use serde::Serialize;
 
+use wallet_common::redact::Redacted;
+
 use crate::utils::serialization::TaggedBytes;
 use crate::Attributes;
 use crate::DataElementIdentifier;
@@ -43,9 +45,10 @@ pub struct UnsignedMdoc {
 ///
 /// See also [`IssuerSignedItem`](super::IssuerSignedItem), which additionally contains the attribute's `random` and
 /// `digestID`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(derive_more::Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Entry {
     pub name: DataElementIdentifier,
+    #[debug("{:?}", Redacted(value))]
     pub value: DataElementValue,
 }
 
@@ -129,9 +129,15 @@ mod tests {
     use std::ops::Add;
 
     use ciborium::Value;
+    use coset::iana;
+    use coset::HeaderBuilder;
+    use coset::ProtectedHeader;
     use indexmap::IndexMap;
+    use p256::ecdsa::signature::hazmat::PrehashSigner;
+    use p256::ecdsa::Signature;
     use p256::ecdsa::SigningKey;
     use rand_core::OsRng;
+    use ring::digest;
     use sd_jwt::metadata::TypeMetadata;
     use sd_jwt::metadata::TypeMetadataChain;
     use wallet_common::generator::TimeGenerator;
@@ -139,6 +145,7 @@ mod tests {
 
     use crate::holder::Mdoc;
     use crate::server_keys::generate::Ca;
+    use crate::server_keys::KeyPair;
     use crate::unsigned::Entry;
     use crate::unsigned::UnsignedMdoc;
     use crate::utils::cose::CoseKey;
@@ -151,13 +158,8 @@ mod tests {
     const ISSUANCE_NAME_SPACE: &str = "example_namespace";
     const ISSUANCE_ATTRS: [(&str, &str); 2] = [("first_name", "John"), ("family_name", "Doe")];
 
-    #[tokio::test]
-    async fn it_works() {
-        let ca = Ca::generate_issuer_mock_ca().unwrap();
-        let issuance_key = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
-        let trust_anchors = &[ca.to_trust_anchor()];
-
-        let unsigned = UnsignedMdoc {
+    fn example_unsigned_mdoc() -> UnsignedMdoc {
+        UnsignedMdoc {
             doc_type: ISSUANCE_DOC_TYPE.to_string(),
             copy_count: NonZeroU8::new(2).unwrap(),
             valid_from: chrono::Utc::now().into(),
@@ -174,14 +176,27 @@ mod tests {
             )])
             .try_into()
             .unwrap(),
-        };
+        }
+    }
+
+    async fn sign_unsigned_mdoc(unsigned: UnsignedMdoc, issuance_key: &KeyPair) -> IssuerSigned {
         let metadata = TypeMetadata::bsn_only_example();
         let metadata_chain = TypeMetadataChain::create(metadata, vec![]).unwrap();
 
         let device_key = CoseKey::try_from(SigningKey::random(&mut OsRng).verifying_key()).unwrap();
-        let issuer_signed = IssuerSigned::sign(unsigned.clone(), metadata_chain, device_key, &issuance_key)
+        IssuerSigned::sign(unsigned, metadata_chain, device_key, issuance_key)
             .await
-            .unwrap();
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_works() {
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let issuance_key = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
+        let trust_anchors = &[ca.to_trust_anchor()];
+
+        let unsigned = example_unsigned_mdoc();
+        let issuer_signed = sign_unsigned_mdoc(unsigned.clone(), &issuance_key).await;
 
         // The IssuerSigned should be valid
         issuer_signed
@@ -209,4 +224,36 @@ mod tests {
         .unwrap();
         mdoc.compare_unsigned(&unsigned).unwrap();
     }
+
+    #[tokio::test]
+    async fn it_verifies_es384_issuer_auth() {
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let issuance_key = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
+        let trust_anchors = &[ca.to_trust_anchor()];
+
+        let mut issuer_signed = sign_unsigned_mdoc(example_unsigned_mdoc(), &issuance_key).await;
+
+        // Sign the IssuerAuth again using ES384 instead of ES256, i.e. using SHA-384 as the hash function.
+        let cose = &mut issuer_signed.issuer_auth.0;
+        cose.protected = ProtectedHeader {
+            original_data: None,
+            header: HeaderBuilder::new().algorithm(iana::Algorithm::ES384).build(),
+        };
+        let signature: Signature = issuance_key
+            .private_key()
+            .sign_prehash(digest::digest(&digest::SHA384, &cose.tbs_data(b"")).as_ref())
+            .unwrap();
+        cose.signature = signature.to_vec();
+
+        // ES384 is allowed by default, so the IssuerSigned should be valid
+        issuer_signed
+            .verify(ValidityRequirement::Valid, &TimeGenerator, trust_anchors)
+            .unwrap();
+
+        // The signature should not verify as ES256
+        issuer_signed.issuer_auth.0.protected.header.alg = Some(coset::Algorithm::Assigned(iana::Algorithm::ES256));
+        issuer_signed
+            .verify(ValidityRequirement::Valid, &TimeGenerator, trust_anchors)
+            .expect_err("ES384 signature should not verify as ES256");
+    }
 }
@@ -13,6 +13,7 @@ use crate::identifiers::AttributeIdentifier;
 use crate::identifiers::AttributeIdentifierHolder;
 use crate::utils::x509::CertificateType;
 use crate::utils::x509::MdocCertificateExtension;
+use crate::verifier::ItemsRequests;
 use crate::ItemsRequest;
 
 use super::LocalizedStrings;
@@ -46,6 +47,20 @@ impl ReaderRegistration {
 
         Ok(())
     }
+
+    /// Returns all attributes that may be requested according to this registration, in the order in which they are
+    /// registered. Requesting any other attribute will result in [`ValidationError::UnregisteredAttributes`].
+    pub fn requestable_attributes(&self) -> Vec<AttributeIdentifier> {
+        self.attribute_identifiers().into_iter().collect()
+    }
+
+    /// Check whether all attributes in `requests` are authorized by this registration, returning the attributes that
+    /// are not, in the order in which they were requested. A wallet should refuse to disclose to an RP that requests
+    /// any attribute beyond its registration.
+    pub fn authorizes(&self, requests: &ItemsRequests) -> Result<(), Vec<AttributeIdentifier>> {
+        self.verify_requested_attributes(&requests.as_ref().iter())
+            .map_err(|ValidationError::UnregisteredAttributes(unauthorized)| unauthorized)
+    }
 }
 
 impl AttributeIdentifierHolder for ReaderRegistration {
@@ -229,6 +244,26 @@ mod tests {
         ])
     }
 
+    #[test]
+    fn requestable_attributes() {
+        let registration = create_registration(vec![
+            ("some_doctype", vec![("some_namespace", vec!["some_attribute"])]),
+            (
+                "another_doctype",
+                vec![("another_namespace", vec!["some_attribute", "another_attribute"])],
+            ),
+        ]);
+
+        assert_eq!(
+            registration.requestable_attributes(),
+            vec![
+                "some_doctype/some_namespace/some_attribute".parse().unwrap(),
+                "another_doctype/another_namespace/some_attribute".parse().unwrap(),
+                "another_doctype/another_namespace/another_attribute".parse().unwrap(),
+            ]
+        );
+    }
+
     #[test]
     fn verify_requested_attributes_in_device_request() {
         let device_request = DeviceRequest::from_items_requests(vec![
@@ -346,6 +381,38 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn authorizes_items_requests() {
+        let registration = create_some_registration();
+
+        let requests = ItemsRequests::from(vec![
+            create_items_request(vec![("some_doctype", vec![("some_namespace", vec!["some_attribute"])])]),
+            create_items_request(vec![(
+                "another_doctype",
+                vec![("another_namespace", vec!["some_attribute", "another_attribute"])],
+            )]),
+        ]);
+        registration.authorizes(&requests).unwrap();
+
+        let requests = ItemsRequests::from(vec![
+            create_items_request(vec![(
+                "some_doctype",
+                vec![("some_namespace", vec!["some_attribute", "missing_attribute"])],
+            )]),
+            create_items_request(vec![(
+                "missing_doctype",
+                vec![("some_namespace", vec!["some_attribute"])],
+            )]),
+        ]);
+        assert_eq!(
+            registration.authorizes(&requests),
+            Err(vec![
+                "some_doctype/some_namespace/missing_attribute".parse().unwrap(),
+                "missing_doctype/some_namespace/some_attribute".parse().unwrap(),
+            ])
+        );
+    }
+
     type Attributes<'a> = Vec<&'a str>;
     type Namespaces<'a> = Vec<(&'a str, Attributes<'a>)>;
     type DocTypes<'a> = Vec<(&'a str, Namespaces<'a>)>;
@@ -7,6 +7,7 @@ use chrono::DateTime;
 use chrono::Utc;
 use ciborium::value::Value;
 use coset::iana;
+use coset::iana::EnumI64;
 use coset::sig_structure_data;
 use coset::CoseMac0;
 use coset::CoseMac0Builder;
@@ -17,9 +18,11 @@ use coset::HeaderBuilder;
 use coset::Label;
 use coset::ProtectedHeader;
 use coset::SignatureContext;
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
 use p256::ecdsa::signature::Verifier;
 use p256::ecdsa::Signature;
 use p256::ecdsa::VerifyingKey;
+use ring::digest;
 use ring::hmac;
 use rustls_pki_types::TrustAnchor;
 use serde::de::DeserializeOwned;
@@ -78,6 +81,9 @@ pub enum CoseError {
     #[error("no signature received")]
     #[category(critical)]
     SignatureMissing,
+    #[error("unexpected COSE algorithm: {0:?}")]
+    #[category(critical)]
+    UnexpectedAlgorithm(Option<coset::Algorithm>),
 }
 
 impl Cose for CoseSign1 {
@@ -95,8 +101,14 @@ impl Cose for CoseSign1 {
             }
 
             let sig = &Signature::try_from(sig).map_err(CoseError::EcdsaSignatureParsingFailed)?;
-            key.verify(data, sig)
-                .map_err(CoseError::EcdsaSignatureVerificationFailed)?;
+            match self.protected.header.alg {
+                // ES384 is ECDSA using SHA-384, which we verify by hashing the data ourselves.
+                Some(coset::Algorithm::Assigned(iana::Algorithm::ES384)) => {
+                    key.verify_prehash(digest::digest(&digest::SHA384, data).as_ref(), sig)
+                }
+                _ => key.verify(data, sig),
+            }
+            .map_err(CoseError::EcdsaSignatureVerificationFailed)?;
             Ok(())
         })
     }
@@ -171,6 +183,11 @@ impl<C, T> From<C> for MdocCose<C, T> {
     }
 }
 
+/// The COSE signature algorithms that are accepted by default when verifying against trust anchors. Note that the
+/// signature is always verified using the P-256 public key from the certificate, so for ES384 this means ECDSA over
+/// P-256 using SHA-384.
+pub const DEFAULT_ALLOWED_ALGORITHMS: [iana::Algorithm; 2] = [iana::Algorithm::ES256, iana::Algorithm::ES384];
+
 /// COSE header label for `x5chain`, defined in [RFC 9360](https://datatracker.ietf.org/doc/rfc9360/).
 pub const COSE_X5CHAIN_HEADER_LABEL: i64 = 33;
 
@@ -236,8 +253,18 @@ impl<T> MdocCose<CoseSign1, T> {
         Ok(cert)
     }
 
+    /// Check that the `alg` in the protected header is one of the `allowed_algorithms`.
+    pub fn verify_algorithm(&self, allowed_algorithms: &[iana::Algorithm]) -> Result<(), CoseError> {
+        let alg = self.0.protected.header.alg.as_ref();
+        match alg {
+            Some(coset::Algorithm::Assigned(alg)) if allowed_algorithms.contains(alg) => Ok(()),
+            _ => Err(CoseError::UnexpectedAlgorithm(alg.cloned())),
+        }
+    }
+
     /// Verify the COSE against the specified trust anchors, using the certificate(s) in the `x5chain` COSE header
-    /// as intermediate certificates.
+    /// as intermediate certificates. The `alg` in the protected header has to be one of
+    /// [`DEFAULT_ALLOWED_ALGORITHMS`].
     pub fn verify_against_trust_anchors(
         &self,
         usage: CertificateUsage,
@@ -247,6 +274,24 @@ impl<T> MdocCose<CoseSign1, T> {
     where
         T: DeserializeOwned,
     {
+        self.verify_against_trust_anchors_with_algorithms(usage, time, trust_anchors, &DEFAULT_ALLOWED_ALGORITHMS)
+    }
+
+    /// Verify the COSE against the specified trust anchors, like [`MdocCose::verify_against_trust_anchors()`],
+    /// while only accepting the specified `allowed_algorithms` in the protected header.
+    pub fn verify_against_trust_anchors_with_algorithms(
+        &self,
+        usage: CertificateUsage,
+        time: &impl Generator<DateTime<Utc>>,
+        trust_anchors: &[TrustAnchor],
+        allowed_algorithms: &[iana::Algorithm],
+    ) -> Result<T, CoseError>
+    where
+        T: DeserializeOwned,
+    {
+        // Reject any unexpected algorithm before doing anything else, to prevent algorithm confusion
+        self.verify_algorithm(allowed_algorithms)?;
+
         let cert = self.signing_cert()?;
 
         // Verify the certificate against the trusted IACAs
@@ -465,6 +510,18 @@ impl From<coset::CoseKey> for CoseKey {
     }
 }
 
+impl CoseKey {
+    /// Returns the elliptic curve of this key, if its `crv` parameter contains a registered curve.
+    pub fn curve(&self) -> Option<iana::EllipticCurve> {
+        self.0.params.iter().find_map(|(label, value)| match (label, value) {
+            (Label::Int(label), Value::Integer(curve)) if *label == iana::Ec2KeyParameter::Crv as i64 => {
+                i64::try_from(*curve).ok().and_then(iana::EllipticCurve::from_i64)
+            }
+            _ => None,
+        })
+    }
+}
+
 impl coset::AsCborValue for CoseKey {
     fn from_cbor_value(value: Value) -> coset::Result<Self> {
         let deserialized = coset::CoseKey::from_cbor_value(value)?.into();
@@ -477,6 +534,9 @@ impl coset::AsCborValue for CoseKey {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+    use coset::iana;
+    use coset::CoseKeyBuilder;
     use coset::Header;
     use coset::HeaderBuilder;
     use coset::Label;
@@ -494,6 +554,7 @@ mod tests {
     use crate::utils::x509::CertificateUsage;
 
     use super::ClonePayload;
+    use super::CoseKey;
     use super::MdocCose;
 
     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -603,6 +664,52 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn cose_with_unexpected_algorithm() {
+        let ca = Ca::generate("ca.example.com", Default::default()).unwrap();
+        let issuer_key_pair = ca
+            .generate_key_pair(
+                "cert.example.com",
+                &IssuerRegistration::new_mock().into(),
+                Default::default(),
+            )
+            .unwrap();
+
+        let payload = ToyMessage::default();
+        let header = cose::new_certificate_header(issuer_key_pair.certificate());
+        let mut cose = MdocCose::sign(&payload, header, issuer_key_pair.private_key(), true)
+            .await
+            .unwrap();
+
+        // The default algorithms only include ES256 and ES384, so verification should fail for any other algorithm
+        for alg in [iana::Algorithm::ES512, iana::Algorithm::EdDSA] {
+            cose.0.protected.header.alg = Some(coset::Algorithm::Assigned(alg));
+            assert_matches!(
+                cose.verify_against_trust_anchors(CertificateUsage::Mdl, &TimeGenerator, &[ca.to_trust_anchor()]),
+                Err(CoseError::UnexpectedAlgorithm(Some(coset::Algorithm::Assigned(unexpected)))) if unexpected == alg
+            );
+        }
+
+        // A missing algorithm should be rejected as well
+        cose.0.protected.header.alg = None;
+        assert_matches!(
+            cose.verify_against_trust_anchors(CertificateUsage::Mdl, &TimeGenerator, &[ca.to_trust_anchor()]),
+            Err(CoseError::UnexpectedAlgorithm(None))
+        );
+
+        // The original algorithm should be rejected if it is not in the explicitly allowed set
+        cose.0.protected.header.alg = Some(coset::Algorithm::Assigned(iana::Algorithm::ES256));
+        assert_matches!(
+            cose.verify_against_trust_anchors_with_algorithms(
+                CertificateUsage::Mdl,
+                &TimeGenerator,
+                &[ca.to_trust_anchor()],
+                &[iana::Algorithm::ES384],
+            ),
+            Err(CoseError::UnexpectedAlgorithm(_))
+        );
+    }
+
     #[tokio::test]
     async fn remove_add_payload() {
         let key = SigningKey::random(&mut OsRng);
@@ -620,4 +727,17 @@ mod tests {
         let verified = with_payload.verify_and_parse(key.verifying_key()).unwrap();
         assert_eq!(payload, verified);
     }
+
+    #[test]
+    fn cose_key_curve() {
+        let key = SigningKey::random(&mut OsRng);
+        let cose_key = CoseKey::try_from(key.verifying_key()).unwrap();
+        assert_eq!(cose_key.curve(), Some(iana::EllipticCurve::P_256));
+
+        let cose_key = CoseKey(CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_384, vec![1], vec![2]).build());
+        assert_eq!(cose_key.curve(), Some(iana::EllipticCurve::P_384));
+
+        let cose_key = CoseKey(CoseKeyBuilder::new().build());
+        assert_eq!(cose_key.curve(), None);
+    }
 }
@@ -0,0 +1,147 @@
+use std::fmt::Debug;
+
+use regex::Captures;
+use regex::Regex;
+
+use crate::iso::disclosure::DeviceResponse;
+use crate::iso::disclosure::IssuerSigned;
+
+/// Wrapper around `T` that implements `Debug` by using `T`'s implementation,
+/// but with byte sequences (which can take a lot of vertical space) replaced with
+/// a CBOR diagnostic-like notation. Well-known CBOR tags are annotated with their meaning.
+///
+/// Example output:
+///
+/// ```text
+/// Test {
+///     a_string: "Hello, World",
+///     an_int: 42,
+///     a_byte_sequence: h'00012AFF',
+/// }
+/// ```
+///
+/// Example code:
+/// ```rust
+/// use nl_wallet_mdoc::utils::debug::DebugCollapseBts;
+///
+/// #[derive(Debug)]
+/// struct Test {
+///     a_string: String,
+///     an_int: u64,
+///     a_byte_sequence: Vec<u8>,
+/// }
+///
+/// let test = Test {
+///     a_string: "Hello, World".to_string(),
+///     an_int: 42,
+///     a_byte_sequence: vec![0, 1, 42, 255],
+/// };
+///
+/// println!("{:#?}", DebugCollapseBts::from(test));
+/// ```
+pub struct DebugCollapseBts<T>(T);
+
+impl<T> From<T> for DebugCollapseBts<T> {
+    fn from(value: T) -> Self {
+        DebugCollapseBts(value)
+    }
+}
+
+impl<T> Debug for DebugCollapseBts<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Match numbers within square brackets, e.g.: [1, 2, 3]
+        let debugstr = format!("{:#?}", self.0);
+        let debugstr_collapsed =
+            Regex::new(r"\[\s*(\d,?\s*)+]")
+                .unwrap()
+                .replace_all(debugstr.as_str(), |caps: &Captures| {
+                    let no_whitespace = remove_whitespace(&caps[0]);
+                    let trimmed = no_whitespace[1..no_whitespace.len() - 2].to_string(); // Remove square brackets
+                    if trimmed.split(',').any(|r| r.parse::<u8>().is_err()) {
+                        // If any of the numbers don't fit in a u8, just return the numbers without whitespace
+                        no_whitespace
+                    } else {
+                        format!(
+                            "h'{}'", // CBOR diagnostic-like notation
+                            hex::encode(
+                                trimmed
+                                    .split(',')
+                                    .map(|i| i.parse::<u8>().unwrap())
+                                    .collect::<Vec<u8>>(),
+                            )
+                            .to_uppercase()
+                        )
+                    }
+                });
+
+        // Match the tag number of a `ciborium::Value::Tag`, e.g.: Tag(24, ...)
+        let debugstr_annotated =
+            Regex::new(r"Tag\((\s*)(\d+),")
+                .unwrap()
+                .replace_all(&debugstr_collapsed, |caps: &Captures| match tag_description(&caps[2]) {
+                    Some(description) => format!("Tag({}{} /* {} */,", &caps[1], &caps[2], description),
+                    None => caps[0].to_string(),
+                });
+
+        write!(f, "{}", debugstr_annotated)
+    }
+}
+
+fn remove_whitespace(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Describes the CBOR tags that are commonly found in mdocs, see RFC 8949 and ISO 18013-5.
+fn tag_description(tag: &str) -> Option<&'static str> {
+    match tag {
+        "0" => Some("date/time string"),
+        "1" => Some("epoch-based date/time"),
+        "24" => Some("encoded CBOR data item"),
+        "1004" => Some("full-date string"),
+        _ => None,
+    }
+}
+
+/// Render a [`DeviceResponse`] in a human-readable way, for debugging purposes.
+pub fn pretty_print(device_response: &DeviceResponse) -> String {
+    format!("{:#?}", DebugCollapseBts::from(device_response))
+}
+
+/// Render an [`IssuerSigned`] in a human-readable way, for debugging purposes.
+pub fn pretty_print_issuer_signed(issuer_signed: &IssuerSigned) -> String {
+    format!("{:#?}", DebugCollapseBts::from(issuer_signed))
+}
+
+#[cfg(test)]
+mod tests {
+    use ciborium::Value;
+
+    use crate::examples::Example;
+    use crate::iso::disclosure::DeviceResponse;
+
+    use super::pretty_print;
+    use super::DebugCollapseBts;
+
+    #[test]
+    fn test_debug_collapse_bts() {
+        let value = (
+            vec![0u8, 1, 42, 255],
+            Value::Tag(1004, Value::Text("2024-01-01".to_string()).into()),
+        );
+        let output = format!("{:?}", DebugCollapseBts::from(&value));
+
+        assert!(output.contains("h'00012AFF'"));
+        assert!(output.contains("1004 /* full-date string */,"));
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let output = pretty_print(&DeviceResponse::example());
+
+        assert!(output.starts_with("DeviceResponse {"));
+        assert!(output.contains("h'"));
+    }
+}
@@ -5,5 +5,8 @@ pub mod x509;
 
 pub mod crypto;
 
+#[cfg(any(test, feature = "debug"))]
+pub mod debug;
+
 pub use auth::issuer_auth;
 pub use auth::reader_auth;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use base64::prelude::*;
 use chrono::DateTime;
 use chrono::Utc;
 use derive_more::Debug;
@@ -35,6 +36,7 @@ use yoke::Yokeable;
 
 use error_category::ErrorCategory;
 use wallet_common::generator::Generator;
+use wallet_common::utils::sha256;
 
 use super::issuer_auth::IssuerRegistration;
 use super::reader_auth::ReaderRegistration;
@@ -190,6 +192,23 @@ impl BorrowingCertificate {
         self.as_ref().to_vec()
     }
 
+    /// Returns the SHA-256 fingerprint of the certificate, which is computed over the DER encoding of the entire
+    /// certificate.
+    pub fn fingerprint_sha256(&self) -> [u8; 32] {
+        // Unwrap is safe here, as a SHA-256 digest is always 32 bytes long.
+        sha256(self.as_ref()).try_into().unwrap()
+    }
+
+    /// Returns the SHA-256 fingerprint of the certificate as a lowercase hex string, for display purposes.
+    pub fn fingerprint_sha256_hex(&self) -> String {
+        hex::encode(self.fingerprint_sha256())
+    }
+
+    /// Returns the SHA-256 fingerprint of the certificate as a standard base64 string, for display purposes.
+    pub fn fingerprint_sha256_base64(&self) -> String {
+        BASE64_STANDARD.encode(self.fingerprint_sha256())
+    }
+
     pub fn subject(&self) -> Result<IndexMap<String, &str>, CertificateError> {
         self.x509_certificate()
             .subject
@@ -404,6 +423,7 @@ mod test {
     use chrono::DateTime;
     use chrono::Duration;
     use chrono::Utc;
+    use hex_literal::hex;
     use p256::pkcs8::ObjectIdentifier;
     use time::macros::datetime;
     use time::OffsetDateTime;
@@ -611,6 +631,35 @@ mod test {
         assert_certificate_validity(x509_cert, now, later);
     }
 
+    #[test]
+    fn certificate_fingerprint_sha256() {
+        // The reader root CA certificate from the ISO 18013-5 examples.
+        let certificate = BorrowingCertificate::from_der(hex!(
+            "3082019030820137a003020102021430d747795405d564b7ac48be6f364ae2c774f2fc300a06082a8648ce3d04030230163114301\
+             206035504030c0b72656164657220726f6f74301e170d3230313030313030303030305a170d3239303932393030303030305a3016\
+             3114301206035504030c0b72656164657220726f6f743059301306072a8648ce3d020106082a8648ce3d030107034200043643293\
+             832e0a480de592df0708fe25b6b923f6397ab39a8b1b7444593adb89c77b7e9c28cf48d6d187b43c9bf7b9c2c5c5ef22f329e44e7\
+             a91b4745b7e2063aa3633061301c0603551d1f041530133011a00fa00d820b6578616d706c652e636f6d301d0603551d0e0416041\
+             4cfb7a881baea5f32b6fb91cc29590c50dfac416e300e0603551d0f0101ff04040302010630120603551d130101ff040830060101\
+             ff020100300a06082a8648ce3d0403020347003044022018ac84baf991a614fb25e76241857b7fd0579dfe8aed8ac7f1306754907\
+             99930022077f46f00b4af3e014d253e0edcc9f146a75a6b1bdfe33e9fa72f30f0880d5237"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            certificate.fingerprint_sha256(),
+            hex!("1f0801e02ea08702182b9a41dfaefb8739f0213001b05d27893fd7cf754e37ba")
+        );
+        assert_eq!(
+            certificate.fingerprint_sha256_hex(),
+            "1f0801e02ea08702182b9a41dfaefb8739f0213001b05d27893fd7cf754e37ba"
+        );
+        assert_eq!(
+            certificate.fingerprint_sha256_base64(),
+            "HwgB4C6ghwIYK5pB3677hznwITABsF0niT/Xz3VON7o="
+        );
+    }
+
     fn assert_certificate_default_validity(certificate: &X509Certificate) {
         let not_before = certificate.validity().not_before.to_datetime();
         let not_after = certificate.validity().not_after.to_datetime();
@@ -1,7 +1,9 @@
 //! RP software, for verifying mdoc disclosures, see [`DeviceResponse::verify()`].
 
 use chrono::DateTime;
+use chrono::Duration;
 use chrono::Utc;
+use coset::iana;
 use derive_more::AsRef;
 use indexmap::IndexMap;
 use p256::SecretKey;
@@ -15,8 +17,10 @@ use tracing::debug;
 use tracing::warn;
 
 use wallet_common::generator::Generator;
+use wallet_common::redact::Redacted;
 
 use crate::identifiers::AttributeIdentifier;
+use crate::identifiers::AttributeIdentifierError;
 use crate::identifiers::AttributeIdentifierHolder;
 use crate::iso::*;
 use crate::utils::cose::ClonePayload;
@@ -32,18 +36,38 @@ use crate::Result;
 /// Attributes of an mdoc that was disclosed in a [`DeviceResponse`], as computed by [`DeviceResponse::verify()`].
 /// Grouped per namespace. Validity information and the attributes issuer's common_name is also included.
 #[serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(derive_more::Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DocumentDisclosedAttributes {
     #[serde_as(as = "IfIsHumanReadable<IndexMap<_, IndexMap<_, FromInto<JsonCborValue>>>>")]
+    #[debug("{:?}", Redacted(attributes))]
     pub attributes: IndexMap<NameSpace, IndexMap<DataElementIdentifier, DataElementValue>>,
     pub issuer: String,
     pub ca: String,
     pub validity_info: ValidityInfo,
 }
+
+impl DocumentDisclosedAttributes {
+    /// Sort the attributes within each namespace by their identifier. By default the attributes are in the order in
+    /// which the holder disclosed them, which may differ between disclosures of the same attributes. Sorting them
+    /// makes the output reproducible, e.g. when processing or storing it.
+    pub fn sort_attributes(&mut self) {
+        self.attributes
+            .values_mut()
+            .for_each(|namespace_attributes| namespace_attributes.sort_keys());
+    }
+}
+
 /// All attributes that were disclosed in a [`DeviceResponse`], as computed by [`DeviceResponse::verify()`].
 pub type DisclosedAttributes = IndexMap<DocType, DocumentDisclosedAttributes>;
 
+/// The elliptic curves that are accepted for the device key of a disclosed mdoc by [`DeviceResponse::verify()`].
+pub const DEFAULT_ALLOWED_DEVICE_CURVES: &[iana::EllipticCurve] = &[iana::EllipticCurve::P_256];
+
+/// The default maximum time between now and the start of the validity of an mdoc that is not yet valid, see
+/// [`ValidityInfo::verify_valid_from_within()`].
+pub const DEFAULT_MAX_FUTURE_VALIDITY: Duration = Duration::days(365);
+
 #[derive(thiserror::Error, Debug)]
 pub enum VerificationError {
     #[error("errors in device response: {0:#?}")]
@@ -70,6 +94,8 @@ pub enum VerificationError {
     UnexpectedCACommonNameCount(usize),
     #[error("unexpected amount of Common Names in issuer certificate: expected 1, found {0}")]
     UnexpectedIssuerCommonNameCount(usize),
+    #[error("device key uses a disallowed curve: {0:?}")]
+    DisallowedDeviceCurve(Option<iana::EllipticCurve>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, AsRef)]
@@ -81,6 +107,43 @@ impl From<Vec<ItemsRequest>> for ItemsRequests {
 }
 
 impl ItemsRequests {
+    /// Normalizes all requested attribute identifiers, see [`AttributeIdentifier::normalize()`], so that a malformed
+    /// request is rejected early rather than resulting in missing attributes during verification. Attributes that are
+    /// requested more than once after normalization are merged, retaining the attribute if any of them intends to.
+    pub fn normalize_attribute_identifiers(self) -> std::result::Result<Self, AttributeIdentifierError> {
+        let items_requests = self
+            .0
+            .into_iter()
+            .map(|items_request| {
+                let mut name_spaces = NameSpaces::new();
+                for (namespace, attributes) in items_request.name_spaces {
+                    for (attribute, intent_to_retain) in attributes {
+                        let identifier = AttributeIdentifier {
+                            credential_type: items_request.doc_type.clone(),
+                            namespace: namespace.clone(),
+                            attribute,
+                        }
+                        .normalize()?;
+
+                        *name_spaces
+                            .entry(identifier.namespace)
+                            .or_default()
+                            .entry(identifier.attribute)
+                            .or_default() |= intent_to_retain;
+                    }
+                }
+
+                Ok(ItemsRequest {
+                    doc_type: items_request.doc_type.trim().to_string(),
+                    name_spaces,
+                    request_info: items_request.request_info,
+                })
+            })
+            .collect::<std::result::Result<_, AttributeIdentifierError>>()?;
+
+        Ok(Self(items_requests))
+    }
+
     /// Checks that all `requested` attributes are disclosed in this [`DeviceResponse`].
     pub fn match_against_response(&self, device_response: &DeviceResponse) -> Result<()> {
         let not_found: Vec<_> = self
@@ -116,12 +179,34 @@ impl DeviceResponse {
     ///   signed by the holder.
     /// - `time` - a generator of the current time.
     /// - `trust_anchors` - trust anchors against which verification is done.
+    ///
+    /// The device keys of the disclosed mdocs have to use one of the [`DEFAULT_ALLOWED_DEVICE_CURVES`], see
+    /// [`DeviceResponse::verify_with_allowed_device_curves()`] to configure this.
     pub fn verify(
         &self,
         eph_reader_key: Option<&SecretKey>,
         session_transcript: &SessionTranscript,
         time: &impl Generator<DateTime<Utc>>,
         trust_anchors: &[TrustAnchor],
+    ) -> Result<DisclosedAttributes> {
+        self.verify_with_allowed_device_curves(
+            eph_reader_key,
+            session_transcript,
+            time,
+            trust_anchors,
+            DEFAULT_ALLOWED_DEVICE_CURVES,
+        )
+    }
+
+    /// Verify a [`DeviceResponse`] like [`DeviceResponse::verify()`], additionally requiring the device key of each
+    /// disclosed mdoc to use one of the `allowed_device_curves`.
+    pub fn verify_with_allowed_device_curves(
+        &self,
+        eph_reader_key: Option<&SecretKey>,
+        session_transcript: &SessionTranscript,
+        time: &impl Generator<DateTime<Utc>>,
+        trust_anchors: &[TrustAnchor],
+        allowed_device_curves: &[iana::EllipticCurve],
     ) -> Result<DisclosedAttributes> {
         if let Some(errors) = &self.document_errors {
             return Err(VerificationError::DeviceResponseErrors(errors.clone()).into());
@@ -138,7 +223,13 @@ impl DeviceResponse {
         for doc in self.documents.as_ref().unwrap() {
             debug!("verifying document with doc_type: {}", doc.doc_type);
             let (doc_type, doc_attrs) = doc
-                .verify(eph_reader_key, session_transcript, time, trust_anchors)
+                .verify_with_allowed_device_curves(
+                    eph_reader_key,
+                    session_transcript,
+                    time,
+                    trust_anchors,
+                    allowed_device_curves,
+                )
                 .map_err(|e| {
                     warn!("document verification failed: {e}");
                     e
@@ -159,6 +250,8 @@ pub enum ValidityError {
     NotYetValid(String),
     #[error("expired at {0}")]
     Expired(String),
+    #[error("valid from {0}, which is too far in the future")]
+    TooFarInFuture(String),
 }
 
 /// Indicate how a [`ValidityInfo`] should be verified against the current date.
@@ -166,7 +259,8 @@ pub enum ValidityError {
 pub enum ValidityRequirement {
     /// The [`ValidityInfo`] must not be expired, but it is allowed to be not yet valid.
     AllowNotYetValid,
-    /// The [`ValidityInfo`] must be valid now and not be expired.
+    /// The [`ValidityInfo`] must be valid now and not be expired. Note that this implies that the validity does not
+    /// start in the future, so [`ValidityInfo::verify_valid_from_within()`] does not need to be checked separately.
     Valid,
 }
 
@@ -184,6 +278,20 @@ impl ValidityInfo {
             Ok(())
         }
     }
+
+    /// Verify that the validity does not start more than `max_future_validity` after `time`. A validity that starts
+    /// that far in the future is implausible and indicates an error in the date handling of the issuer.
+    pub fn verify_valid_from_within(
+        &self,
+        time: DateTime<Utc>,
+        max_future_validity: Duration,
+    ) -> std::result::Result<(), ValidityError> {
+        if DateTime::<Utc>::try_from(&self.valid_from)? > time + max_future_validity {
+            Err(ValidityError::TooFarInFuture(self.valid_from.0 .0.clone()))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl IssuerSigned {
@@ -278,6 +386,25 @@ impl Document {
         session_transcript: &SessionTranscript,
         time: &impl Generator<DateTime<Utc>>,
         trust_anchors: &[TrustAnchor],
+    ) -> Result<(DocType, DocumentDisclosedAttributes)> {
+        self.verify_with_allowed_device_curves(
+            eph_reader_key,
+            session_transcript,
+            time,
+            trust_anchors,
+            DEFAULT_ALLOWED_DEVICE_CURVES,
+        )
+    }
+
+    /// Verify this [`Document`] like [`Document::verify()`], requiring its device key to use one of the
+    /// `allowed_device_curves`.
+    pub fn verify_with_allowed_device_curves(
+        &self,
+        eph_reader_key: Option<&SecretKey>,
+        session_transcript: &SessionTranscript,
+        time: &impl Generator<DateTime<Utc>>,
+        trust_anchors: &[TrustAnchor],
+        allowed_device_curves: &[iana::EllipticCurve],
     ) -> Result<(DocType, DocumentDisclosedAttributes)> {
         debug!("verifying document with doc_type: {:?}", &self.doc_type);
         debug!("verify issuer_signed");
@@ -300,6 +427,12 @@ impl Document {
         debug!("serializing device_authentication");
         let device_authentication_bts = cbor_serialize(&TaggedBytes(CborSeq(device_authentication)))?;
 
+        debug!("checking device_key curve");
+        let device_curve = mso.device_key_info.device_key.curve();
+        if !device_curve.is_some_and(|curve| allowed_device_curves.contains(&curve)) {
+            return Err(VerificationError::DisallowedDeviceCurve(device_curve).into());
+        }
+
         debug!("extracting device_key");
         let device_key = (&mso.device_key_info.device_key).try_into()?;
         match &self.device_signed.device_auth {
@@ -342,6 +475,7 @@ impl ItemsRequest {
 mod tests {
     use std::ops::Add;
 
+    use assert_matches::assert_matches;
     use chrono::Duration;
     use chrono::Utc;
     use rstest::rstest;
@@ -357,7 +491,7 @@ mod tests {
     use crate::examples::EXAMPLE_NAMESPACE;
     use crate::identifiers::AttributeIdentifierHolder;
     use crate::test;
-    use crate::test::DebugCollapseBts;
+    use crate::utils::debug::DebugCollapseBts;
     use crate::DeviceAuthenticationBytes;
     use crate::DeviceResponse;
     use crate::Document;
@@ -406,6 +540,23 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn validity_info_valid_from_within() {
+        let now = Utc::now();
+
+        new_validity_info(-1, 1)
+            .verify_valid_from_within(now, Duration::days(7))
+            .unwrap();
+        new_validity_info(6, 8)
+            .verify_valid_from_within(now, Duration::days(7))
+            .unwrap();
+
+        assert!(matches!(
+            new_validity_info(8, 10).verify_valid_from_within(now, Duration::days(7)),
+            Err(ValidityError::TooFarInFuture(_))
+        ));
+    }
+
     /// Verify the example disclosure from the standard.
     #[test]
     fn verify_iso_example_disclosure() {
@@ -446,6 +597,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn document_disclosed_attributes_sort_attributes() {
+        let mut disclosed_attributes = DocumentDisclosedAttributes {
+            attributes: IndexMap::from([(
+                EXAMPLE_NAMESPACE.to_string(),
+                IndexMap::from([
+                    (
+                        "family_name".to_string(),
+                        DataElementValue::Text("De Bruijn".to_string()),
+                    ),
+                    (
+                        "birth_date".to_string(),
+                        DataElementValue::Text("1997-05-10".to_string()),
+                    ),
+                    ("given_name".to_string(), DataElementValue::Text("Willeke".to_string())),
+                ]),
+            )]),
+            issuer: "issuer".to_string(),
+            ca: "ca".to_string(),
+            validity_info: new_validity_info(-1, 1),
+        };
+
+        disclosed_attributes.sort_attributes();
+
+        let identifiers = disclosed_attributes.attributes[EXAMPLE_NAMESPACE]
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(identifiers, ["birth_date", "family_name", "given_name"]);
+    }
+
+    #[test]
+    fn verify_iso_example_disclosure_disallowed_device_curve() {
+        let device_response = DeviceResponse::example();
+        let eph_reader_key = Examples::ephemeral_reader_key();
+        let trust_anchors = Examples::iaca_trust_anchors();
+        let session_transcript = &DeviceAuthenticationBytes::example().0 .0.session_transcript;
+
+        // The device key of the example mdoc uses P-256, which is accepted explicitly.
+        device_response
+            .verify_with_allowed_device_curves(
+                Some(&eph_reader_key),
+                session_transcript,
+                &IsoCertTimeGenerator,
+                trust_anchors,
+                &[iana::EllipticCurve::P_256, iana::EllipticCurve::P_384],
+            )
+            .expect("verifying device response with P-256 device key should succeed");
+
+        // Requiring a P-384 device key should result in an error.
+        let error = device_response
+            .verify_with_allowed_device_curves(
+                Some(&eph_reader_key),
+                session_transcript,
+                &IsoCertTimeGenerator,
+                trust_anchors,
+                &[iana::EllipticCurve::P_384],
+            )
+            .expect_err("verifying device response with P-256 device key should fail");
+
+        assert_matches!(
+            error,
+            Error::Verification(VerificationError::DisallowedDeviceCurve(Some(
+                iana::EllipticCurve::P_256
+            )))
+        );
+    }
+
     #[rstest]
     #[case(do_nothing())]
     #[case(swap_attributes())]
@@ -1,6 +1,7 @@
 use std::num::NonZeroU8;
 use std::num::TryFromIntError;
 
+use derive_more::Debug;
 use indexmap::IndexMap;
 use serde::Deserialize;
 use serde::Serialize;
@@ -11,19 +12,20 @@ use nl_wallet_mdoc::unsigned::UnsignedAttributesError;
 use nl_wallet_mdoc::unsigned::UnsignedMdoc;
 use nl_wallet_mdoc::DataElementValue;
 use nl_wallet_mdoc::Tdate;
+use wallet_common::redact::Redacted;
 use wallet_common::vec_at_least::VecNonEmpty;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum AttributeValue {
-    Number(i64),
-    Bool(bool),
-    Text(String),
+    Number(#[debug("{:?}", Redacted(_0))] i64),
+    Bool(#[debug("{:?}", Redacted(_0))] bool),
+    Text(#[debug("{:?}", Redacted(_0))] String),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum AttributeError {
-    #[error("unable to convert mdoc value: {0:?}")]
+    #[error("unable to convert mdoc value: {:?}", Redacted(.0))]
     FromCborConversion(DataElementValue),
 
     #[error("unable to convert number to cbor: {0}")]
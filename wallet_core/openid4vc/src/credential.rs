@@ -1,8 +1,11 @@
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::num::NonZeroUsize;
 
-use futures::future::try_join_all;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
 use nutype::nutype;
 use sd_jwt::metadata::SpecOptionalImplRequired;
 use serde::Deserialize;
@@ -115,7 +118,20 @@ pub struct CredentialResponses {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "format", rename_all = "snake_case")]
 pub enum CredentialResponse {
-    MsoMdoc { credential: Box<CborBase64<IssuerSigned>> },
+    MsoMdoc {
+        credential: Box<CborBase64<IssuerSigned>>,
+        /// Identifies the issued credential in subsequent notifications to the issuer, see [`NotificationRequest`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        notification_id: Option<String>,
+    },
+}
+
+impl CredentialResponse {
+    pub fn notification_id(&self) -> Option<&str> {
+        match self {
+            CredentialResponse::MsoMdoc { notification_id, .. } => notification_id.as_deref(),
+        }
+    }
 }
 
 impl CredentialFormat for CredentialResponse {
@@ -126,6 +142,31 @@ impl CredentialFormat for CredentialResponse {
     }
 }
 
+/// <https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0-13.html#section-10.1>.
+/// Sent JSON-encoded to `POST /notification`.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NotificationRequest {
+    pub notification_id: String,
+    pub event: NotificationEvent,
+    pub event_description: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    CredentialAccepted,
+    CredentialFailure,
+    CredentialDeleted,
+}
+
+/// <https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0-14.html#section-7.2>.
+/// Received JSON-encoded in response to `POST` to the Nonce Endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NonceResponse {
+    pub c_nonce: String,
+}
+
 pub const OPENID4VCI_VC_POP_JWT_TYPE: &str = "openid4vci-proof+jwt";
 
 impl CredentialRequestProof {
@@ -135,6 +176,7 @@ impl CredentialRequestProof {
         credential_issuer_identifier: BaseUrl,
         number_of_keys: u64,
         key_factory: &impl KeyFactory<Key = K>,
+        concurrency_limit: NonZeroUsize,
     ) -> Result<Vec<(K, CredentialRequestProof)>, IssuanceSessionError> {
         let keys = key_factory
             .generate_new_multiple(number_of_keys)
@@ -147,14 +189,17 @@ impl CredentialRequestProof {
             credential_issuer_identifier.as_ref().to_string(),
         );
 
-        let keys_and_jwt_payloads = try_join_all(keys.into_iter().map(|privkey| async {
-            let header = jwk_jwt_header(OPENID4VCI_VC_POP_JWT_TYPE, &privkey).await?;
-            let payload = payload.clone();
-            Ok::<_, IssuanceSessionError>((privkey, (payload, header)))
-        }))
-        .await?;
-
-        let keys_and_proofs = Jwt::sign_bulk(keys_and_jwt_payloads, key_factory)
+        let keys_and_jwt_payloads = stream::iter(keys)
+            .map(|privkey| async {
+                let header = jwk_jwt_header(OPENID4VCI_VC_POP_JWT_TYPE, &privkey).await?;
+                let payload = payload.clone();
+                Ok::<_, IssuanceSessionError>((privkey, (payload, header)))
+            })
+            .buffered(concurrency_limit.get())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let keys_and_proofs = Jwt::sign_bulk(keys_and_jwt_payloads, key_factory, concurrency_limit)
             .await?
             .into_iter()
             .map(|(key, jwt)| (key, CredentialRequestProof::Jwt { jwt }))
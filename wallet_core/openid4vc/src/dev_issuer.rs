@@ -0,0 +1,148 @@
+//! Support for accepting a single self-signed issuer certificate during issuance, for local development against an
+//! issuer that does not chain to any of the configured trust anchors. The certificate is pinned by its hex encoded
+//! SHA-256 fingerprint, which is read from the `DEV_SELF_SIGNED_ISSUER_SHA256` environment variable at compile time.
+//!
+//! This module is only compiled when the `insecure_dev_issuer` feature is enabled, which is refused for release builds.
+
+#[cfg(not(debug_assertions))]
+compile_error!("the `insecure_dev_issuer` feature must never be enabled in release builds");
+
+use rustls_pki_types::TrustAnchor;
+use tracing::error;
+use tracing::warn;
+
+use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use wallet_common::trust_anchor::BorrowingTrustAnchor;
+
+use crate::credential_formats::CredentialFormats;
+use crate::token::CredentialPreview;
+
+const DEV_SELF_SIGNED_ISSUER_SHA256: Option<&str> = option_env!("DEV_SELF_SIGNED_ISSUER_SHA256");
+
+/// Returns `trust_anchors`, extended with the issuer certificate from the credential previews that matches the
+/// configured fingerprint, provided that certificate is self-signed.
+pub(crate) fn with_self_signed_issuer<'a>(
+    trust_anchors: &[TrustAnchor<'a>],
+    credential_previews: &[CredentialFormats<CredentialPreview>],
+) -> Vec<TrustAnchor<'a>> {
+    match DEV_SELF_SIGNED_ISSUER_SHA256 {
+        Some(fingerprint) => with_self_signed_issuer_fingerprint(trust_anchors, credential_previews, fingerprint),
+        None => trust_anchors.to_vec(),
+    }
+}
+
+fn with_self_signed_issuer_fingerprint<'a>(
+    trust_anchors: &[TrustAnchor<'a>],
+    credential_previews: &[CredentialFormats<CredentialPreview>],
+    fingerprint: &str,
+) -> Vec<TrustAnchor<'a>> {
+    let Ok(fingerprint) = hex::decode(fingerprint) else {
+        error!("INSECURE: ignoring self-signed issuer fingerprint, as it is not valid hex: {fingerprint}");
+
+        return trust_anchors.to_vec();
+    };
+
+    let self_signed_anchors = credential_previews
+        .iter()
+        .flat_map(|formats| formats.as_ref().as_slice())
+        .filter_map(|preview| {
+            let CredentialPreview::MsoMdoc { issuer_certificate, .. } = preview;
+
+            (issuer_certificate.fingerprint_sha256().as_slice() == fingerprint).then_some(issuer_certificate)
+        })
+        .filter_map(|issuer_certificate| {
+            if !is_self_signed(issuer_certificate) {
+                error!("INSECURE: issuer certificate matches the configured fingerprint, but is not self-signed");
+
+                return None;
+            }
+
+            let trust_anchor = BorrowingTrustAnchor::from_der(issuer_certificate.to_vec())
+                .inspect_err(|error| error!("INSECURE: could not use self-signed issuer certificate: {error}"))
+                .ok()?;
+
+            warn!(
+                "INSECURE: accepting self-signed issuer certificate with fingerprint {}, this should only ever happen \
+                 during local development",
+                issuer_certificate.fingerprint_sha256_hex()
+            );
+
+            Some(trust_anchor.to_owned_trust_anchor())
+        });
+
+    trust_anchors.iter().cloned().chain(self_signed_anchors).collect()
+}
+
+fn is_self_signed(certificate: &BorrowingCertificate) -> bool {
+    let x509_certificate = certificate.x509_certificate();
+
+    x509_certificate.issuer().as_raw() == x509_certificate.subject().as_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use nl_wallet_mdoc::server_keys::generate::Ca;
+    use nl_wallet_mdoc::test::data;
+    use nl_wallet_mdoc::unsigned::UnsignedMdoc;
+    use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+    use sd_jwt::metadata::TypeMetadata;
+    use sd_jwt::metadata::TypeMetadataChain;
+    use wallet_common::vec_at_least::VecNonEmpty;
+
+    use crate::credential_formats::CredentialFormats;
+    use crate::token::CredentialPreview;
+
+    use super::with_self_signed_issuer_fingerprint;
+
+    fn credential_previews(issuer_certificate: BorrowingCertificate) -> Vec<CredentialFormats<CredentialPreview>> {
+        let preview = CredentialPreview::MsoMdoc {
+            unsigned_mdoc: UnsignedMdoc::from(data::pid_family_name().into_first().unwrap()),
+            issuer_certificate,
+            metadata_chain: TypeMetadataChain::create(TypeMetadata::bsn_only_example(), vec![]).unwrap(),
+        };
+
+        vec![CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap()]
+    }
+
+    #[test]
+    fn test_with_self_signed_issuer_fingerprint() {
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let trust_anchors = vec![ca.to_trust_anchor()];
+
+        // A root CA certificate is self-signed, so it is used to stand in for a self-signed issuer certificate.
+        let self_signed_ca = Ca::generate_issuer_mock_ca().unwrap();
+        let self_signed_certificate =
+            BorrowingCertificate::from_der(self_signed_ca.as_certificate_der().to_vec()).unwrap();
+        let fingerprint = self_signed_certificate.fingerprint_sha256_hex();
+
+        // The self-signed certificate is only added when its fingerprint matches.
+        let extended = with_self_signed_issuer_fingerprint(
+            &trust_anchors,
+            &credential_previews(self_signed_certificate.clone()),
+            &fingerprint,
+        );
+        assert_eq!(extended, vec![ca.to_trust_anchor(), self_signed_ca.to_trust_anchor()]);
+
+        let extended = with_self_signed_issuer_fingerprint(
+            &trust_anchors,
+            &credential_previews(self_signed_certificate.clone()),
+            &hex::encode([0; 32]),
+        );
+        assert_eq!(extended, trust_anchors);
+
+        // An invalid fingerprint is ignored.
+        let extended = with_self_signed_issuer_fingerprint(
+            &trust_anchors,
+            &credential_previews(self_signed_certificate),
+            "not hex",
+        );
+        assert_eq!(extended, trust_anchors);
+
+        // A certificate that is not self-signed is never added, even when its fingerprint matches.
+        let issuer_certificate = ca.generate_issuer_mock(None).unwrap().certificate().clone();
+        let fingerprint = issuer_certificate.fingerprint_sha256_hex();
+        let extended =
+            with_self_signed_issuer_fingerprint(&trust_anchors, &credential_previews(issuer_certificate), &fingerprint);
+        assert_eq!(extended, trust_anchors);
+    }
+}
@@ -39,6 +39,7 @@ use crate::openid4vp::VpAuthorizationRequest;
 use crate::openid4vp::VpAuthorizationResponse;
 use crate::openid4vp::VpRequestUriObject;
 use crate::openid4vp::VpResponse;
+use crate::openid4vp::VpResponseMode;
 use crate::openid4vp::WalletRequest;
 use crate::verifier::SessionType;
 use crate::verifier::VerifierUrlParameters;
@@ -65,6 +66,9 @@ pub enum VpClientError {
     #[error("no reader registration in RP certificate")]
     #[category(critical)]
     MissingReaderRegistration,
+    #[error("unsupported response mode: {0}, only direct_post.jwt is supported")]
+    #[category(critical)]
+    UnsupportedResponseMode(VpResponseMode),
     #[error("error validating requested attributes: {0}")]
     RequestedAttributesValidation(#[from] ValidationError),
     #[error("error matching requested attributes against mdocs: {0}")]
@@ -275,7 +279,7 @@ impl VpMessageClient for HttpVpMessageClient {
     ) -> Result<Option<BaseUrl>, VpMessageClientError> {
         self.http_client
             .post(url.into_inner())
-            .form(&VpToken { vp_token: jwe })
+            .form(&VpToken::new_encrypted(jwe))
             .send()
             .map_err(VpMessageClientError::from)
             .and_then(|response| async {
@@ -511,6 +515,7 @@ where
         let error_code = match error {
             VpClientError::IncorrectClientId { .. }
             | VpClientError::MissingReaderRegistration
+            | VpClientError::UnsupportedResponseMode(_)
             | VpClientError::RequestedAttributesValidation(_)
             | VpClientError::AuthRequestValidation(_)
             | VpClientError::Request(VpMessageClientError::Json(_))
@@ -549,6 +554,8 @@ where
     {
         // The `client_id` in the Authorization Request, which has been authenticated, has to equal
         // the `client_id` that the RP sent in the Request URI object at the start of the session.
+        // As `VpAuthorizationRequest::validate()` already checked that this `client_id` equals the DNS SAN
+        // of the RP certificate, this ensures that a tampered Request URI cannot redirect disclosure to another RP.
         if auth_request.client_id != request_uri_object.client_id {
             return Err(VpClientError::IncorrectClientId {
                 expected: request_uri_object.client_id.clone(),
@@ -556,14 +563,21 @@ where
             });
         }
 
+        // The `direct_post` response mode offers no way of including a PoA, so we only support `direct_post.jwt`.
+        if auth_request.response_mode != VpResponseMode::DirectPostJwt {
+            return Err(VpClientError::UnsupportedResponseMode(auth_request.response_mode));
+        }
+
         // Extract `ReaderRegistration` from the certificate.
         let reader_registration = match CertificateType::from_certificate(certificate)? {
             CertificateType::ReaderAuth(Some(reader_registration)) => *reader_registration,
             _ => return Err(VpClientError::MissingReaderRegistration),
         };
 
-        // Verify that the requested attributes are included in the reader authentication.
-        reader_registration.verify_requested_attributes(&auth_request.items_requests.as_ref().iter())?;
+        // Refuse to proceed if the RP requests attributes that are not included in its reader registration.
+        reader_registration
+            .authorizes(&auth_request.items_requests)
+            .map_err(ValidationError::UnregisteredAttributes)?;
 
         // Fetch documents from the database, calculate which ones satisfy the request and
         // formulate proposals for those documents. If there is a mismatch, return an error.
@@ -783,6 +797,7 @@ mod tests {
     use wallet_common::utils::random_string;
     use wallet_common::vec_at_least::VecAtLeastTwoUnique;
 
+    use crate::authorization::ResponseMode;
     use crate::jwt::JwtX5cError;
     use crate::openid4vp::AuthRequestValidationError;
     use crate::openid4vp::VerifiablePresentation;
@@ -790,6 +805,7 @@ mod tests {
     use crate::openid4vp::VpClientMetadata;
     use crate::openid4vp::VpJwks;
     use crate::openid4vp::VpRequestUriObject;
+    use crate::openid4vp::VpResponseMode;
     use crate::test::disclosure_session_start;
     use crate::test::iso_auth_request;
     use crate::test::test_disclosure_session_start_error_http_client;
@@ -1487,6 +1503,33 @@ mod tests {
         _ = wallet_messages.last().unwrap().error(); // This RP error should be reported back to the RP
     }
 
+    #[tokio::test]
+    async fn test_disclosure_session_start_error_unsupported_response_mode() {
+        // Starting a `DisclosureSession` with an Authorization Request that uses the `direct_post` response mode
+        // should result in an error, as that offers no way of including a PoA.
+        let (error, verifier_session) = disclosure_session_start(
+            SessionType::SameDevice,
+            DisclosureUriSource::Link,
+            ReaderCertificateKind::WithReaderRegistration,
+            identity,
+            identity,
+            |mut auth_request| {
+                auth_request.oauth_request.response_mode = Some(ResponseMode::DirectPost);
+                auth_request
+            },
+        )
+        .await
+        .expect_err("Starting disclosure session should have resulted in an error");
+        assert_matches!(
+            error,
+            VpClientError::UnsupportedResponseMode(VpResponseMode::DirectPost)
+        );
+
+        let wallet_messages = verifier_session.wallet_messages.lock();
+        assert_eq!(wallet_messages.len(), 2);
+        _ = wallet_messages.last().unwrap().error(); // This RP error should be reported back to the RP
+    }
+
     #[allow(clippy::type_complexity)]
     fn create_disclosure_session_proposal<F>(
         response_factory: F,
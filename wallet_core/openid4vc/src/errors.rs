@@ -66,6 +66,64 @@ pub enum CredentialErrorCode {
     ServerError,
 }
 
+/// Reason for a failed credential request, as presented to the user of the wallet. This is derived from the
+/// [`CredentialErrorCode`] returned by the issuer, the raw [`ErrorResponse`] of which should only be used for logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialRequestFailure {
+    InvalidProof,
+    CredentialRequestDenied,
+    UnsupportedCredential,
+    AccessTokenRejected,
+    ServerError,
+}
+
+/// Describes how the wallet may recover from a [`CredentialRequestFailure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuanceRecovery {
+    /// The same issuance session may be retried.
+    Retry,
+    /// The user has to authenticate again, which starts a new issuance session.
+    Reauthenticate,
+    /// Issuance cannot succeed and should be aborted.
+    Abort,
+}
+
+impl From<CredentialErrorCode> for CredentialRequestFailure {
+    fn from(error_code: CredentialErrorCode) -> Self {
+        match error_code {
+            CredentialErrorCode::InvalidProof | CredentialErrorCode::InvalidEncryptionParameters => {
+                CredentialRequestFailure::InvalidProof
+            }
+            CredentialErrorCode::InvalidCredentialRequest
+            | CredentialErrorCode::InvalidRequest
+            | CredentialErrorCode::InsufficientScope => CredentialRequestFailure::CredentialRequestDenied,
+            CredentialErrorCode::UnsupportedCredentialType | CredentialErrorCode::UnsupportedCredentialFormat => {
+                CredentialRequestFailure::UnsupportedCredential
+            }
+            CredentialErrorCode::InvalidToken => CredentialRequestFailure::AccessTokenRejected,
+            CredentialErrorCode::ServerError => CredentialRequestFailure::ServerError,
+        }
+    }
+}
+
+impl CredentialRequestFailure {
+    pub fn recovery(&self) -> IssuanceRecovery {
+        match self {
+            CredentialRequestFailure::ServerError => IssuanceRecovery::Retry,
+            // The proofs of possession are bound to the `c_nonce` of the session, which retrying would reuse. Only a
+            // new session provides a fresh nonce.
+            CredentialRequestFailure::InvalidProof | CredentialRequestFailure::AccessTokenRejected => {
+                IssuanceRecovery::Reauthenticate
+            }
+            CredentialRequestFailure::CredentialRequestDenied | CredentialRequestFailure::UnsupportedCredential => {
+                IssuanceRecovery::Abort
+            }
+        }
+    }
+}
+
 impl From<CredentialRequestError> for ErrorResponse<CredentialErrorCode> {
     fn from(err: CredentialRequestError) -> ErrorResponse<CredentialErrorCode> {
         let description = err.to_string();
@@ -128,6 +186,14 @@ impl ErrorStatusCode for CredentialErrorCode {
     }
 }
 
+/// <https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0-13.html#name-notification-error-response>
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationErrorCode {
+    InvalidNotificationId,
+    InvalidNotificationRequest,
+}
+
 /// <https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0-13.html#section-6.3>
 /// and <https://www.rfc-editor.org/rfc/rfc6749.html#section-5.2>.
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -202,10 +268,10 @@ impl From<GetAuthRequestError> for ErrorResponse<GetRequestErrorCode> {
         ErrorResponse {
             error: match err {
                 GetAuthRequestError::ExpiredEphemeralId(_) => GetRequestErrorCode::ExpiredEphemeralId,
-                GetAuthRequestError::Session(SessionError::UnexpectedState(SessionStatus::Expired)) => {
+                GetAuthRequestError::Session(SessionError::UnexpectedState(SessionStatus::Expired { .. })) => {
                     GetRequestErrorCode::ExpiredSession
                 }
-                GetAuthRequestError::Session(SessionError::UnexpectedState(SessionStatus::Cancelled)) => {
+                GetAuthRequestError::Session(SessionError::UnexpectedState(SessionStatus::Cancelled { .. })) => {
                     GetRequestErrorCode::CancelledSession
                 }
                 GetAuthRequestError::Session(SessionError::UnknownSession(_)) => GetRequestErrorCode::UnknownSession,
@@ -258,10 +324,10 @@ impl From<PostAuthResponseError> for ErrorResponse<PostAuthResponseErrorCode> {
         let description = err.to_string();
         ErrorResponse {
             error: match err {
-                PostAuthResponseError::Session(SessionError::UnexpectedState(SessionStatus::Expired)) => {
+                PostAuthResponseError::Session(SessionError::UnexpectedState(SessionStatus::Expired { .. })) => {
                     PostAuthResponseErrorCode::ExpiredSession
                 }
-                PostAuthResponseError::Session(SessionError::UnexpectedState(SessionStatus::Cancelled)) => {
+                PostAuthResponseError::Session(SessionError::UnexpectedState(SessionStatus::Cancelled { .. })) => {
                     PostAuthResponseErrorCode::CancelledSession
                 }
                 PostAuthResponseError::Session(SessionError::SessionStore(_)) => PostAuthResponseErrorCode::ServerError,
@@ -358,7 +424,8 @@ impl From<&NewSessionError> for VerificationErrorCode {
             NewSessionError::Session(session_error) => session_error.into(),
             NewSessionError::NoItemsRequests
             | NewSessionError::UnknownUseCase(_)
-            | NewSessionError::ReturnUrlConfigurationMismatch => VerificationErrorCode::InvalidRequest,
+            | NewSessionError::ReturnUrlConfigurationMismatch
+            | NewSessionError::InvalidAttributeIdentifier(_) => VerificationErrorCode::InvalidRequest,
         }
     }
 }
@@ -412,6 +479,7 @@ impl From<CancelSessionError> for HttpJsonError<VerificationErrorCode> {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DisclosedAttributesErrorData {
     pub session_status: Option<String>,
+    pub session_status_reason: Option<String>,
     pub session_error: Option<String>,
 }
 
@@ -420,25 +488,29 @@ impl From<DisclosedAttributesError> for HttpJsonError<VerificationErrorCode> {
         let r#type = (&error).into();
         let detail = error.to_string();
 
-        // The `session_status` field is only included if the session was in an unexpected state,
-        // while the `session_error` field is further only included if that status is "FAILED".
+        // The `session_status` field is only included if the session was in an unexpected state, while the
+        // `session_status_reason` field is further only included if that status is "CANCELLED" or "EXPIRED"
+        // and the `session_error` field is only included if that status is "FAILED".
         let data = match error {
             DisclosedAttributesError::Session(SessionError::UnexpectedState(session_status)) => {
                 let status = Some(session_status.to_string());
-                let error = match session_status {
-                    SessionStatus::Failed { error } => Some(error),
-                    _ => None,
+                let (reason, error) = match session_status {
+                    SessionStatus::Failed { error } => (None, Some(error)),
+                    SessionStatus::Cancelled { reason } => (Some(reason.to_string()), None),
+                    SessionStatus::Expired { reason } => (Some(reason.to_string()), None),
+                    _ => (None, None),
                 };
 
                 DisclosedAttributesErrorData {
                     session_status: status,
+                    session_status_reason: reason,
                     session_error: error,
                 }
             }
             _ => Default::default(),
         };
 
-        // As `DisclosedAttributesErrorData` is a struct that only contains two simple strings,
+        // As `DisclosedAttributesErrorData` is a struct that only contains simple strings,
         // we can assume that this will serialize to a `serde_json::Map` without fault.
         let Ok(serde_json::Value::Object(data)) = serde_json::to_value(data) else {
             panic!("serialized DisclosedAttributesErrorData should be an object");
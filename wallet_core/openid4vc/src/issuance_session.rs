@@ -1,36 +1,50 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 
+use chrono::Duration;
 use derive_more::Debug;
-use futures::future::try_join_all;
 use futures::future::OptionFuture;
+use futures::stream;
+use futures::StreamExt;
 use futures::TryFutureExt;
+use futures::TryStreamExt;
 use http::Uri;
 use itertools::Itertools;
+use jsonwebtoken::jwk::JwkSet;
 use jsonwebtoken::Algorithm;
 use jsonwebtoken::Header;
 use p256::ecdsa::SigningKey;
 use p256::ecdsa::VerifyingKey;
 use p256::elliptic_curve::rand_core::OsRng;
+use parking_lot::Mutex;
 use reqwest::header::ToStrError;
 use reqwest::header::AUTHORIZATION;
 use reqwest::Method;
 use rustls_pki_types::TrustAnchor;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tracing::warn;
 use url::Url;
 
 use error_category::ErrorCategory;
 use nl_wallet_mdoc::holder::IssuedAttributesMismatch;
 use nl_wallet_mdoc::holder::Mdoc;
 use nl_wallet_mdoc::utils::cose::CoseError;
+use nl_wallet_mdoc::utils::serialization::cbor_serialize;
 use nl_wallet_mdoc::utils::serialization::CborBase64;
 use nl_wallet_mdoc::utils::serialization::CborError;
 use nl_wallet_mdoc::utils::serialization::TaggedBytes;
 use nl_wallet_mdoc::utils::x509::CertificateError;
+use nl_wallet_mdoc::verifier::ValidityError;
+use nl_wallet_mdoc::verifier::DEFAULT_MAX_FUTURE_VALIDITY;
 use nl_wallet_mdoc::ATTR_RANDOM_LENGTH;
 use sd_jwt::metadata::TypeMetadataError;
+use wallet_common::generator::Generator;
 use wallet_common::generator::TimeGenerator;
+use wallet_common::jwt::jwk_to_p256;
 use wallet_common::jwt::JwkConversionError;
 use wallet_common::jwt::Jwt;
 use wallet_common::jwt::JwtError;
@@ -51,7 +65,11 @@ use crate::credential::CredentialRequests;
 use crate::credential::CredentialResponse;
 use crate::credential::CredentialResponses;
 use crate::credential::MdocCopies;
+use crate::credential::NonceResponse;
+use crate::credential::NotificationEvent;
+use crate::credential::NotificationRequest;
 use crate::credential::WteDisclosure;
+use crate::credential_formats::CredentialFormat;
 use crate::credential_formats::CredentialFormats;
 use crate::credential_payload::CredentialPayload;
 use crate::credential_payload::CredentialPayloadError;
@@ -65,10 +83,13 @@ use crate::metadata::IssuerMetadata;
 use crate::oidc;
 use crate::token::AccessToken;
 use crate::token::CredentialPreview;
+use crate::token::CredentialPreviewError;
 use crate::token::TokenRequest;
 use crate::token::TokenResponseWithPreviews;
 use crate::CredentialErrorCode;
 use crate::ErrorResponse;
+use crate::Format;
+use crate::NotificationErrorCode;
 use crate::TokenErrorCode;
 
 #[derive(Debug, thiserror::Error, ErrorCategory)]
@@ -111,6 +132,9 @@ pub enum IssuanceSessionError {
     #[error("error requesting credentials: {0:?}")]
     #[category(pd)]
     CredentialRequest(ErrorResponse<CredentialErrorCode>),
+    #[error("error sending notification: {0:?}")]
+    #[category(pd)]
+    NotificationRequest(ErrorResponse<NotificationErrorCode>),
     #[error("generating credential private keys failed: {0}")]
     #[category(pd)]
     PrivateKeyGeneration(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
@@ -144,6 +168,21 @@ pub enum IssuanceSessionError {
     #[error("malformed attribute: random too short (was {0}; minimum {1}")]
     #[category(critical)]
     AttributeRandomLength(usize, usize),
+    #[error("received credential does not contain any attributes")]
+    #[category(critical)]
+    EmptyAttestation,
+    #[error("attribute {0} is too large (was {1} bytes; maximum {2})")]
+    #[category(critical)]
+    AttributeSize(String, usize, usize),
+    #[error("credential is too large (was {0} bytes; maximum {1})")]
+    #[category(critical)]
+    CredentialSize(usize, usize),
+    #[error("invalid validity of issued credential: {0}")]
+    #[category(critical)]
+    Validity(#[source] ValidityError),
+    #[error("issuer signing key not found in published JWKS")]
+    #[category(critical)]
+    KeyNotInJwks,
     #[error("received zero credential copies")]
     #[category(critical)]
     NoCredentialCopies,
@@ -152,6 +191,17 @@ pub enum IssuanceSessionError {
     Poa(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("error converting to a CredentialPayload: {0}")]
     CredentialPayload(#[from] CredentialPayloadError),
+    #[error("none of the offered credentials were selected")]
+    #[category(expected)]
+    NoCredentialsSelected,
+    #[error("error reading issuer registration from credential preview: {0}")]
+    CredentialPreview(#[from] CredentialPreviewError),
+    #[error("credential previews are not all from the same issuer")]
+    #[category(critical)]
+    MixedIssuers,
+    #[error("issuer offered credential in format that is not allowed: {0}")]
+    #[category(critical)]
+    CredentialFormatNotAllowed(Format),
 }
 
 #[derive(Clone, Debug)]
@@ -260,13 +310,107 @@ pub trait IssuanceSession<H = HttpVcMessageClient> {
         credential_issuer_identifier: BaseUrl,
     ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError>;
 
+    /// Accept only the offered credentials whose credential type is contained in `selected_doc_types`, as opposed to
+    /// [`IssuanceSession::accept_issuance()`] which accepts all of them.
+    async fn accept_issuance_selective<K: CredentialEcdsaKey + Eq + Hash>(
+        &self,
+        selected_doc_types: &HashSet<String>,
+        trust_anchors: &[TrustAnchor<'_>],
+        key_factory: &impl KeyFactory<Key = K>,
+        wte: Option<JwtCredential<WteClaims>>,
+        credential_issuer_identifier: BaseUrl,
+    ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError>;
+
+    /// Notify the issuer of the given event for each of the credentials received when accepting issuance, e.g. once the
+    /// wallet has stored them. Nothing is sent if the issuer does not support notifications.
+    async fn notify(&self, event: NotificationEvent) -> Result<(), IssuanceSessionError>;
+
+    /// Set the maximum amount of credential private keys that are used concurrently when accepting issuance, which
+    /// defaults to [`DEFAULT_CONCURRENCY_LIMIT`]. This applies to generating the keys as well as to signing with them.
+    fn with_concurrency_limit(self, concurrency_limit: NonZeroUsize) -> Self
+    where
+        Self: Sized;
+
+    /// Only request credentials in the `allowed_formats` when accepting issuance, which fails if any of the offered
+    /// credentials is in another format. By default, credentials are requested in all of the offered formats.
+    fn with_allowed_formats(self, allowed_formats: Vec<Format>) -> Self
+    where
+        Self: Sized;
+
+    /// Set the maximum sizes of the attributes and credentials that are accepted when accepting issuance, which
+    /// default to [`DEFAULT_MAX_ATTRIBUTE_SIZE`] and [`DEFAULT_MAX_CREDENTIAL_SIZE`].
+    fn with_size_limits(self, size_limits: CredentialSizeLimits) -> Self
+    where
+        Self: Sized;
+
+    /// Set the maximum time between now and the start of the validity of the issued credentials, which defaults to
+    /// [`DEFAULT_MAX_FUTURE_VALIDITY`]. Credentials whose validity starts later than that are rejected.
+    fn with_max_future_validity(self, max_future_validity: Duration) -> Self
+    where
+        Self: Sized;
+
+    /// Additionally require the signing keys of the issuer to be present in the JWKS it publishes at `jwks_url`
+    /// when accepting issuance, see [`HttpIssuanceSession::verify_key_in_jwks()`]. This is disabled by default.
+    fn with_jwks_verification(self, jwks_url: Url) -> Self
+    where
+        Self: Sized;
+
+    /// Bind all copies of each credential to a single holder key if `single_holder_key` is set, instead of generating
+    /// a distinct key per copy. This is disabled by default, and requires the issuer to accept this.
+    ///
+    /// Note that this comes at the cost of privacy: distinct copies exist so that the wallet can disclose a different
+    /// copy each time, which RPs cannot link to each other. As the holder key is included in each disclosure, copies
+    /// sharing a key are linkable. This should only be enabled for deployments that explicitly accept this trade-off.
+    fn with_single_holder_key(self, single_holder_key: bool) -> Self
+    where
+        Self: Sized;
+
     async fn reject_issuance(self) -> Result<(), IssuanceSessionError>;
+
+    /// Discover the Credential Issuer metadata, outside of the context of an issuance session.
+    async fn discover_metadata(message_client: H, base_url: BaseUrl) -> Result<IssuerMetadata, IssuanceSessionError>;
+}
+
+/// The default maximum amount of credential private keys that are used concurrently during issuance, see
+/// [`IssuanceSession::with_concurrency_limit()`].
+pub const DEFAULT_CONCURRENCY_LIMIT: NonZeroUsize = NonZeroUsize::new(8).unwrap();
+
+/// The default maximum size in bytes of a single CBOR-encoded attribute value in an issued credential.
+pub const DEFAULT_MAX_ATTRIBUTE_SIZE: usize = 1024 * 1024;
+
+/// The default maximum size in bytes of a single CBOR-encoded issued credential.
+pub const DEFAULT_MAX_CREDENTIAL_SIZE: usize = 2 * 1024 * 1024;
+
+/// The maximum sizes of issued credentials that are accepted, see [`IssuanceSession::with_size_limits()`].
+/// As every copy of a credential is stored separately, this bounds the storage used by a single issuance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CredentialSizeLimits {
+    pub max_attribute_size: usize,
+    pub max_credential_size: usize,
+}
+
+impl Default for CredentialSizeLimits {
+    fn default() -> Self {
+        Self {
+            max_attribute_size: DEFAULT_MAX_ATTRIBUTE_SIZE,
+            max_credential_size: DEFAULT_MAX_CREDENTIAL_SIZE,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct HttpIssuanceSession<H = HttpVcMessageClient> {
     message_client: H,
     session_state: IssuanceState,
+    concurrency_limit: NonZeroUsize,
+    size_limits: CredentialSizeLimits,
+    max_future_validity: Duration,
+    jwks_url: Option<Url>,
+    jwks_cache: Mutex<HashMap<Url, JwkSet>>,
+    single_holder_key: bool,
+    allowed_formats: Option<Vec<Format>>,
+    /// The notification IDs of the credentials received when accepting issuance, one per credential.
+    notification_ids: Mutex<Vec<String>>,
 }
 
 /// Contract for sending OpenID4VCI protocol messages.
@@ -282,24 +426,39 @@ pub trait VcMessageClient {
         dpop_header: &Dpop,
     ) -> Result<(TokenResponseWithPreviews, Option<String>), IssuanceSessionError>;
 
+    async fn request_nonce(&self, url: &Url) -> Result<NonceResponse, IssuanceSessionError>;
+
+    /// Request a credential, returning the DPoP nonce that the issuer may have sent along with the response, which it
+    /// may also do when responding with an error.
     async fn request_credential(
         &self,
         url: &Url,
         credential_request: &CredentialRequest,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponse, IssuanceSessionError>;
+    ) -> (Result<CredentialResponse, IssuanceSessionError>, Option<String>);
 
+    /// Request multiple credentials, returning the DPoP nonce like [`VcMessageClient::request_credential`].
     async fn request_credentials(
         &self,
         url: &Url,
         credential_requests: &CredentialRequests,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponses, IssuanceSessionError>;
+    ) -> (Result<CredentialResponses, IssuanceSessionError>, Option<String>);
 
     async fn reject(&self, url: &Url, dpop_header: &str, access_token_header: &str)
         -> Result<(), IssuanceSessionError>;
+
+    async fn notify(
+        &self,
+        url: &Url,
+        notification_request: &NotificationRequest,
+        dpop_header: &str,
+        access_token_header: &str,
+    ) -> Result<(), IssuanceSessionError>;
+
+    async fn jwks(&self, url: &Url) -> Result<JwkSet, IssuanceSessionError>;
 }
 
 pub struct HttpVcMessageClient {
@@ -352,12 +511,7 @@ impl VcMessageClient for HttpVcMessageClient {
                     let error = response.json::<ErrorResponse<TokenErrorCode>>().await?;
                     Err(IssuanceSessionError::TokenRequest(error))
                 } else {
-                    let dpop_nonce = response
-                        .headers()
-                        .get(DPOP_NONCE_HEADER_NAME)
-                        .map(|val| val.to_str())
-                        .transpose()?
-                        .map(str::to_string);
+                    let dpop_nonce = Self::dpop_nonce(&response)?;
                     let deserialized = response.json::<TokenResponseWithPreviews>().await?;
                     Ok((deserialized, dpop_nonce))
                 }
@@ -365,13 +519,25 @@ impl VcMessageClient for HttpVcMessageClient {
             .await
     }
 
+    async fn request_nonce(&self, url: &Url) -> Result<NonceResponse, IssuanceSessionError> {
+        let nonce_response = self
+            .http_client
+            .post(url.as_ref())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(nonce_response)
+    }
+
     async fn request_credential(
         &self,
         url: &Url,
         credential_request: &CredentialRequest,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponse, IssuanceSessionError> {
+    ) -> (Result<CredentialResponse, IssuanceSessionError>, Option<String>) {
         self.request(url, credential_request, dpop_header, access_token_header)
             .await
     }
@@ -382,7 +548,7 @@ impl VcMessageClient for HttpVcMessageClient {
         credential_requests: &CredentialRequests,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponses, IssuanceSessionError> {
+    ) -> (Result<CredentialResponses, IssuanceSessionError>, Option<String>) {
         self.request(url, credential_requests, dpop_header, access_token_header)
             .await
     }
@@ -412,39 +578,102 @@ impl VcMessageClient for HttpVcMessageClient {
             .await?;
         Ok(())
     }
-}
 
-impl HttpVcMessageClient {
-    async fn request<T: Serialize, S: DeserializeOwned>(
+    async fn notify(
         &self,
         url: &Url,
-        request: &T,
+        notification_request: &NotificationRequest,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<S, IssuanceSessionError> {
+    ) -> Result<(), IssuanceSessionError> {
         self.http_client
             .post(url.as_ref())
             .header(DPOP_HEADER_NAME, dpop_header)
             .header(AUTHORIZATION, access_token_header)
-            .json(request)
+            .json(notification_request)
             .send()
             .map_err(IssuanceSessionError::from)
             .and_then(|response| async {
                 // If the HTTP response code is 4xx or 5xx, parse the JSON as an error
                 let status = response.status();
                 if status.is_client_error() || status.is_server_error() {
-                    let error = response.json::<ErrorResponse<CredentialErrorCode>>().await?;
-                    Err(IssuanceSessionError::CredentialRequest(error))
+                    let error = response.json::<ErrorResponse<NotificationErrorCode>>().await?;
+                    Err(IssuanceSessionError::NotificationRequest(error))
                 } else {
-                    let response = response.json().await?;
-                    Ok(response)
+                    Ok(())
                 }
             })
+            .await?;
+        Ok(())
+    }
+
+    async fn jwks(&self, url: &Url) -> Result<JwkSet, IssuanceSessionError> {
+        let jwks = self
+            .http_client
+            .get(url.as_ref())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(jwks)
+    }
+}
+
+impl HttpVcMessageClient {
+    /// Read the DPoP nonce from the response headers, which the server may send to rotate the nonce.
+    fn dpop_nonce(response: &reqwest::Response) -> Result<Option<String>, IssuanceSessionError> {
+        let dpop_nonce = response
+            .headers()
+            .get(DPOP_NONCE_HEADER_NAME)
+            .map(|val| val.to_str())
+            .transpose()?
+            .map(str::to_string);
+
+        Ok(dpop_nonce)
+    }
+
+    async fn request<T: Serialize, S: DeserializeOwned>(
+        &self,
+        url: &Url,
+        request: &T,
+        dpop_header: &str,
+        access_token_header: &str,
+    ) -> (Result<S, IssuanceSessionError>, Option<String>) {
+        let response = match self
+            .http_client
+            .post(url.as_ref())
+            .header(DPOP_HEADER_NAME, dpop_header)
+            .header(AUTHORIZATION, access_token_header)
+            .json(request)
+            .send()
             .await
+        {
+            Ok(response) => response,
+            Err(error) => return (Err(error.into()), None),
+        };
+
+        // The issuer may send a new DPoP nonce in both successful and error responses.
+        let dpop_nonce = match Self::dpop_nonce(&response) {
+            Ok(dpop_nonce) => dpop_nonce,
+            Err(error) => return (Err(error), None),
+        };
+
+        // If the HTTP response code is 4xx or 5xx, parse the JSON as an error
+        let status = response.status();
+        let result = if status.is_client_error() || status.is_server_error() {
+            match response.json::<ErrorResponse<CredentialErrorCode>>().await {
+                Ok(error) => Err(IssuanceSessionError::CredentialRequest(error)),
+                Err(error) => Err(error.into()),
+            }
+        } else {
+            response.json().await.map_err(IssuanceSessionError::from)
+        };
+
+        (result, dpop_nonce)
     }
 }
 
-#[cfg_attr(test, derive(Clone))]
 #[derive(Debug)]
 struct IssuanceState {
     access_token: AccessToken,
@@ -453,10 +682,65 @@ struct IssuanceState {
     issuer_url: BaseUrl,
     #[debug(skip)]
     dpop_private_key: SigningKey,
-    dpop_nonce: Option<String>,
+    /// The most recent DPoP nonce received from the issuer, which may rotate it in any of its responses.
+    dpop_nonce: Mutex<Option<String>>,
 }
 
 impl<H: VcMessageClient> HttpIssuanceSession<H> {
+    /// Create a new session for the specified state, using the default settings.
+    fn new(message_client: H, session_state: IssuanceState) -> Self {
+        Self {
+            message_client,
+            session_state,
+            concurrency_limit: DEFAULT_CONCURRENCY_LIMIT,
+            size_limits: CredentialSizeLimits::default(),
+            max_future_validity: DEFAULT_MAX_FUTURE_VALIDITY,
+            jwks_url: None,
+            jwks_cache: Mutex::default(),
+            single_holder_key: false,
+            allowed_formats: None,
+            notification_ids: Mutex::default(),
+        }
+    }
+
+    /// Verify that `key` is present in the JWKS published at `jwks_url`. If `kid` is specified, the key with that key
+    /// ID has to equal `key`. Keys in the JWKS that are not P-256 keys never match. The JWKS is only fetched once
+    /// during the session and is cached afterwards.
+    pub async fn verify_key_in_jwks(
+        &self,
+        kid: Option<&str>,
+        key: &VerifyingKey,
+        jwks_url: &Url,
+    ) -> Result<(), IssuanceSessionError> {
+        let cached_jwks = self.jwks_cache.lock().get(jwks_url).cloned();
+        let jwks = match cached_jwks {
+            Some(jwks) => jwks,
+            None => {
+                let jwks = self.message_client.jwks(jwks_url).await?;
+                self.jwks_cache.lock().insert(jwks_url.clone(), jwks.clone());
+                jwks
+            }
+        };
+
+        let key_present = match kid {
+            Some(kid) => jwks
+                .find(kid)
+                .and_then(|jwk| jwk_to_p256(jwk).ok())
+                .is_some_and(|jwks_key| jwks_key == *key),
+            None => jwks
+                .keys
+                .iter()
+                .filter_map(|jwk| jwk_to_p256(jwk).ok())
+                .any(|jwks_key| jwks_key == *key),
+        };
+
+        if !key_present {
+            return Err(IssuanceSessionError::KeyNotInJwks);
+        }
+
+        Ok(())
+    }
+
     /// Discover the token endpoint from the OAuth server metadata.
     async fn discover_token_endpoint(message_client: &H, base_url: &BaseUrl) -> Result<Url, IssuanceSessionError> {
         let issuer_metadata = message_client.discover_metadata(base_url).await?;
@@ -499,53 +783,85 @@ impl<H: VcMessageClient> HttpIssuanceSession<H> {
             .map(|url| url.as_ref().clone());
         Ok(url)
     }
-}
 
-impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
-    async fn start_issuance(
-        message_client: H,
-        base_url: BaseUrl,
-        token_request: TokenRequest,
-        trust_anchors: &[TrustAnchor<'_>],
-    ) -> Result<(Self, Vec<CredentialFormats<CredentialPreview>>), IssuanceSessionError> {
-        let token_endpoint = Self::discover_token_endpoint(&message_client, &base_url).await?;
+    /// Discover the nonce endpoint from the Credential Issuer metadata, which is optional.
+    async fn discover_nonce_endpoint(
+        message_client: &H,
+        base_url: &BaseUrl,
+    ) -> Result<Option<Url>, IssuanceSessionError> {
+        let url = message_client
+            .discover_metadata(base_url)
+            .await?
+            .issuer_config
+            .nonce_endpoint
+            .map(|url| url.as_ref().clone());
+        Ok(url)
+    }
 
-        let dpop_private_key = SigningKey::random(&mut OsRng);
-        let dpop_header = Dpop::new(&dpop_private_key, token_endpoint.clone(), Method::POST, None, None).await?;
+    /// Obtain a `c_nonce` from the nonce endpoint of the issuer, for issuers that do not include it in the Token
+    /// Response.
+    async fn fetch_nonce(message_client: &H, base_url: &BaseUrl) -> Result<String, IssuanceSessionError> {
+        let url = Self::discover_nonce_endpoint(message_client, base_url)
+            .await?
+            .ok_or(IssuanceSessionError::MissingNonce)?;
 
-        let (token_response, dpop_nonce) = message_client
-            .request_token(&token_endpoint, &token_request, &dpop_header)
-            .await?;
+        let nonce_response = message_client.request_nonce(&url).await?;
 
-        token_response
-            .credential_previews
-            .as_slice()
-            .iter()
-            .try_for_each(|preview| preview.verify(trust_anchors))?;
+        Ok(nonce_response.c_nonce)
+    }
 
-        let credential_previews = token_response.credential_previews.clone().into_inner();
+    /// Discover the notification endpoint from the Credential Issuer metadata, which is optional.
+    async fn discover_notification_endpoint(
+        message_client: &H,
+        base_url: &BaseUrl,
+    ) -> Result<Option<Url>, IssuanceSessionError> {
+        let url = message_client
+            .discover_metadata(base_url)
+            .await?
+            .issuer_config
+            .notification_endpoint
+            .map(|url| url.as_ref().clone());
+        Ok(url)
+    }
 
-        let session_state = IssuanceState {
-            access_token: token_response.token_response.access_token,
-            c_nonce: token_response
-                .token_response
-                .c_nonce
-                .ok_or(IssuanceSessionError::MissingNonce)?,
-            credential_previews: token_response.credential_previews,
-            issuer_url: base_url,
-            dpop_private_key,
-            dpop_nonce,
-        };
+    /// Notify the issuer of the given event for each of the credentials identified by `notification_ids`. Nothing is
+    /// sent if the issuer does not have a notification endpoint.
+    async fn send_notifications(
+        &self,
+        notification_ids: &[String],
+        event: NotificationEvent,
+    ) -> Result<(), IssuanceSessionError> {
+        if notification_ids.is_empty() {
+            return Ok(());
+        }
 
-        let issuance_client = Self {
-            message_client,
-            session_state,
+        let Some(url) =
+            Self::discover_notification_endpoint(&self.message_client, &self.session_state.issuer_url).await?
+        else {
+            return Ok(());
         };
-        Ok((issuance_client, credential_previews))
+
+        for notification_id in notification_ids {
+            let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::POST).await?;
+
+            let notification_request = NotificationRequest {
+                notification_id: notification_id.clone(),
+                event,
+                event_description: None,
+            };
+
+            self.message_client
+                .notify(&url, &notification_request, &dpop_header, &access_token_header)
+                .await?;
+        }
+
+        Ok(())
     }
 
-    async fn accept_issuance<K: CredentialEcdsaKey + Eq + Hash>(
+    /// Request the credentials for the specified subset of the credential previews, which should not be empty.
+    async fn accept_credential_previews<K: CredentialEcdsaKey + Eq + Hash>(
         &self,
+        credential_previews: &[&CredentialFormats<CredentialPreview>],
         trust_anchors: &[TrustAnchor<'_>],
         key_factory: &impl KeyFactory<Key = K>,
         wte: Option<JwtCredential<WteClaims>>,
@@ -556,24 +872,71 @@ impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
         // We implement this below by simply flattening the relevant nested iterators when communicating with the
         // issuer.
 
-        let credential_previews = self
-            .session_state
-            .credential_previews
-            .as_slice()
+        #[cfg(feature = "insecure_dev_issuer")]
+        let trust_anchors = &crate::dev_issuer::with_self_signed_issuer(
+            trust_anchors,
+            self.session_state.credential_previews.as_slice(),
+        );
+
+        // If configured, check that all credentials are offered in allowed formats before requesting any of them.
+        if let Some(allowed_formats) = self.allowed_formats.as_ref() {
+            let disallowed_format = credential_previews
+                .iter()
+                .flat_map(|formats| formats.as_ref().as_slice())
+                .map(CredentialFormat::format)
+                .find(|format| !allowed_formats.contains(format));
+
+            if let Some(format) = disallowed_format {
+                return Err(IssuanceSessionError::CredentialFormatNotAllowed(format));
+            }
+        }
+
+        // If configured, check that the keys with which the credentials will be signed are published by the issuer,
+        // before requesting them. The issued credentials are checked to be signed by these keys in `into_credential()`.
+        if let Some(jwks_url) = self.jwks_url.as_ref() {
+            for preview in credential_previews
+                .iter()
+                .flat_map(|formats| formats.as_ref().as_slice())
+            {
+                match preview {
+                    CredentialPreview::MsoMdoc { issuer_certificate, .. } => {
+                        self.verify_key_in_jwks(None, issuer_certificate.public_key(), jwks_url)
+                            .await?
+                    }
+                }
+            }
+        }
+
+        let credential_copies = credential_previews
             .iter()
             .flat_map(|formats| formats.flatten_copies())
             .collect_vec();
 
+        // Determine for each of the copies the index of the key to which it is to be bound. Normally every copy gets
+        // its own key, but if configured all copies of a credential share a single key.
+        let copy_key_indices = if self.single_holder_key {
+            credential_previews
+                .iter()
+                .flat_map(|formats| formats.as_ref().as_slice())
+                .enumerate()
+                .flat_map(|(index, preview)| itertools::repeat_n(index, preview.copy_count().into()))
+                .collect_vec()
+        } else {
+            (0..credential_copies.len()).collect_vec()
+        };
+        let key_count = copy_key_indices.iter().unique().count();
+
         // Generate the PoPs to be sent to the issuer, and the private keys with which they were generated
         // (i.e., the private key of the future mdoc).
-        // If N is the total amount of copies of credentials to be issued, then this returns N key/proof pairs.
-        // Note that N > 0 because self.session_state.credential_previews which we mapped above is NonEmpty<_>.
+        // If N is the total amount of keys, then this returns N key/proof pairs. Note that N > 0 because the caller
+        // ensures that `credential_previews` is not empty.
         let keys_and_proofs = CredentialRequestProof::new_multiple(
             self.session_state.c_nonce.clone(),
             NL_WALLET_CLIENT_ID.to_string(),
             credential_issuer_identifier.clone(),
-            credential_previews.len().try_into().unwrap(),
+            key_count.try_into().unwrap(),
             key_factory,
+            self.concurrency_limit,
         )
         .await?;
 
@@ -610,27 +973,41 @@ impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
         });
         let mut poa = OptionFuture::from(poa).await.transpose()?;
 
-        // Split into N keys and N credential requests, so we can send the credential request proofs separately
-        // to the issuer.
-        let (pubkeys, credential_requests): (Vec<_>, Vec<_>) =
-            try_join_all(keys_and_proofs.into_iter().zip(credential_previews).map(
-                |((key, response), credential_preview)| async move {
-                    let pubkey = key
-                        .verifying_key()
-                        .await
-                        .map_err(|e| IssuanceSessionError::VerifyingKeyFromPrivateKey(e.into()))?;
-                    let id = key.identifier().to_string();
-                    let cred_request = CredentialRequest {
-                        credential_type: credential_preview.credential_request_type().into(),
-                        proof: Some(response),
-                        attestations: None, // We set this field below if necessary
-                        poa: None,          // We set this field below if necessary
-                    };
-                    Ok::<_, IssuanceSessionError>(((pubkey, id), cred_request))
-                },
-            ))
-            .await?
+        // Retrieve the public key and identifier of each of the N keys.
+        // Note that this limits the amount of keys that are used concurrently, as these may be backed by the Wallet
+        // Provider or by the secure element of the device. The futures are collected up front, as the resulting
+        // future is otherwise not recognised as being `Send`.
+        let pubkey_futures = keys_and_proofs
+            .iter()
+            .map(|(key, response)| async move {
+                let pubkey = key
+                    .verifying_key()
+                    .await
+                    .map_err(|e| IssuanceSessionError::VerifyingKeyFromPrivateKey(e.into()))?;
+                let id = key.identifier().to_string();
+                Ok::<_, IssuanceSessionError>(((pubkey, id), response))
+            })
+            .collect_vec();
+        let pubkeys_and_proofs = stream::iter(pubkey_futures)
+            .buffered(self.concurrency_limit.get())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        // Split into a key and a credential request per copy, so we can send the credential request proofs
+        // separately to the issuer.
+        let (pubkeys, credential_requests): (Vec<_>, Vec<_>) = copy_key_indices
             .into_iter()
+            .zip(credential_copies)
+            .map(|(key_index, credential_preview)| {
+                let (pubkey_and_id, response) = &pubkeys_and_proofs[key_index];
+                let cred_request = CredentialRequest {
+                    credential_type: credential_preview.credential_request_type().into(),
+                    proof: Some((*response).clone()),
+                    attestations: None, // We set this field below if necessary
+                    poa: None,          // We set this field below if necessary
+                };
+                (pubkey_and_id.clone(), cred_request)
+            })
             .unzip();
 
         // The following two unwraps are safe because N > 0, see above.
@@ -648,12 +1025,22 @@ impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
                     .await?
             }
         };
+        // The issuer is notified once per credential instead of once per copy, using the notification ID in the
+        // response for the first copy of each credential.
+        let notification_ids = credential_previews
+            .iter()
+            .flat_map(|formats| formats.as_ref().as_slice())
+            .scan(0, |first_copy_index, preview| {
+                let index = *first_copy_index;
+                *first_copy_index += usize::from(preview.copy_count());
+                Some(index)
+            })
+            .filter_map(|index| responses.get(index)?.notification_id().map(str::to_string))
+            .collect_vec();
         let mut responses_and_pubkeys: VecDeque<_> = responses.into_iter().zip(pubkeys).collect();
 
-        let docs = self
-            .session_state
-            .credential_previews
-            .as_slice()
+        // Only the selected previews were requested, so the responses correspond to the copies of those previews.
+        let docs = credential_previews
             .iter()
             .map(|formats| {
                 formats
@@ -669,7 +1056,14 @@ impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
                             .map(|(cred_response, (pubkey, key_id))| {
                                 // Convert the response into a credential, verifying it against both the
                                 // trust anchors and the credential preview we received in the preview.
-                                cred_response.into_credential::<K>(key_id, &pubkey, preview, trust_anchors)
+                                cred_response.into_credential::<K>(
+                                    key_id,
+                                    &pubkey,
+                                    preview,
+                                    trust_anchors,
+                                    &self.size_limits,
+                                    self.max_future_validity,
+                                )
                             })
                             .collect::<Result<Vec<IssuedCredential>, _>>()?;
 
@@ -678,40 +1072,222 @@ impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
                     .collect::<Result<Vec<IssuedCredentialCopies>, _>>()
             })
             // Flatten the results, s.t. we're left with a mixed vector of IssuedCredentialCopies
-            .process_results(|i| i.flatten().collect())?;
+            .process_results(|i| i.flatten().collect());
+
+        // If the credentials are valid, the caller notifies the issuer using `notify()` once it has stored them.
+        // Otherwise, the issuer is notified here of the failure, which is best effort and does not affect the result.
+        match docs {
+            Ok(_) => *self.notification_ids.lock() = notification_ids,
+            Err(_) => {
+                if let Err(error) = self
+                    .send_notifications(&notification_ids, NotificationEvent::CredentialFailure)
+                    .await
+                {
+                    warn!("could not send notification to issuer: {error}");
+                }
+            }
+        }
 
-        Ok(docs)
+        docs
     }
+}
 
-    async fn reject_issuance(self) -> Result<(), IssuanceSessionError> {
-        let url = Self::discover_batch_credential_endpoint(&self.message_client, &self.session_state.issuer_url)
-            .await?
-            .ok_or(IssuanceSessionError::NoBatchCredentialEndpoint)?;
-        let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::DELETE).await?;
-
-        self.message_client
-            .reject(&url, &dpop_header, &access_token_header)
-            .await?;
-
-        Ok(())
+/// Check that all credential previews within a single issuance session are from the same issuer organization, as
+/// mixing issuers within one session is suspicious. Note that an issuer may use a different certificate per doc type.
+fn verify_single_issuer(
+    credential_previews: &[CredentialFormats<CredentialPreview>],
+) -> Result<(), IssuanceSessionError> {
+    let organizations = credential_previews
+        .iter()
+        .flat_map(|formats| formats.as_ref().as_slice())
+        .map(|preview| {
+            preview
+                .issuer_registration()
+                .map(|registration| registration.organization)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !organizations.iter().all_equal() {
+        return Err(IssuanceSessionError::MixedIssuers);
     }
+
+    Ok(())
 }
 
-impl<H: VcMessageClient> HttpIssuanceSession<H> {
-    async fn request_credential(
-        &self,
-        credential_request: &CredentialRequest,
-    ) -> Result<CredentialResponse, IssuanceSessionError> {
-        let url = Self::discover_credential_endpoint(&self.message_client, &self.session_state.issuer_url).await?;
-        let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::POST).await?;
+impl<H: VcMessageClient> IssuanceSession<H> for HttpIssuanceSession<H> {
+    async fn start_issuance(
+        message_client: H,
+        base_url: BaseUrl,
+        token_request: TokenRequest,
+        trust_anchors: &[TrustAnchor<'_>],
+    ) -> Result<(Self, Vec<CredentialFormats<CredentialPreview>>), IssuanceSessionError> {
+        let token_endpoint = Self::discover_token_endpoint(&message_client, &base_url).await?;
 
-        let response = self
-            .message_client
-            .request_credential(&url, credential_request, &dpop_header, &access_token_header)
-            .await?;
+        let dpop_private_key = SigningKey::random(&mut OsRng);
+        let dpop_header = Dpop::new(&dpop_private_key, token_endpoint.clone(), Method::POST, None, None).await?;
 
-        Ok(response)
-    }
+        let (token_response, dpop_nonce) = message_client
+            .request_token(&token_endpoint, &token_request, &dpop_header)
+            .await?;
+
+        #[cfg(feature = "insecure_dev_issuer")]
+        let trust_anchors =
+            &crate::dev_issuer::with_self_signed_issuer(trust_anchors, token_response.credential_previews.as_slice());
+
+        token_response
+            .credential_previews
+            .as_slice()
+            .iter()
+            .try_for_each(|preview| preview.verify(trust_anchors))?;
+
+        verify_single_issuer(token_response.credential_previews.as_slice())?;
+
+        let credential_previews = token_response.credential_previews.clone().into_inner();
+
+        // Newer issuers do not include the `c_nonce` in the Token Response, but offer a separate nonce endpoint.
+        let c_nonce = match token_response.token_response.c_nonce {
+            Some(c_nonce) => c_nonce,
+            None => Self::fetch_nonce(&message_client, &base_url).await?,
+        };
+
+        let session_state = IssuanceState {
+            access_token: token_response.token_response.access_token,
+            c_nonce,
+            credential_previews: token_response.credential_previews,
+            issuer_url: base_url,
+            dpop_private_key,
+            dpop_nonce: Mutex::new(dpop_nonce),
+        };
+
+        let issuance_client = Self::new(message_client, session_state);
+        Ok((issuance_client, credential_previews))
+    }
+
+    async fn accept_issuance<K: CredentialEcdsaKey + Eq + Hash>(
+        &self,
+        trust_anchors: &[TrustAnchor<'_>],
+        key_factory: &impl KeyFactory<Key = K>,
+        wte: Option<JwtCredential<WteClaims>>,
+        credential_issuer_identifier: BaseUrl,
+    ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError> {
+        let credential_previews = self.session_state.credential_previews.as_slice().iter().collect_vec();
+
+        self.accept_credential_previews(
+            &credential_previews,
+            trust_anchors,
+            key_factory,
+            wte,
+            credential_issuer_identifier,
+        )
+        .await
+    }
+
+    async fn accept_issuance_selective<K: CredentialEcdsaKey + Eq + Hash>(
+        &self,
+        selected_doc_types: &HashSet<String>,
+        trust_anchors: &[TrustAnchor<'_>],
+        key_factory: &impl KeyFactory<Key = K>,
+        wte: Option<JwtCredential<WteClaims>>,
+        credential_issuer_identifier: BaseUrl,
+    ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError> {
+        let credential_previews = self
+            .session_state
+            .credential_previews
+            .as_slice()
+            .iter()
+            .filter(|formats| selected_doc_types.contains(&formats.credential_type()))
+            .collect_vec();
+
+        if credential_previews.is_empty() {
+            return Err(IssuanceSessionError::NoCredentialsSelected);
+        }
+
+        self.accept_credential_previews(
+            &credential_previews,
+            trust_anchors,
+            key_factory,
+            wte,
+            credential_issuer_identifier,
+        )
+        .await
+    }
+
+    async fn notify(&self, event: NotificationEvent) -> Result<(), IssuanceSessionError> {
+        let notification_ids = self.notification_ids.lock().clone();
+
+        self.send_notifications(&notification_ids, event).await
+    }
+
+    fn with_concurrency_limit(mut self, concurrency_limit: NonZeroUsize) -> Self {
+        self.concurrency_limit = concurrency_limit;
+        self
+    }
+
+    fn with_allowed_formats(mut self, allowed_formats: Vec<Format>) -> Self {
+        self.allowed_formats = Some(allowed_formats);
+        self
+    }
+
+    fn with_size_limits(mut self, size_limits: CredentialSizeLimits) -> Self {
+        self.size_limits = size_limits;
+        self
+    }
+
+    fn with_max_future_validity(mut self, max_future_validity: Duration) -> Self {
+        self.max_future_validity = max_future_validity;
+        self
+    }
+
+    fn with_jwks_verification(mut self, jwks_url: Url) -> Self {
+        self.jwks_url = Some(jwks_url);
+        self
+    }
+
+    fn with_single_holder_key(mut self, single_holder_key: bool) -> Self {
+        self.single_holder_key = single_holder_key;
+        self
+    }
+
+    async fn reject_issuance(self) -> Result<(), IssuanceSessionError> {
+        // If credentials were already received, the issuer is told that these have been deleted by the user. This is
+        // best effort, as rejecting the session at the issuer is what matters.
+        if let Err(error) = self.notify(NotificationEvent::CredentialDeleted).await {
+            warn!("could not send notification to issuer: {error}");
+        }
+
+        let url = Self::discover_batch_credential_endpoint(&self.message_client, &self.session_state.issuer_url)
+            .await?
+            .ok_or(IssuanceSessionError::NoBatchCredentialEndpoint)?;
+        let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::DELETE).await?;
+
+        self.message_client
+            .reject(&url, &dpop_header, &access_token_header)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn discover_metadata(message_client: H, base_url: BaseUrl) -> Result<IssuerMetadata, IssuanceSessionError> {
+        message_client.discover_metadata(&base_url).await
+    }
+}
+
+impl<H: VcMessageClient> HttpIssuanceSession<H> {
+    async fn request_credential(
+        &self,
+        credential_request: &CredentialRequest,
+    ) -> Result<CredentialResponse, IssuanceSessionError> {
+        let url = Self::discover_credential_endpoint(&self.message_client, &self.session_state.issuer_url).await?;
+        let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::POST).await?;
+
+        let (result, dpop_nonce) = self
+            .message_client
+            .request_credential(&url, credential_request, &dpop_header, &access_token_header)
+            .await;
+        self.session_state.update_dpop_nonce(dpop_nonce);
+
+        result
+    }
 
     async fn request_batch_credentials(
         &self,
@@ -725,7 +1301,7 @@ impl<H: VcMessageClient> HttpIssuanceSession<H> {
         let (dpop_header, access_token_header) = self.session_state.auth_headers(url.clone(), Method::POST).await?;
 
         let expected_response_count = credential_requests.len().get();
-        let responses = self
+        let (result, dpop_nonce) = self
             .message_client
             .request_credentials(
                 &url,
@@ -737,7 +1313,9 @@ impl<H: VcMessageClient> HttpIssuanceSession<H> {
                 &dpop_header,
                 &access_token_header,
             )
-            .await?;
+            .await;
+        self.session_state.update_dpop_nonce(dpop_nonce);
+        let responses = result?;
 
         // The server must have responded with enough credential responses, N, so that the caller has exactly enough
         // responses for all copies of all credentials constructed.
@@ -760,10 +1338,13 @@ impl CredentialResponse {
         verifying_key: &VerifyingKey,
         preview: &CredentialPreview,
         trust_anchors: &[TrustAnchor<'_>],
+        size_limits: &CredentialSizeLimits,
+        max_future_validity: Duration,
     ) -> Result<IssuedCredential, IssuanceSessionError> {
         match self {
             CredentialResponse::MsoMdoc {
                 credential: issuer_signed,
+                ..
             } => {
                 let CborBase64(issuer_signed) = *issuer_signed;
                 let CredentialPreview::MsoMdoc {
@@ -782,24 +1363,55 @@ impl CredentialResponse {
 
                 // Calculate the minimum of all the lengths of the random bytes
                 // included in the attributes of `IssuerSigned`. If this value
-                // is too low, we should not accept the attributes.
-                if let Some(name_spaces) = issuer_signed.name_spaces.as_ref() {
-                    let min_random_length = name_spaces
-                        .as_ref()
-                        .values()
-                        .flat_map(|attributes| attributes.as_ref().iter().map(|TaggedBytes(item)| item.random.len()))
-                        .min();
-
-                    if let Some(min_random_length) = min_random_length {
-                        if min_random_length < ATTR_RANDOM_LENGTH {
-                            return Err(IssuanceSessionError::AttributeRandomLength(
-                                min_random_length,
-                                ATTR_RANDOM_LENGTH,
-                            ));
-                        }
+                // is too low, we should not accept the attributes. If there are
+                // no attributes at all, the credential is malformed.
+                let min_random_length = issuer_signed
+                    .name_spaces
+                    .as_ref()
+                    .and_then(|name_spaces| {
+                        name_spaces
+                            .as_ref()
+                            .values()
+                            .flat_map(|attributes| {
+                                attributes.as_ref().iter().map(|TaggedBytes(item)| item.random.len())
+                            })
+                            .min()
+                    })
+                    .ok_or(IssuanceSessionError::EmptyAttestation)?;
+
+                if min_random_length < ATTR_RANDOM_LENGTH {
+                    return Err(IssuanceSessionError::AttributeRandomLength(
+                        min_random_length,
+                        ATTR_RANDOM_LENGTH,
+                    ));
+                }
+
+                // Check the size of the individual attributes and of the credential as a whole, so that an issuer
+                // cannot exhaust the storage of the device by sending overly large credentials.
+                for TaggedBytes(item) in issuer_signed
+                    .name_spaces
+                    .iter()
+                    .flat_map(|name_spaces| name_spaces.as_ref().values())
+                    .flat_map(|attributes| attributes.as_ref())
+                {
+                    let attribute_size = cbor_serialize(&item.element_value)?.len();
+                    if attribute_size > size_limits.max_attribute_size {
+                        return Err(IssuanceSessionError::AttributeSize(
+                            item.element_identifier.clone(),
+                            attribute_size,
+                            size_limits.max_attribute_size,
+                        ));
                     }
                 }
 
+                let credential_size = cbor_serialize(&issuer_signed)?.len();
+                if credential_size > size_limits.max_credential_size {
+                    return Err(IssuanceSessionError::CredentialSize(
+                        credential_size,
+                        size_limits.max_credential_size,
+                    ));
+                }
+
                 // The issuer certificate inside the mdoc has to equal the one that the issuer previously announced
                 // in the credential preview.
                 if issuer_signed.issuer_auth.signing_cert()? != *issuer_certificate {
@@ -810,6 +1422,12 @@ impl CredentialResponse {
                 let mdoc = Mdoc::new::<K>(key_id, issuer_signed, &TimeGenerator, trust_anchors)
                     .map_err(IssuanceSessionError::MdocVerification)?;
 
+                // The mdoc is allowed to be not yet valid, but not if its validity starts implausibly far in the
+                // future.
+                mdoc.validity_info()
+                    .verify_valid_from_within(TimeGenerator.generate(), max_future_validity)
+                    .map_err(IssuanceSessionError::Validity)?;
+
                 // Check that our mdoc contains exactly the attributes the issuer said it would have
                 mdoc.compare_unsigned(unsigned_mdoc)
                     .map_err(IssuanceSessionError::IssuedMdocAttributesMismatch)?;
@@ -825,13 +1443,24 @@ impl CredentialResponse {
 }
 
 impl IssuanceState {
+    fn dpop_nonce(&self) -> Option<String> {
+        self.dpop_nonce.lock().clone()
+    }
+
+    /// Store the DPoP nonce from a response of the issuer, if it sent one, so that it is used in subsequent requests.
+    fn update_dpop_nonce(&self, dpop_nonce: Option<String>) {
+        if let Some(dpop_nonce) = dpop_nonce {
+            *self.dpop_nonce.lock() = Some(dpop_nonce);
+        }
+    }
+
     async fn auth_headers(&self, url: Url, method: reqwest::Method) -> Result<(String, String), IssuanceSessionError> {
         let dpop_header = Dpop::new(
             &self.dpop_private_key,
             url,
             method,
             Some(&self.access_token),
-            self.dpop_nonce.clone(),
+            self.dpop_nonce(),
         )
         .await?;
 
@@ -868,22 +1497,27 @@ where
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
+    use chrono::Utc;
     use rstest::rstest;
     use serde_bytes::ByteBuf;
+    use serde_json::json;
 
     use nl_wallet_mdoc::server_keys::generate::Ca;
     use nl_wallet_mdoc::test::data;
     use nl_wallet_mdoc::unsigned::UnsignedMdoc;
+    use nl_wallet_mdoc::utils::auth::Organization;
     use nl_wallet_mdoc::utils::issuer_auth::IssuerRegistration;
     use nl_wallet_mdoc::utils::serialization::CborBase64;
     use nl_wallet_mdoc::utils::serialization::TaggedBytes;
     use nl_wallet_mdoc::IssuerSigned;
     use sd_jwt::metadata::TypeMetadata;
     use sd_jwt::metadata::TypeMetadataChain;
+    use wallet_common::jwt::jwk_from_p256;
     use wallet_common::keys::factory::KeyFactory;
     use wallet_common::keys::mock_remote::MockRemoteEcdsaKey;
     use wallet_common::keys::mock_remote::MockRemoteKeyFactory;
 
+    use crate::credential_formats::CredentialType;
     use crate::token::TokenResponse;
 
     use super::*;
@@ -905,13 +1539,26 @@ mod tests {
         TrustAnchor<'static>,
         VerifyingKey,
         MockRemoteKeyFactory,
+    ) {
+        create_credential_response_with_unsigned_mdoc(|_| {}).await
+    }
+
+    async fn create_credential_response_with_unsigned_mdoc(
+        modify_unsigned_mdoc: impl FnOnce(&mut UnsignedMdoc),
+    ) -> (
+        CredentialResponse,
+        CredentialPreview,
+        TrustAnchor<'static>,
+        VerifyingKey,
+        MockRemoteKeyFactory,
     ) {
         let ca = Ca::generate_issuer_mock_ca().unwrap();
         let issuance_key = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
         let key_factory = MockRemoteKeyFactory::default();
         let trust_anchor = ca.to_trust_anchor().to_owned();
 
-        let unsigned_mdoc = UnsignedMdoc::from(data::pid_family_name().into_first().unwrap());
+        let mut unsigned_mdoc = UnsignedMdoc::from(data::pid_family_name().into_first().unwrap());
+        modify_unsigned_mdoc(&mut unsigned_mdoc);
         let metadata = TypeMetadata::bsn_only_example();
         let metadata_chain = TypeMetadataChain::create(metadata, vec![]).unwrap();
 
@@ -933,6 +1580,7 @@ mod tests {
         .unwrap();
         let credential_response = CredentialResponse::MsoMdoc {
             credential: Box::new(issuer_signed.into()),
+            notification_id: None,
         };
 
         (
@@ -995,6 +1643,134 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_start_issuance_mixed_issuers() {
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let trust_anchor = ca.to_trust_anchor().to_owned();
+
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client
+            .expect_request_token()
+            .return_once(move |_url, _token_request, _dpop_header| {
+                // Generate two credential previews by trusted issuers, but from different organizations.
+                let other_registration = IssuerRegistration {
+                    organization: Organization {
+                        display_name: vec![("en", "Other issuer")].into(),
+                        ..Organization::new_mock()
+                    },
+                };
+                let previews = [IssuerRegistration::new_mock(), other_registration]
+                    .into_iter()
+                    .map(|registration| {
+                        let issuance_key = ca.generate_issuer_mock(registration.into()).unwrap();
+                        let metadata_chain =
+                            TypeMetadataChain::create(TypeMetadata::bsn_only_example(), vec![]).unwrap();
+
+                        let preview = CredentialPreview::MsoMdoc {
+                            unsigned_mdoc: UnsignedMdoc::from(data::pid_family_name().into_first().unwrap()),
+                            issuer_certificate: issuance_key.certificate().clone(),
+                            metadata_chain,
+                        };
+
+                        CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap()
+                    })
+                    .collect_vec();
+
+                Ok((
+                    TokenResponseWithPreviews {
+                        token_response: TokenResponse::new("access_token".to_string().into(), "c_nonce".to_string()),
+                        credential_previews: VecNonEmpty::try_from(previews).unwrap(),
+                    },
+                    None,
+                ))
+            });
+
+        let error = HttpIssuanceSession::start_issuance(
+            mock_msg_client,
+            "https://example.com".parse().unwrap(),
+            TokenRequest::new_mock(),
+            &[trust_anchor],
+        )
+        .await
+        .unwrap_err();
+
+        assert_matches!(error, IssuanceSessionError::MixedIssuers);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_start_issuance_nonce_endpoint(#[values(true, false)] has_nonce_endpoint: bool) {
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let trust_anchor = ca.to_trust_anchor().to_owned();
+        let issuance_key = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
+
+        let mut mock_msg_client = MockVcMessageClient::new();
+        mock_msg_client.expect_discover_metadata().returning(move |url| {
+            let mut metadata = IssuerMetadata::new_mock(url);
+            metadata.issuer_config.nonce_endpoint = has_nonce_endpoint.then(|| url.join_base_url("/nonce"));
+            Ok(metadata)
+        });
+        mock_msg_client
+            .expect_discover_oauth_metadata()
+            .returning(|url| Ok(oidc::Config::new_mock(url)));
+        mock_msg_client
+            .expect_request_token()
+            .return_once(move |_url, _token_request, _dpop_header| {
+                let metadata_chain = TypeMetadataChain::create(TypeMetadata::bsn_only_example(), vec![]).unwrap();
+                let preview = CredentialPreview::MsoMdoc {
+                    unsigned_mdoc: UnsignedMdoc::from(data::pid_family_name().into_first().unwrap()),
+                    issuer_certificate: issuance_key.certificate().clone(),
+                    metadata_chain,
+                };
+
+                // The Token Response does not contain a `c_nonce`, as is the case for newer issuers.
+                let token_response = TokenResponse {
+                    c_nonce: None,
+                    ..TokenResponse::new("access_token".to_string().into(), "c_nonce".to_string())
+                };
+
+                Ok((
+                    TokenResponseWithPreviews {
+                        token_response,
+                        credential_previews: VecNonEmpty::try_from(vec![CredentialFormats::try_new(
+                            VecNonEmpty::try_from(vec![preview]).unwrap(),
+                        )
+                        .unwrap()])
+                        .unwrap(),
+                    },
+                    None,
+                ))
+            });
+        mock_msg_client
+            .expect_request_nonce()
+            .times(usize::from(has_nonce_endpoint))
+            .returning(|_url| {
+                Ok(NonceResponse {
+                    c_nonce: "nonce_endpoint_c_nonce".to_string(),
+                })
+            });
+
+        let result = HttpIssuanceSession::start_issuance(
+            mock_msg_client,
+            "https://example.com".parse().unwrap(),
+            TokenRequest::new_mock(),
+            &[trust_anchor],
+        )
+        .await;
+
+        if has_nonce_endpoint {
+            let (session, _) = result.expect("starting issuance should succeed");
+
+            assert_eq!(session.session_state.c_nonce, "nonce_endpoint_c_nonce");
+        } else {
+            let Err(error) = result else {
+                panic!("starting issuance should fail");
+            };
+
+            assert_matches!(error, IssuanceSessionError::MissingNonce);
+        }
+    }
+
     /// Return a new session ready for `accept_issuance()`.
     fn new_session_state(previews: Vec<CredentialFormats<CredentialPreview>>) -> IssuanceState {
         IssuanceState {
@@ -1003,31 +1779,37 @@ mod tests {
             credential_previews: VecNonEmpty::try_from(previews).unwrap(),
             issuer_url: "https://issuer.example.com".parse().unwrap(),
             dpop_private_key: SigningKey::random(&mut OsRng),
-            dpop_nonce: Some("dpop_nonce".to_string()),
+            dpop_nonce: Mutex::new(Some("dpop_nonce".to_string())),
         }
     }
 
-    /// Check consistency and validity of the input of the /(batch_)credential endpoints.
+    /// Return the DPoP public key and access token of a session state, to check requests against.
+    fn session_auth(session_state: &IssuanceState) -> (VerifyingKey, AccessToken) {
+        (
+            *session_state.dpop_private_key.verifying_key(),
+            session_state.access_token.clone(),
+        )
+    }
+
+    /// Check consistency and validity of the input of the /(batch_)credential endpoints, for a session state created
+    /// by `new_session_state()` that uses the specified DPoP key and access token.
     fn check_credential_endpoint_input(
         url: &Url,
-        session_state: &IssuanceState,
+        (dpop_public_key, access_token): &(VerifyingKey, AccessToken),
         dpop_header: &str,
         access_token_header: &str,
         attestations: &Option<WteDisclosure>,
         use_wte: bool,
     ) {
-        assert_eq!(
-            access_token_header,
-            "DPoP ".to_string() + session_state.access_token.as_ref()
-        );
+        assert_eq!(access_token_header, "DPoP ".to_string() + access_token.as_ref());
 
         Dpop::from(dpop_header.to_string())
             .verify_expecting_key(
-                session_state.dpop_private_key.verifying_key(),
+                dpop_public_key,
                 url,
                 &Method::POST,
-                Some(&session_state.access_token),
-                session_state.dpop_nonce.as_deref(),
+                Some(access_token),
+                Some("dpop_nonce"),
             )
             .unwrap();
 
@@ -1051,6 +1833,7 @@ mod tests {
         } else {
             vec![format]
         });
+        let auth = session_auth(&session_state);
 
         let mut mock_msg_client = mock_openid_message_client();
 
@@ -1058,51 +1841,104 @@ mod tests {
         // is being issued, and `request_credential()` instead (which uses `/credential`).
         if multiple_creds {
             mock_msg_client.expect_request_credentials().times(1).return_once({
-                let session_state = session_state.clone();
+                let auth = auth.clone();
                 move |url, credential_requests, dpop_header, access_token_header| {
                     check_credential_endpoint_input(
                         url,
-                        &session_state,
+                        &auth,
                         dpop_header,
                         access_token_header,
                         &credential_requests.attestations,
                         use_wte,
                     );
-                    Ok(CredentialResponses {
-                        credential_responses: vec![cred_response.clone(), cred_response],
-                    })
+                    (
+                        Ok(CredentialResponses {
+                            credential_responses: vec![cred_response.clone(), cred_response],
+                        }),
+                        None,
+                    )
                 }
             });
         } else {
             mock_msg_client.expect_request_credential().times(1).return_once({
-                let session_state = session_state.clone();
+                let auth = auth.clone();
                 move |url, credential_request, dpop_header, access_token_header| {
                     check_credential_endpoint_input(
                         url,
-                        &session_state,
+                        &auth,
                         dpop_header,
                         access_token_header,
                         &credential_request.attestations,
                         use_wte,
                     );
-                    Ok(cred_response)
+                    (Ok(cred_response), None)
                 }
             });
         }
 
         // _ is an error because our mock does not behave like an actual issuer should, but it doesn't matter
         // because we are just inspecting what the client sent in this test with the expectation above.
-        let _ = HttpIssuanceSession {
-            message_client: mock_msg_client,
-            session_state,
-        }
-        .accept_issuance(
-            &[trust_anchor],
-            &key_factory,
-            wte,
-            "https://issuer.example.com".parse().unwrap(),
-        )
-        .await;
+        let _ = HttpIssuanceSession::new(mock_msg_client, session_state)
+            .accept_issuance(
+                &[trust_anchor],
+                &key_factory,
+                wte,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_accept_issuance_selective() {
+        let (cred_response, preview, trust_anchor, _, key_factory) = create_credential_response().await;
+
+        // Offer two different credentials, of which only the first one will be selected.
+        let selected_doc_type = preview.credential_type();
+        let mut other_preview = preview.clone();
+        let CredentialPreview::MsoMdoc { unsigned_mdoc, .. } = &mut other_preview;
+        unsigned_mdoc.doc_type = "com.example.other".to_string();
+
+        let session_state = new_session_state(vec![
+            CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview.clone()]).unwrap()).unwrap(),
+            CredentialFormats::try_new(VecNonEmpty::try_from(vec![other_preview]).unwrap()).unwrap(),
+        ]);
+
+        // As only one credential is selected, the client should use the `/credential` endpoint to request it.
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client.expect_request_credential().times(1).return_once(
+            move |_url, credential_request, _dpop_header, _access_token_header| {
+                assert!(credential_request.credential_type.as_ref().matches(&preview));
+
+                (Ok(cred_response), None)
+            },
+        );
+
+        let session = HttpIssuanceSession::new(mock_msg_client, session_state);
+
+        // _ is an error because our mock does not behave like an actual issuer should, see `test_accept_issuance()`.
+        let _ = session
+            .accept_issuance_selective(
+                &HashSet::from([selected_doc_type]),
+                &[trust_anchor.clone()],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await;
+
+        // Selecting none of the offered credentials should result in an error.
+        let error = session
+            .accept_issuance_selective(
+                &HashSet::from(["com.example.not_offered".to_string()]),
+                &[trust_anchor],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .expect_err("accepting no credentials should fail");
+
+        assert_matches!(error, IssuanceSessionError::NoCredentialsSelected);
     }
 
     #[tokio::test]
@@ -1112,25 +1948,25 @@ mod tests {
 
         mock_msg_client.expect_request_credentials().return_once(
             |_url, _credential_requests, _dpop_header, _access_token_header| {
-                Ok(CredentialResponses {
-                    credential_responses: vec![cred_response], // return one credential response
-                })
+                (
+                    Ok(CredentialResponses {
+                        credential_responses: vec![cred_response], // return one credential response
+                    }),
+                    None,
+                )
             },
         );
 
         let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
-        let error = HttpIssuanceSession {
-            message_client: mock_msg_client,
-            session_state: new_session_state(vec![format.clone(), format]),
-        }
-        .accept_issuance(
-            &[trust_anchor],
-            &MockRemoteKeyFactory::default(),
-            None,
-            "https://issuer.example.com".parse().unwrap(),
-        )
-        .await
-        .unwrap_err();
+        let error = HttpIssuanceSession::new(mock_msg_client, new_session_state(vec![format.clone(), format]))
+            .accept_issuance(
+                &[trust_anchor],
+                &MockRemoteKeyFactory::default(),
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .unwrap_err();
 
         assert_matches!(
             error,
@@ -1138,12 +1974,359 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_accept_issuance_credential_format_not_allowed() {
+        let (_, preview, trust_anchor, _, _) = create_credential_response().await;
+
+        // No credential should be requested when the offered format is not allowed.
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client.expect_request_credential().never();
+
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+        let error = HttpIssuanceSession::new(mock_msg_client, new_session_state(vec![format]))
+            .with_allowed_formats(vec![Format::Jwt])
+            .accept_issuance(
+                &[trust_anchor],
+                &MockRemoteKeyFactory::default(),
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .expect_err("accepting credentials in a format that is not allowed should fail");
+
+        assert_matches!(error, IssuanceSessionError::CredentialFormatNotAllowed(Format::MsoMdoc));
+    }
+
+    #[tokio::test]
+    async fn test_accept_issuance_notification() {
+        let (cred_response, preview, trust_anchor, _, key_factory) = create_credential_response().await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+        let session_state = new_session_state(vec![format]);
+
+        // Have the issuer announce a notification endpoint and include a notification ID in its response.
+        let mut mock_msg_client = MockVcMessageClient::new();
+        mock_msg_client.expect_discover_metadata().returning(|url| {
+            let mut metadata = IssuerMetadata::new_mock(url);
+            metadata.issuer_config.notification_endpoint = Some(url.join_base_url("/notification"));
+            Ok(metadata)
+        });
+        mock_msg_client.expect_request_credential().times(1).return_once(
+            move |_url, _credential_request, _dpop_header, _access_token_header| {
+                let CredentialResponse::MsoMdoc { credential, .. } = cred_response;
+                let cred_response = CredentialResponse::MsoMdoc {
+                    credential,
+                    notification_id: Some("notification_id".to_string()),
+                };
+
+                (Ok(cred_response), None)
+            },
+        );
+
+        // As our mock does not behave like an actual issuer should (see `test_accept_issuance()`), processing the
+        // credential fails, which should be reported to the issuer.
+        let auth = session_auth(&session_state);
+        mock_msg_client.expect_notify().times(1).return_once(
+            move |url, notification_request, dpop_header, access_token_header| {
+                assert_eq!(url.path(), "/notification");
+                assert_eq!(notification_request.notification_id, "notification_id");
+                assert_eq!(notification_request.event, NotificationEvent::CredentialFailure);
+
+                check_credential_endpoint_input(url, &auth, dpop_header, access_token_header, &None, false);
+
+                Ok(())
+            },
+        );
+
+        HttpIssuanceSession::new(mock_msg_client, session_state)
+            .accept_issuance(
+                &[trust_anchor],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .expect_err("accepting issuance should fail");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_notify_received_credentials(#[values(true, false)] reject: bool) {
+        let (_, preview, _, _, _) = create_credential_response().await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+        let session_state = new_session_state(vec![format]);
+
+        let mut mock_msg_client = MockVcMessageClient::new();
+        mock_msg_client.expect_discover_metadata().returning(|url| {
+            let mut metadata = IssuerMetadata::new_mock(url);
+            metadata.issuer_config.notification_endpoint = Some(url.join_base_url("/notification"));
+            Ok(metadata)
+        });
+
+        // Rejecting the session after the credentials were received should report these as deleted by the user.
+        let expected_event = if reject {
+            NotificationEvent::CredentialDeleted
+        } else {
+            NotificationEvent::CredentialAccepted
+        };
+        mock_msg_client.expect_notify().times(1).return_once(
+            move |url, notification_request, _dpop_header, _access_token_header| {
+                assert_eq!(url.path(), "/notification");
+                assert_eq!(notification_request.notification_id, "notification_id");
+                assert_eq!(notification_request.event, expected_event);
+
+                Ok(())
+            },
+        );
+        mock_msg_client
+            .expect_reject()
+            .times(usize::from(reject))
+            .returning(|_url, _dpop_header, _access_token_header| Ok(()));
+
+        let session = HttpIssuanceSession {
+            notification_ids: Mutex::new(vec!["notification_id".to_string()]),
+            ..HttpIssuanceSession::new(mock_msg_client, session_state)
+        };
+
+        if reject {
+            session
+                .reject_issuance()
+                .await
+                .expect("rejecting issuance should succeed");
+        } else {
+            session
+                .notify(NotificationEvent::CredentialAccepted)
+                .await
+                .expect("notifying issuer should succeed");
+        }
+    }
+
+    async fn jwks_session(jwks: JwkSet) -> HttpIssuanceSession<MockVcMessageClient> {
+        let (_, preview, _, _, _) = create_credential_response().await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+
+        // The JWKS should only be fetched once, after which it is cached.
+        let mut mock_msg_client = MockVcMessageClient::new();
+        mock_msg_client.expect_jwks().times(1).return_once(move |_url| Ok(jwks));
+
+        HttpIssuanceSession::new(mock_msg_client, new_session_state(vec![format]))
+    }
+
+    #[tokio::test]
+    async fn test_verify_key_in_jwks() {
+        let key = *SigningKey::random(&mut OsRng).verifying_key();
+        let mut jwk = jwk_from_p256(&key).unwrap();
+        jwk.common.key_id = Some("kid".to_string());
+
+        let session = jwks_session(JwkSet { keys: vec![jwk] }).await;
+        let jwks_url: Url = "https://issuer.example.com/.well-known/jwks.json".parse().unwrap();
+
+        session.verify_key_in_jwks(None, &key, &jwks_url).await.unwrap();
+        session.verify_key_in_jwks(Some("kid"), &key, &jwks_url).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_key_in_jwks_error_key_not_in_jwks() {
+        let key = *SigningKey::random(&mut OsRng).verifying_key();
+        let mut jwk = jwk_from_p256(&key).unwrap();
+        jwk.common.key_id = Some("kid".to_string());
+        let symmetric_jwk =
+            serde_json::from_value(json!({"kty": "oct", "k": "c2VjcmV0", "kid": "symmetric_kid"})).unwrap();
+
+        let session = jwks_session(JwkSet {
+            keys: vec![jwk, symmetric_jwk],
+        })
+        .await;
+        let jwks_url: Url = "https://issuer.example.com/.well-known/jwks.json".parse().unwrap();
+
+        let other_key = *SigningKey::random(&mut OsRng).verifying_key();
+        let error = session
+            .verify_key_in_jwks(None, &other_key, &jwks_url)
+            .await
+            .expect_err("verifying key should fail");
+        assert_matches!(error, IssuanceSessionError::KeyNotInJwks);
+
+        let error = session
+            .verify_key_in_jwks(Some("other_kid"), &key, &jwks_url)
+            .await
+            .expect_err("verifying key should fail");
+        assert_matches!(error, IssuanceSessionError::KeyNotInJwks);
+
+        let error = session
+            .verify_key_in_jwks(Some("symmetric_kid"), &key, &jwks_url)
+            .await
+            .expect_err("verifying key should fail");
+        assert_matches!(error, IssuanceSessionError::KeyNotInJwks);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_accept_issuance_single_holder_key(#[values(true, false)] single_holder_key: bool) {
+        let (cred_response, preview, trust_anchor, _, key_factory) =
+            create_credential_response_with_unsigned_mdoc(|unsigned_mdoc| {
+                unsigned_mdoc.copy_count = 2.try_into().unwrap();
+            })
+            .await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+        let session_state = new_session_state(vec![format]);
+
+        // Both copies should be requested, with the same proof (and thus key) iff a single holder key is configured.
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client.expect_request_credentials().times(1).return_once(
+            move |_url, credential_requests, _dpop_header, _access_token_header| {
+                let proofs = credential_requests
+                    .credential_requests
+                    .as_slice()
+                    .iter()
+                    .map(|request| serde_json::to_string(request.proof.as_ref().unwrap()).unwrap())
+                    .collect_vec();
+
+                assert_eq!(proofs.len(), 2);
+                assert_eq!(proofs.iter().all_equal(), single_holder_key);
+
+                (
+                    Ok(CredentialResponses {
+                        credential_responses: vec![cred_response.clone(), cred_response],
+                    }),
+                    None,
+                )
+            },
+        );
+
+        let session =
+            HttpIssuanceSession::new(mock_msg_client, session_state).with_single_holder_key(single_holder_key);
+
+        // Our mock does not behave like an actual issuer should, as the credentials it returns are not bound to the
+        // keys that were sent. See `openid4vc/tests/issuance.rs` for this test against an actual issuer.
+        let error = session
+            .accept_issuance(
+                &[trust_anchor],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .expect_err("accepting issuance should fail");
+        assert_matches!(error, IssuanceSessionError::PublicKeyMismatch);
+    }
+
+    #[tokio::test]
+    async fn test_dpop_nonce_propagation() {
+        let (cred_response, preview, trust_anchor, _, key_factory) = create_credential_response().await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+        let session_state = new_session_state(vec![format.clone(), format]);
+        let (dpop_public_key, access_token) = session_auth(&session_state);
+
+        // The issuer rotates the DPoP nonce in its response to the credential request.
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client.expect_request_credentials().times(1).return_once({
+            let access_token = access_token.clone();
+            move |url, _credential_requests, dpop_header, _access_token_header| {
+                Dpop::from(dpop_header.to_string())
+                    .verify_expecting_key(
+                        &dpop_public_key,
+                        url,
+                        &Method::POST,
+                        Some(&access_token),
+                        Some("dpop_nonce"),
+                    )
+                    .unwrap();
+
+                (
+                    Ok(CredentialResponses {
+                        credential_responses: vec![cred_response.clone(), cred_response],
+                    }),
+                    Some("new_dpop_nonce".to_string()),
+                )
+            }
+        });
+
+        // The subsequent rejection should use the rotated DPoP nonce.
+        mock_msg_client
+            .expect_reject()
+            .times(1)
+            .return_once(move |url, dpop_header, _access_token_header| {
+                Dpop::from(dpop_header.to_string())
+                    .verify_expecting_key(
+                        &dpop_public_key,
+                        url,
+                        &Method::DELETE,
+                        Some(&access_token),
+                        Some("new_dpop_nonce"),
+                    )
+                    .unwrap();
+
+                Ok(())
+            });
+
+        let session = HttpIssuanceSession::new(mock_msg_client, session_state);
+
+        // _ is an error because our mock does not behave like an actual issuer should, see `test_accept_issuance()`.
+        let _ = session
+            .accept_issuance(
+                &[trust_anchor],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await;
+
+        assert_eq!(session.session_state.dpop_nonce().as_deref(), Some("new_dpop_nonce"));
+
+        session
+            .reject_issuance()
+            .await
+            .expect("rejecting issuance should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_dpop_nonce_propagation_error_response() {
+        let (_, preview, trust_anchor, _, key_factory) = create_credential_response().await;
+        let format = CredentialFormats::try_new(VecNonEmpty::try_from(vec![preview]).unwrap()).unwrap();
+
+        // The issuer may also rotate the DPoP nonce when responding with an error.
+        let mut mock_msg_client = mock_openid_message_client();
+        mock_msg_client.expect_request_credential().times(1).return_once(
+            |_url, _credential_request, _dpop_header, _access_token_header| {
+                (
+                    Err(IssuanceSessionError::CredentialRequest(ErrorResponse {
+                        error: CredentialErrorCode::InvalidProof,
+                        error_description: None,
+                        error_uri: None,
+                    })),
+                    Some("new_dpop_nonce".to_string()),
+                )
+            },
+        );
+
+        let session = HttpIssuanceSession::new(mock_msg_client, new_session_state(vec![format]));
+
+        let error = session
+            .accept_issuance(
+                &[trust_anchor],
+                &key_factory,
+                None,
+                "https://issuer.example.com".parse().unwrap(),
+            )
+            .await
+            .expect_err("accepting issuance should fail");
+
+        assert_matches!(error, IssuanceSessionError::CredentialRequest(_));
+        assert_eq!(session.session_state.dpop_nonce().as_deref(), Some("new_dpop_nonce"));
+    }
+
     #[tokio::test]
     async fn test_credential_response_into_mdoc() {
         let (credential_response, preview, trust_anchor, mdoc_public_key, _) = create_credential_response().await;
 
         let _ = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &mdoc_public_key, &preview, &[trust_anchor])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect("should be able to convert CredentialResponse into Mdoc");
     }
 
@@ -1155,7 +2338,14 @@ mod tests {
         // public key than the one contained within the response should fail.
         let other_public_key = *SigningKey::random(&mut OsRng).verifying_key();
         let error = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &other_public_key, &preview, &[trust_anchor])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &other_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect_err("should not be able to convert CredentialResponse into Mdoc");
 
         assert_matches!(error, IssuanceSessionError::PublicKeyMismatch);
@@ -1168,7 +2358,10 @@ mod tests {
         // Converting a `CredentialResponse` into an `Mdoc` from a response
         // that contains insufficient random data should fail.
         let credential_response = match credential_response {
-            CredentialResponse::MsoMdoc { mut credential } => {
+            CredentialResponse::MsoMdoc {
+                mut credential,
+                notification_id,
+            } => {
                 let CborBase64(ref mut credential_inner) = *credential;
                 let name_spaces = credential_inner.name_spaces.as_mut().unwrap();
 
@@ -1178,12 +2371,22 @@ mod tests {
                     first_item.random = ByteBuf::from(b"12345");
                 });
 
-                CredentialResponse::MsoMdoc { credential }
+                CredentialResponse::MsoMdoc {
+                    credential,
+                    notification_id,
+                }
             }
         };
 
         let error = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &mdoc_public_key, &preview, &[trust_anchor])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect_err("should not be able to convert CredentialResponse into Mdoc");
 
         assert_matches!(
@@ -1192,6 +2395,114 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_credential_response_into_mdoc_empty_attestation_error() {
+        let (credential_response, preview, trust_anchor, mdoc_public_key, _) = create_credential_response().await;
+
+        // Converting a `CredentialResponse` into an `Mdoc` from a response
+        // that does not contain any attributes should fail.
+        let credential_response = match credential_response {
+            CredentialResponse::MsoMdoc {
+                mut credential,
+                notification_id,
+            } => {
+                let CborBase64(ref mut credential_inner) = *credential;
+                credential_inner.name_spaces = None;
+
+                CredentialResponse::MsoMdoc {
+                    credential,
+                    notification_id,
+                }
+            }
+        };
+
+        let error = credential_response
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
+            .expect_err("should not be able to convert CredentialResponse into Mdoc");
+
+        assert_matches!(error, IssuanceSessionError::EmptyAttestation);
+    }
+
+    #[tokio::test]
+    async fn test_credential_response_into_mdoc_attribute_size_error() {
+        let (credential_response, preview, trust_anchor, mdoc_public_key, _) = create_credential_response().await;
+
+        // Converting a `CredentialResponse` into an `Mdoc` that contains
+        // an attribute exceeding the maximum attribute size should fail.
+        let size_limits = CredentialSizeLimits {
+            max_attribute_size: 1,
+            ..Default::default()
+        };
+        let error = credential_response
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &size_limits,
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
+            .expect_err("should not be able to convert CredentialResponse into Mdoc");
+
+        assert_matches!(error, IssuanceSessionError::AttributeSize(_, size, 1) if size > 1);
+    }
+
+    #[tokio::test]
+    async fn test_credential_response_into_mdoc_credential_size_error() {
+        let (credential_response, preview, trust_anchor, mdoc_public_key, _) = create_credential_response().await;
+
+        // Converting a `CredentialResponse` into an `Mdoc` that exceeds
+        // the maximum credential size should fail.
+        let size_limits = CredentialSizeLimits {
+            max_credential_size: 64,
+            ..Default::default()
+        };
+        let error = credential_response
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &size_limits,
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
+            .expect_err("should not be able to convert CredentialResponse into Mdoc");
+
+        assert_matches!(error, IssuanceSessionError::CredentialSize(size, 64) if size > 64);
+    }
+
+    #[tokio::test]
+    async fn test_credential_response_into_mdoc_max_future_validity_error() {
+        let (credential_response, preview, trust_anchor, mdoc_public_key, _) =
+            create_credential_response_with_unsigned_mdoc(|unsigned_mdoc| {
+                unsigned_mdoc.valid_from = (Utc::now() + Duration::days(30)).into();
+                unsigned_mdoc.valid_until = (Utc::now() + Duration::days(60)).into();
+            })
+            .await;
+
+        // Converting a `CredentialResponse` into an `Mdoc` whose validity
+        // starts too far in the future should fail.
+        let error = credential_response
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                Duration::days(7),
+            )
+            .expect_err("should not be able to convert CredentialResponse into Mdoc");
+
+        assert_matches!(error, IssuanceSessionError::Validity(ValidityError::TooFarInFuture(_)));
+    }
+
     #[tokio::test]
     async fn test_credential_response_into_mdoc_issuer_certificate_mismatch_error() {
         let (credential_response, preview, trust_anchor, mdoc_public_key, _) = create_credential_response().await;
@@ -1215,7 +2526,14 @@ mod tests {
         };
 
         let error = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &mdoc_public_key, &preview, &[trust_anchor])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect_err("should not be able to convert CredentialResponse into Mdoc");
 
         assert_matches!(error, IssuanceSessionError::IssuerMismatch);
@@ -1228,7 +2546,14 @@ mod tests {
         // Converting a `CredentialResponse` into an `Mdoc` that is
         // validated against incorrect trust anchors should fail.
         let error = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &mdoc_public_key, &preview, &[])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect_err("should not be able to convert CredentialResponse into Mdoc");
 
         assert_matches!(error, IssuanceSessionError::MdocVerification(_));
@@ -1253,7 +2578,14 @@ mod tests {
         };
 
         let error = credential_response
-            .into_credential::<MockRemoteEcdsaKey>("key_id".to_string(), &mdoc_public_key, &preview, &[trust_anchor])
+            .into_credential::<MockRemoteEcdsaKey>(
+                "key_id".to_string(),
+                &mdoc_public_key,
+                &preview,
+                &[trust_anchor],
+                &CredentialSizeLimits::default(),
+                DEFAULT_MAX_FUTURE_VALIDITY,
+            )
             .expect_err("should not be able to convert CredentialResponse into Mdoc");
 
         assert_matches!(
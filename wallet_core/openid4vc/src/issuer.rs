@@ -306,6 +306,10 @@ pub struct IssuerData<K, W> {
     wte_issuer_pubkey: EcdsaDecodingKey,
 
     wte_tracker: Arc<W>,
+
+    /// Whether to accept credential requests in which the wallet binds multiple copies of a credential to the same
+    /// key, which it only associates once in its PoA.
+    accept_single_holder_keys: bool,
 }
 
 impl<A, K, S, W> Drop for Issuer<A, K, S, W> {
@@ -331,6 +335,7 @@ where
         wallet_client_ids: Vec<String>,
         wte_issuer_pubkey: VerifyingKey,
         wte_tracker: W,
+        accept_single_holder_keys: bool,
     ) -> Self {
         let sessions = Arc::new(sessions);
         let wte_tracker = Arc::new(wte_tracker);
@@ -342,6 +347,7 @@ where
             accepted_wallet_client_ids: wallet_client_ids,
             wte_issuer_pubkey: (&wte_issuer_pubkey).into(),
             wte_tracker: Arc::clone(&wte_tracker),
+            accept_single_holder_keys,
 
             // In this implementation, for now the Credential Issuer Identifier also always acts as
             // the public server URL.
@@ -361,6 +367,7 @@ where
                     credential_endpoint: issuer_url.join_base_url("/credential"),
                     batch_credential_endpoint: Some(issuer_url.join_base_url("/batch_credential")),
                     deferred_credential_endpoint: None,
+                    nonce_endpoint: None,
                     notification_endpoint: None,
                     credential_response_encryption: CredentialResponseEncryption {
                         alg_values_supported: vec![],
@@ -773,8 +780,17 @@ impl Session<WaitingForResponse> {
             return Err(CredentialRequestError::WteAlreadyUsed);
         }
 
+        // If allowed, the wallet may bind multiple copies of a credential to the same key, which is associated only
+        // once in the PoA. Otherwise, the PoA verification below rejects duplicate keys.
+        let attestation_keys = attestation_keys.chain([wte_pubkey]);
+        let attestation_keys = if issuer_data.accept_single_holder_keys {
+            attestation_keys.sorted().dedup().collect_vec()
+        } else {
+            attestation_keys.collect_vec()
+        };
+
         poa.ok_or(CredentialRequestError::MissingPoa)?.verify(
-            &attestation_keys.chain([wte_pubkey]).collect_vec(),
+            &attestation_keys,
             issuer_identifier,
             NL_WALLET_CLIENT_ID,
             &self.state.data.c_nonce,
@@ -870,24 +886,36 @@ impl Session<WaitingForResponse> {
 
         self.check_credential_endpoint_access(&access_token, &dpop, "batch_credential", issuer_data)?;
 
-        let previews_and_holder_pubkeys = try_join_all(
-            credential_requests
-                .credential_requests
-                .as_slice()
-                .iter()
-                .zip(
-                    session_data
-                        .credential_previews
-                        .iter()
-                        .flat_map(|preview| preview.flatten_copies()),
-                )
-                .map(|(cred_req, preview)| async move {
-                    let key = cred_req.verify(&session_data.c_nonce, &preview, issuer_data)?;
-
-                    Ok::<_, CredentialRequestError>((preview, key))
-                }),
-        )
-        .await?;
+        // Match each credential request to the first remaining offered credential of the same type. This allows the
+        // wallet to request only some of the offered credentials, in which case it omits the others from its requests.
+        let mut offered_previews = session_data
+            .credential_previews
+            .iter()
+            .flat_map(|preview| preview.flatten_copies())
+            .collect_vec();
+        let requests_and_previews = credential_requests
+            .credential_requests
+            .as_slice()
+            .iter()
+            .map(|cred_req| {
+                let index = offered_previews
+                    .iter()
+                    .position(|preview| cred_req.credential_type.as_ref().matches(preview))
+                    .ok_or_else(|| {
+                        CredentialRequestError::CredentialTypeNotOffered(cred_req.credential_type.as_ref().to_string())
+                    })?;
+
+                Ok((cred_req, offered_previews.remove(index)))
+            })
+            .collect::<Result<Vec<_>, CredentialRequestError>>()?;
+
+        let previews_and_holder_pubkeys =
+            try_join_all(requests_and_previews.into_iter().map(|(cred_req, preview)| async move {
+                let key = cred_req.verify(&session_data.c_nonce, &preview, issuer_data)?;
+
+                Ok::<_, CredentialRequestError>((preview, key))
+            }))
+            .await?;
 
         self.verify_wte_and_poa(
             credential_requests.attestations,
@@ -1007,6 +1035,7 @@ impl CredentialResponse {
 
                 Ok(CredentialResponse::MsoMdoc {
                     credential: Box::new(issuer_signed.into()),
+                    notification_id: None,
                 })
             }
         }
@@ -1,6 +1,3 @@
-use serde::Deserialize;
-use serde::Serialize;
-
 // Data structures implemening OAuth/OpenID(4VCI) protocol messages.
 pub mod authorization;
 pub mod credential;
@@ -18,6 +15,9 @@ pub mod attributes;
 pub mod issuance_session;
 pub mod issuer;
 
+#[cfg(feature = "insecure_dev_issuer")]
+mod dev_issuer;
+
 // Verification code for the server and client.
 pub mod disclosure_session;
 pub mod openid4vp;
@@ -39,19 +39,4 @@ pub mod mock;
 #[cfg(test)]
 mod test;
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Format {
-    #[default]
-    MsoMdoc,
-    Jwt,
-
-    // Other formats we don't currently support; we include them here so we can give the appropriate error message
-    // when they might be requested by the wallet (as opposed to a deserialization error).
-    // The OpenID4VCI and OpenID4VP specs aim to be general and do not provide an exhaustive list; the formats below
-    // are found as examples in the specs.
-    LdpVc,
-    JwtVc,
-    JwtVcJson,
-    AcVc, // Anonymous Credentials i.e. Idemix
-}
+pub use wallet_common::format::Format;
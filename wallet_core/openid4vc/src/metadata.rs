@@ -3,10 +3,13 @@ use std::collections::HashMap;
 use chrono::serde::ts_seconds;
 use chrono::DateTime;
 use chrono::Utc;
+use indexmap::IndexMap;
 use serde::Deserialize;
 use serde::Serialize;
-
 use serde_with::skip_serializing_none;
+
+use nl_wallet_mdoc::unsigned::Entry;
+use nl_wallet_mdoc::NameSpace;
 use wallet_common::jwt::Jwt;
 use wallet_common::urls::BaseUrl;
 
@@ -71,6 +74,11 @@ pub struct IssuerData {
     /// not support the Deferred Credential Endpoint.
     pub deferred_credential_endpoint: Option<BaseUrl>,
 
+    /// URL of the Credential Issuer's Nonce Endpoint, as defined in Section 7 of draft 14 of the spec. This URL MUST
+    /// use the https scheme and MAY contain port, path, and query parameter components. If omitted, the Credential
+    /// Issuer either does not require a `c_nonce` or returns it in the Token Response.
+    pub nonce_endpoint: Option<BaseUrl>,
+
     /// URL of the Credential Issuer's Notification Endpoint, as defined in Section 10. This URL MUST use the https
     /// scheme and MAY contain port, path, and query parameter components. If omitted, the Credential Issuer does not
     /// support the Notification Endpoint.
@@ -107,6 +115,71 @@ impl IssuerData {
             Some(_) | None => vec![self.credential_issuer.clone()],
         }
     }
+
+    /// Returns the claims that the issuer announces for mdocs of the given doctype, if any.
+    pub fn mdoc_claims(&self, doc_type: &str) -> Option<&HashMap<String, HashMap<String, MsoMdocClaim>>> {
+        self.credential_configurations_supported
+            .values()
+            .find_map(|metadata| match &metadata.format {
+                CredentialFormat::MsoMdoc { doctype, claims, .. } if doctype == doc_type => Some(claims),
+                _ => None,
+            })
+    }
+
+    /// Check the attributes of a previously issued mdoc against the claims that the issuer currently announces for its
+    /// doctype, returning all differences found. An empty result means that the mdoc still conforms to the metadata.
+    pub fn mdoc_metadata_drift(
+        &self,
+        doc_type: &str,
+        attributes: &IndexMap<NameSpace, Vec<Entry>>,
+    ) -> Vec<CredentialMetadataDrift> {
+        let Some(claims) = self.mdoc_claims(doc_type) else {
+            return vec![CredentialMetadataDrift::DocTypeNotSupported];
+        };
+
+        let unknown = attributes.iter().flat_map(|(name_space, entries)| {
+            entries
+                .iter()
+                .filter(|entry| {
+                    !claims
+                        .get(name_space)
+                        .is_some_and(|name_space_claims| name_space_claims.contains_key(&entry.name))
+                })
+                .map(|entry| CredentialMetadataDrift::UnknownAttribute {
+                    name_space: name_space.clone(),
+                    attribute: entry.name.clone(),
+                })
+        });
+
+        let missing = claims.iter().flat_map(|(name_space, name_space_claims)| {
+            name_space_claims
+                .iter()
+                .filter(|(_, claim)| claim.mandatory.unwrap_or_default())
+                .filter(|(attribute, _)| {
+                    !attributes
+                        .get(name_space)
+                        .is_some_and(|entries| entries.iter().any(|entry| entry.name == **attribute))
+                })
+                .map(|(attribute, _)| CredentialMetadataDrift::MissingMandatoryAttribute {
+                    name_space: name_space.clone(),
+                    attribute: attribute.clone(),
+                })
+        });
+
+        unknown.chain(missing).collect()
+    }
+}
+
+/// A difference between a previously issued credential and the Credential Issuer metadata as it is currently
+/// announced by the issuer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialMetadataDrift {
+    /// The issuer no longer announces a credential configuration for the doctype of the credential.
+    DocTypeNotSupported,
+    /// The credential contains an attribute that the issuer no longer announces.
+    UnknownAttribute { name_space: String, attribute: String },
+    /// The issuer announces a mandatory attribute that the credential does not contain.
+    MissingMandatoryAttribute { name_space: String, attribute: String },
 }
 
 /// Claims of a JWT containing [`IssuerData`].
@@ -364,7 +437,13 @@ pub struct BackgroundImage {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use assert_matches::assert_matches;
+    use ciborium::Value;
+    use indexmap::IndexMap;
+
+    use nl_wallet_mdoc::unsigned::Entry;
 
     use crate::metadata::CredentialMetadata;
     use crate::metadata::CredentialSigningAlg;
@@ -373,6 +452,7 @@ mod tests {
     use crate::metadata::ProofType;
 
     use super::CredentialFormat;
+    use super::CredentialMetadataDrift;
     use super::IssuerMetadata;
 
     #[test]
@@ -596,4 +676,70 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn test_mdoc_metadata_drift() {
+        let credential_metadata: CredentialMetadata = serde_json::from_str(
+            r#"{
+                "format": "mso_mdoc",
+                "doctype": "com.example.pid",
+                "claims": {
+                    "com.example.pid": {
+                        "given_name": {},
+                        "birth_date": { "mandatory": true }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut metadata = IssuerMetadata::new_mock(&"https://issuer.example.com".parse().unwrap());
+        metadata.issuer_config.credential_configurations_supported =
+            HashMap::from([("pid".to_string(), credential_metadata)]);
+
+        let entry = |name: &str| Entry {
+            name: name.to_string(),
+            value: Value::Text("value".to_string()),
+        };
+
+        // An mdoc containing only announced attributes, including all mandatory ones, conforms.
+        let attributes = IndexMap::from([(
+            "com.example.pid".to_string(),
+            vec![entry("given_name"), entry("birth_date")],
+        )]);
+        assert!(metadata
+            .issuer_config
+            .mdoc_metadata_drift("com.example.pid", &attributes)
+            .is_empty());
+
+        // An mdoc with an attribute the issuer no longer announces and missing a mandatory attribute has drifted.
+        let attributes = IndexMap::from([(
+            "com.example.pid".to_string(),
+            vec![entry("given_name"), entry("nickname")],
+        )]);
+        let drift = metadata
+            .issuer_config
+            .mdoc_metadata_drift("com.example.pid", &attributes);
+        assert_eq!(
+            drift,
+            vec![
+                CredentialMetadataDrift::UnknownAttribute {
+                    name_space: "com.example.pid".to_string(),
+                    attribute: "nickname".to_string(),
+                },
+                CredentialMetadataDrift::MissingMandatoryAttribute {
+                    name_space: "com.example.pid".to_string(),
+                    attribute: "birth_date".to_string(),
+                },
+            ]
+        );
+
+        // A doctype that the issuer no longer announces has drifted entirely.
+        assert_eq!(
+            metadata
+                .issuer_config
+                .mdoc_metadata_drift("com.example.other", &attributes),
+            vec![CredentialMetadataDrift::DocTypeNotSupported]
+        );
+    }
 }
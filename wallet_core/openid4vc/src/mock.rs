@@ -1,7 +1,11 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
 
+use chrono::Duration;
 use indexmap::IndexSet;
 use rustls_pki_types::TrustAnchor;
+use url::Url;
 
 use wallet_common::keys::factory::KeyFactory;
 use wallet_common::keys::CredentialEcdsaKey;
@@ -9,7 +13,9 @@ use wallet_common::wte::WteClaims;
 
 use wallet_common::urls::BaseUrl;
 
+use crate::credential::NotificationEvent;
 use crate::credential_formats::CredentialFormats;
+use crate::issuance_session::CredentialSizeLimits;
 use crate::issuance_session::HttpVcMessageClient;
 use crate::issuance_session::IssuanceSession;
 use crate::issuance_session::IssuanceSessionError;
@@ -22,6 +28,7 @@ use crate::oidc::Config;
 use crate::token::CredentialPreview;
 use crate::token::TokenRequest;
 use crate::token::TokenRequestGrantType;
+use crate::Format;
 
 // We can't use `mockall::automock!` on the `IssuerClient` trait directly since `automock` doesn't accept
 // traits using generic methods, and "impl trait" arguments, so we use `mockall::mock!` to make an indirection.
@@ -36,7 +43,16 @@ mockall::mock! {
             &self,
         ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError>;
 
+        pub fn accept_selective(
+            &self,
+            selected_doc_types: &HashSet<String>,
+        ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError>;
+
+        pub fn notify_event(&self, event: NotificationEvent) -> Result<(), IssuanceSessionError>;
+
         pub fn reject(self) -> Result<(), IssuanceSessionError>;
+
+        pub fn metadata(base_url: BaseUrl) -> Result<IssuerMetadata, IssuanceSessionError>;
     }
 }
 
@@ -63,9 +79,55 @@ impl IssuanceSession for MockIssuanceSession {
         self.accept()
     }
 
+    async fn accept_issuance_selective<K: CredentialEcdsaKey>(
+        &self,
+        selected_doc_types: &HashSet<String>,
+        _: &[TrustAnchor<'_>],
+        _: &impl KeyFactory<Key = K>,
+        _: Option<JwtCredential<WteClaims>>,
+        _: BaseUrl,
+    ) -> Result<Vec<IssuedCredentialCopies>, IssuanceSessionError> {
+        self.accept_selective(selected_doc_types)
+    }
+
+    async fn notify(&self, event: NotificationEvent) -> Result<(), IssuanceSessionError> {
+        self.notify_event(event)
+    }
+
+    fn with_concurrency_limit(self, _: NonZeroUsize) -> Self {
+        self
+    }
+
+    fn with_allowed_formats(self, _: Vec<Format>) -> Self {
+        self
+    }
+
+    fn with_size_limits(self, _: CredentialSizeLimits) -> Self {
+        self
+    }
+
+    fn with_max_future_validity(self, _: Duration) -> Self {
+        self
+    }
+
+    fn with_jwks_verification(self, _: Url) -> Self {
+        self
+    }
+
+    fn with_single_holder_key(self, _: bool) -> Self {
+        self
+    }
+
     async fn reject_issuance(self) -> Result<(), IssuanceSessionError> {
         self.reject()
     }
+
+    async fn discover_metadata(
+        _: HttpVcMessageClient,
+        base_url: BaseUrl,
+    ) -> Result<IssuerMetadata, IssuanceSessionError> {
+        Self::metadata(base_url)
+    }
 }
 
 impl Config {
@@ -123,6 +185,7 @@ impl IssuerMetadata {
                 credential_endpoint: url.join_base_url("/credential"),
                 batch_credential_endpoint: Some(url.join_base_url("/batch_credential")),
                 deferred_credential_endpoint: None,
+                nonce_endpoint: None,
                 notification_endpoint: None,
                 credential_response_encryption: CredentialResponseEncryption {
                     alg_values_supported: vec![],
@@ -6,6 +6,8 @@ use chrono::Utc;
 use indexmap::IndexSet;
 use itertools::Itertools;
 use josekit::jwe::alg::ecdh_es::EcdhEsJweAlgorithm;
+use josekit::jwe::alg::ecdh_es::EcdhEsJweDecrypter;
+use josekit::jwe::JweDecrypter;
 use josekit::jwe::JweHeader;
 use josekit::jwk::alg::ec::EcKeyPair;
 use josekit::jwk::Jwk;
@@ -23,9 +25,11 @@ use serde_with::OneOrMany;
 
 use error_category::ErrorCategory;
 use nl_wallet_mdoc::errors::Error as MdocError;
+use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
 use nl_wallet_mdoc::utils::serialization::CborBase64;
 use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
 use nl_wallet_mdoc::utils::x509::CertificateError;
+use nl_wallet_mdoc::utils::x509::CertificateType;
 use nl_wallet_mdoc::verifier::DisclosedAttributes;
 use nl_wallet_mdoc::verifier::ItemsRequests;
 use nl_wallet_mdoc::DeviceResponse;
@@ -82,6 +86,36 @@ pub enum RequestUriMethod {
     POST,
 }
 
+/// The response modes in which a verifier can receive the Authorization Response at its `response_uri`. Both are
+/// defined by OpenID4VP:
+/// - `direct_post`: the wallet posts the `vp_token`, `presentation_submission` and `state` parameters as is.
+/// - `direct_post.jwt`: the wallet posts a JWE, which encrypts these parameters to the encryption key of the verifier.
+///   This is the response mode required by ISO 18013-7 and the only one supported by our own wallet.
+///
+/// Note that ISO 18013-7 conveys the mdoc generated nonce, which is part of the session transcript, in the `apu`
+/// header of the JWE. As `direct_post` offers no standardized way of conveying it, this response mode relies on a
+/// private extension, see [`VpDirectPostResponse`]. It is therefore never the default and has to be enabled
+/// explicitly per use case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, strum::Display)]
+pub enum VpResponseMode {
+    #[serde(rename = "direct_post")]
+    #[strum(to_string = "direct_post")]
+    DirectPost,
+    #[default]
+    #[serde(rename = "direct_post.jwt")]
+    #[strum(to_string = "direct_post.jwt")]
+    DirectPostJwt,
+}
+
+impl From<VpResponseMode> for ResponseMode {
+    fn from(value: VpResponseMode) -> Self {
+        match value {
+            VpResponseMode::DirectPost => ResponseMode::DirectPost,
+            VpResponseMode::DirectPostJwt => ResponseMode::DirectPostJwt,
+        }
+    }
+}
+
 /// An OpenID4VP Authorization Request, allowing an RP to request a set of credentials/attributes from a wallet.
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -304,6 +338,9 @@ pub enum AuthRequestValidationError {
     #[error("mismatch in wallet nonce: did not receive nonce when one was expected, or vice versa")]
     #[category(critical)]
     WalletNonceMismatch,
+    #[error("no reader registration present in X.509 certificate")]
+    #[category(critical)]
+    MissingReaderRegistration,
 }
 
 impl VpAuthorizationRequest {
@@ -321,6 +358,23 @@ impl VpAuthorizationRequest {
         )?)
     }
 
+    /// Verify the signing certificate of an Authorization Request JWT against the specified reader trust anchors and
+    /// extract the [`ReaderRegistration`] from it, so that the user can be shown who is requesting their attributes.
+    /// Note that this does not validate the contents of the Authorization Request, see [`Self::validate()`].
+    pub fn verify_reader(
+        jws: &Jwt<VpAuthorizationRequest>,
+        trust_anchors: &[TrustAnchor],
+    ) -> Result<(BorrowingCertificate, ReaderRegistration), AuthRequestValidationError> {
+        let (_, certificate) = Self::try_new(jws, trust_anchors)?;
+
+        let reader_registration = match CertificateType::from_certificate(&certificate)? {
+            CertificateType::ReaderAuth(Some(reader_registration)) => *reader_registration,
+            _ => return Err(AuthRequestValidationError::MissingReaderRegistration),
+        };
+
+        Ok((certificate, reader_registration))
+    }
+
     /// Validate that an Authorization Request satisfies the following:
     /// - the request contents are compliant with the profile from ISO 18013-7 Appendix B,
     /// - the `client_id` equals the DNS SAN name in the X.509 certificate, as required by the [`x509_san_dns` value for
@@ -365,6 +419,8 @@ pub struct IsoVpAuthorizationRequest {
     pub nonce: String,
     pub encryption_pubkey: Jwk,
     pub response_uri: BaseUrl,
+    #[serde(default)]
+    pub response_mode: VpResponseMode,
     pub items_requests: ItemsRequests,
     pub presentation_definition: PresentationDefinition,
     pub client_metadata: ClientMetadata,
@@ -393,6 +449,7 @@ impl IsoVpAuthorizationRequest {
             nonce,
             encryption_pubkey: encryption_pubkey.clone(),
             response_uri,
+            response_mode: VpResponseMode::default(),
             presentation_definition: items_requests.into(),
             items_requests: items_requests.clone(),
             client_metadata: ClientMetadata {
@@ -419,7 +476,7 @@ impl From<IsoVpAuthorizationRequest> for VpAuthorizationRequest {
                 response_type: ResponseType::VpToken.into(),
                 client_id: value.client_id,
                 nonce: Some(value.nonce),
-                response_mode: Some(ResponseMode::DirectPostJwt),
+                response_mode: Some(value.response_mode.into()),
                 redirect_uri: None,
                 state: None,
                 authorization_details: None,
@@ -482,13 +539,17 @@ impl TryFrom<VpAuthorizationRequest> for IsoVpAuthorizationRequest {
                 found: serde_json::to_string(&vp_auth_request.oauth_request.response_type).unwrap(),
             });
         }
-        if *vp_auth_request.oauth_request.response_mode.as_ref().unwrap() != ResponseMode::DirectPostJwt {
-            return Err(AuthRequestValidationError::UnsupportedFieldValue {
-                field: "response_mode",
-                expected: "direct_post.jwt",
-                found: serde_json::to_string(&vp_auth_request.oauth_request.response_mode).unwrap(),
-            });
-        }
+        let response_mode = match vp_auth_request.oauth_request.response_mode.unwrap() {
+            ResponseMode::DirectPost => VpResponseMode::DirectPost,
+            ResponseMode::DirectPostJwt => VpResponseMode::DirectPostJwt,
+            response_mode => {
+                return Err(AuthRequestValidationError::UnsupportedFieldValue {
+                    field: "response_mode",
+                    expected: "direct_post or direct_post.jwt",
+                    found: serde_json::to_string(&response_mode).unwrap(),
+                })
+            }
+        };
         if *vp_auth_request.client_id_scheme.as_ref().unwrap() != ClientIdScheme::X509SanDns {
             return Err(AuthRequestValidationError::UnsupportedFieldValue {
                 field: "client_id_scheme",
@@ -533,6 +594,7 @@ impl TryFrom<VpAuthorizationRequest> for IsoVpAuthorizationRequest {
             encryption_pubkey: jwk,
             items_requests: (&presentation_definition).try_into()?,
             response_uri: vp_auth_request.response_uri.unwrap(),
+            response_mode,
             presentation_definition,
             client_metadata,
             state: vp_auth_request.oauth_request.state,
@@ -560,6 +622,8 @@ pub enum AuthResponseError {
     Base64(#[from] DecodeError),
     #[error("missing apu field from JWE")]
     MissingApu,
+    #[error("missing mdoc_generated_nonce parameter from Authorization Response")]
+    MissingMdocGeneratedNonce,
     #[error("missing apv field from JWE")]
     MissingApv,
     #[error("failed to decode apu/apv field from JWE")]
@@ -578,6 +642,28 @@ pub enum AuthResponseError {
     MissingPoa,
     #[error("error verifying PoA: {0}")]
     PoaVerification(#[from] PoaVerificationError),
+    #[error("response mode of Authorization Response does not match: expected {expected}, found {found}")]
+    ResponseModeMismatch {
+        expected: VpResponseMode,
+        found: VpResponseMode,
+    },
+}
+
+/// A private key with which the JWE containing a [`VpAuthorizationResponse`] can be decrypted. This abstracts over
+/// where the key lives: by implementing [`JweDecrypter`], the ECDH-ES key agreement may for example be performed by
+/// an HSM, instead of by a software key in memory.
+pub trait JweDecryptionKey {
+    type Decrypter: JweDecrypter;
+
+    fn decrypter(&self) -> Result<Self::Decrypter, JoseError>;
+}
+
+impl JweDecryptionKey for EcKeyPair {
+    type Decrypter = EcdhEsJweDecrypter;
+
+    fn decrypter(&self) -> Result<Self::Decrypter, JoseError> {
+        EcdhEsJweAlgorithm::EcdhEs.decrypter_from_jwk(&self.to_jwk_key_pair())
+    }
 }
 
 // We do not reuse or embed the `AuthorizationResponse` struct from `authorization.rs`, because in no variant
@@ -612,6 +698,41 @@ pub enum VerifiablePresentation {
     MsoMdoc(CborBase64<DeviceResponse>),
 }
 
+/// The parameters of an Authorization Response as posted by the wallet in the `direct_post` response mode.
+///
+/// The `mdoc_generated_nonce` parameter is a private extension that is not defined by OpenID4VP or ISO 18013-7.
+/// The latter only specifies the encrypted `direct_post.jwt` response mode, in which the mdoc generated nonce is
+/// conveyed in the `apu` header of the JWE. Without it the session transcript that the disclosed mdocs are signed
+/// against cannot be reconstructed, so this response mode only interoperates with wallets that include it.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VpDirectPostResponse {
+    /// The Verifiable Presentation, which is a base64url encoded CBOR `DeviceResponse`.
+    pub vp_token: String,
+    /// The JSON encoded Presentation Submission.
+    pub presentation_submission: String,
+    pub state: Option<String>,
+    pub mdoc_generated_nonce: Option<String>,
+}
+
+impl VpDirectPostResponse {
+    /// Parse the Authorization Response from these parameters, returning it together with the mdoc generated nonce.
+    pub fn into_response(self) -> Result<(VpAuthorizationResponse, String), AuthResponseError> {
+        let mdoc_nonce = self
+            .mdoc_generated_nonce
+            .ok_or(AuthResponseError::MissingMdocGeneratedNonce)?;
+
+        let response = VpAuthorizationResponse {
+            vp_token: vec![serde_json::from_value(serde_json::Value::String(self.vp_token))?],
+            presentation_submission: serde_json::from_str(&self.presentation_submission)?,
+            state: self.state,
+            poa: None,
+        };
+
+        Ok((response, mdoc_nonce))
+    }
+}
+
 impl VpAuthorizationResponse {
     fn new(device_response: DeviceResponse, auth_request: &IsoVpAuthorizationRequest, poa: Option<Poa>) -> Self {
         let presentation_submission = PresentationSubmission {
@@ -681,7 +802,7 @@ impl VpAuthorizationResponse {
 
     pub fn decrypt_and_verify(
         jwe: &str,
-        private_key: &EcKeyPair,
+        private_key: &impl JweDecryptionKey,
         auth_request: &IsoVpAuthorizationRequest,
         time: &impl Generator<DateTime<Utc>>,
         trust_anchors: &[TrustAnchor],
@@ -693,12 +814,10 @@ impl VpAuthorizationResponse {
 
     pub fn decrypt(
         jwe: &str,
-        private_key: &EcKeyPair,
+        private_key: &impl JweDecryptionKey,
         nonce: &str,
     ) -> Result<(VpAuthorizationResponse, String), AuthResponseError> {
-        let decrypter = EcdhEsJweAlgorithm::EcdhEs
-            .decrypter_from_jwk(&private_key.to_jwk_key_pair())
-            .map_err(AuthResponseError::JwkConversion)?;
+        let decrypter = private_key.decrypter().map_err(AuthResponseError::JwkConversion)?;
         let (payload, header) = josekit::jwt::decode_with_decrypter(jwe, &decrypter).map_err(AuthResponseError::Jwe)?;
 
         let jwe_nonce = String::from_utf8(header.agreement_partyvinfo().ok_or(AuthResponseError::MissingApv)?)?;
@@ -711,6 +830,33 @@ impl VpAuthorizationResponse {
         Ok((payload, mdoc_nonce))
     }
 
+    /// Serialize the Authorization Response to the parameters that the wallet posts in the `direct_post` response
+    /// mode. Note that this response mode offers no way of including a PoA.
+    pub fn new_direct_post(
+        device_response: DeviceResponse,
+        auth_request: &IsoVpAuthorizationRequest,
+        mdoc_nonce: &str,
+    ) -> Result<VpDirectPostResponse, AuthResponseError> {
+        let VpAuthorizationResponse {
+            vp_token,
+            presentation_submission,
+            state,
+            ..
+        } = Self::new(device_response, auth_request, None);
+        let [vp_token] = <[VerifiablePresentation; 1]>::try_from(vp_token)
+            .map_err(|vp_token| AuthResponseError::UnexpectedVpCount(vp_token.len()))?;
+
+        let direct_post_response = VpDirectPostResponse {
+            // A `VerifiablePresentation` serializes to a string, see `CborBase64`.
+            vp_token: serde_json::from_value(serde_json::to_value(vp_token)?)?,
+            presentation_submission: serde_json::to_string(&presentation_submission)?,
+            state,
+            mdoc_generated_nonce: Some(mdoc_nonce.to_string()),
+        };
+
+        Ok(direct_post_response)
+    }
+
     fn device_response(&self) -> Result<&DeviceResponse, AuthResponseError> {
         if self.vp_token.len() != 1 {
             return Err(AuthResponseError::UnexpectedVpCount(self.vp_token.len()));
@@ -814,14 +960,25 @@ pub struct VpResponse {
 mod tests {
     use std::borrow::Cow;
     use std::num::NonZeroU8;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
 
+    use assert_matches::assert_matches;
     use chrono::DateTime;
     use chrono::Utc;
     use futures::future::join_all;
     use indexmap::IndexMap;
     use itertools::Itertools;
+    use josekit::jwe::alg::ecdh_es::EcdhEsJweDecrypter;
+    use josekit::jwe::JweAlgorithm;
+    use josekit::jwe::JweContentEncryption;
+    use josekit::jwe::JweDecrypter;
+    use josekit::jwe::JweHeader;
     use josekit::jwk::alg::ec::EcCurve;
     use josekit::jwk::alg::ec::EcKeyPair;
+    use josekit::JoseError;
+    use rstest::rstest;
     use rustls_pki_types::TrustAnchor;
     use serde_json::json;
 
@@ -832,6 +989,7 @@ mod tests {
     use nl_wallet_mdoc::server_keys::KeyPair;
     use nl_wallet_mdoc::test::data::addr_street;
     use nl_wallet_mdoc::test::data::pid_full_name;
+    use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
     use nl_wallet_mdoc::utils::serialization::cbor_serialize;
     use nl_wallet_mdoc::utils::serialization::CborBase64;
     use nl_wallet_mdoc::utils::serialization::CborSeq;
@@ -855,8 +1013,11 @@ mod tests {
     use wallet_common::keys::poa::Poa;
     use wallet_common::vec_at_least::VecAtLeastTwoUnique;
 
+    use crate::authorization::ResponseMode;
+    use crate::openid4vp::AuthRequestValidationError;
     use crate::openid4vp::AuthResponseError;
     use crate::openid4vp::IsoVpAuthorizationRequest;
+    use crate::openid4vp::JweDecryptionKey;
     use crate::AuthorizationErrorCode;
     use crate::VpAuthorizationErrorCode;
 
@@ -864,6 +1025,8 @@ mod tests {
     use super::VerifiablePresentation;
     use super::VpAuthorizationRequest;
     use super::VpAuthorizationResponse;
+    use super::VpDirectPostResponse;
+    use super::VpResponseMode;
 
     #[test]
     fn test_vp_authorization_error_code_serialization() {
@@ -935,6 +1098,157 @@ mod tests {
         assert_eq!(decrypted_document.issuer_signed, encrypted_document.issuer_signed);
     }
 
+    /// Stands in for a key that is not held in memory, such as one in an HSM, by only exposing the key agreement
+    /// through [`JweDecrypter`] and counting how often it is performed.
+    struct MockDecryptionKey {
+        key_pair: EcKeyPair,
+        decryption_count: Arc<AtomicUsize>,
+    }
+
+    #[derive(Debug, Clone)]
+    struct MockDecrypter {
+        decrypter: EcdhEsJweDecrypter,
+        decryption_count: Arc<AtomicUsize>,
+    }
+
+    impl JweDecryptionKey for MockDecryptionKey {
+        type Decrypter = MockDecrypter;
+
+        fn decrypter(&self) -> Result<Self::Decrypter, JoseError> {
+            Ok(MockDecrypter {
+                decrypter: self.key_pair.decrypter()?,
+                decryption_count: Arc::clone(&self.decryption_count),
+            })
+        }
+    }
+
+    impl JweDecrypter for MockDecrypter {
+        fn algorithm(&self) -> &dyn JweAlgorithm {
+            self.decrypter.algorithm()
+        }
+
+        fn key_id(&self) -> Option<&str> {
+            self.decrypter.key_id()
+        }
+
+        fn decrypt(
+            &self,
+            encrypted_key: Option<&[u8]>,
+            cencryption: &dyn JweContentEncryption,
+            header: &JweHeader,
+        ) -> Result<Cow<'_, [u8]>, JoseError> {
+            self.decryption_count.fetch_add(1, Ordering::Relaxed);
+
+            self.decrypter.decrypt(encrypted_key, cencryption, header)
+        }
+
+        fn box_clone(&self) -> Box<dyn JweDecrypter> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_decrypt_authorization_response_with_decryption_key() {
+        let (_, _, encryption_privkey, auth_request) = setup();
+        let decryption_key = MockDecryptionKey {
+            key_pair: encryption_privkey,
+            decryption_count: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let mdoc_nonce = "mdoc_nonce".to_string();
+        let auth_request = IsoVpAuthorizationRequest::try_from(auth_request).unwrap();
+        let auth_response = VpAuthorizationResponse::new(DeviceResponse::example(), &auth_request, None);
+        let jwe = auth_response.encrypt(&auth_request, &mdoc_nonce).unwrap();
+
+        let (_, jwe_mdoc_nonce) = VpAuthorizationResponse::decrypt(&jwe, &decryption_key, &auth_request.nonce)
+            .expect("decrypting with the decryption key should succeed");
+
+        // The key agreement should have been performed by the decryption key exactly once.
+        assert_eq!(jwe_mdoc_nonce, mdoc_nonce);
+        assert_eq!(decryption_key.decryption_count.load(Ordering::Relaxed), 1);
+
+        // A decryption key that does not match the public key the response was encrypted to should be rejected.
+        let other_key = MockDecryptionKey {
+            key_pair: EcKeyPair::generate(EcCurve::P256).unwrap(),
+            decryption_count: Arc::new(AtomicUsize::new(0)),
+        };
+        let error = VpAuthorizationResponse::decrypt(&jwe, &other_key, &auth_request.nonce)
+            .expect_err("decrypting with another decryption key should fail");
+
+        assert_matches!(error, AuthResponseError::Jwe(_));
+        assert_eq!(other_key.decryption_count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_direct_post_authorization_response() {
+        let (_, _, _, auth_request) = setup();
+
+        let mdoc_nonce = "mdoc_nonce".to_string();
+        let device_response = DeviceResponse::example();
+        let auth_request = IsoVpAuthorizationRequest::try_from(auth_request).unwrap();
+        let direct_post_response =
+            VpAuthorizationResponse::new_direct_post(device_response.clone(), &auth_request, &mdoc_nonce).unwrap();
+
+        // The parameters should survive being posted form encoded.
+        let direct_post_response: VpDirectPostResponse =
+            serde_urlencoded::from_str(&serde_urlencoded::to_string(&direct_post_response).unwrap()).unwrap();
+
+        let (parsed, parsed_mdoc_nonce) = direct_post_response.clone().into_response().unwrap();
+        assert_eq!(parsed_mdoc_nonce, mdoc_nonce);
+
+        let VerifiablePresentation::MsoMdoc(CborBase64(parsed_device_response)) = parsed.vp_token.first().unwrap();
+        let document = device_response.documents.as_ref().unwrap().first().unwrap();
+        let parsed_document = parsed_device_response.documents.as_ref().unwrap().first().unwrap();
+
+        assert_eq!(parsed_document.doc_type, document.doc_type);
+        assert_eq!(parsed_document.issuer_signed, document.issuer_signed);
+        assert_eq!(parsed.state, auth_request.state);
+        assert!(parsed.poa.is_none());
+
+        // Without the mdoc generated nonce, the Authorization Response should be rejected.
+        let error = VpDirectPostResponse {
+            mdoc_generated_nonce: None,
+            ..direct_post_response.clone()
+        }
+        .into_response()
+        .expect_err("should fail without mdoc generated nonce");
+        assert_matches!(error, AuthResponseError::MissingMdocGeneratedNonce);
+
+        // Anything other than a JSON encoded Presentation Submission should be rejected.
+        VpDirectPostResponse {
+            presentation_submission: "not_json".to_string(),
+            ..direct_post_response
+        }
+        .into_response()
+        .expect_err("should fail to parse");
+    }
+
+    #[rstest]
+    #[case(ResponseMode::DirectPostJwt, Some(VpResponseMode::DirectPostJwt))]
+    #[case(ResponseMode::DirectPost, Some(VpResponseMode::DirectPost))]
+    #[case(ResponseMode::Fragment, None)]
+    #[case(ResponseMode::Query, None)]
+    fn test_authorization_request_response_mode(
+        #[case] response_mode: ResponseMode,
+        #[case] expected_response_mode: Option<VpResponseMode>,
+    ) {
+        let (_, _, _, mut auth_request) = setup();
+        auth_request.oauth_request.response_mode = Some(response_mode);
+
+        let result = IsoVpAuthorizationRequest::try_from(auth_request);
+
+        match expected_response_mode {
+            Some(expected_response_mode) => assert_eq!(result.unwrap().response_mode, expected_response_mode),
+            None => assert_matches!(
+                result,
+                Err(AuthRequestValidationError::UnsupportedFieldValue {
+                    field: "response_mode",
+                    ..
+                })
+            ),
+        }
+    }
+
     #[tokio::test]
     async fn test_authorization_request_jwt() {
         let (trust_anchor, rp_keypair, _, auth_request) = setup();
@@ -945,6 +1259,39 @@ mod tests {
         auth_request.validate(&cert, None).unwrap();
     }
 
+    #[tokio::test]
+    async fn test_authorization_request_verify_reader() {
+        let ca = Ca::generate_reader_mock_ca().unwrap();
+        let trust_anchor = ca.to_trust_anchor();
+        let rp_keypair = ca.generate_reader_mock(Some(ReaderRegistration::new_mock())).unwrap();
+        let (_, _, _, auth_request) = setup();
+
+        let auth_request_jwt = jwt::sign_with_certificate(&auth_request, &rp_keypair).await.unwrap();
+
+        let (cert, reader_registration) =
+            VpAuthorizationRequest::verify_reader(&auth_request_jwt, &[trust_anchor]).unwrap();
+        assert_eq!(&cert, rp_keypair.certificate());
+        assert_eq!(reader_registration, ReaderRegistration::new_mock());
+    }
+
+    #[tokio::test]
+    async fn test_authorization_request_verify_reader_error() {
+        let (trust_anchor, rp_keypair, _, auth_request) = setup();
+
+        let auth_request_jwt = jwt::sign_with_certificate(&auth_request, &rp_keypair).await.unwrap();
+
+        // The RP certificate generated by `setup()` does not contain a reader registration.
+        let error = VpAuthorizationRequest::verify_reader(&auth_request_jwt, &[trust_anchor])
+            .expect_err("verifying reader should fail");
+        assert_matches!(error, AuthRequestValidationError::MissingReaderRegistration);
+
+        // Verifying against other trust anchors should fail.
+        let other_ca = Ca::generate_reader_mock_ca().unwrap();
+        let error = VpAuthorizationRequest::verify_reader(&auth_request_jwt, &[other_ca.to_trust_anchor()])
+            .expect_err("verifying reader should fail");
+        assert_matches!(error, AuthRequestValidationError::JwtVerification(_));
+    }
+
     #[test]
     fn deserialize_authorization_request_example() {
         let example_json = json!(
@@ -69,6 +69,8 @@ where
 {
     async fn get(&self, token: &SessionToken) -> Result<Option<SessionState<T>>, SessionStoreError>;
     async fn write(&self, session: SessionState<T>, is_new: bool) -> Result<(), SessionStoreError>;
+
+    /// Perform a single cleanup pass, using the current time of the session store.
     async fn cleanup(&self) -> Result<(), SessionStoreError>;
 
     fn start_cleanup_task(self: Arc<Self>, interval: Duration) -> JoinHandle<()>
@@ -177,7 +179,19 @@ where
     }
 
     async fn cleanup(&self) -> Result<(), SessionStoreError> {
-        let now = self.time.generate();
+        self.cleanup_at(self.time.generate());
+
+        Ok(())
+    }
+}
+
+impl<T, G> MemorySessionStore<T, G>
+where
+    T: HasProgress + Expirable,
+{
+    /// Perform a single cleanup pass as if the current time is `now`, i.e. expire active sessions and remove finished
+    /// sessions based on their last activity relative to `now`.
+    pub(crate) fn cleanup_at(&self, now: DateTime<Utc>) {
         let succeeded_cutoff = now - self.timeouts.successful_deletion;
         let failed_cutoff = now - self.timeouts.failed_deletion;
         let expiry_cutoff = now - self.timeouts.expiration;
@@ -205,8 +219,6 @@ where
                 session.data.expire();
             }
         });
-
-        Ok(())
     }
 }
 
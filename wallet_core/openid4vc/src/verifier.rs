@@ -5,11 +5,15 @@ use std::fmt::Display;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::serde::ts_seconds;
 use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveDate;
 use chrono::SecondsFormat;
 use chrono::Utc;
 use derive_more::AsRef;
 use derive_more::From;
+use indexmap::IndexSet;
 use josekit::jwk::alg::ec::EcCurve;
 use josekit::jwk::alg::ec::EcKeyPair;
 use josekit::jwk::Jwk;
@@ -21,30 +25,46 @@ use serde::Serialize;
 use serde_with::hex::Hex;
 use serde_with::serde_as;
 use serde_with::skip_serializing_none;
+use serde_with::FromInto;
 use tokio::task::JoinHandle;
 use tracing::debug;
 use tracing::info;
 use tracing::warn;
 
+use nl_wallet_mdoc::identifiers::AttributeIdentifier;
+use nl_wallet_mdoc::identifiers::AttributeIdentifierError;
+use nl_wallet_mdoc::identifiers::AttributeIdentifierHolder;
 use nl_wallet_mdoc::server_keys::KeyPair;
+use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
+use nl_wallet_mdoc::utils::serialization::cbor_serialize;
+use nl_wallet_mdoc::utils::serialization::CborError;
+use nl_wallet_mdoc::utils::serialization::JsonCborValue;
 use nl_wallet_mdoc::utils::x509::CertificateError;
+use nl_wallet_mdoc::utils::x509::MdocCertificateExtension;
 use nl_wallet_mdoc::verifier::DisclosedAttributes;
 use nl_wallet_mdoc::verifier::ItemsRequests;
+use nl_wallet_mdoc::DataElementValue;
+use nl_wallet_mdoc::SessionTranscript;
 use wallet_common::generator::Generator;
 use wallet_common::jwt::Jwt;
 use wallet_common::jwt::JwtError;
+use wallet_common::redact::Redacted;
 use wallet_common::urls::BaseUrl;
 use wallet_common::utils::random_string;
+use wallet_common::utils::sha256;
 
 use crate::jwt;
 use crate::openid4vp::AuthRequestError;
 use crate::openid4vp::AuthResponseError;
 use crate::openid4vp::IsoVpAuthorizationRequest;
+use crate::openid4vp::JweDecryptionKey;
 use crate::openid4vp::RequestUriMethod;
 use crate::openid4vp::VpAuthorizationRequest;
 use crate::openid4vp::VpAuthorizationResponse;
+use crate::openid4vp::VpDirectPostResponse;
 use crate::openid4vp::VpRequestUriObject;
 use crate::openid4vp::VpResponse;
+use crate::openid4vp::VpResponseMode;
 use crate::return_url::ReturnUrlTemplate;
 use crate::server_state::Expirable;
 use crate::server_state::HasProgress;
@@ -60,6 +80,8 @@ use crate::VpAuthorizationErrorCode;
 
 pub const EPHEMERAL_ID_VALIDITY_SECONDS: Duration = Duration::from_secs(10);
 
+const BIRTH_DATE_ATTRIBUTE: &str = "birth_date";
+
 /// Errors that can occur during processing of any of the endpoints.
 #[derive(Debug, thiserror::Error)]
 pub enum SessionError {
@@ -82,6 +104,8 @@ pub enum NewSessionError {
     UnknownUseCase(String),
     #[error("presence or absence of return url template does not match configuration for the required use case")]
     ReturnUrlConfigurationMismatch,
+    #[error("invalid attribute identifier in ItemsRequest: {0}")]
+    InvalidAttributeIdentifier(#[from] AttributeIdentifierError),
 }
 
 /// Errors returned by the session status endpoint, used by the web front-end.
@@ -110,6 +134,17 @@ pub enum DisclosedAttributesError {
     RedirectUriNonceMismatch(String),
 }
 
+/// Errors that can occur when creating a receipt of a disclosure session.
+#[derive(Debug, thiserror::Error)]
+pub enum DisclosureReceiptError {
+    #[error("session error: {0}")]
+    Session(#[from] SessionError),
+    #[error("could not serialize attribute value: {0}")]
+    Cbor(#[from] CborError),
+    #[error("could not sign receipt: {0}")]
+    Jwt(#[from] JwtError),
+}
+
 /// Errors returned by the endpoint that returns the Authorization Request.
 #[derive(thiserror::Error, Debug)]
 pub enum GetAuthRequestError {
@@ -151,6 +186,8 @@ pub enum UseCaseCertificateError {
     MissingSAN,
     #[error("RP certificate error: {0}")]
     Certificate(#[from] CertificateError),
+    #[error("missing reader registration from RP certificate")]
+    MissingReaderRegistration,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -205,6 +242,8 @@ pub struct WaitingForResponse {
     auth_request: IsoVpAuthorizationRequest,
     encryption_key: EncryptionPrivateKey,
     redirect_uri: Option<RedirectUri>,
+    #[serde(default)]
+    derive_age_over: Vec<u8>,
 }
 
 /// State for a session that has ended (for any reason).
@@ -220,12 +259,190 @@ enum SessionResult {
     Done {
         disclosed_attributes: DisclosedAttributes,
         redirect_uri_nonce: Option<String>,
+        #[serde(default)]
+        conformance: DisclosureConformance,
+        #[serde(default)]
+        derived_attributes: Vec<DerivedAttribute>,
+        #[serde(default)]
+        requested_attributes: Vec<AttributeIdentifier>,
+        #[serde(default)]
+        transcript_hash: Option<Vec<u8>>,
     },
     Failed {
         error: String,
     },
-    Cancelled,
-    Expired,
+    Cancelled {
+        #[serde(default)]
+        reason: CancelReason,
+    },
+    Expired {
+        #[serde(default)]
+        reason: ExpiryReason,
+    },
+}
+
+/// The reason why a session was cancelled, as recorded when the session transitioned to the `Cancelled` state.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CancelReason {
+    /// The RP explicitly cancelled the session, see [`Verifier::cancel()`].
+    RelyingParty,
+    /// The user refused to disclose the requested attributes in their wallet.
+    UserRefused,
+    /// The session was cancelled before the reason was recorded.
+    #[default]
+    Unknown,
+}
+
+/// The reason why a session expired, as recorded when the session transitioned to the `Expired` state. Note that an
+/// expired ephemeral ID does not expire the session, see [`Verifier::process_get_request()`], so it is not included.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ExpiryReason {
+    /// The session was not finished within the expiration timeout of the session store and was expired by its cleanup
+    /// task.
+    SessionTimeout,
+    /// The session expired before the reason was recorded.
+    #[default]
+    Unknown,
+}
+
+/// The difference between the attributes that were requested in a session and the attributes that were disclosed.
+/// Note that attributes that were disclosed but not requested may indicate that the wallet shares more than necessary.
+/// As a disclosure that lacks requested attributes is rejected, `requested_but_missing` is empty for completed
+/// sessions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisclosureConformance {
+    pub requested_but_missing: Vec<AttributeIdentifier>,
+    pub disclosed_but_unrequested: Vec<AttributeIdentifier>,
+}
+
+impl DisclosureConformance {
+    pub fn new(items_requests: &ItemsRequests, disclosed_attributes: &DisclosedAttributes) -> Self {
+        let requested = items_requests
+            .0
+            .iter()
+            .flat_map(|items_request| items_request.attribute_identifiers())
+            .collect::<IndexSet<_>>();
+        let disclosed = disclosed_attributes
+            .iter()
+            .flat_map(|(doc_type, document)| {
+                document.attributes.iter().flat_map(move |(namespace, attributes)| {
+                    attributes.keys().map(move |attribute| AttributeIdentifier {
+                        credential_type: doc_type.clone(),
+                        namespace: namespace.clone(),
+                        attribute: attribute.clone(),
+                    })
+                })
+            })
+            .collect::<IndexSet<_>>();
+
+        Self {
+            requested_but_missing: requested.difference(&disclosed).cloned().collect(),
+            disclosed_but_unrequested: disclosed.difference(&requested).cloned().collect(),
+        }
+    }
+
+    /// Returns `true` if exactly the requested attributes were disclosed.
+    pub fn is_exact(&self) -> bool {
+        self.requested_but_missing.is_empty() && self.disclosed_but_unrequested.is_empty()
+    }
+}
+
+/// An attribute that was not asserted by the issuer, but derived by the verifier from an attribute that was. Note that
+/// the issuer vouches only for the source attribute, so derived attributes should be trusted accordingly.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAttribute {
+    /// The disclosed attribute from which this attribute was derived.
+    pub source: AttributeIdentifier,
+    pub name: String,
+    pub value: bool,
+}
+
+impl DerivedAttribute {
+    /// Derive `age_over_NN` attributes for each of the `thresholds` from every disclosed `birth_date` attribute, at the
+    /// date of `time`. Birth dates that cannot be parsed as a full date are ignored.
+    pub fn derive_age_over(
+        disclosed_attributes: &DisclosedAttributes,
+        thresholds: &[u8],
+        time: DateTime<Utc>,
+    ) -> Vec<Self> {
+        let today = time.date_naive();
+
+        disclosed_attributes
+            .iter()
+            .flat_map(|(doc_type, document)| {
+                document
+                    .attributes
+                    .iter()
+                    .filter_map(|(namespace, attributes)| {
+                        let birth_date = Self::parse_full_date(attributes.get(BIRTH_DATE_ATTRIBUTE)?)?;
+                        let source = AttributeIdentifier {
+                            credential_type: doc_type.clone(),
+                            namespace: namespace.clone(),
+                            attribute: BIRTH_DATE_ATTRIBUTE.to_string(),
+                        };
+
+                        Some((source, birth_date))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .flat_map(|(source, birth_date)| {
+                // The age is only incremented once the birthday has passed in the current year, which means that
+                // someone born on the 29th of February becomes a year older on the 1st of March in non-leap years.
+                let birthday_passed = (today.month(), today.day()) >= (birth_date.month(), birth_date.day());
+                let age = today.year() - birth_date.year() - i32::from(!birthday_passed);
+
+                thresholds.iter().map(move |threshold| Self {
+                    source: source.clone(),
+                    name: format!("age_over_{threshold}"),
+                    value: age >= i32::from(*threshold),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse a date that is either encoded as a `full-date` string, or as such a string with CBOR tag 1004.
+    fn parse_full_date(value: &ciborium::Value) -> Option<NaiveDate> {
+        let text = match value {
+            ciborium::Value::Tag(1004, value) => value.as_text(),
+            value => value.as_text(),
+        }?;
+
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+    }
+}
+
+/// A receipt of a successfully completed disclosure session, signed by the RP for non-repudiation purposes. The
+/// disclosed attributes are included as HMAC-SHA256 values over their CBOR encoding, keyed with a secret of the RP so
+/// that low-entropy values cannot be brute-forced from the receipt. Their values are only included when explicitly
+/// requested.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DisclosureReceipt {
+    pub session_token: SessionToken,
+    #[serde(with = "ts_seconds")]
+    pub iat: DateTime<Utc>,
+    pub requested_attributes: Vec<AttributeIdentifier>,
+    pub disclosed_attributes: Vec<DisclosureReceiptAttribute>,
+    /// SHA-256 hash over the CBOR encoding of the `SessionTranscript` the wallet signed.
+    #[serde_as(as = "Option<Hex>")]
+    pub transcript_hash: Option<Vec<u8>>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, derive_more::Debug, Clone)]
+pub struct DisclosureReceiptAttribute {
+    pub attribute: AttributeIdentifier,
+    #[serde_as(as = "Hex")]
+    pub value_hash: Vec<u8>,
+    #[serde_as(as = "Option<FromInto<JsonCborValue>>")]
+    #[debug("{:?}", Redacted(value))]
+    pub value: Option<DataElementValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -234,9 +451,10 @@ pub struct RedirectUri {
     nonce: String,
 }
 
-/// Wrapper for [`EcKeyPair`] that can be serialized.
+/// Wrapper for the private key with which Authorization Responses are decrypted, which can be serialized when it is
+/// an [`EcKeyPair`].
 #[derive(Debug, Clone, AsRef, From)]
-struct EncryptionPrivateKey(EcKeyPair);
+struct EncryptionPrivateKey<K = EcKeyPair>(K);
 
 // Ordinarily we might use DER encoding here instead of PEM, but `EcKeyPair::to_der_private_key()` does not encode
 // to PKCS8 which is expected by `EcKeyPair::from_der()`. A workaround would be to explicitly pass the EC curve
@@ -256,9 +474,35 @@ impl<'de> Deserialize<'de> for EncryptionPrivateKey {
     }
 }
 
+impl<K: JweDecryptionKey> JweDecryptionKey for EncryptionPrivateKey<K> {
+    type Decrypter = K::Decrypter;
+
+    fn decrypter(&self) -> Result<Self::Decrypter, JoseError> {
+        self.0.decrypter()
+    }
+}
+
+/// The Authorization Response as posted by the wallet, in one of the response modes a use case can be configured with.
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct VpToken {
-    pub vp_token: String,
+#[serde(untagged)]
+pub enum VpToken {
+    /// The `direct_post` response mode, in which the parameters of the Authorization Response are posted as is.
+    DirectPost(VpDirectPostResponse),
+    /// The `direct_post.jwt` response mode, in which `vp_token` contains the Authorization Response JWE.
+    DirectPostJwt { vp_token: String },
+}
+
+impl VpToken {
+    pub fn new_encrypted(jwe: String) -> Self {
+        Self::DirectPostJwt { vp_token: jwe }
+    }
+
+    pub fn response_mode(&self) -> VpResponseMode {
+        match self {
+            Self::DirectPost(_) => VpResponseMode::DirectPost,
+            Self::DirectPostJwt { .. } => VpResponseMode::DirectPostJwt,
+        }
+    }
 }
 
 /// Sent by the wallet to the `response_uri`: either an Authorization Response JWE or an error, which either indicates
@@ -301,14 +545,17 @@ impl Expirable for DisclosureData {
         matches!(
             self,
             Self::Done(Done {
-                session_result: SessionResult::Expired
+                session_result: SessionResult::Expired { .. }
             })
         )
     }
 
     fn expire(&mut self) {
+        // Sessions are only expired by the cleanup task of the session store.
         *self = Self::Done(Done {
-            session_result: SessionResult::Expired,
+            session_result: SessionResult::Expired {
+                reason: ExpiryReason::SessionTimeout,
+            },
         });
     }
 }
@@ -409,8 +656,8 @@ pub enum SessionStatus {
     WaitingForResponse,
     Done,
     Failed { error: String },
-    Cancelled,
-    Expired,
+    Cancelled { reason: CancelReason },
+    Expired { reason: ExpiryReason },
 }
 
 impl From<DisclosureData> for SessionStatus {
@@ -421,8 +668,8 @@ impl From<DisclosureData> for SessionStatus {
             DisclosureData::Done(Done { session_result }) => match session_result {
                 SessionResult::Done { .. } => Self::Done,
                 SessionResult::Failed { error } => Self::Failed { error },
-                SessionResult::Cancelled => Self::Cancelled,
-                SessionResult::Expired => Self::Expired,
+                SessionResult::Cancelled { reason } => Self::Cancelled { reason },
+                SessionResult::Expired { reason } => Self::Expired { reason },
             },
         }
     }
@@ -455,6 +702,10 @@ pub struct UseCase {
     pub key_pair: KeyPair,
     pub client_id: String,
     pub session_type_return_url: SessionTypeReturnUrl,
+    /// The ages for which `age_over_NN` attributes should be derived from a disclosed `birth_date`, if any.
+    pub derive_age_over: Vec<u8>,
+    /// The response mode in which the Authorization Response should be posted by the wallet.
+    pub response_mode: VpResponseMode,
 }
 
 impl UseCase {
@@ -472,10 +723,21 @@ impl UseCase {
             key_pair,
             client_id,
             session_type_return_url,
+            derive_age_over: Vec::new(),
+            response_mode: VpResponseMode::default(),
         };
 
         Ok(use_case)
     }
+
+    /// Returns all attributes that may be requested in this use case, according to the reader registration in its
+    /// certificate. This can be used to check [`ItemsRequests`] before passing them to [`Verifier::new_session()`].
+    pub fn requestable_attributes(&self) -> Result<Vec<AttributeIdentifier>, UseCaseCertificateError> {
+        let reader_registration = ReaderRegistration::from_certificate(self.key_pair.certificate())?
+            .ok_or(UseCaseCertificateError::MissingReaderRegistration)?;
+
+        Ok(reader_registration.requestable_attributes())
+    }
 }
 
 #[derive(Debug)]
@@ -544,6 +806,8 @@ where
             return Err(NewSessionError::NoItemsRequests);
         }
 
+        let items_requests = items_requests.normalize_attribute_identifiers()?;
+
         let use_case = match self.use_cases.as_ref().get(&usecase_id) {
             Some(use_case) => use_case,
             None => return Err(NewSessionError::UnknownUseCase(usecase_id)),
@@ -744,7 +1008,9 @@ where
             DisclosureData::Created(_) | DisclosureData::WaitingForResponse(_) => SessionState::new(
                 token,
                 DisclosureData::Done(Done {
-                    session_result: SessionResult::Cancelled,
+                    session_result: SessionResult::Cancelled {
+                        reason: CancelReason::RelyingParty,
+                    },
                 }),
             ),
             DisclosureData::Done(_) => return Err(SessionError::UnexpectedState(data.into()).into()),
@@ -764,6 +1030,102 @@ where
         session_token: &SessionToken,
         redirect_uri_nonce: Option<String>,
     ) -> Result<DisclosedAttributes, DisclosedAttributesError> {
+        let (disclosed_attributes, _, _) = self.disclosure_result(session_token, redirect_uri_nonce).await?;
+
+        Ok(disclosed_attributes)
+    }
+
+    /// Returns how the disclosed attributes relate to the requested attributes for a session with status `Done`
+    /// and an error otherwise
+    pub async fn disclosure_conformance(
+        &self,
+        session_token: &SessionToken,
+        redirect_uri_nonce: Option<String>,
+    ) -> Result<DisclosureConformance, DisclosedAttributesError> {
+        let (_, conformance, _) = self.disclosure_result(session_token, redirect_uri_nonce).await?;
+
+        Ok(conformance)
+    }
+
+    /// Returns the attributes that were derived from the disclosed attributes, as configured for the use case, for a
+    /// session with status `Done` and an error otherwise
+    pub async fn derived_attributes(
+        &self,
+        session_token: &SessionToken,
+        redirect_uri_nonce: Option<String>,
+    ) -> Result<Vec<DerivedAttribute>, DisclosedAttributesError> {
+        let (_, _, derived_attributes) = self.disclosure_result(session_token, redirect_uri_nonce).await?;
+
+        Ok(derived_attributes)
+    }
+
+    /// Create a receipt of a session with status `Done`, signed using `signing_key`. The values of the disclosed
+    /// attributes are only included in the receipt if `include_values` is set, otherwise only their HMACs using
+    /// `value_hmac_key` are.
+    pub async fn disclosure_receipt(
+        &self,
+        session_token: &SessionToken,
+        signing_key: &KeyPair,
+        value_hmac_key: &hmac::Key,
+        include_values: bool,
+        time: &impl Generator<DateTime<Utc>>,
+    ) -> Result<Jwt<DisclosureReceipt>, DisclosureReceiptError> {
+        let (disclosed_attributes, requested_attributes, transcript_hash) =
+            match self.get_session_state(session_token).await?.data {
+                DisclosureData::Done(Done {
+                    session_result:
+                        SessionResult::Done {
+                            disclosed_attributes,
+                            requested_attributes,
+                            transcript_hash,
+                            ..
+                        },
+                }) => (disclosed_attributes, requested_attributes, transcript_hash),
+                data => Err(SessionError::UnexpectedState(data.into()))?,
+            };
+
+        let disclosed_attributes = disclosed_attributes
+            .into_iter()
+            .flat_map(|(doc_type, document)| {
+                document
+                    .attributes
+                    .into_iter()
+                    .flat_map(move |(namespace, attributes)| {
+                        let doc_type = doc_type.clone();
+                        attributes.into_iter().map(move |(attribute, value)| {
+                            let receipt_attribute = DisclosureReceiptAttribute {
+                                attribute: AttributeIdentifier {
+                                    credential_type: doc_type.clone(),
+                                    namespace: namespace.clone(),
+                                    attribute,
+                                },
+                                value_hash: hmac::sign(value_hmac_key, &cbor_serialize(&value)?).as_ref().to_vec(),
+                                value: include_values.then_some(value),
+                            };
+
+                            Ok(receipt_attribute)
+                        })
+                    })
+            })
+            .collect::<Result<_, CborError>>()?;
+
+        let receipt = DisclosureReceipt {
+            session_token: session_token.clone(),
+            iat: time.generate(),
+            requested_attributes,
+            disclosed_attributes,
+            transcript_hash,
+        };
+        let jwt = jwt::sign_with_certificate(&receipt, signing_key).await?;
+
+        Ok(jwt)
+    }
+
+    async fn disclosure_result(
+        &self,
+        session_token: &SessionToken,
+        redirect_uri_nonce: Option<String>,
+    ) -> Result<(DisclosedAttributes, DisclosureConformance, Vec<DerivedAttribute>), DisclosedAttributesError> {
         let disclosure_data = self.get_session_state(session_token).await?.data;
 
         match disclosure_data {
@@ -772,11 +1134,16 @@ where
                     SessionResult::Done {
                         redirect_uri_nonce: expected_nonce,
                         disclosed_attributes,
+                        conformance,
+                        derived_attributes,
+                        ..
                     },
             }) => match (redirect_uri_nonce, expected_nonce) {
-                (_, None) => Ok(disclosed_attributes),
+                (_, None) => Ok((disclosed_attributes, conformance, derived_attributes)),
                 (None, Some(_)) => Err(DisclosedAttributesError::RedirectUriNonceMissing),
-                (Some(received), Some(expected)) if received == expected => Ok(disclosed_attributes),
+                (Some(received), Some(expected)) if received == expected => {
+                    Ok((disclosed_attributes, conformance, derived_attributes))
+                }
                 (Some(received), Some(_)) => Err(DisclosedAttributesError::RedirectUriNonceMismatch(received)),
             },
             data => Err(SessionError::UnexpectedState(data.into()))?,
@@ -911,10 +1278,17 @@ impl Session<Created> {
             .await
         {
             Ok((jws, auth_request, redirect_uri, enc_keypair)) => {
+                // The use case exists, as this was checked when constructing the Authorization Request.
+                let derive_age_over = use_cases
+                    .as_ref()
+                    .get(&self.state().usecase_id)
+                    .map(|usecase| usecase.derive_age_over.clone())
+                    .unwrap_or_default();
                 let next = WaitingForResponse {
                     auth_request,
                     encryption_key: EncryptionPrivateKey::from(enc_keypair),
                     redirect_uri,
+                    derive_age_over,
                 };
                 let next = self.transition(next);
                 Ok((jws, next))
@@ -969,7 +1343,7 @@ impl Session<Created> {
         let nonce = random_string(32);
         let encryption_keypair = EcKeyPair::generate(EcCurve::P256)
             .map_err(|err| WithRedirectUri::new(err.into(), redirect_uri.as_ref().map(|u| u.uri.clone())))?;
-        let auth_request = IsoVpAuthorizationRequest::new(
+        let mut auth_request = IsoVpAuthorizationRequest::new(
             &self.state.data.items_requests,
             usecase.key_pair.certificate(),
             nonce.clone(),
@@ -978,6 +1352,7 @@ impl Session<Created> {
             wallet_nonce,
         )
         .map_err(|err| WithRedirectUri::new(err.into(), redirect_uri.as_ref().map(|u| u.uri.clone())))?;
+        auth_request.response_mode = usecase.response_mode;
 
         let vp_auth_request = VpAuthorizationRequest::from(auth_request.clone());
         let jws = jwt::sign_with_certificate(&vp_auth_request, &usecase.key_pair)
@@ -1040,8 +1415,8 @@ impl Session<WaitingForResponse> {
     ) {
         debug!("Session({}): process response", self.state.token);
 
-        let jwe = match wallet_response {
-            WalletAuthResponse::Response(VpToken { vp_token }) => vp_token,
+        let vp_token = match wallet_response {
+            WalletAuthResponse::Response(vp_token) => vp_token,
             WalletAuthResponse::Error(err) => {
                 // Check if the error code indicates that the user refused to disclose.
                 let user_refused = matches!(
@@ -1063,20 +1438,21 @@ impl Session<WaitingForResponse> {
         };
 
         debug!(
-            "Session({}): process response: decrypting and deserializing Authorization Response JWE",
+            "Session({}): process response: deserializing Authorization Response",
             self.state.token
         );
-        let (result, next) = match VpAuthorizationResponse::decrypt_and_verify(
-            &jwe,
-            self.state().encryption_key.as_ref(),
-            &self.state().auth_request,
-            time,
-            trust_anchors,
-        ) {
-            Ok(disclosed) => {
+        let auth_request = &self.state().auth_request;
+        let verify_result = self
+            .parse_authorization_response(vp_token)
+            .and_then(|(response, mdoc_nonce)| {
+                let disclosed = response.verify(auth_request, &mdoc_nonce, time, trust_anchors)?;
+                Ok((disclosed, mdoc_nonce))
+            });
+        let (result, next) = match verify_result {
+            Ok((disclosed, mdoc_nonce)) => {
                 let redirect_uri_nonce = self.state().redirect_uri.as_ref().map(|u| u.nonce.clone());
                 let response = self.ok_response();
-                let next = self.transition_finish(disclosed, redirect_uri_nonce);
+                let next = self.transition_finish(disclosed, redirect_uri_nonce, &mdoc_nonce, time.generate());
                 (Ok(response), next)
             }
             Err(err) => {
@@ -1089,28 +1465,299 @@ impl Session<WaitingForResponse> {
         (result, next)
     }
 
+    /// Parse the Authorization Response according to the response mode of this session, returning it together with
+    /// the mdoc generated nonce.
+    fn parse_authorization_response(
+        &self,
+        vp_token: VpToken,
+    ) -> Result<(VpAuthorizationResponse, String), AuthResponseError> {
+        let auth_request = &self.state().auth_request;
+
+        match (auth_request.response_mode, vp_token) {
+            (VpResponseMode::DirectPostJwt, VpToken::DirectPostJwt { vp_token: jwe }) => {
+                VpAuthorizationResponse::decrypt(&jwe, &self.state().encryption_key, &auth_request.nonce)
+            }
+            (VpResponseMode::DirectPost, VpToken::DirectPost(direct_post_response)) => {
+                direct_post_response.into_response()
+            }
+            (expected, vp_token) => Err(AuthResponseError::ResponseModeMismatch {
+                expected,
+                found: vp_token.response_mode(),
+            }),
+        }
+    }
+
     fn ok_response(&self) -> VpResponse {
         VpResponse {
             redirect_uri: self.state().redirect_uri.as_ref().map(|u| u.uri.clone()),
         }
     }
 
-    fn transition_finish(self, disclosed_attributes: DisclosedAttributes, nonce: Option<String>) -> Session<Done> {
+    fn transition_finish(
+        self,
+        disclosed_attributes: DisclosedAttributes,
+        nonce: Option<String>,
+        mdoc_nonce: &str,
+        time: DateTime<Utc>,
+    ) -> Session<Done> {
+        let auth_request = &self.state().auth_request;
+
+        let derived_attributes =
+            DerivedAttribute::derive_age_over(&disclosed_attributes, &self.state().derive_age_over, time);
+        let conformance = DisclosureConformance::new(&auth_request.items_requests, &disclosed_attributes);
+        let requested_attributes = auth_request
+            .items_requests
+            .0
+            .iter()
+            .flat_map(|items_request| items_request.attribute_identifiers())
+            .collect();
+
+        // Keep a hash of the session transcript, so that it can be included in a receipt of this session.
+        let session_transcript = SessionTranscript::new_oid4vp(
+            &auth_request.response_uri,
+            &auth_request.client_id,
+            auth_request.nonce.clone(),
+            mdoc_nonce,
+        );
+        let transcript_hash = cbor_serialize(&session_transcript)
+            .inspect_err(|error| {
+                warn!(
+                    "Session({}): could not hash session transcript: {error}",
+                    self.state.token
+                )
+            })
+            .ok()
+            .map(|transcript| sha256(&transcript));
+
+        if !conformance.disclosed_but_unrequested.is_empty() {
+            warn!(
+                "Session({}): wallet disclosed attributes that were not requested: {:?}",
+                self.state.token, conformance.disclosed_but_unrequested
+            );
+        }
+
         self.transition(Done {
             session_result: SessionResult::Done {
                 disclosed_attributes,
                 redirect_uri_nonce: nonce,
+                conformance,
+                derived_attributes,
+                requested_attributes,
+                transcript_hash,
             },
         })
     }
 
     fn transition_abort(self) -> Session<Done> {
         self.transition(Done {
-            session_result: SessionResult::Cancelled,
+            session_result: SessionResult::Cancelled {
+                reason: CancelReason::UserRefused,
+            },
         })
     }
 }
 
+#[cfg(any(test, feature = "test"))]
+pub mod test {
+    use chrono::DateTime;
+    use chrono::Utc;
+    use josekit::jwk::alg::ec::EcCurve;
+    use josekit::jwk::alg::ec::EcKeyPair;
+
+    use nl_wallet_mdoc::identifiers::AttributeIdentifier;
+    use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+    use nl_wallet_mdoc::verifier::DisclosedAttributes;
+    use nl_wallet_mdoc::verifier::ItemsRequests;
+    use wallet_common::urls::BaseUrl;
+
+    use crate::openid4vp::IsoVpAuthorizationRequest;
+    use crate::return_url::ReturnUrlTemplate;
+    use crate::server_state::MemorySessionStore;
+
+    use super::CancelReason;
+    use super::Created;
+    use super::DerivedAttribute;
+    use super::DisclosureConformance;
+    use super::DisclosureData;
+    use super::Done;
+    use super::EncryptionPrivateKey;
+    use super::ExpiryReason;
+    use super::RedirectUri;
+    use super::SessionResult;
+    use super::Verifier;
+    use super::WaitingForResponse;
+
+    impl<G> Verifier<MemorySessionStore<DisclosureData, G>> {
+        /// Perform a single cleanup pass over the sessions, as if the current time is `now`. This expires sessions
+        /// that have been inactive for too long and removes finished sessions, independent of the periodic cleanup
+        /// task.
+        pub fn run_cleanup_once(&self, now: DateTime<Utc>) {
+            self.sessions.cleanup_at(now)
+        }
+    }
+
+    /// Builder for [`DisclosureData`] in any of its states, for use in tests that need to put a session in the
+    /// session store of a [`super::Verifier`] without going through the entire disclosure protocol.
+    #[derive(Debug, Clone)]
+    pub struct DisclosureDataBuilder {
+        items_requests: ItemsRequests,
+        usecase_id: String,
+        client_id: String,
+        redirect_uri_template: Option<ReturnUrlTemplate>,
+        redirect_uri: Option<(BaseUrl, String)>,
+        derive_age_over: Vec<u8>,
+        disclosed_attributes: DisclosedAttributes,
+        derived_attributes: Vec<DerivedAttribute>,
+        requested_attributes: Vec<AttributeIdentifier>,
+        transcript_hash: Option<Vec<u8>>,
+    }
+
+    impl Default for DisclosureDataBuilder {
+        fn default() -> Self {
+            Self {
+                items_requests: vec![].into(),
+                usecase_id: "usecase_id".to_string(),
+                client_id: "client_id".to_string(),
+                redirect_uri_template: None,
+                redirect_uri: None,
+                derive_age_over: Default::default(),
+                disclosed_attributes: Default::default(),
+                derived_attributes: Default::default(),
+                requested_attributes: Default::default(),
+                transcript_hash: None,
+            }
+        }
+    }
+
+    impl DisclosureDataBuilder {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn items_requests(mut self, items_requests: ItemsRequests) -> Self {
+            self.items_requests = items_requests;
+            self
+        }
+
+        pub fn usecase_id(mut self, usecase_id: String) -> Self {
+            self.usecase_id = usecase_id;
+            self
+        }
+
+        pub fn client_id(mut self, client_id: String) -> Self {
+            self.client_id = client_id;
+            self
+        }
+
+        pub fn redirect_uri_template(mut self, redirect_uri_template: ReturnUrlTemplate) -> Self {
+            self.redirect_uri_template = Some(redirect_uri_template);
+            self
+        }
+
+        /// Set the redirect URI of the session, along with the nonce that the RP has to present to retrieve the
+        /// disclosed attributes once the session is done.
+        pub fn redirect_uri(mut self, uri: BaseUrl, nonce: String) -> Self {
+            self.redirect_uri = Some((uri, nonce));
+            self
+        }
+
+        pub fn derive_age_over(mut self, derive_age_over: Vec<u8>) -> Self {
+            self.derive_age_over = derive_age_over;
+            self
+        }
+
+        pub fn disclosed_attributes(mut self, disclosed_attributes: DisclosedAttributes) -> Self {
+            self.disclosed_attributes = disclosed_attributes;
+            self
+        }
+
+        pub fn derived_attributes(mut self, derived_attributes: Vec<DerivedAttribute>) -> Self {
+            self.derived_attributes = derived_attributes;
+            self
+        }
+
+        pub fn requested_attributes(mut self, requested_attributes: Vec<AttributeIdentifier>) -> Self {
+            self.requested_attributes = requested_attributes;
+            self
+        }
+
+        pub fn transcript_hash(mut self, transcript_hash: Vec<u8>) -> Self {
+            self.transcript_hash = Some(transcript_hash);
+            self
+        }
+
+        /// Build a session that has just been created by the RP.
+        pub fn created(self) -> DisclosureData {
+            DisclosureData::Created(Created {
+                items_requests: self.items_requests,
+                usecase_id: self.usecase_id,
+                client_id: self.client_id,
+                redirect_uri_template: self.redirect_uri_template,
+            })
+        }
+
+        /// Build a session in which the wallet has retrieved an Authorization Request, which is constructed using a
+        /// newly generated encryption key and the specified RP certificate. The latter must contain a SAN DNS name.
+        pub fn waiting_for_response(
+            self,
+            rp_certificate: &BorrowingCertificate,
+            response_uri: BaseUrl,
+            nonce: String,
+        ) -> DisclosureData {
+            let encryption_keypair = EcKeyPair::generate(EcCurve::P256).unwrap();
+            let auth_request = IsoVpAuthorizationRequest::new(
+                &self.items_requests,
+                rp_certificate,
+                nonce,
+                encryption_keypair.to_jwk_public_key().try_into().unwrap(),
+                response_uri,
+                None,
+            )
+            .unwrap();
+
+            DisclosureData::WaitingForResponse(Box::new(WaitingForResponse {
+                auth_request,
+                encryption_key: EncryptionPrivateKey::from(encryption_keypair),
+                redirect_uri: self.redirect_uri.map(|(uri, nonce)| RedirectUri { uri, nonce }),
+                derive_age_over: self.derive_age_over,
+            }))
+        }
+
+        /// Build a session in which the wallet has successfully disclosed the configured attributes.
+        pub fn done(self) -> DisclosureData {
+            let conformance = DisclosureConformance::new(&self.items_requests, &self.disclosed_attributes);
+
+            Self::finished(SessionResult::Done {
+                disclosed_attributes: self.disclosed_attributes,
+                redirect_uri_nonce: self.redirect_uri.map(|(_, nonce)| nonce),
+                conformance,
+                derived_attributes: self.derived_attributes,
+                requested_attributes: self.requested_attributes,
+                transcript_hash: self.transcript_hash,
+            })
+        }
+
+        /// Build a session that has failed with the specified error message.
+        pub fn failed(self, error: String) -> DisclosureData {
+            Self::finished(SessionResult::Failed { error })
+        }
+
+        /// Build a session that was cancelled by either the user or the RP.
+        pub fn cancelled(self, reason: CancelReason) -> DisclosureData {
+            Self::finished(SessionResult::Cancelled { reason })
+        }
+
+        /// Build a session that has expired.
+        pub fn expired(self, reason: ExpiryReason) -> DisclosureData {
+            Self::finished(SessionResult::Expired { reason })
+        }
+
+        fn finished(session_result: SessionResult) -> DisclosureData {
+            DisclosureData::Done(Done { session_result })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -1123,24 +1770,41 @@ mod tests {
     use ring::rand;
     use rstest::rstest;
 
+    use nl_wallet_mdoc::identifiers::AttributeIdentifier;
+    use nl_wallet_mdoc::identifiers::AttributeIdentifierError;
+    use nl_wallet_mdoc::identifiers::AttributeIdentifierHolder;
     use nl_wallet_mdoc::server_keys::generate::Ca;
     use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
+    use nl_wallet_mdoc::utils::serialization::cbor_serialize;
+    use nl_wallet_mdoc::verifier::DisclosedAttributes;
+    use nl_wallet_mdoc::verifier::DocumentDisclosedAttributes;
     use nl_wallet_mdoc::ItemsRequest;
+    use nl_wallet_mdoc::ValidityInfo;
+    use wallet_common::generator::mock::MockTimeGenerator;
     use wallet_common::generator::Generator;
     use wallet_common::generator::TimeGenerator;
 
+    use crate::jwt::verify_against_trust_anchors;
     use crate::server_state::MemorySessionStore;
     use crate::server_state::SessionToken;
 
+    use super::test::DisclosureDataBuilder;
+    use super::AuthResponseError;
     use super::AuthorizationErrorCode;
+    use super::CancelReason;
+    use super::DerivedAttribute;
     use super::DisclosedAttributesError;
+    use super::DisclosureConformance;
     use super::DisclosureData;
+    use super::DisclosureReceiptError;
     use super::Done;
     use super::ErrorResponse;
+    use super::ExpiryReason;
     use super::GetAuthRequestError;
     use super::HashMap;
     use super::ItemsRequests;
     use super::NewSessionError;
+    use super::PostAuthResponseError;
     use super::SessionError;
     use super::SessionResult;
     use super::SessionState;
@@ -1150,9 +1814,11 @@ mod tests {
     use super::SessionTypeReturnUrl;
     use super::StatusResponse;
     use super::UseCase;
+    use super::UseCaseCertificateError;
     use super::Verifier;
     use super::VpAuthorizationErrorCode;
     use super::VpRequestUriObject;
+    use super::VpResponseMode;
     use super::WalletAuthResponse;
     use super::EPHEMERAL_ID_VALIDITY_SECONDS;
 
@@ -1163,6 +1829,7 @@ mod tests {
     const DISCLOSURE_USECASE_NO_REDIRECT_URI: &str = "example_usecase_no_redirect_uri";
     const DISCLOSURE_USECASE: &str = "example_usecase";
     const DISCLOSURE_USECASE_ALL_REDIRECT_URI: &str = "example_usecase_all_redirect_uri";
+    const DISCLOSURE_USECASE_DIRECT_POST: &str = "example_usecase_direct_post";
 
     fn new_disclosure_request() -> ItemsRequests {
         vec![ItemsRequest {
@@ -1193,6 +1860,8 @@ mod tests {
                     key_pair: ca.generate_reader_mock(reader_registration.clone()).unwrap(),
                     session_type_return_url: SessionTypeReturnUrl::Neither,
                     client_id: "client_id".to_string(),
+                    derive_age_over: vec![],
+                    response_mode: VpResponseMode::DirectPostJwt,
                 },
             ),
             (
@@ -1201,6 +1870,18 @@ mod tests {
                     key_pair: ca.generate_reader_mock(reader_registration.clone()).unwrap(),
                     session_type_return_url: SessionTypeReturnUrl::SameDevice,
                     client_id: "client_id".to_string(),
+                    derive_age_over: vec![],
+                    response_mode: VpResponseMode::DirectPostJwt,
+                },
+            ),
+            (
+                DISCLOSURE_USECASE_DIRECT_POST.to_string(),
+                UseCase {
+                    key_pair: ca.generate_reader_mock(reader_registration.clone()).unwrap(),
+                    session_type_return_url: SessionTypeReturnUrl::SameDevice,
+                    client_id: "client_id".to_string(),
+                    derive_age_over: vec![],
+                    response_mode: VpResponseMode::DirectPost,
                 },
             ),
             (
@@ -1209,6 +1890,8 @@ mod tests {
                     key_pair: ca.generate_reader_mock(reader_registration).unwrap(),
                     session_type_return_url: SessionTypeReturnUrl::Both,
                     client_id: "client_id".to_string(),
+                    derive_age_over: vec![],
+                    response_mode: VpResponseMode::DirectPostJwt,
                 },
             ),
         ])
@@ -1224,6 +1907,47 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_use_case_requestable_attributes() {
+        let ca = Ca::generate_reader_mock_ca().unwrap();
+        let items_requests = new_disclosure_request();
+
+        let use_case = UseCase {
+            key_pair: ca
+                .generate_reader_mock(Some(ReaderRegistration::new_mock_from_requests(&items_requests)))
+                .unwrap(),
+            session_type_return_url: SessionTypeReturnUrl::Neither,
+            client_id: "client_id".to_string(),
+            derive_age_over: vec![],
+            response_mode: VpResponseMode::DirectPostJwt,
+        };
+
+        let requestable_attributes = use_case
+            .requestable_attributes()
+            .expect("use case should have requestable attributes");
+
+        assert_eq!(
+            requestable_attributes,
+            items_requests
+                .0
+                .iter()
+                .attribute_identifiers()
+                .into_iter()
+                .collect_vec()
+        );
+
+        let use_case = UseCase {
+            key_pair: ca.generate_reader_mock(None).unwrap(),
+            ..use_case
+        };
+
+        let error = use_case
+            .requestable_attributes()
+            .expect_err("use case without reader registration should not have requestable attributes");
+
+        assert_matches!(error, UseCaseCertificateError::MissingReaderRegistration);
+    }
+
     #[rstest]
     #[case(DISCLOSURE_USECASE_NO_REDIRECT_URI, false, true)]
     #[case(DISCLOSURE_USECASE_NO_REDIRECT_URI, true, false)]
@@ -1252,12 +1976,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_verifier_new_session_invalid_attribute_identifier() {
+        let verifier = create_verifier();
+
+        // Add a space within the namespace, which would never match any disclosed attribute.
+        let mut items_requests = new_disclosure_request();
+        let name_spaces = &mut items_requests.0.first_mut().unwrap().name_spaces;
+        let attributes = name_spaces.swap_remove(DISCLOSURE_NAME_SPACE).unwrap();
+        name_spaces.insert(DISCLOSURE_NAME_SPACE.replace('_', " "), attributes);
+
+        let error = verifier
+            .new_session(
+                items_requests,
+                DISCLOSURE_USECASE.to_string(),
+                Some("https://example.com/{session_token}".parse().unwrap()),
+            )
+            .await
+            .expect_err("creating a new session should not succeed");
+
+        assert_matches!(
+            error,
+            NewSessionError::InvalidAttributeIdentifier(AttributeIdentifierError::InvalidCharacters { .. })
+        );
+    }
+
     async fn init_and_start_disclosure(
         time: &impl Generator<DateTime<Utc>>,
     ) -> (
         Verifier<MemorySessionStore<DisclosureData>>,
         SessionToken,
         VpRequestUriObject,
+    ) {
+        init_and_start_disclosure_for_use_case(DISCLOSURE_USECASE, time).await
+    }
+
+    async fn init_and_start_disclosure_for_use_case(
+        usecase_id: &str,
+        time: &impl Generator<DateTime<Utc>>,
+    ) -> (
+        Verifier<MemorySessionStore<DisclosureData>>,
+        SessionToken,
+        VpRequestUriObject,
     ) {
         let verifier = create_verifier();
 
@@ -1265,7 +2025,7 @@ mod tests {
         let session_token = verifier
             .new_session(
                 new_disclosure_request(),
-                DISCLOSURE_USECASE.to_string(),
+                usecase_id.to_string(),
                 Some("https://example.com/{session_token}".parse().unwrap()),
             )
             .await
@@ -1329,7 +2089,122 @@ mod tests {
         else {
             panic!("unexpected session state")
         };
-        assert_matches!(session_state.session_result, SessionResult::Cancelled);
+        assert_matches!(
+            session_state.session_result,
+            SessionResult::Cancelled {
+                reason: CancelReason::UserRefused
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verifier_run_cleanup_once() {
+        let verifier = create_verifier();
+        let timeouts = verifier.sessions.timeouts;
+
+        let session_token = verifier
+            .new_session(
+                new_disclosure_request(),
+                DISCLOSURE_USECASE_NO_REDIRECT_URI.to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        let last_active = verifier
+            .sessions
+            .get(&session_token)
+            .await
+            .unwrap()
+            .unwrap()
+            .last_active;
+
+        // The session should not be expired as long as the expiration timeout has not passed.
+        verifier.run_cleanup_once(last_active + timeouts.expiration);
+
+        let session = verifier.sessions.get(&session_token).await.unwrap().unwrap();
+        assert_matches!(session.data, DisclosureData::Created(_));
+        assert_eq!(session.last_active, last_active);
+
+        // After the expiration timeout has passed, the session should be expired.
+        let expiry_time = last_active + timeouts.expiration + Duration::seconds(1);
+        verifier.run_cleanup_once(expiry_time);
+
+        let session = verifier.sessions.get(&session_token).await.unwrap().unwrap();
+        assert_matches!(
+            session.data,
+            DisclosureData::Done(Done {
+                session_result: SessionResult::Expired {
+                    reason: ExpiryReason::SessionTimeout
+                }
+            })
+        );
+        assert_eq!(session.last_active, expiry_time);
+
+        // The expired session should be removed once the deletion timeout for failed sessions has passed.
+        verifier.run_cleanup_once(expiry_time + timeouts.failed_deletion + Duration::seconds(1));
+
+        assert!(verifier.sessions.get(&session_token).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(
+        DISCLOSURE_USECASE,
+        "vp_token=vp_token&presentation_submission=%7B%7D&mdoc_generated_nonce=mdoc_nonce",
+        VpResponseMode::DirectPostJwt,
+        VpResponseMode::DirectPost
+    )]
+    #[case(
+        DISCLOSURE_USECASE_DIRECT_POST,
+        "vp_token=jwe",
+        VpResponseMode::DirectPost,
+        VpResponseMode::DirectPostJwt
+    )]
+    async fn test_verifier_response_mode_mismatch(
+        #[case] usecase_id: &str,
+        #[case] posted_response: &str,
+        #[case] expected_response_mode: VpResponseMode,
+        #[case] response_mode: VpResponseMode,
+    ) {
+        let (verifier, session_token, request_uri_object) =
+            init_and_start_disclosure_for_use_case(usecase_id, &TimeGenerator).await;
+
+        verifier
+            .process_get_request(
+                &session_token,
+                format!("https://example.com/disclosure/{session_token}/response_uri")
+                    .parse()
+                    .unwrap(),
+                request_uri_object.request_uri.as_ref().query(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Post a response in a response mode other than the one configured for the use case.
+        let wallet_response: WalletAuthResponse = serde_urlencoded::from_str(posted_response).unwrap();
+        assert_matches!(
+            &wallet_response,
+            WalletAuthResponse::Response(vp_token) if vp_token.response_mode() == response_mode
+        );
+
+        let error = verifier
+            .process_authorization_response(&session_token, wallet_response, &TimeGenerator)
+            .await
+            .expect_err("response in other response mode should be rejected");
+
+        assert_matches!(
+            error.error,
+            PostAuthResponseError::AuthResponse(AuthResponseError::ResponseModeMismatch { expected, found })
+                if expected == expected_response_mode && found == response_mode
+        );
+
+        // The session should have failed.
+        let DisclosureData::Done(session_state) = verifier.sessions.get(&session_token).await.unwrap().unwrap().data
+        else {
+            panic!("unexpected session state")
+        };
+        assert_matches!(session_state.session_result, SessionResult::Failed { .. });
     }
 
     struct ExpiredEphemeralIdGenerator;
@@ -1424,6 +2299,10 @@ mod tests {
                 session_result: SessionResult::Done {
                     disclosed_attributes: Default::default(),
                     redirect_uri_nonce: None,
+                    conformance: Default::default(),
+                    derived_attributes: Default::default(),
+                    requested_attributes: Default::default(),
+                    transcript_hash: None,
                 },
             }),
         );
@@ -1433,13 +2312,19 @@ mod tests {
                 session_result: SessionResult::Done {
                     disclosed_attributes: Default::default(),
                     redirect_uri_nonce: "this-is-the-nonce".to_string().into(),
+                    conformance: Default::default(),
+                    derived_attributes: Default::default(),
+                    requested_attributes: Default::default(),
+                    transcript_hash: None,
                 },
             }),
         );
         let session3 = SessionState::new(
             "token3".into(),
             DisclosureData::Done(Done {
-                session_result: SessionResult::Expired,
+                session_result: SessionResult::Expired {
+                    reason: ExpiryReason::SessionTimeout,
+                },
             }),
         );
 
@@ -1488,17 +2373,341 @@ mod tests {
                 .disclosed_attributes(&"token3".into(), None)
                 .await
                 .expect_err("should fail to return disclosed attributes"),
-            DisclosedAttributesError::Session(SessionError::UnexpectedState(SessionStatus::Expired))
+            DisclosedAttributesError::Session(SessionError::UnexpectedState(SessionStatus::Expired {
+                reason: ExpiryReason::SessionTimeout
+            }))
         );
         assert_matches!(
             verifier
                 .disclosed_attributes(&"token3".into(), "nonsense".to_string().into())
                 .await
                 .expect_err("should fail to return disclosed attributes"),
-            DisclosedAttributesError::Session(SessionError::UnexpectedState(SessionStatus::Expired))
+            DisclosedAttributesError::Session(SessionError::UnexpectedState(SessionStatus::Expired {
+                reason: ExpiryReason::SessionTimeout
+            }))
         );
     }
 
+    #[tokio::test]
+    async fn test_verifier_disclosure_receipt() {
+        let verifier = create_verifier();
+
+        let now = Utc::now();
+        let birth_date = ciborium::Value::Text("2000-03-01".to_string());
+        let disclosed_attributes = IndexMap::from([(
+            DISCLOSURE_DOC_TYPE.to_string(),
+            DocumentDisclosedAttributes {
+                attributes: IndexMap::from([(
+                    DISCLOSURE_NAME_SPACE.to_string(),
+                    IndexMap::from([("birth_date".to_string(), birth_date.clone())]),
+                )]),
+                issuer: "issuer.example.com".to_string(),
+                ca: "ca.issuer.example.com".to_string(),
+                validity_info: ValidityInfo {
+                    signed: now.into(),
+                    valid_from: now.into(),
+                    valid_until: (now + Duration::days(1)).into(),
+                    expected_update: None,
+                },
+            },
+        )]);
+        let identifier = AttributeIdentifier {
+            credential_type: DISCLOSURE_DOC_TYPE.to_string(),
+            namespace: DISCLOSURE_NAME_SPACE.to_string(),
+            attribute: "birth_date".to_string(),
+        };
+
+        let session1 = SessionState::new(
+            "token1".into(),
+            DisclosureData::Done(Done {
+                session_result: SessionResult::Done {
+                    disclosed_attributes,
+                    redirect_uri_nonce: None,
+                    conformance: Default::default(),
+                    derived_attributes: Default::default(),
+                    requested_attributes: vec![identifier.clone()],
+                    transcript_hash: Some(vec![1, 2, 3]),
+                },
+            }),
+        );
+        let session2 = SessionState::new(
+            "token2".into(),
+            DisclosureData::Done(Done {
+                session_result: SessionResult::Expired {
+                    reason: ExpiryReason::SessionTimeout,
+                },
+            }),
+        );
+
+        verifier.sessions.write(session1, true).await.unwrap();
+        verifier.sessions.write(session2, true).await.unwrap();
+
+        let ca = Ca::generate_reader_mock_ca().unwrap();
+        let signing_key = ca.generate_reader_mock(None).unwrap();
+        let value_hmac_key = hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
+        let time = MockTimeGenerator::new(now);
+        let audience: &[String] = &[];
+
+        // By default, the receipt should only contain a hash of the disclosed values.
+        let receipt_jwt = verifier
+            .disclosure_receipt(&"token1".into(), &signing_key, &value_hmac_key, false, &time)
+            .await
+            .expect("should create disclosure receipt");
+        let (receipt, _) =
+            verify_against_trust_anchors(&receipt_jwt, audience, &[ca.to_trust_anchor()], &TimeGenerator)
+                .expect("receipt should be signed by the RP");
+
+        assert_eq!(receipt.session_token, "token1".into());
+        assert_eq!(receipt.iat.timestamp(), now.timestamp());
+        assert_eq!(receipt.requested_attributes, vec![identifier.clone()]);
+        assert_eq!(receipt.transcript_hash, Some(vec![1, 2, 3]));
+        assert_eq!(receipt.disclosed_attributes.len(), 1);
+
+        let receipt_attribute = receipt.disclosed_attributes.first().unwrap();
+        assert_eq!(receipt_attribute.attribute, identifier);
+        assert_eq!(
+            receipt_attribute.value_hash,
+            hmac::sign(&value_hmac_key, &cbor_serialize(&birth_date).unwrap()).as_ref()
+        );
+        assert!(receipt_attribute.value.is_none());
+
+        // The values are included only when explicitly requested.
+        let receipt_jwt = verifier
+            .disclosure_receipt(&"token1".into(), &signing_key, &value_hmac_key, true, &time)
+            .await
+            .expect("should create disclosure receipt");
+        let (receipt, _) =
+            verify_against_trust_anchors(&receipt_jwt, audience, &[ca.to_trust_anchor()], &TimeGenerator)
+                .expect("receipt should be signed by the RP");
+
+        assert_eq!(receipt.disclosed_attributes.first().unwrap().value, Some(birth_date));
+
+        // No receipt can be created for a session that did not result in a disclosure.
+        assert_matches!(
+            verifier
+                .disclosure_receipt(&"token2".into(), &signing_key, &value_hmac_key, false, &time)
+                .await
+                .expect_err("should fail to create disclosure receipt"),
+            DisclosureReceiptError::Session(SessionError::UnexpectedState(SessionStatus::Expired { .. }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verifier_status_response_builder() {
+        let verifier = create_verifier();
+
+        let ca = Ca::generate_reader_mock_ca().unwrap();
+        let rp_key_pair = ca.generate_reader_mock(None).unwrap();
+
+        let sessions = [
+            ("created", DisclosureDataBuilder::new().created()),
+            (
+                "waiting",
+                DisclosureDataBuilder::new()
+                    .items_requests(new_disclosure_request())
+                    .waiting_for_response(
+                        rp_key_pair.certificate(),
+                        "https://example.com/response_uri".parse().unwrap(),
+                        "nonce".to_string(),
+                    ),
+            ),
+            ("done", DisclosureDataBuilder::new().done()),
+            ("failed", DisclosureDataBuilder::new().failed("error".to_string())),
+            (
+                "cancelled",
+                DisclosureDataBuilder::new().cancelled(CancelReason::RelyingParty),
+            ),
+            (
+                "expired",
+                DisclosureDataBuilder::new().expired(ExpiryReason::SessionTimeout),
+            ),
+        ];
+        for (token, data) in sessions {
+            verifier
+                .sessions
+                .write(SessionState::new(token.into(), data), true)
+                .await
+                .unwrap();
+        }
+
+        let mut statuses = vec![];
+        for token in ["created", "waiting", "done", "failed", "cancelled", "expired"] {
+            let status = verifier
+                .status_response(
+                    &token.into(),
+                    None,
+                    &"https://app.example.com/app".parse().unwrap(),
+                    "https://example.com/disclosure".parse().unwrap(),
+                    &TimeGenerator,
+                )
+                .await
+                .expect("should result in status response for session");
+            statuses.push(status);
+        }
+
+        assert_matches!(
+            statuses.as_slice(),
+            [
+                StatusResponse::Created { ul: None },
+                StatusResponse::WaitingForResponse,
+                StatusResponse::Done,
+                StatusResponse::Failed,
+                StatusResponse::Cancelled,
+                StatusResponse::Expired,
+            ]
+        );
+
+        // The disclosed attributes of a done session are only returned with the configured redirect URI nonce.
+        verifier
+            .sessions
+            .write(
+                SessionState::new(
+                    "done_with_nonce".into(),
+                    DisclosureDataBuilder::new()
+                        .redirect_uri("https://example.com/return".parse().unwrap(), "nonce".to_string())
+                        .done(),
+                ),
+                true,
+            )
+            .await
+            .unwrap();
+
+        verifier
+            .disclosed_attributes(&"done_with_nonce".into(), Some("nonce".to_string()))
+            .await
+            .expect("should return disclosed attributes");
+        assert_matches!(
+            verifier
+                .disclosed_attributes(&"done_with_nonce".into(), None)
+                .await
+                .expect_err("should fail to return disclosed attributes"),
+            DisclosedAttributesError::RedirectUriNonceMissing
+        );
+    }
+
+    #[test]
+    fn test_disclosure_conformance() {
+        let items_requests = new_disclosure_request();
+        let disclosed_attributes = |attributes: &[&str]| -> DisclosedAttributes {
+            let now = Utc::now();
+
+            IndexMap::from([(
+                DISCLOSURE_DOC_TYPE.to_string(),
+                DocumentDisclosedAttributes {
+                    attributes: IndexMap::from([(
+                        DISCLOSURE_NAME_SPACE.to_string(),
+                        attributes
+                            .iter()
+                            .map(|name| (name.to_string(), ciborium::Value::Text("value".to_string())))
+                            .collect(),
+                    )]),
+                    issuer: "issuer.example.com".to_string(),
+                    ca: "ca.issuer.example.com".to_string(),
+                    validity_info: ValidityInfo {
+                        signed: now.into(),
+                        valid_from: now.into(),
+                        valid_until: (now + Duration::days(1)).into(),
+                        expected_update: None,
+                    },
+                },
+            )])
+        };
+        let identifier = |attribute: &str| AttributeIdentifier {
+            credential_type: DISCLOSURE_DOC_TYPE.to_string(),
+            namespace: DISCLOSURE_NAME_SPACE.to_string(),
+            attribute: attribute.to_string(),
+        };
+
+        // Disclosing exactly the requested attributes conforms.
+        let conformance =
+            DisclosureConformance::new(&items_requests, &disclosed_attributes(&["first_name", "family_name"]));
+        assert!(conformance.is_exact());
+
+        // Disclosing attributes other than the requested ones should be reported.
+        let conformance = DisclosureConformance::new(
+            &items_requests,
+            &disclosed_attributes(&["first_name", "family_name", "bsn"]),
+        );
+        assert!(!conformance.is_exact());
+        assert!(conformance.requested_but_missing.is_empty());
+        assert_eq!(conformance.disclosed_but_unrequested, vec![identifier("bsn")]);
+
+        // Disclosing fewer or other attributes than requested should be reported.
+        let conformance = DisclosureConformance::new(&items_requests, &disclosed_attributes(&["first_name", "bsn"]));
+        assert!(!conformance.is_exact());
+        assert_eq!(conformance.requested_but_missing, vec![identifier("family_name")]);
+        assert_eq!(conformance.disclosed_but_unrequested, vec![identifier("bsn")]);
+
+        // When nothing was disclosed, all requested attributes are missing.
+        let conformance = DisclosureConformance::new(&items_requests, &DisclosedAttributes::new());
+        assert_eq!(
+            conformance.requested_but_missing,
+            vec![identifier("first_name"), identifier("family_name")]
+        );
+        assert!(conformance.disclosed_but_unrequested.is_empty());
+    }
+
+    #[rstest]
+    #[case(ciborium::Value::Text("2000-03-01".to_string()), "2018-03-01", true, false)]
+    #[case(ciborium::Value::Tag(1004, ciborium::Value::Text("2000-03-01".to_string()).into()), "2018-03-01", true, false)]
+    #[case(ciborium::Value::Text("2000-03-02".to_string()), "2018-03-01", false, false)]
+    #[case(ciborium::Value::Text("1979-01-01".to_string()), "2018-03-01", true, false)]
+    #[case(ciborium::Value::Text("1978-03-01".to_string()), "2018-03-01", true, true)]
+    #[case(ciborium::Value::Text("2000-02-29".to_string()), "2018-02-28", false, false)]
+    #[case(ciborium::Value::Text("2000-02-29".to_string()), "2018-03-01", true, false)]
+    #[case(ciborium::Value::Text("2000-02-29".to_string()), "2020-02-29", true, false)]
+    fn test_derive_age_over(
+        #[case] birth_date: ciborium::Value,
+        #[case] today: &str,
+        #[case] over_18: bool,
+        #[case] over_40: bool,
+    ) {
+        let time = format!("{today}T12:00:00Z").parse().unwrap();
+        let disclosed_attributes = IndexMap::from([(
+            DISCLOSURE_DOC_TYPE.to_string(),
+            DocumentDisclosedAttributes {
+                attributes: IndexMap::from([(
+                    DISCLOSURE_NAME_SPACE.to_string(),
+                    IndexMap::from([("birth_date".to_string(), birth_date)]),
+                )]),
+                issuer: "issuer.example.com".to_string(),
+                ca: "ca.issuer.example.com".to_string(),
+                validity_info: ValidityInfo {
+                    signed: Utc::now().into(),
+                    valid_from: Utc::now().into(),
+                    valid_until: Utc::now().into(),
+                    expected_update: None,
+                },
+            },
+        )]);
+
+        let derived = DerivedAttribute::derive_age_over(&disclosed_attributes, &[18, 40], time);
+
+        let source = AttributeIdentifier {
+            credential_type: DISCLOSURE_DOC_TYPE.to_string(),
+            namespace: DISCLOSURE_NAME_SPACE.to_string(),
+            attribute: "birth_date".to_string(),
+        };
+        assert_eq!(
+            derived,
+            vec![
+                DerivedAttribute {
+                    source: source.clone(),
+                    name: "age_over_18".to_string(),
+                    value: over_18,
+                },
+                DerivedAttribute {
+                    source,
+                    name: "age_over_40".to_string(),
+                    value: over_40,
+                },
+            ]
+        );
+
+        // Nothing is derived when no thresholds are configured.
+        assert!(DerivedAttribute::derive_age_over(&disclosed_attributes, &[], time).is_empty());
+    }
+
     #[test]
     fn test_verifier_url() {
         let ephemeral_id_secret = hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
@@ -47,6 +47,7 @@ use openid4vc::openid4vp::IsoVpAuthorizationRequest;
 use openid4vc::openid4vp::VpAuthorizationRequest;
 use openid4vc::openid4vp::VpAuthorizationResponse;
 use openid4vc::openid4vp::VpRequestUriObject;
+use openid4vc::openid4vp::VpResponseMode;
 use openid4vc::return_url::ReturnUrlTemplate;
 use openid4vc::server_state::MemorySessionStore;
 use openid4vc::server_state::SessionToken;
@@ -311,6 +312,7 @@ impl VpMessageClient for DirectMockVpMessageClient {
 const NO_RETURN_URL_USE_CASE: &str = "no_return_url";
 const DEFAULT_RETURN_URL_USE_CASE: &str = "default_return_url";
 const ALL_RETURN_URL_USE_CASE: &str = "all_return_url";
+const DIRECT_POST_USE_CASE: &str = "direct_post";
 
 struct MockMdocDataSource(HashMap<DocType, MdocCopies>);
 
@@ -548,6 +550,89 @@ async fn test_client_and_server(
     expected_documents.assert_matches(&disclosed_documents);
 }
 
+#[tokio::test]
+async fn test_client_and_server_direct_post() {
+    let items_requests = pid_full_name().into();
+
+    let (verifier, rp_trust_anchor, issuer_ca) = setup_verifier(&items_requests);
+
+    // Start a session for a use case that expects the `direct_post` response mode.
+    let session_token = verifier
+        .new_session(items_requests, DIRECT_POST_USE_CASE.to_string(), None)
+        .await
+        .unwrap();
+
+    let request_uri = request_uri_from_status_endpoint(&verifier, &session_token, SessionType::CrossDevice).await;
+    let request_uri_object: VpRequestUriObject = serde_urlencoded::from_str(&request_uri).unwrap();
+
+    // Act as a wallet that supports the `direct_post` response mode, which starts by fetching the Authorization
+    // Request.
+    let auth_request_jws = verifier
+        .process_get_request(
+            &session_token,
+            format!("https://example.com/verifier_base_url/{session_token}/response_uri")
+                .parse()
+                .unwrap(),
+            request_uri_object.request_uri.as_ref().query(),
+            None,
+        )
+        .await
+        .unwrap();
+    let (vp_auth_request, certificate) =
+        VpAuthorizationRequest::try_new(&auth_request_jws, &[rp_trust_anchor]).unwrap();
+    let auth_request = vp_auth_request.validate(&certificate, None).unwrap();
+    assert_eq!(auth_request.response_mode, VpResponseMode::DirectPost);
+
+    // Compute the disclosure of the stored documents.
+    let key_factory = MockRemoteKeyFactory::default();
+    let mdocs = future::join_all(
+        pid_full_name()
+            .into_iter()
+            .map(|doc| async { doc.sign(&issuer_ca, &key_factory, NonZeroU8::new(1).unwrap()).await }),
+    )
+    .await;
+    let mdocs = MockMdocDataSource::from(mdocs);
+
+    let mdoc_nonce = "mdoc_nonce".to_string();
+    let session_transcript = SessionTranscript::new_oid4vp(
+        &auth_request.response_uri,
+        &auth_request.client_id,
+        auth_request.nonce.clone(),
+        &mdoc_nonce,
+    );
+    let DisclosureRequestMatch::Candidates(candidates) =
+        DisclosureRequestMatch::new(auth_request.items_requests.as_ref().iter(), &mdocs, &session_transcript)
+            .await
+            .unwrap()
+    else {
+        panic!("should have found requested attributes")
+    };
+    let to_disclose = candidates.into_values().map(|mut docs| docs.pop().unwrap()).collect();
+    let (device_response, _) = DeviceResponse::from_proposed_documents(to_disclose, &key_factory)
+        .await
+        .unwrap();
+
+    // Post the Authorization Response form encoded, in the `direct_post` response mode.
+    let direct_post_response =
+        VpAuthorizationResponse::new_direct_post(device_response, &auth_request, &mdoc_nonce).unwrap();
+    let wallet_response: WalletAuthResponse =
+        serde_urlencoded::from_str(&serde_urlencoded::to_string(&direct_post_response).unwrap()).unwrap();
+    assert_matches!(
+        &wallet_response,
+        WalletAuthResponse::Response(vp_token) if vp_token.response_mode() == VpResponseMode::DirectPost
+    );
+
+    verifier
+        .process_authorization_response(&session_token, wallet_response, &TimeGenerator)
+        .await
+        .unwrap();
+
+    // The verifier should have accepted the disclosed attributes.
+    let disclosed_documents = verifier.disclosed_attributes(&session_token, None).await.unwrap();
+
+    pid_full_name().assert_matches(&disclosed_documents);
+}
+
 #[tokio::test]
 async fn test_client_and_server_cancel_after_created() {
     let stored_documents = pid_full_name();
@@ -779,6 +864,17 @@ fn setup_verifier(items_requests: &ItemsRequests) -> (Arc<MockVerifier>, TrustAn
             )
             .unwrap(),
         ),
+        (
+            DIRECT_POST_USE_CASE.to_string(),
+            UseCase {
+                response_mode: VpResponseMode::DirectPost,
+                ..UseCase::try_new(
+                    rp_ca.generate_reader_mock(reader_registration.clone()).unwrap(),
+                    SessionTypeReturnUrl::Neither,
+                )
+                .unwrap()
+            },
+        ),
         (
             ALL_RETURN_URL_USE_CASE.to_string(),
             UseCase::try_new(
@@ -915,7 +1011,7 @@ impl VpMessageClient for VerifierMockVpMessageClient {
             .verifier
             .process_authorization_response(
                 &session_token,
-                WalletAuthResponse::Response(VpToken { vp_token: jwe }),
+                WalletAuthResponse::Response(VpToken::new_encrypted(jwe)),
                 &TimeGenerator,
             )
             .await
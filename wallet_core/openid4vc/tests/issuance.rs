@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::num::NonZeroU8;
 use std::num::NonZeroUsize;
 use std::ops::Add;
@@ -7,6 +8,7 @@ use chrono::Days;
 use chrono::Utc;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use jsonwebtoken::jwk::JwkSet;
 use p256::ecdsa::SigningKey;
 use rand_core::OsRng;
 use rstest::rstest;
@@ -25,6 +27,8 @@ use openid4vc::credential::CredentialRequestProof;
 use openid4vc::credential::CredentialRequests;
 use openid4vc::credential::CredentialResponse;
 use openid4vc::credential::CredentialResponses;
+use openid4vc::credential::NonceResponse;
+use openid4vc::credential::NotificationRequest;
 use openid4vc::dpop::Dpop;
 use openid4vc::issuance_session::mock_wte;
 use openid4vc::issuance_session::HttpIssuanceSession;
@@ -32,6 +36,7 @@ use openid4vc::issuance_session::IssuanceSession;
 use openid4vc::issuance_session::IssuanceSessionError;
 use openid4vc::issuance_session::IssuedCredentialCopies;
 use openid4vc::issuance_session::VcMessageClient;
+use openid4vc::issuance_session::DEFAULT_CONCURRENCY_LIMIT;
 use openid4vc::issuer::AttributeService;
 use openid4vc::issuer::IssuableCredential;
 use openid4vc::issuer::IssuanceData;
@@ -70,6 +75,7 @@ fn setup_mock_issuer(
         },
         &ca,
         issuance_keypair,
+        false,
     )
 }
 
@@ -77,6 +83,7 @@ fn setup(
     attr_service: MockAttributeService,
     ca: &Ca,
     issuance_keypair: KeyPair,
+    accept_single_holder_keys: bool,
 ) -> (MockIssuer, TrustAnchor<'static>, BaseUrl, SigningKey) {
     let server_url: BaseUrl = "https://example.com/".parse().unwrap();
     let wte_issuer_privkey = SigningKey::random(&mut OsRng);
@@ -90,6 +97,7 @@ fn setup(
         vec!["https://wallet.edi.rijksoverheid.nl".to_string()],
         *wte_issuer_privkey.verifying_key(),
         MemoryWteTracker::new(),
+        accept_single_holder_keys,
     );
 
     (
@@ -105,6 +113,7 @@ fn setup(
 async fn accept_issuance(
     #[values(NonZeroUsize::new(1).unwrap(), NonZeroUsize::new(2).unwrap())] attestation_count: NonZeroUsize,
     #[values(NonZeroU8::new(1).unwrap(), NonZeroU8::new(2).unwrap())] copy_count: NonZeroU8,
+    #[values(DEFAULT_CONCURRENCY_LIMIT, NonZeroUsize::MIN)] concurrency_limit: NonZeroUsize,
 ) {
     let (issuer, trust_anchor, server_url, wte_issuer_privkey) = setup_mock_issuer(attestation_count, copy_count);
     let trust_anchors = &[trust_anchor];
@@ -118,6 +127,7 @@ async fn accept_issuance(
     )
     .await
     .unwrap();
+    let session = session.with_concurrency_limit(concurrency_limit);
 
     let key_factory = MockRemoteKeyFactory::default();
     let wte = mock_wte(&key_factory, &wte_issuer_privkey).await;
@@ -143,6 +153,104 @@ async fn accept_issuance(
         });
 }
 
+#[rstest]
+#[tokio::test]
+async fn accept_issuance_selective(
+    #[values(NonZeroU8::new(1).unwrap(), NonZeroU8::new(2).unwrap())] copy_count: NonZeroU8,
+) {
+    let (issuer, trust_anchor, server_url, wte_issuer_privkey) =
+        setup_mock_issuer(NonZeroUsize::new(2).unwrap(), copy_count);
+    let trust_anchors = &[trust_anchor];
+    let message_client = MockOpenidMessageClient::new(issuer);
+
+    let (session, previews) = HttpIssuanceSession::start_issuance(
+        message_client,
+        server_url.clone(),
+        TokenRequest::new_mock(),
+        trust_anchors,
+    )
+    .await
+    .unwrap();
+
+    let key_factory = MockRemoteKeyFactory::default();
+    let wte = mock_wte(&key_factory, &wte_issuer_privkey).await;
+
+    // Accept only the second of the two offered credentials.
+    let selected_preview = previews.into_iter().flatten().nth(1).unwrap();
+    let CredentialPreview::MsoMdoc { unsigned_mdoc, .. } = &selected_preview;
+
+    let issued_creds = session
+        .accept_issuance_selective(
+            &HashSet::from([unsigned_mdoc.doc_type.clone()]),
+            trust_anchors,
+            &key_factory,
+            Some(wte),
+            server_url,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(issued_creds.len(), 1);
+
+    let copies = issued_creds.into_iter().next().unwrap();
+    assert_eq!(copies.len(), copy_count.get() as usize);
+    match copies {
+        IssuedCredentialCopies::MsoMdoc(mdocs) => mdocs.first().compare_unsigned(unsigned_mdoc).unwrap(),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn accept_issuance_single_holder_key(#[values(true, false)] accept_single_holder_keys: bool) {
+    let attestation_count = NonZeroUsize::new(2).unwrap();
+    let copy_count = NonZeroU8::new(2).unwrap();
+
+    let ca = Ca::generate_issuer_mock_ca().unwrap();
+    let issuance_keypair = ca.generate_issuer_mock(IssuerRegistration::new_mock().into()).unwrap();
+    let (issuer, trust_anchor, server_url, wte_issuer_privkey) = setup(
+        MockAttributeService {
+            attestations: mock_issuable_attestation(attestation_count, copy_count),
+        },
+        &ca,
+        issuance_keypair,
+        accept_single_holder_keys,
+    );
+    let trust_anchors = &[trust_anchor];
+    let message_client = MockOpenidMessageClient::new(issuer);
+
+    let (session, _previews) = HttpIssuanceSession::start_issuance(
+        message_client,
+        server_url.clone(),
+        TokenRequest::new_mock(),
+        trust_anchors,
+    )
+    .await
+    .unwrap();
+    let session = session.with_single_holder_key(true);
+
+    let key_factory = MockRemoteKeyFactory::default();
+    let wte = mock_wte(&key_factory, &wte_issuer_privkey).await;
+
+    let result = session
+        .accept_issuance(trust_anchors, &key_factory, Some(wte), server_url)
+        .await;
+
+    // The issuer only accepts copies sharing a key if configured to do so, as otherwise the PoA lacks keys.
+    if accept_single_holder_keys {
+        let issued_creds = result.expect("accepting issuance should succeed");
+
+        assert_eq!(issued_creds.len(), attestation_count.get());
+        assert!(issued_creds
+            .iter()
+            .all(|copies| copies.len() == copy_count.get() as usize));
+    } else {
+        assert_matches!(
+            result,
+            Err(IssuanceSessionError::CredentialRequest(err)) if matches!(err.error, CredentialErrorCode::InvalidProof)
+        );
+    }
+}
+
 #[tokio::test]
 async fn reject_issuance() {
     let (issuer, trust_anchor, server_url, _) =
@@ -421,21 +529,29 @@ impl VcMessageClient for MockOpenidMessageClient {
         Ok((token_response, Some(dpop_nonce)))
     }
 
+    async fn request_nonce(&self, _url: &Url) -> Result<NonceResponse, IssuanceSessionError> {
+        // The mock issuer returns the `c_nonce` in the Token Response, so this should never be called.
+        unreachable!("the mock issuer does not have a nonce endpoint")
+    }
+
     async fn request_credential(
         &self,
         _url: &Url,
         credential_request: &CredentialRequest,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponse, IssuanceSessionError> {
-        self.issuer
+    ) -> (Result<CredentialResponse, IssuanceSessionError>, Option<String>) {
+        let result = self
+            .issuer
             .process_credential(
                 self.access_token(access_token_header),
                 self.dpop(dpop_header),
                 self.credential_request(credential_request.clone()),
             )
             .await
-            .map_err(|err| IssuanceSessionError::CredentialRequest(err.into()))
+            .map_err(|err| IssuanceSessionError::CredentialRequest(err.into()));
+
+        (result, None)
     }
 
     async fn request_credentials(
@@ -444,15 +560,18 @@ impl VcMessageClient for MockOpenidMessageClient {
         credential_requests: &CredentialRequests,
         dpop_header: &str,
         access_token_header: &str,
-    ) -> Result<CredentialResponses, IssuanceSessionError> {
-        self.issuer
+    ) -> (Result<CredentialResponses, IssuanceSessionError>, Option<String>) {
+        let result = self
+            .issuer
             .process_batch_credential(
                 self.access_token(access_token_header),
                 self.dpop(dpop_header),
                 self.credential_requests(credential_requests.clone()),
             )
             .await
-            .map_err(|err| IssuanceSessionError::CredentialRequest(err.into()))
+            .map_err(|err| IssuanceSessionError::CredentialRequest(err.into()));
+
+        (result, None)
     }
 
     async fn reject(
@@ -470,6 +589,22 @@ impl VcMessageClient for MockOpenidMessageClient {
             .await
             .map_err(|err| IssuanceSessionError::CredentialRequest(err.into()))
     }
+
+    async fn notify(
+        &self,
+        _url: &Url,
+        _notification_request: &NotificationRequest,
+        _dpop_header: &str,
+        _access_token_header: &str,
+    ) -> Result<(), IssuanceSessionError> {
+        // The mock issuer metadata does not announce a notification endpoint, so this should never be called.
+        unreachable!("the mock issuer does not support notifications")
+    }
+
+    async fn jwks(&self, _url: &Url) -> Result<JwkSet, IssuanceSessionError> {
+        // JWKS verification is not enabled in these tests, so this should never be called.
+        unreachable!("the mock issuer does not publish a JWKS")
+    }
 }
 
 const MOCK_DOCTYPES: [&str; 2] = ["com.example.pid", "com.example.address"];
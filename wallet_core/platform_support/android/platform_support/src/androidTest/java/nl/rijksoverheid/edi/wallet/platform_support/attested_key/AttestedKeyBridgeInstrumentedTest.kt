@@ -141,7 +141,7 @@ class AttestedKeyBridgeInstrumentedTest {
         val challenge = CHALLENGE.toByteArray().toUByteList()
 
         // Verify public key for 'id' does not exist
-        assertFails<AttestedKeyException.Other>("reason=precondition failed: Key not found for alias: `ecdsa_id`") {
+        assertFails<AttestedKeyException.KeyNotFound> {
             attestedKeyBridge.publicKey(id)
         }
 
@@ -160,7 +160,7 @@ class AttestedKeyBridgeInstrumentedTest {
         attestedKeyBridge.delete(id)
 
         // Verify public key for 'id' does no longer exist
-        assertFails<AttestedKeyException.Other>("reason=precondition failed: Key not found for alias: `ecdsa_id`") {
+        assertFails<AttestedKeyException.KeyNotFound> {
             attestedKeyBridge.publicKey(id)
         }
     }
@@ -170,7 +170,7 @@ class AttestedKeyBridgeInstrumentedTest {
         val id = "id"
 
         // Verify public key for 'id' does not exist
-        assertFails<AttestedKeyException.Other>("reason=precondition failed: Key not found for alias: `ecdsa_id`") {
+        assertFails<AttestedKeyException.KeyNotFound> {
             attestedKeyBridge.publicKey(id)
         }
 
@@ -203,7 +203,7 @@ class AttestedKeyBridgeInstrumentedTest {
         val id = "id"
         val valueToSign = "value to sign".toByteArray().toUByteList()
 
-        assertFails<AttestedKeyException.Other>("reason=precondition failed: Key not found for alias: `ecdsa_id`") {
+        assertFails<AttestedKeyException.KeyNotFound> {
             attestedKeyBridge.sign(id, valueToSign)
         }
     }
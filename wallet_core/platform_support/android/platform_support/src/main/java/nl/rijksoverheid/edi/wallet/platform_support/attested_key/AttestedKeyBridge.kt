@@ -168,10 +168,10 @@ class AttestedKeyBridge(context: Context) : KeyBridge(context), RustAttestedKeyB
     private fun getKey(keyAlias: String): SigningKey {
         try {
             verifyDeviceUnlocked()
-            verifyKeyExists(keyAlias)
         } catch (e: IllegalStateException) {
             throw AttestedKeyException.Other("precondition failed: ${e.message}")
         }
+        if (!keyExists(keyAlias)) throw AttestedKeyException.KeyNotFound()
         return SigningKey(keyAlias)
     }
 
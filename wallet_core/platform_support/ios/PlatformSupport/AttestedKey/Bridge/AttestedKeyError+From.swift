@@ -15,6 +15,8 @@ extension AttestedKeyError {
             return .AttestationNotSupported
         case DCError.serverUnavailable:
             return .ServerUnreachable(details: error.localizedDescription)
+        case DCError.invalidKey:
+            return .KeyNotFound
         default:
             return .Other(reason: error.localizedDescription)
         }
@@ -13,6 +13,8 @@ pub enum AttestedKeyError {
     MethodUnimplemented,
     #[error("vendor server is unreachable: {details}")]
     ServerUnreachable { details: String },
+    #[error("attested key does not exist")]
+    KeyNotFound,
     #[error("{reason}")]
     Other { reason: String },
 }
@@ -5,6 +5,7 @@ use jsonwebtoken::Algorithm;
 use jsonwebtoken::EncodingKey;
 use jsonwebtoken::Header;
 use p256::ecdsa::SigningKey;
+use p256::pkcs8::DecodePrivateKey;
 use p256::pkcs8::EncodePrivateKey;
 use rand_core::OsRng;
 use regex::Regex;
@@ -12,6 +13,7 @@ use reqwest::header::HeaderValue;
 use tokio::fs;
 
 use tests_integration::common::*;
+use tests_integration::utils::read_file;
 use wallet::errors::ConfigurationError;
 use wallet::wallet_deps::default_config_server_config;
 use wallet::wallet_deps::default_wallet_config;
@@ -21,6 +23,7 @@ use wallet::wallet_deps::RepositoryUpdateState;
 use wallet::wallet_deps::UpdateableRepository;
 use wallet_common::config::config_server_config::ConfigServerConfiguration;
 use wallet_common::config::http::TlsPinningConfig;
+use wallet_common::jwt::DetachedJws;
 use wallet_common::jwt::JwtError;
 
 #[tokio::test]
@@ -74,6 +77,51 @@ async fn test_wallet_config() {
     assert_matches!(result, RepositoryUpdateState::Unmodified(_));
 }
 
+#[tokio::test]
+async fn test_wallet_config_detached_signature() {
+    let mut served_wallet_config = default_wallet_config();
+    served_wallet_config.lock_timeouts.inactive_timeout = 1;
+    served_wallet_config.lock_timeouts.background_timeout = 1;
+    served_wallet_config.version = 2;
+
+    // Serve the raw wallet configuration along with a detached JWS over it
+    let signing_key = SigningKey::from_pkcs8_pem(&String::from_utf8(read_file("config_signing.pem")).unwrap()).unwrap();
+    let configuration = serde_json::to_string(&served_wallet_config).unwrap();
+    let signature = DetachedJws::<()>::sign(configuration.as_bytes(), &signing_key)
+        .await
+        .unwrap();
+
+    let (mut cs_settings, cs_root_ca) = config_server_settings();
+    cs_settings.wallet_config_jwt = format!(r#"{{"signature":"{}","configuration":{}}}"#, signature.0, configuration);
+    let port = cs_settings.port;
+    start_config_server(cs_settings, cs_root_ca.clone()).await;
+
+    let config_server_config = ConfigServerConfiguration {
+        http_config: TlsPinningConfig {
+            base_url: local_config_base_url(&port),
+            trust_anchors: vec![cs_root_ca],
+        },
+        ..default_config_server_config()
+    };
+
+    let storage_dir = tempfile::tempdir().unwrap();
+    let http_config = HttpConfigurationRepository::new(
+        (&config_server_config.signing_public_key.0).into(),
+        storage_dir.path().to_path_buf(),
+        default_wallet_config(),
+    )
+    .await
+    .unwrap();
+
+    let before = http_config.get();
+    let result = http_config.fetch(&config_server_config.http_config).await.unwrap();
+    let after = http_config.get();
+
+    assert_matches!(result, RepositoryUpdateState::Updated { .. });
+    assert_ne!(before.lock_timeouts, after.lock_timeouts);
+    assert_eq!(after.version, 2);
+}
+
 #[tokio::test]
 async fn test_wallet_config_stale() {
     let (settings, _) = wallet_provider_settings();
@@ -18,13 +18,13 @@ async fn test_unlock_ok(#[values(WalletDeviceVendor::Apple, WalletDeviceVendor::
     let mut wallet = setup_wallet_and_default_env(vendor).await;
     wallet = do_wallet_registration(wallet, pin.clone()).await;
 
-    wallet.lock();
+    wallet.lock().await;
     assert!(wallet.is_locked());
 
     wallet.unlock(pin.clone()).await.expect("Should unlock wallet");
     assert!(!wallet.is_locked());
 
-    wallet.lock();
+    wallet.lock().await;
 
     // Test multiple instructions
     wallet.unlock(pin).await.expect("Should unlock wallet");
@@ -51,7 +51,7 @@ async fn test_block() {
     .await;
     wallet = do_wallet_registration(wallet, pin).await;
 
-    wallet.lock();
+    wallet.lock().await;
     assert!(wallet.is_locked());
 
     let result = wallet
@@ -89,7 +89,7 @@ async fn test_unlock_error() {
     let mut wallet = setup_wallet_and_default_env(WalletDeviceVendor::Apple).await;
     wallet = do_wallet_registration(wallet, pin.clone()).await;
 
-    wallet.lock();
+    wallet.lock().await;
     assert!(wallet.is_locked());
 
     let r1 = wallet
@@ -124,12 +124,99 @@ fn verify_configurations() {
         "config-server-config.json",
         "default-config-server-config.json",
     );
+    println!("cargo::rustc-env=WALLET_BUILD_CONFIG_ENV={}", current_env());
     println!("cargo::rerun-if-env-changed=CONFIG_ENV");
     println!("cargo::rerun-if-env-changed=PROFILE");
 }
 
+/// Find the configurations of additional environments, which consist of a "wallet-config-<env>.json" and a
+/// "config-server-config-<env>.json" file, verify them and generate the code that embeds them in the wallet. These are
+/// only included when the "config_environments" feature is enabled, which is not allowed for production builds. The
+/// universal link base URL of an additional environment is read from the "UNIVERSAL_LINK_BASE_<ENV>" environment
+/// variable, falling back to the default universal link base URL.
+fn embed_config_environments() {
+    let crate_path: PathBuf = env::var("CARGO_MANIFEST_DIR").expect("Could not get crate path").into();
+    let out_path: PathBuf = env::var("OUT_DIR").expect("Could not get output path").into();
+
+    let mut environments = Vec::new();
+
+    if env::var("CARGO_FEATURE_CONFIG_ENVIRONMENTS").is_ok() {
+        if current_env() == "production" {
+            panic!("The \"config_environments\" feature is not allowed for production builds");
+        }
+
+        let mut environment_names = fs::read_dir(&crate_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter_map(|file_name| {
+                file_name
+                    .strip_prefix("wallet-config-")
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .map(str::to_string)
+            })
+            .collect::<Vec<_>>();
+        environment_names.sort();
+
+        for environment in environment_names {
+            if environment == "production" || environment == current_env() {
+                panic!("Additional configuration environment '{}' is not allowed", environment);
+            }
+
+            let wallet_config_file = crate_path.join(format!("wallet-config-{}.json", environment));
+            let config_server_config_file = crate_path.join(format!("config-server-config-{}.json", environment));
+
+            let wallet_config: WalletConfiguration =
+                serde_json::from_slice(&fs::read(&wallet_config_file).unwrap()).expect("Could not parse config json");
+            let config_server_config: ConfigServerConfiguration = serde_json::from_slice(
+                &fs::read(&config_server_config_file)
+                    .unwrap_or_else(|_| panic!("Could not read {}", config_server_config_file.to_str().unwrap())),
+            )
+            .expect("Could not parse config json");
+
+            if wallet_config.environment() != environment || config_server_config.environment() != environment {
+                panic!(
+                    "Configuration files do not match additional environment '{}'",
+                    environment
+                );
+            }
+
+            let universal_link_base_var =
+                format!("UNIVERSAL_LINK_BASE_{}", environment.to_uppercase().replace('-', "_"));
+            let universal_link_base = env::var(&universal_link_base_var)
+                .map(|universal_link_base| format!("{:?}", universal_link_base))
+                .unwrap_or_else(|_| String::from("DEFAULT_UNIVERSAL_LINK_BASE"));
+
+            println!("cargo:rerun-if-changed={}", wallet_config_file.to_str().unwrap());
+            println!("cargo:rerun-if-changed={}", config_server_config_file.to_str().unwrap());
+            println!("cargo::rerun-if-env-changed={}", universal_link_base_var);
+
+            environments.push(format!(
+                "EmbeddedConfig {{ environment: {:?}, wallet_config_json: include_str!({:?}), \
+                 config_server_config_json: include_str!({:?}), universal_link_base: {} }},",
+                environment,
+                wallet_config_file.to_str().unwrap(),
+                config_server_config_file.to_str().unwrap(),
+                universal_link_base,
+            ));
+        }
+
+        // Make sure that newly added configuration files are picked up.
+        println!("cargo:rerun-if-changed={}", crate_path.to_str().unwrap());
+    }
+
+    fs::write(
+        out_path.join("config_environments.rs"),
+        format!(
+            "const ADDITIONAL_ENVIRONMENTS: &[EmbeddedConfig] = &[{}];\n",
+            environments.join(" ")
+        ),
+    )
+    .unwrap();
+}
+
 fn main() {
     android_x86_64_workaround();
 
     verify_configurations();
+    embed_config_environments();
 }
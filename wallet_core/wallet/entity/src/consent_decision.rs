@@ -0,0 +1,19 @@
+use chrono::DateTime;
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, Eq, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "consent_decision")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub relying_party_certificate: Vec<u8>,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub attributes_hash: Vec<u8>,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
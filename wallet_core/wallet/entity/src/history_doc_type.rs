@@ -1,5 +1,7 @@
 use sea_orm::entity::prelude::*;
 
+use crate::deletion_history_event;
+use crate::deletion_history_event_doc_type;
 use crate::disclosure_history_event;
 use crate::disclosure_history_event_doc_type;
 use crate::issuance_history_event;
@@ -18,6 +20,16 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Related<deletion_history_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        deletion_history_event_doc_type::Relation::HistoryEvent.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(deletion_history_event_doc_type::Relation::HistoryDocType.def().rev())
+    }
+}
+
 impl Related<disclosure_history_event::Entity> for Entity {
     fn to() -> RelationDef {
         disclosure_history_event_doc_type::Relation::HistoryEvent.def()
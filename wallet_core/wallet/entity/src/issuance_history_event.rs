@@ -12,6 +12,7 @@ pub struct Model {
     pub id: Uuid,
     pub timestamp: DateTime<Utc>,
     pub attributes: Json,
+    pub renewed: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,3 +1,6 @@
+pub mod consent_decision;
+pub mod deletion_history_event;
+pub mod deletion_history_event_doc_type;
 pub mod disclosure_history_event;
 pub mod disclosure_history_event_doc_type;
 pub mod history_doc_type;
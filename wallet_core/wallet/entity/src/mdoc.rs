@@ -1,3 +1,5 @@
+use chrono::DateTime;
+use chrono::Utc;
 use sea_orm::entity::prelude::*;
 use uuid::Uuid;
 
@@ -7,6 +9,8 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub doc_type: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub issuer_url: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -3,6 +3,11 @@ pub use sea_orm_migration::prelude::*;
 mod m20230425_140221_create_keyed_data_table;
 mod m20230922_095234_create_mdoc_tables;
 mod m20231115_100948_create_history_tables;
+mod m20250115_100000_add_mdoc_last_used_at;
+mod m20250210_120000_create_consent_decision_table;
+mod m20250301_100000_create_deletion_history_tables;
+mod m20250320_100000_add_mdoc_issuer_url;
+mod m20250325_100000_add_issuance_history_event_renewed;
 
 pub struct Migrator;
 
@@ -12,6 +17,11 @@ impl MigratorTrait for Migrator {
             Box::new(m20230425_140221_create_keyed_data_table::Migration),
             Box::new(m20230922_095234_create_mdoc_tables::Migration),
             Box::new(m20231115_100948_create_history_tables::Migration),
+            Box::new(m20250115_100000_add_mdoc_last_used_at::Migration),
+            Box::new(m20250210_120000_create_consent_decision_table::Migration),
+            Box::new(m20250301_100000_create_deletion_history_tables::Migration),
+            Box::new(m20250320_100000_add_mdoc_issuer_url::Migration),
+            Box::new(m20250325_100000_add_issuance_history_event_renewed::Migration),
         ]
     }
 }
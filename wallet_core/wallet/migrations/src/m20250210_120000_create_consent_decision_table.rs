@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConsentDecision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConsentDecision::RelyingPartyCertificate)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ConsentDecision::AttributesHash).binary().not_null())
+                    .col(ColumnDef::new(ConsentDecision::GrantedAt).timestamp().not_null())
+                    .col(ColumnDef::new(ConsentDecision::ExpiresAt).timestamp().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(ConsentDecision::RelyingPartyCertificate)
+                            .col(ConsentDecision::AttributesHash),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ConsentDecision::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ConsentDecision {
+    Table,
+    RelyingPartyCertificate,
+    AttributesHash,
+    GrantedAt,
+    ExpiresAt,
+}
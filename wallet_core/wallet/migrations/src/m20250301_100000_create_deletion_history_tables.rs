@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeletionHistoryEvent::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(DeletionHistoryEvent::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(DeletionHistoryEvent::Timestamp).timestamp().not_null())
+                    .col(ColumnDef::new(DeletionHistoryEvent::Attributes).json().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeletionHistoryEventDocType::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DeletionHistoryEventDocType::DeletionHistoryEventId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeletionHistoryEventDocType::HistoryDocTypeId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(DeletionHistoryEventDocType::DeletionHistoryEventId)
+                            .col(DeletionHistoryEventDocType::HistoryDocTypeId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeletionHistoryEventDocType::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(DeletionHistoryEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DeletionHistoryEvent {
+    Table,
+    Id,
+    Timestamp,
+    Attributes,
+}
+
+#[derive(DeriveIden)]
+enum DeletionHistoryEventDocType {
+    Table,
+    DeletionHistoryEventId,
+    HistoryDocTypeId,
+}
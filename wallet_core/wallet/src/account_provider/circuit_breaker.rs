@@ -0,0 +1,397 @@
+use std::future::Future;
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::time::Instant;
+
+use wallet_common::account::messages::auth::Registration;
+use wallet_common::account::messages::auth::WalletCertificate;
+use wallet_common::account::messages::instructions::Instruction;
+use wallet_common::account::messages::instructions::InstructionAndResult;
+use wallet_common::account::messages::instructions::InstructionChallengeRequest;
+use wallet_common::account::messages::instructions::InstructionResult;
+use wallet_common::account::signed::ChallengeResponse;
+use wallet_common::config::http::TlsPinningConfig;
+use wallet_common::config::wallet_config::AccountServerConfiguration;
+
+use crate::pin::change::ChangePinClientError;
+
+use super::AccountProviderClient;
+use super::AccountProviderError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// The amount of consecutive retryable failures after which the circuit is opened.
+    pub failure_threshold: NonZeroU32,
+    /// The time for which requests are rejected after the circuit has been opened.
+    pub cooldown: Duration,
+}
+
+impl From<&AccountServerConfiguration> for CircuitBreakerConfig {
+    fn from(value: &AccountServerConfiguration) -> Self {
+        Self {
+            failure_threshold: value.circuit_breaker_failure_threshold,
+            cooldown: value.circuit_breaker_cooldown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent to the Wallet Provider as normal.
+    Closed,
+    /// The Wallet Provider is considered unavailable and requests are rejected without being sent.
+    Open,
+    /// The cooldown has passed, the next request will be sent to probe if the Wallet Provider has recovered. While
+    /// this probe is in flight, other requests are rejected as if the circuit is open.
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Wraps an [`AccountProviderClient`] and stops sending requests to the Wallet Provider for a configurable cooldown
+/// period once a number of consecutive requests have failed. Only errors that are classified as network errors (see
+/// [`ChangePinClientError::is_network_error()`]) count as failures, any other result resets the circuit breaker.
+#[derive(Debug)]
+pub struct CircuitBreakerAccountProviderClient<C> {
+    client: C,
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl<C> CircuitBreakerAccountProviderClient<C> {
+    pub fn new(client: C, config: CircuitBreakerConfig) -> Self {
+        Self {
+            client,
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.circuit_state(&self.state.lock())
+    }
+
+    fn circuit_state(&self, state: &BreakerState) -> CircuitState {
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.config.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    fn record_result<T>(&self, result: &Result<T, AccountProviderError>) {
+        let mut state = self.state.lock();
+
+        match result {
+            Err(error) if error.is_network_error() => {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+
+                // Note that a failing request while half-open immediately re-opens the circuit,
+                // as the failure count has not been reset since the circuit was opened.
+                if state.consecutive_failures >= self.config.failure_threshold.get() {
+                    if state.opened_at.is_none() {
+                        tracing::warn!(
+                            "Wallet Provider failed {} consecutive times, opening circuit breaker",
+                            state.consecutive_failures
+                        );
+                    }
+
+                    state.opened_at = Some(Instant::now());
+                }
+            }
+            _ => {
+                if state.opened_at.is_some() {
+                    tracing::info!("Wallet Provider responded, closing circuit breaker");
+                }
+
+                *state = BreakerState::default();
+            }
+        }
+    }
+
+    async fn call<T>(
+        &self,
+        request: impl Future<Output = Result<T, AccountProviderError>>,
+    ) -> Result<T, AccountProviderError> {
+        {
+            let mut state = self.state.lock();
+
+            match self.circuit_state(&state) {
+                CircuitState::Closed => {}
+                CircuitState::Open => return Err(AccountProviderError::CircuitOpen),
+                // Only let this request through as a probe. By restarting the cooldown, any concurrent requests are
+                // rejected until the result of the probe is known. Should the probe never finish, e.g. because it is
+                // cancelled, another request is let through once the cooldown has passed again.
+                CircuitState::HalfOpen => state.opened_at = Some(Instant::now()),
+            }
+        }
+
+        let result = request.await;
+        self.record_result(&result);
+
+        result
+    }
+}
+
+impl<C> AccountProviderClient for CircuitBreakerAccountProviderClient<C>
+where
+    C: AccountProviderClient,
+{
+    async fn registration_challenge(&self, client_config: &TlsPinningConfig) -> Result<Vec<u8>, AccountProviderError> {
+        self.call(self.client.registration_challenge(client_config)).await
+    }
+
+    async fn register(
+        &self,
+        client_config: &TlsPinningConfig,
+        registration_message: ChallengeResponse<Registration>,
+    ) -> Result<WalletCertificate, AccountProviderError> {
+        self.call(self.client.register(client_config, registration_message))
+            .await
+    }
+
+    async fn instruction_challenge(
+        &self,
+        client_config: &TlsPinningConfig,
+        challenge_request: InstructionChallengeRequest,
+    ) -> Result<Vec<u8>, AccountProviderError> {
+        self.call(self.client.instruction_challenge(client_config, challenge_request))
+            .await
+    }
+
+    async fn instruction<I>(
+        &self,
+        client_config: &TlsPinningConfig,
+        instruction: Instruction<I>,
+    ) -> Result<InstructionResult<I::Result>, AccountProviderError>
+    where
+        I: InstructionAndResult + 'static,
+    {
+        self.call(self.client.instruction(client_config, instruction)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use assert_matches::assert_matches;
+    use futures::FutureExt;
+    use http::StatusCode;
+    use rstest::rstest;
+
+    use wallet_common::account::messages::errors::AccountError;
+
+    use crate::account_provider::AccountProviderResponseError;
+    use crate::account_provider::MockAccountProviderClient;
+
+    use super::*;
+
+    const THRESHOLD: u32 = 3;
+    const COOLDOWN: Duration = Duration::from_secs(10);
+
+    fn network_error() -> AccountProviderError {
+        AccountProviderResponseError::Status(StatusCode::BAD_GATEWAY).into()
+    }
+
+    fn circuit_breaker(
+        client: MockAccountProviderClient,
+    ) -> CircuitBreakerAccountProviderClient<MockAccountProviderClient> {
+        CircuitBreakerAccountProviderClient::new(
+            client,
+            CircuitBreakerConfig {
+                failure_threshold: NonZeroU32::new(THRESHOLD).unwrap(),
+                cooldown: COOLDOWN,
+            },
+        )
+    }
+
+    fn client_config() -> TlsPinningConfig {
+        TlsPinningConfig {
+            base_url: "https://example.com".parse().unwrap(),
+            trust_anchors: vec![],
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_opens_after_threshold() {
+        let mut client = MockAccountProviderClient::new();
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize)
+            .returning(|_| Err(network_error()));
+
+        let circuit_breaker = circuit_breaker(client);
+        let client_config = client_config();
+
+        for _ in 0..THRESHOLD {
+            assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+
+            let error = circuit_breaker
+                .registration_challenge(&client_config)
+                .await
+                .expect_err("registration challenge should fail");
+
+            assert_matches!(error, AccountProviderError::Response(_));
+        }
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+
+        // The wrapped client should not be called while the circuit is open.
+        let error = circuit_breaker
+            .registration_challenge(&client_config)
+            .await
+            .expect_err("registration challenge should fail");
+
+        assert_matches!(error, AccountProviderError::CircuitOpen);
+
+        tokio::time::advance(COOLDOWN - Duration::from_millis(1)).await;
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+    }
+
+    #[rstest]
+    #[case(Ok(vec![1, 2, 3]), CircuitState::Closed)]
+    #[case(Err(network_error()), CircuitState::Open)]
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_half_open(
+        #[case] probe_result: Result<Vec<u8>, AccountProviderError>,
+        #[case] expected_state: CircuitState,
+    ) {
+        let mut client = MockAccountProviderClient::new();
+        let mut sequence = mockall::Sequence::new();
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize)
+            .in_sequence(&mut sequence)
+            .returning(|_| Err(network_error()));
+        client
+            .expect_registration_challenge()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .return_once(move |_| probe_result);
+
+        let circuit_breaker = circuit_breaker(client);
+        let client_config = client_config();
+
+        for _ in 0..THRESHOLD {
+            let _ = circuit_breaker.registration_challenge(&client_config).await;
+        }
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+
+        tokio::time::advance(COOLDOWN).await;
+
+        assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+
+        // After the cooldown a single request is let through, the result of which determines the new state.
+        let _ = circuit_breaker.registration_challenge(&client_config).await;
+
+        assert_eq!(circuit_breaker.state(), expected_state);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_half_open_single_probe() {
+        let mut client = MockAccountProviderClient::new();
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize)
+            .returning(|_| Err(network_error()));
+
+        let circuit_breaker = circuit_breaker(client);
+        let client_config = client_config();
+
+        for _ in 0..THRESHOLD {
+            let _ = circuit_breaker.registration_challenge(&client_config).await;
+        }
+
+        tokio::time::advance(COOLDOWN).await;
+
+        assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+
+        // Start a probe that does not finish, which is then cancelled.
+        let probe = circuit_breaker
+            .call(future::pending::<Result<(), AccountProviderError>>())
+            .now_or_never();
+
+        assert!(probe.is_none());
+
+        // Any request made while the probe was in flight should have been rejected.
+        let error = circuit_breaker
+            .call(async { Ok(()) })
+            .await
+            .expect_err("request should be rejected");
+
+        assert_matches!(error, AccountProviderError::CircuitOpen);
+
+        // As the probe never finished, another request should be let through once the cooldown has passed again.
+        tokio::time::advance(COOLDOWN).await;
+
+        circuit_breaker
+            .call(async { Ok(()) })
+            .await
+            .expect("request should be let through");
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_reset_on_success() {
+        let mut client = MockAccountProviderClient::new();
+        let mut sequence = mockall::Sequence::new();
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize - 1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Err(network_error()));
+        client
+            .expect_registration_challenge()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(vec![1, 2, 3]));
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize - 1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Err(network_error()));
+
+        let circuit_breaker = circuit_breaker(client);
+        let client_config = client_config();
+
+        // The successful request in between the failures should prevent the circuit from opening.
+        for _ in 0..(THRESHOLD * 2 - 1) {
+            let _ = circuit_breaker.registration_challenge(&client_config).await;
+        }
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_ignores_non_network_errors() {
+        let mut client = MockAccountProviderClient::new();
+        client
+            .expect_registration_challenge()
+            .times(THRESHOLD as usize)
+            .returning(|_| {
+                Err(AccountProviderError::Response(AccountProviderResponseError::Account(
+                    AccountError::ChallengeValidation,
+                    None,
+                )))
+            });
+
+        let circuit_breaker = circuit_breaker(client);
+        let client_config = client_config();
+
+        for _ in 0..THRESHOLD {
+            let _ = circuit_breaker.registration_challenge(&client_config).await;
+        }
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+    }
+}
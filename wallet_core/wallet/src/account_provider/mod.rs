@@ -1,3 +1,4 @@
+mod circuit_breaker;
 mod client;
 
 use reqwest::StatusCode;
@@ -15,6 +16,9 @@ use wallet_common::account::messages::instructions::InstructionResult;
 use wallet_common::account::signed::ChallengeResponse;
 use wallet_common::config::http::TlsPinningConfig;
 
+pub use self::circuit_breaker::CircuitBreakerAccountProviderClient;
+pub use self::circuit_breaker::CircuitBreakerConfig;
+pub use self::circuit_breaker::CircuitState;
 pub use self::client::HttpAccountProviderClient;
 
 #[derive(Debug, thiserror::Error, ErrorCategory)]
@@ -28,6 +32,9 @@ pub enum AccountProviderError {
     #[error("could not parse base URL: {0}")]
     #[category(pd)]
     BaseUrl(#[from] ParseError),
+    #[error("request not sent, Wallet Provider is considered unavailable after repeated failures")]
+    #[category(expected)]
+    CircuitOpen,
 }
 
 #[derive(Debug, thiserror::Error, ErrorCategory)]
@@ -1,6 +1,8 @@
 use std::path::Path;
 use std::path::PathBuf;
 
+use serde::Deserialize;
+use serde::Serialize;
 use tokio::fs;
 
 use wallet_common::config::wallet_config::WalletConfiguration;
@@ -23,6 +25,64 @@ pub async fn update_config_file(storage_path: &Path, config: &WalletConfiguratio
     write_config(path.as_path(), config).await
 }
 
+pub async fn get_signed_config_file(storage_path: &Path) -> Result<Option<String>, FileStorageError> {
+    let path = path_for_signed_config_file(storage_path);
+
+    if !fs::try_exists(&path).await? {
+        return Ok(None);
+    }
+
+    let signed_config = fs::read_to_string(path).await?;
+    Ok(Some(signed_config))
+}
+
+pub async fn update_signed_config_file(storage_path: &Path, signed_config: &str) -> Result<(), FileStorageError> {
+    let path = path_for_signed_config_file(storage_path);
+    fs::write(path, signed_config).await?;
+    Ok(())
+}
+
+pub async fn remove_signed_config_file(storage_path: &Path) -> Result<(), FileStorageError> {
+    let path = path_for_signed_config_file(storage_path);
+
+    if fs::try_exists(&path).await? {
+        fs::remove_file(path).await?;
+    }
+
+    Ok(())
+}
+
+/// The configuration environments that are stored on disk, so that they persist between launches of the app.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredConfigEnvironment {
+    /// The environment that should be used the next time the wallet is initialized, if one was selected.
+    pub selected: Option<String>,
+    /// The environment that was in use when the wallet was last initialized.
+    pub active: Option<String>,
+}
+
+pub async fn get_environment_file(storage_path: &Path) -> Result<StoredConfigEnvironment, FileStorageError> {
+    let path = path_for_environment_file(storage_path);
+
+    if !fs::try_exists(&path).await? {
+        return Ok(StoredConfigEnvironment::default());
+    }
+
+    let content = fs::read(path).await?;
+    let stored_environment = serde_json::from_slice(&content)?;
+    Ok(stored_environment)
+}
+
+pub async fn update_environment_file(
+    storage_path: &Path,
+    stored_environment: &StoredConfigEnvironment,
+) -> Result<(), FileStorageError> {
+    let path = path_for_environment_file(storage_path);
+    let contents = serde_json::to_vec(stored_environment)?;
+    fs::write(path, contents).await?;
+    Ok(())
+}
+
 async fn write_config(path: &Path, config: &WalletConfiguration) -> Result<(), FileStorageError> {
     let contents = serde_json::to_vec(config)?;
     fs::write(path, contents).await?;
@@ -39,6 +99,14 @@ fn path_for_config_file(storage_path: &Path) -> PathBuf {
     storage_path.join("configuration.json")
 }
 
+fn path_for_signed_config_file(storage_path: &Path) -> PathBuf {
+    storage_path.join("configuration.signed")
+}
+
+fn path_for_environment_file(storage_path: &Path) -> PathBuf {
+    storage_path.join("configuration-environment.json")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::config_file::get_config_file;
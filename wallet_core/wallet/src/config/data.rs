@@ -1,30 +1,300 @@
+use std::path::Path;
 use std::sync::LazyLock;
+use std::sync::OnceLock;
+
+use url::Host;
+use url::Url;
 
 use wallet_common::config::config_server_config::ConfigServerConfiguration;
 use wallet_common::config::wallet_config::WalletConfiguration;
 use wallet_common::urls::BaseUrl;
 use wallet_common::urls::DEFAULT_UNIVERSAL_LINK_BASE;
 
-const WALLET_CONFIG_JSON: &str = include_str!("../../wallet-config.json");
-const CONFIG_SERVER_CONFIG_JSON: &str = include_str!("../../config-server-config.json");
+use super::config_file;
+use super::ConfigurationError;
+
+/// The configuration of an environment that is embedded in the wallet. The JSON has already been parsed in build.rs.
+struct EmbeddedConfig {
+    environment: &'static str,
+    wallet_config_json: &'static str,
+    config_server_config_json: &'static str,
+    universal_link_base: &'static str,
+}
+
+/// The configuration of the environment the wallet was built for, which is used unless another environment is selected.
+const BUILD_ENVIRONMENT: EmbeddedConfig = EmbeddedConfig {
+    environment: env!("WALLET_BUILD_CONFIG_ENV"),
+    wallet_config_json: include_str!("../../wallet-config.json"),
+    config_server_config_json: include_str!("../../config-server-config.json"),
+    universal_link_base: match option_env!("UNIVERSAL_LINK_BASE") {
+        Some(universal_link_base) => universal_link_base,
+        None => DEFAULT_UNIVERSAL_LINK_BASE,
+    },
+};
+
+// Defines `ADDITIONAL_ENVIRONMENTS`, which is empty unless the "config_environments" feature is enabled.
+include!(concat!(env!("OUT_DIR"), "/config_environments.rs"));
+
+static SELECTED_ENVIRONMENT: OnceLock<&'static EmbeddedConfig> = OnceLock::new();
+
+/// The custom URL scheme of [`DEFAULT_UNIVERSAL_LINK_BASE`], which is used during local development instead of the
+/// `https` scheme that actual universal links require.
+const DEBUG_UNIVERSAL_LINK_SCHEME: &str = "walletdebuginteraction";
+
+/// The environment that local development builds use by default, for which the debug scheme is allowed.
+const DEV_ENVIRONMENT: &str = "dev";
+
+#[derive(Debug, thiserror::Error)]
+pub enum UniversalLinkBaseUrlError {
+    #[error("could not parse universal link base URL: {0}")]
+    Parse(#[from] url::ParseError),
+    #[error("universal link base URL has unexpected scheme: {0}")]
+    Scheme(String),
+    #[error("universal link base URL does not have a domain name as host")]
+    Host,
+    #[error("universal link base URL should not contain credentials, a query or a fragment")]
+    UnexpectedComponent,
+}
 
 pub static UNIVERSAL_LINK_BASE_URL: LazyLock<BaseUrl> = LazyLock::new(|| {
-    option_env!("UNIVERSAL_LINK_BASE")
-        .unwrap_or(DEFAULT_UNIVERSAL_LINK_BASE)
-        .parse::<BaseUrl>()
+    parse_universal_link_base_url(universal_link_base(), allow_debug_universal_link_scheme())
         .expect("Could not parse universal link base url")
 });
 
-pub fn init_universal_link_base_url() {
+fn universal_link_base() -> &'static str {
+    selected_environment().universal_link_base
+}
+
+/// The debug scheme is only allowed for debug builds and for the development environment, so that a release build for
+/// any other environment cannot accidentally fall back to [`DEFAULT_UNIVERSAL_LINK_BASE`].
+fn allow_debug_universal_link_scheme() -> bool {
+    cfg!(debug_assertions) || current_config_environment() == DEV_ENVIRONMENT
+}
+
+/// Parse and validate the universal link base URL, which should be a `https` URL with a domain name as host and
+/// nothing but a path, as every universal link and QR code that the wallet handles is derived from it. If
+/// `allow_debug_scheme` is set, the [`DEBUG_UNIVERSAL_LINK_SCHEME`] is accepted as well.
+fn parse_universal_link_base_url(url: &str, allow_debug_scheme: bool) -> Result<BaseUrl, UniversalLinkBaseUrlError> {
+    let url = Url::parse(url)?;
+
+    if url.scheme() != "https" && !(allow_debug_scheme && url.scheme() == DEBUG_UNIVERSAL_LINK_SCHEME) {
+        return Err(UniversalLinkBaseUrlError::Scheme(url.scheme().to_string()));
+    }
+
+    if !matches!(url.host(), Some(Host::Domain(_))) {
+        return Err(UniversalLinkBaseUrlError::Host);
+    }
+
+    if !url.username().is_empty() || url.password().is_some() || url.query().is_some() || url.fragment().is_some() {
+        return Err(UniversalLinkBaseUrlError::UnexpectedComponent);
+    }
+
+    // A URL with a host can always be used as a base, so this will not fail.
+    let base_url = BaseUrl::try_from(url).unwrap();
+
+    Ok(base_url)
+}
+
+/// Validate and initialize the universal link base URL, so that a malformed value results in an error at startup.
+pub fn init_universal_link_base_url() -> Result<(), ConfigurationError> {
+    parse_universal_link_base_url(universal_link_base(), allow_debug_universal_link_scheme())?;
     LazyLock::force(&UNIVERSAL_LINK_BASE_URL);
+
+    Ok(())
+}
+
+fn find_environment(environment: &str) -> Option<&'static EmbeddedConfig> {
+    std::iter::once(&BUILD_ENVIRONMENT)
+        .chain(ADDITIONAL_ENVIRONMENTS)
+        .find(|embedded_config| embedded_config.environment == environment)
+}
+
+fn selected_environment() -> &'static EmbeddedConfig {
+    SELECTED_ENVIRONMENT.get_or_init(|| &BUILD_ENVIRONMENT)
+}
+
+/// Returns the names of all configuration environments embedded in the wallet, starting with the build environment.
+pub fn config_environments() -> Vec<&'static str> {
+    std::iter::once(&BUILD_ENVIRONMENT)
+        .chain(ADDITIONAL_ENVIRONMENTS)
+        .map(|embedded_config| embedded_config.environment)
+        .collect()
+}
+
+/// Returns the name of the configuration environment that is currently in use.
+pub fn current_config_environment() -> &'static str {
+    selected_environment().environment
+}
+
+/// Select one of the embedded configuration environments, which is stored so that it is used from the next time the
+/// wallet is initialized onwards, see [`init_config_environment()`]. Note that only the build environment is embedded,
+/// unless the "config_environments" feature is enabled.
+pub async fn select_config_environment(storage_path: &Path, environment: &str) -> Result<(), ConfigurationError> {
+    find_environment(environment).ok_or_else(|| ConfigurationError::UnknownEnvironment(environment.to_string()))?;
+
+    let mut stored_environment = config_file::get_environment_file(storage_path).await?;
+    stored_environment.selected = Some(environment.to_string());
+    config_file::update_environment_file(storage_path, &stored_environment).await?;
+
+    Ok(())
+}
+
+fn set_config_environment(embedded_config: &'static EmbeddedConfig) -> Result<(), ConfigurationError> {
+    match SELECTED_ENVIRONMENT.set(embedded_config) {
+        Ok(()) => Ok(()),
+        // Selecting the environment that is already in use is not considered an error.
+        Err(_) if current_config_environment() == embedded_config.environment => Ok(()),
+        Err(_) => Err(ConfigurationError::EnvironmentAlreadySelected(
+            current_config_environment().to_string(),
+        )),
+    }
+}
+
+/// Use the configuration environment that was selected with `select_config_environment()`, if any, and record which
+/// environment is in use. This should be called when initializing the wallet, before the default configuration or the
+/// universal link base URL are used. If another environment was in use before, the signed configuration of that
+/// environment is removed and the name of that environment is returned, so that the wallet can remove its other data.
+pub async fn init_config_environment(storage_path: &Path) -> Result<Option<String>, ConfigurationError> {
+    let mut stored_environment = config_file::get_environment_file(storage_path).await?;
+
+    if let Some(selected) = stored_environment.selected.as_deref() {
+        match find_environment(selected) {
+            Some(embedded_config) => set_config_environment(embedded_config)?,
+            None => tracing::warn!("Selected configuration environment \"{selected}\" is not embedded, ignoring"),
+        }
+    }
+
+    let current_environment = current_config_environment();
+
+    if stored_environment.active.as_deref() == Some(current_environment) {
+        return Ok(None);
+    }
+
+    // If no environment was recorded, this is either the first launch or an update from a version that did not record
+    // the environment. In the latter case, any existing data belongs to the build environment.
+    let previous_environment = stored_environment
+        .active
+        .replace(current_environment.to_string())
+        .or_else(|| {
+            (current_environment != BUILD_ENVIRONMENT.environment).then(|| BUILD_ENVIRONMENT.environment.to_string())
+        });
+
+    if previous_environment.is_some() {
+        config_file::remove_signed_config_file(storage_path).await?;
+    }
+
+    config_file::update_environment_file(storage_path, &stored_environment).await?;
+
+    Ok(previous_environment)
 }
 
 pub fn default_wallet_config() -> WalletConfiguration {
     // The JSON has already been parsed in build.rs, so unwrap is safe here
-    serde_json::from_str(WALLET_CONFIG_JSON).unwrap()
+    serde_json::from_str(selected_environment().wallet_config_json).unwrap()
 }
 
 pub fn default_config_server_config() -> ConfigServerConfiguration {
     // The JSON has already been parsed in build.rs, so unwrap is safe here
-    serde_json::from_str(CONFIG_SERVER_CONFIG_JSON).unwrap()
+    serde_json::from_str(selected_environment().config_server_config_json).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_find_environment() {
+        let embedded_config = find_environment(BUILD_ENVIRONMENT.environment).expect("should find build environment");
+        assert_eq!(embedded_config.wallet_config_json, BUILD_ENVIRONMENT.wallet_config_json);
+
+        assert!(find_environment("unknown").is_none());
+        assert_eq!(config_environments()[0], BUILD_ENVIRONMENT.environment);
+    }
+
+    #[tokio::test]
+    async fn test_init_config_environment() {
+        let storage_dir = tempfile::tempdir().unwrap();
+        let storage_path = storage_dir.path();
+
+        // On first launch, the build environment is recorded as being in use.
+        let previous_environment = init_config_environment(storage_path).await.unwrap();
+        assert!(previous_environment.is_none());
+
+        let stored_environment = config_file::get_environment_file(storage_path).await.unwrap();
+        assert_eq!(stored_environment.active.as_deref(), Some(current_config_environment()));
+
+        // Initializing again using the same environment should not result in a switch.
+        let previous_environment = init_config_environment(storage_path).await.unwrap();
+        assert!(previous_environment.is_none());
+
+        // When another environment was in use before, its name should be returned and its signed configuration removed.
+        config_file::update_environment_file(
+            storage_path,
+            &config_file::StoredConfigEnvironment {
+                selected: None,
+                active: Some("other".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        config_file::update_signed_config_file(storage_path, "signed")
+            .await
+            .unwrap();
+
+        let previous_environment = init_config_environment(storage_path).await.unwrap();
+        assert_eq!(previous_environment.as_deref(), Some("other"));
+        assert!(config_file::get_signed_config_file(storage_path)
+            .await
+            .unwrap()
+            .is_none());
+
+        let stored_environment = config_file::get_environment_file(storage_path).await.unwrap();
+        assert_eq!(stored_environment.active.as_deref(), Some(current_config_environment()));
+    }
+
+    #[test]
+    fn test_default_config_matches_current_environment() {
+        assert_eq!(default_wallet_config().environment, current_config_environment());
+        assert_eq!(default_config_server_config().environment, current_config_environment());
+    }
+
+    #[rstest]
+    #[case("https://app.example.com/deeplink/", false)]
+    #[case("https://app.example.com", false)]
+    #[case("https://app.example.com/deeplink/", true)]
+    #[case(DEFAULT_UNIVERSAL_LINK_BASE, true)]
+    fn test_parse_universal_link_base_url(#[case] url: &str, #[case] allow_debug_scheme: bool) {
+        parse_universal_link_base_url(url, allow_debug_scheme).expect("universal link base URL should be valid");
+    }
+
+    #[test]
+    fn test_parse_universal_link_base_url_error() {
+        assert_matches!(
+            parse_universal_link_base_url("app.example.com/deeplink/", true),
+            Err(UniversalLinkBaseUrlError::Parse(_))
+        );
+        assert_matches!(
+            parse_universal_link_base_url("http://app.example.com/deeplink/", true),
+            Err(UniversalLinkBaseUrlError::Scheme(scheme)) if scheme == "http"
+        );
+        assert_matches!(
+            parse_universal_link_base_url(DEFAULT_UNIVERSAL_LINK_BASE, false),
+            Err(UniversalLinkBaseUrlError::Scheme(scheme)) if scheme == DEBUG_UNIVERSAL_LINK_SCHEME
+        );
+        assert_matches!(
+            parse_universal_link_base_url("https://127.0.0.1/deeplink/", true),
+            Err(UniversalLinkBaseUrlError::Host)
+        );
+        assert_matches!(
+            parse_universal_link_base_url("https://app.example.com/deeplink/?foo=bar", true),
+            Err(UniversalLinkBaseUrlError::UnexpectedComponent)
+        );
+        assert_matches!(
+            parse_universal_link_base_url("https://user@app.example.com/deeplink/", true),
+            Err(UniversalLinkBaseUrlError::UnexpectedComponent)
+        );
+    }
 }
@@ -10,6 +10,8 @@ use crate::repository::RepositoryUpdateState;
 use crate::repository::UpdateableRepository;
 
 use super::config_file;
+use super::ConfigSignatureInfo;
+use super::ConfigSignatureRepository;
 use super::ConfigurationError;
 use super::HttpConfigurationRepository;
 
@@ -25,9 +27,13 @@ impl<B> FileStorageConfigurationRepository<HttpConfigurationRepository<B>> {
         initial_config: WalletConfiguration,
     ) -> Result<Self, ConfigurationError> {
         let default_config = match config_file::get_config_file(storage_path.as_path()).await? {
-            Some(stored_config) if initial_config.version > stored_config.version => {
+            Some(stored_config)
+                if initial_config.version > stored_config.version
+                    || initial_config.environment != stored_config.environment =>
+            {
                 // When the initial configuration is newer than the stored configuration (e.g. due to an app update)
-                // that version is used and the stored configuration is overwritten.
+                // that version is used and the stored configuration is overwritten. The same applies when another
+                // configuration environment has been selected since the configuration was stored.
                 config_file::update_config_file(storage_path.as_path(), &initial_config).await?;
                 initial_config
             }
@@ -60,6 +66,15 @@ where
     }
 }
 
+impl<T> ConfigSignatureRepository for FileStorageConfigurationRepository<T>
+where
+    T: ConfigSignatureRepository,
+{
+    fn verify_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError> {
+        self.wrapped.verify_signature()
+    }
+}
+
 impl<T, B> UpdateableRepository<Arc<WalletConfiguration>, B> for FileStorageConfigurationRepository<T>
 where
     T: UpdateableRepository<Arc<WalletConfiguration>, B, Error = ConfigurationError> + Sync,
@@ -208,4 +223,39 @@ mod tests {
             "newer embedded config should have been stored"
         );
     }
+
+    #[tokio::test]
+    async fn should_use_embedded_wallet_config_of_other_environment() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let path = config_dir.into_path();
+        let verifying_key = *SigningKey::random(&mut OsRng).verifying_key();
+        let config_decoding_key: EcdsaDecodingKey = (&verifying_key).into();
+
+        let mut initially_stored_wallet_config = default_wallet_config();
+        initially_stored_wallet_config.environment = "other".to_string();
+        initially_stored_wallet_config.version = 10;
+
+        // store initial wallet config of another environment, having a higher version
+        config_file::update_config_file(path.as_path(), &initially_stored_wallet_config)
+            .await
+            .unwrap();
+
+        let embedded_wallet_config = default_wallet_config();
+
+        let repo: FileStorageConfigurationRepository<HttpConfigurationRepository<TlsPinningConfig>> =
+            FileStorageConfigurationRepository::init(path.clone(), config_decoding_key, embedded_wallet_config.clone())
+                .await
+                .unwrap();
+        assert_eq!(
+            embedded_wallet_config.environment,
+            repo.get().environment,
+            "should use embedded config"
+        );
+
+        let stored_config = config_file::get_config_file(path.as_path()).await.unwrap().unwrap();
+        assert_eq!(
+            embedded_wallet_config.environment, stored_config.environment,
+            "embedded config should have been stored"
+        );
+    }
 }
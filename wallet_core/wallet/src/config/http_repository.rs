@@ -1,15 +1,31 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use parking_lot::RwLock;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::value::RawValue;
 use tracing::info;
+use tracing::warn;
 
 use wallet_common::config::wallet_config::WalletConfiguration;
 use wallet_common::jwt::validations;
+use wallet_common::jwt::DetachedJws;
 use wallet_common::jwt::EcdsaDecodingKey;
 use wallet_common::jwt::Jwt;
+use wallet_common::jwt::JwtError;
 use wallet_common::reqwest::ReqwestBuilder;
 
+use crate::config::config_file;
+use crate::config::ConfigSignatureInfo;
+use crate::config::ConfigSignatureRepository;
+use crate::config::ConfigSignatureValidity;
 use crate::config::ConfigurationError;
 use crate::repository::EtagHttpClient;
 use crate::repository::HttpClient;
@@ -18,10 +34,107 @@ use crate::repository::Repository;
 use crate::repository::RepositoryUpdateState;
 use crate::repository::UpdateableRepository;
 
+/// The signed wallet configuration, as served by the configuration server. This supports two formats:
+///
+/// - Attached: a compact JWS containing the Base64url encoded configuration as its payload. This is the default.
+/// - Detached: a JSON object containing the raw configuration JSON in the `configuration` field and an RFC 7797 JWS
+///   with a detached and unencoded payload in the `signature` field, which signs the exact bytes of the configuration.
+///   This prevents the configuration from having to be Base64url encoded, which reduces the size of the response.
+#[derive(Debug)]
+enum SignedWalletConfiguration {
+    Attached(Jwt<WalletConfiguration>),
+    Detached(DetachedWalletConfiguration),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DetachedWalletConfiguration {
+    signature: DetachedJws<WalletConfiguration>,
+    configuration: Box<RawValue>,
+}
+
+/// The optional registered claims that limit the validity of the signed configuration.
+#[derive(Debug, Default, Deserialize)]
+struct ValidityClaims {
+    iat: Option<i64>,
+    exp: Option<i64>,
+}
+
+impl From<ValidityClaims> for ConfigSignatureValidity {
+    fn from(value: ValidityClaims) -> Self {
+        Self {
+            issued_at: value.iat.and_then(|iat| DateTime::<Utc>::from_timestamp(iat, 0)),
+            expires_at: value.exp.and_then(|exp| DateTime::<Utc>::from_timestamp(exp, 0)),
+        }
+    }
+}
+
+impl SignedWalletConfiguration {
+    fn parse_and_verify(&self, signing_public_key: &EcdsaDecodingKey) -> Result<WalletConfiguration, JwtError> {
+        let (config, _, _) = self.parse_and_verify_with_signature_info(signing_public_key)?;
+
+        Ok(config)
+    }
+
+    /// Verify the signed configuration, returning it along with the key id from the JWS header, if present, and the
+    /// validity claims contained in the signed payload.
+    fn parse_and_verify_with_signature_info(
+        &self,
+        signing_public_key: &EcdsaDecodingKey,
+    ) -> Result<(WalletConfiguration, Option<String>, ConfigSignatureValidity), JwtError> {
+        match self {
+            Self::Attached(jwt) => {
+                let (header, config) = jwt.parse_and_verify_with_header(signing_public_key, &validations())?;
+
+                // The signature has been verified above, so the payload can safely be parsed again.
+                let (_, validity) = Jwt::<ValidityClaims>::from(jwt.0.clone()).dangerous_parse_unverified()?;
+
+                Ok((config, header.kid, validity.into()))
+            }
+            // The header of a detached JWS does not contain a key id.
+            Self::Detached(DetachedWalletConfiguration {
+                signature,
+                configuration,
+            }) => {
+                let config = signature.parse_and_verify(configuration.get().as_bytes(), signing_public_key)?;
+                let validity = serde_json::from_str::<ValidityClaims>(configuration.get())?;
+
+                Ok((config, None, validity.into()))
+            }
+        }
+    }
+}
+
+impl Display for SignedWalletConfiguration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Attached(jwt) => write!(f, "{}", jwt.0),
+            // As this only contains a string and a raw JSON value, serialization cannot fail.
+            Self::Detached(detached) => write!(f, "{}", serde_json::to_string(detached).map_err(|_| fmt::Error)?),
+        }
+    }
+}
+
+impl FromStr for SignedWalletConfiguration {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A compact JWS consists of Base64url encoded parts, so it can never start with a JSON object.
+        let signed_config = if s.trim_start().starts_with('{') {
+            Self::Detached(serde_json::from_str(s)?)
+        } else {
+            Self::Attached(s.into())
+        };
+
+        Ok(signed_config)
+    }
+}
+
 pub struct HttpConfigurationRepository<B> {
-    client: EtagHttpClient<Jwt<WalletConfiguration>, B, ConfigurationError>,
+    client: EtagHttpClient<SignedWalletConfiguration, B, ConfigurationError>,
     signing_public_key: EcdsaDecodingKey,
+    storage_path: PathBuf,
     config: RwLock<Arc<WalletConfiguration>>,
+    signed_config: RwLock<Option<SignedWalletConfiguration>>,
 }
 
 impl<B> HttpConfigurationRepository<B> {
@@ -30,14 +143,26 @@ impl<B> HttpConfigurationRepository<B> {
         storage_path: PathBuf,
         initial_config: WalletConfiguration,
     ) -> Result<Self, ConfigurationError> {
+        // The signed configuration that was last received is kept, so that its signature can be verified on demand.
+        let signed_config = config_file::get_signed_config_file(storage_path.as_path())
+            .await?
+            .and_then(|signed_config| {
+                signed_config
+                    .parse()
+                    .inspect_err(|error| warn!("Could not parse stored signed wallet configuration: {error}"))
+                    .ok()
+            });
+
         let repo = Self {
             client: EtagHttpClient::new(
                 "wallet-config".parse().expect("should be a valid filename"),
-                storage_path,
+                storage_path.clone(),
             )
             .await?,
             signing_public_key,
+            storage_path,
             config: RwLock::new(Arc::new(initial_config)),
+            signed_config: RwLock::new(signed_config),
         };
 
         Ok(repo)
@@ -62,7 +187,7 @@ where
         let response = self.client.fetch(config).await?;
         match response {
             HttpResponse::Parsed(parsed_response) => {
-                let new_config = parsed_response.parse_and_verify(&self.signing_public_key, &validations())?;
+                let new_config = parsed_response.parse_and_verify(&self.signing_public_key)?;
 
                 {
                     let current_config = self.config.read();
@@ -77,6 +202,10 @@ where
 
                 info!("Received new wallet configuration with version: {}", new_config.version);
 
+                config_file::update_signed_config_file(self.storage_path.as_path(), &parsed_response.to_string())
+                    .await?;
+                *self.signed_config.write() = Some(parsed_response);
+
                 let mut config = self.config.write();
                 let from = Arc::clone(&*config);
                 *config = Arc::new(new_config);
@@ -94,3 +223,167 @@ where
         }
     }
 }
+
+impl<B> ConfigSignatureRepository for HttpConfigurationRepository<B> {
+    fn verify_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError> {
+        let config = self.get();
+        let signed_config = self.signed_config.read();
+
+        let Some(signed_config) = signed_config.as_ref() else {
+            return Ok(ConfigSignatureInfo::Unsigned {
+                version: config.version,
+            });
+        };
+
+        let (signed, key_id, validity) =
+            signed_config.parse_and_verify_with_signature_info(&self.signing_public_key)?;
+
+        // The configuration bundled with the app may be newer than the signed configuration that was last received.
+        if signed.version < config.version {
+            return Ok(ConfigSignatureInfo::Unsigned {
+                version: config.version,
+            });
+        }
+
+        if signed != *config {
+            return Err(ConfigurationError::SignatureMismatch);
+        }
+
+        Ok(ConfigSignatureInfo::Signed {
+            version: config.version,
+            key_id,
+            validity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use chrono::DateTime;
+    use futures::FutureExt;
+    use p256::ecdsa::SigningKey;
+    use rand_core::OsRng;
+    use serde_json::json;
+
+    use wallet_common::config::http::TlsPinningConfig;
+    use wallet_common::jwt::DetachedJws;
+    use wallet_common::jwt::Jwt;
+    use wallet_common::jwt::JwtError;
+
+    use crate::config::config_file;
+    use crate::config::default_wallet_config;
+    use crate::config::ConfigSignatureInfo;
+    use crate::config::ConfigSignatureRepository;
+    use crate::config::ConfigSignatureValidity;
+    use crate::config::ConfigurationError;
+
+    use super::HttpConfigurationRepository;
+    use super::SignedWalletConfiguration;
+
+    #[test]
+    fn test_signed_wallet_configuration() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let config = default_wallet_config();
+
+        // An attached JWS should be parsed and verified.
+        let jwt = Jwt::sign(&config, &wallet_common::jwt::header(), &signing_key)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let signed_config = jwt.0.parse::<SignedWalletConfiguration>().unwrap();
+        assert_matches!(signed_config, SignedWalletConfiguration::Attached(_));
+        let verified_config = signed_config
+            .parse_and_verify(&signing_key.verifying_key().into())
+            .unwrap();
+        assert_eq!(verified_config, config);
+
+        // A detached JWS should be verified against the raw configuration JSON.
+        let payload = serde_json::to_string(&config).unwrap();
+        let jws = DetachedJws::<()>::sign(payload.as_bytes(), &signing_key)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let body = format!(r#"{{"signature":"{}","configuration":{}}}"#, jws.0, payload);
+        let signed_config = body.parse::<SignedWalletConfiguration>().unwrap();
+        assert_matches!(signed_config, SignedWalletConfiguration::Detached(_));
+        let verified_config = signed_config
+            .parse_and_verify(&signing_key.verifying_key().into())
+            .unwrap();
+        assert_eq!(verified_config, config);
+
+        // Any change to the raw configuration JSON should result in a verification error.
+        let body = format!(
+            r#"{{"signature":"{}","configuration":{}}}"#,
+            jws.0,
+            payload.replacen("\"version\":", "\"version\": ", 1)
+        );
+        let error = body
+            .parse::<SignedWalletConfiguration>()
+            .unwrap()
+            .parse_and_verify(&signing_key.verifying_key().into())
+            .unwrap_err();
+        assert_matches!(error, JwtError::Validation(_));
+    }
+
+    #[tokio::test]
+    async fn test_http_configuration_repository_verify_signature() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let config = default_wallet_config();
+
+        // Sign the configuration along with validity claims and a key id, as the configuration server would.
+        let mut payload = serde_json::to_value(&config).unwrap();
+        payload["iat"] = json!(1_700_000_000);
+        payload["exp"] = json!(4_000_000_000_i64);
+        let header = jsonwebtoken::Header {
+            kid: Some("config_key".to_string()),
+            ..wallet_common::jwt::header()
+        };
+        let jwt = Jwt::sign(&payload, &header, &signing_key).await.unwrap();
+
+        let storage_dir = tempfile::tempdir().unwrap();
+        config_file::update_signed_config_file(storage_dir.path(), &jwt.0)
+            .await
+            .unwrap();
+
+        let repository = HttpConfigurationRepository::<TlsPinningConfig>::new(
+            signing_key.verifying_key().into(),
+            storage_dir.path().to_path_buf(),
+            config.clone(),
+        )
+        .await
+        .unwrap();
+
+        let info = repository
+            .verify_signature()
+            .expect("verifying configuration signature should succeed");
+
+        assert_eq!(
+            info,
+            ConfigSignatureInfo::Signed {
+                version: config.version,
+                key_id: Some("config_key".to_string()),
+                validity: ConfigSignatureValidity {
+                    issued_at: DateTime::from_timestamp(1_700_000_000, 0),
+                    expires_at: DateTime::from_timestamp(4_000_000_000, 0),
+                },
+            }
+        );
+
+        // The stored signature should not verify against a different public key.
+        let other_key = SigningKey::random(&mut OsRng);
+        let repository = HttpConfigurationRepository::<TlsPinningConfig>::new(
+            other_key.verifying_key().into(),
+            storage_dir.path().to_path_buf(),
+            config,
+        )
+        .await
+        .unwrap();
+
+        let error = repository
+            .verify_signature()
+            .expect_err("verifying configuration signature should fail");
+
+        assert_matches!(error, ConfigurationError::Jwt(JwtError::Validation(_)));
+    }
+}
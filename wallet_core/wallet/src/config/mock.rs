@@ -9,6 +9,8 @@ use crate::repository::RepositoryCallback;
 use crate::repository::RepositoryUpdateState;
 use crate::repository::UpdateableRepository;
 
+use super::ConfigSignatureInfo;
+use super::ConfigSignatureRepository;
 use super::ConfigurationError;
 
 pub struct LocalConfigurationRepository {
@@ -49,6 +51,14 @@ where
     }
 }
 
+impl ConfigSignatureRepository for LocalConfigurationRepository {
+    fn verify_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError> {
+        Ok(ConfigSignatureInfo::Unsigned {
+            version: self.config.version,
+        })
+    }
+}
+
 impl ObservableRepository<Arc<WalletConfiguration>> for LocalConfigurationRepository {
     fn register_callback_on_update(
         &self,
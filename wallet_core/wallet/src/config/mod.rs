@@ -6,6 +6,9 @@ mod http_repository;
 mod mock;
 mod updating_repository;
 
+use chrono::DateTime;
+use chrono::Utc;
+
 use error_category::ErrorCategory;
 use wallet_common::config::http::TlsPinningConfig;
 use wallet_common::jwt::JwtError;
@@ -13,9 +16,14 @@ use wallet_common::jwt::JwtError;
 use crate::repository::FileStorageError;
 use crate::repository::HttpClientError;
 
+pub use self::data::config_environments;
+pub use self::data::current_config_environment;
 pub use self::data::default_config_server_config;
 pub use self::data::default_wallet_config;
+pub use self::data::init_config_environment;
 pub use self::data::init_universal_link_base_url;
+pub use self::data::select_config_environment;
+pub use self::data::UniversalLinkBaseUrlError;
 pub use self::data::UNIVERSAL_LINK_BASE_URL;
 pub use self::file_repository::FileStorageConfigurationRepository;
 pub use self::http_repository::HttpConfigurationRepository;
@@ -36,4 +44,42 @@ pub enum ConfigurationError {
     Jwt(#[from] JwtError),
     #[error("http client error: {0}")]
     HttpClient(#[from] HttpClientError),
+    #[error("signed configuration does not match the active configuration")]
+    #[category(critical)]
+    SignatureMismatch,
+    #[error("unknown configuration environment: {0}")]
+    #[category(critical)]
+    UnknownEnvironment(String),
+    #[error("configuration environment cannot be changed, as \"{0}\" is already in use")]
+    #[category(critical)]
+    EnvironmentAlreadySelected(String),
+    #[error("invalid universal link base URL: {0}")]
+    #[category(critical)]
+    UniversalLinkBaseUrl(#[from] UniversalLinkBaseUrlError),
+}
+
+/// Describes the signature of the active wallet configuration, see [`ConfigSignatureRepository`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSignatureInfo {
+    /// The active configuration was received from the configuration server and its signature is valid.
+    Signed {
+        version: u64,
+        key_id: Option<String>,
+        validity: ConfigSignatureValidity,
+    },
+    /// No signature is available for the active configuration, e.g. because it is the one bundled with the app.
+    Unsigned { version: u64 },
+}
+
+/// The validity period of a signed configuration, as specified by the `iat` and `exp` claims in its payload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSignatureValidity {
+    pub issued_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub trait ConfigSignatureRepository {
+    /// Verify the signature of the active configuration against the configured public key. Note that this does not
+    /// fetch the configuration again, it only re-verifies the signed configuration that was last received.
+    fn verify_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError>;
 }
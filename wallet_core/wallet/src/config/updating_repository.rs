@@ -18,6 +18,8 @@ use crate::repository::RepositoryCallback;
 use crate::repository::RepositoryUpdateState;
 use crate::repository::UpdateableRepository;
 
+use super::ConfigSignatureInfo;
+use super::ConfigSignatureRepository;
 use super::ConfigurationError;
 use super::FileStorageConfigurationRepository;
 use super::WalletConfigurationRepository;
@@ -103,6 +105,15 @@ where
     }
 }
 
+impl<T> ConfigSignatureRepository for UpdatingConfigurationRepository<T>
+where
+    T: ConfigSignatureRepository,
+{
+    fn verify_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError> {
+        self.wrapped.verify_signature()
+    }
+}
+
 impl<T> ObservableRepository<Arc<WalletConfiguration>> for UpdatingConfigurationRepository<T>
 where
     T: Repository<Arc<WalletConfiguration>>,
@@ -4,10 +4,12 @@ mod mdoc;
 use std::collections::HashMap;
 
 use chrono::NaiveDate;
+use derive_more::Debug;
 use indexmap::IndexMap;
 
 use nl_wallet_mdoc::utils::issuer_auth::IssuerRegistration;
 use sd_jwt::metadata::DisplayMetadata;
+use wallet_common::redact::Redacted;
 
 #[cfg(feature = "snapshot_test")]
 use serde::Serialize;
@@ -59,10 +61,10 @@ pub struct Attribute {
 #[cfg_attr(feature = "snapshot_test", derive(Serialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AttributeValue {
-    String(String),
-    Boolean(bool),
-    Date(NaiveDate),
-    Gender(GenderAttributeValue),
+    String(#[debug("{:?}", Redacted(_0))] String),
+    Boolean(#[debug("{:?}", Redacted(_0))] bool),
+    Date(#[debug("{:?}", Redacted(_0))] NaiveDate),
+    Gender(#[debug("{:?}", Redacted(_0))] GenderAttributeValue),
 }
 
 #[cfg_attr(feature = "snapshot_test", derive(Serialize))]
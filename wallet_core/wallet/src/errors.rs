@@ -8,7 +8,10 @@ pub mod openid4vc {
     pub use openid4vc::disclosure_session::VpMessageClientError;
     pub use openid4vc::disclosure_session::VpMessageClientErrorType;
     pub use openid4vc::errors::AuthorizationErrorCode;
+    pub use openid4vc::errors::CredentialErrorCode;
+    pub use openid4vc::errors::CredentialRequestFailure;
     pub use openid4vc::errors::ErrorResponse;
+    pub use openid4vc::errors::IssuanceRecovery;
     pub use openid4vc::issuance_session::IssuanceSessionError;
     pub use openid4vc::oidc::OidcError;
 }
@@ -29,12 +32,17 @@ pub use crate::repository::HttpClientError;
 pub use crate::storage::KeyFileError;
 pub use crate::storage::StorageError;
 pub use crate::update_policy::UpdatePolicyError;
+pub use crate::wallet::AttestedKeyContinuityError;
+pub use crate::wallet::CertificateValidationError;
+pub use crate::wallet::CredentialMetadataError;
 pub use crate::wallet::DisclosureError;
 pub use crate::wallet::EventConversionError;
 pub use crate::wallet::EventStorageError;
 pub use crate::wallet::HistoryError;
 pub use crate::wallet::PidIssuanceError;
+pub use crate::wallet::RemoveCredentialsError;
 pub use crate::wallet::ResetError;
+pub use crate::wallet::TrustAnchorError;
 pub use crate::wallet::UriIdentificationError;
 pub use crate::wallet::WalletInitError;
 pub use crate::wallet::WalletRegistrationError;
@@ -15,10 +15,15 @@ mod wte;
 
 pub mod errors;
 
+pub use crate::account_provider::CircuitState;
 pub use crate::attestation::Attestation;
 pub use crate::attestation::AttestationAttribute;
 pub use crate::attestation::AttestationIdentity;
 pub use crate::attestation::LocalizedString;
+pub use crate::config::config_environments;
+pub use crate::config::current_config_environment;
+pub use crate::config::ConfigSignatureInfo;
+pub use crate::config::ConfigSignatureValidity;
 pub use crate::disclosure::DisclosureUriSource;
 pub use crate::document::Attribute;
 pub use crate::document::AttributeKey;
@@ -35,10 +40,16 @@ pub use crate::document::DocumentType;
 pub use crate::document::GenderAttributeValue;
 pub use crate::document::MissingDisclosureAttributes;
 pub use crate::pin::validation::validate_pin;
+pub use crate::wallet::select_config_environment;
+pub use crate::wallet::AttestedKeyContinuity;
+pub use crate::wallet::DisclosurePreview;
+pub use crate::wallet::DisclosurePreviewAttribute;
 pub use crate::wallet::DisclosureProposal;
 pub use crate::wallet::EventStatus;
 pub use crate::wallet::HistoryEvent;
 pub use crate::wallet::LockCallback;
+pub use crate::wallet::TrustAnchorInfo;
+pub use crate::wallet::TrustAnchorSummary;
 pub use crate::wallet::UnlockMethod;
 pub use crate::wallet::UriType;
 pub use crate::wallet::Wallet;
@@ -57,6 +68,7 @@ pub mod mdoc {
 pub mod openid4vc {
     pub use openid4vc::attributes::Attribute;
     pub use openid4vc::attributes::AttributeValue;
+    pub use openid4vc::metadata::CredentialMetadataDrift;
     pub use openid4vc::verifier::SessionType;
 }
 
@@ -80,6 +92,8 @@ pub mod wallet_common {
 #[cfg(feature = "wallet_deps")]
 pub mod wallet_deps {
     pub use crate::account_provider::AccountProviderClient;
+    pub use crate::account_provider::CircuitBreakerAccountProviderClient;
+    pub use crate::account_provider::CircuitBreakerConfig;
     pub use crate::account_provider::HttpAccountProviderClient;
     pub use crate::config::default_config_server_config;
     pub use crate::config::default_wallet_config;
@@ -0,0 +1,108 @@
+use chrono::DateTime;
+use chrono::Utc;
+use itertools::Itertools;
+
+use entity::consent_decision;
+use nl_wallet_mdoc::verifier::ItemsRequests;
+use wallet_common::utils::sha256;
+
+use super::StorageResult;
+
+/// A remembered decision of the user to disclose a specific set of attributes to a relying party.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsentDecision {
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ConsentDecision {
+    pub fn is_expired_at(&self, time: DateTime<Utc>) -> bool {
+        self.expires_at <= time
+    }
+}
+
+impl From<consent_decision::Model> for ConsentDecision {
+    fn from(value: consent_decision::Model) -> Self {
+        Self {
+            granted_at: value.granted_at,
+            expires_at: value.expires_at,
+        }
+    }
+}
+
+/// Calculate a hash over the set of attributes requested in `requests`, including whether the relying party intends to
+/// retain each of them. This hash does not depend on the order in which the attributes are requested or whether an
+/// attribute is requested more than once.
+pub(super) fn requested_attributes_hash(requests: &ItemsRequests) -> StorageResult<Vec<u8>> {
+    let identifiers = requests
+        .0
+        .iter()
+        .flat_map(|items_request| {
+            items_request
+                .name_spaces
+                .iter()
+                .flat_map(move |(namespace, attributes)| {
+                    attributes.iter().map(move |(attribute, intent_to_retain)| {
+                        (&items_request.doc_type, namespace, attribute, *intent_to_retain)
+                    })
+                })
+        })
+        .sorted()
+        .dedup()
+        .collect_vec();
+
+    // Serialize the identifiers as JSON before hashing, as the separate parts may contain any character.
+    let hash = sha256(&serde_json::to_vec(&identifiers)?);
+
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use nl_wallet_mdoc::ItemsRequest;
+
+    use super::*;
+
+    fn items_request(doc_type: &str, attributes: &[&str], intent_to_retain: bool) -> ItemsRequest {
+        ItemsRequest {
+            doc_type: doc_type.to_string(),
+            name_spaces: IndexMap::from([(
+                doc_type.to_string(),
+                attributes
+                    .iter()
+                    .map(|attribute| (attribute.to_string(), intent_to_retain))
+                    .collect(),
+            )]),
+            request_info: None,
+        }
+    }
+
+    #[test]
+    fn test_requested_attributes_hash() {
+        let requests = ItemsRequests(vec![
+            items_request("com.example.pid", &["given_name", "family_name"], false),
+            items_request("com.example.address", &["city"], false),
+        ]);
+        let reordered_requests = ItemsRequests(vec![
+            items_request("com.example.address", &["city"], false),
+            items_request("com.example.pid", &["family_name", "given_name"], false),
+        ]);
+        let other_requests = ItemsRequests(vec![items_request(
+            "com.example.pid",
+            &["given_name", "family_name"],
+            false,
+        )]);
+        let retaining_requests = ItemsRequests(vec![
+            items_request("com.example.pid", &["given_name", "family_name"], false),
+            items_request("com.example.address", &["city"], true),
+        ]);
+
+        let hash = requested_attributes_hash(&requests).unwrap();
+
+        assert_eq!(requested_attributes_hash(&reordered_requests).unwrap(), hash);
+        assert_ne!(requested_attributes_hash(&other_requests).unwrap(), hash);
+        assert_ne!(requested_attributes_hash(&retaining_requests).unwrap(), hash);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
 
+use chrono::DateTime;
+use chrono::Utc;
 use futures::try_join;
 use sea_orm::sea_query::Alias;
 use sea_orm::sea_query::BinOper;
@@ -12,6 +14,7 @@ use sea_orm::ColumnTrait;
 use sea_orm::ConnectionTrait;
 use sea_orm::DbErr;
 use sea_orm::EntityTrait;
+use sea_orm::FromQueryResult;
 use sea_orm::IntoSimpleExpr;
 use sea_orm::JoinType;
 use sea_orm::ModelTrait;
@@ -31,6 +34,9 @@ use tokio::fs;
 use tracing::warn;
 use uuid::Uuid;
 
+use entity::consent_decision;
+use entity::deletion_history_event;
+use entity::deletion_history_event_doc_type;
 use entity::disclosure_history_event;
 use entity::disclosure_history_event::EventStatus;
 use entity::disclosure_history_event_doc_type;
@@ -44,9 +50,13 @@ use nl_wallet_mdoc::utils::serialization::cbor_deserialize;
 use nl_wallet_mdoc::utils::serialization::cbor_serialize;
 use nl_wallet_mdoc::utils::serialization::CborError;
 use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use nl_wallet_mdoc::verifier::ItemsRequests;
 use openid4vc::credential::MdocCopies;
 use platform_support::hw_keystore::PlatformEncryptionKey;
+use wallet_common::urls::BaseUrl;
 
+use super::consent::requested_attributes_hash;
+use super::consent::ConsentDecision;
 use super::data::KeyedData;
 use super::database::Database;
 use super::database::SqliteUrl;
@@ -151,23 +161,32 @@ impl<K> DatabaseStorage<K> {
                 mdoc_copy::Column::Mdoc,
             ])
             .column_as(mdoc_copy::Column::DisclosureCount.min(), "disclosure_count")
+            .column(mdoc::Column::LastUsedAt)
+            .column(mdoc::Column::IssuerUrl)
+            .inner_join(mdoc::Entity)
             .group_by(mdoc_copy::Column::MdocId);
 
-        let mdoc_copies = transform_select(select).all(database.connection()).await?;
+        let mdoc_copies = transform_select(select)
+            .into_model::<UniqueMdocCopyModel>()
+            .all(database.connection())
+            .await?;
 
         let mdocs = mdoc_copies
             .into_iter()
             .map(|model| {
                 let mdoc = cbor_deserialize(model.mdoc.as_slice())?;
+                let issuer_url = model.issuer_url.map(|url| url.parse::<BaseUrl>()).transpose()?;
                 let stored_mdoc_copy = StoredMdocCopy {
                     mdoc_id: model.mdoc_id,
                     mdoc_copy_id: model.id,
                     mdoc,
+                    issuer_url,
+                    last_used_at: model.last_used_at,
                 };
 
                 Ok(stored_mdoc_copy)
             })
-            .collect::<Result<_, CborError>>()?;
+            .collect::<StorageResult<_>>()?;
 
         Ok(mdocs)
     }
@@ -209,6 +228,137 @@ impl<K> DatabaseStorage<K> {
             .await
     }
 
+    async fn insert_mdoc_copies(
+        connection: &impl ConnectionTrait,
+        mdocs: Vec<MdocCopies>,
+        issuer_url: &BaseUrl,
+    ) -> StorageResult<()> {
+        // All copies of an mdoc are used interchangeably when disclosing, so they should only differ in their keys and
+        // the random bytes of their attributes. Reject the entire batch if this is not the case.
+        if let Some(mdoc_copies) = mdocs
+            .iter()
+            .find(|mdoc_copies| !mdoc_copies_are_interchangeable(mdoc_copies))
+        {
+            return Err(StorageError::MdocCopiesMismatch(mdoc_copies.first().doc_type().clone()));
+        }
+
+        // Construct a vec of tuples of 1 `mdoc` and 1 or more `mdoc_copy` models,
+        // based on the unique `MdocCopies`, to be inserted into the database.
+        let mdoc_models = mdocs
+            .into_iter()
+            .map(|mdoc_copies| {
+                let mdoc_id = Uuid::new_v4();
+
+                let copy_models = mdoc_copies
+                    .as_ref()
+                    .iter()
+                    .map(|mdoc| {
+                        let model = mdoc_copy::ActiveModel {
+                            id: Set(Uuid::new_v4()),
+                            mdoc_id: Set(mdoc_id),
+                            mdoc: Set(cbor_serialize(&mdoc)?),
+                            ..Default::default()
+                        };
+
+                        Ok(model)
+                    })
+                    .collect::<Result<Vec<_>, CborError>>()?;
+
+                // `mdoc_copies.cred_copies` is guaranteed to contain at least one value because of the filter() above.
+                let doc_type = mdoc_copies.into_iter().next().unwrap().doc_type().clone();
+                let mdoc_model = mdoc::ActiveModel {
+                    id: Set(mdoc_id),
+                    doc_type: Set(doc_type),
+                    last_used_at: Set(None),
+                    issuer_url: Set(Some(issuer_url.to_string())),
+                };
+
+                Ok((mdoc_model, copy_models))
+            })
+            .collect::<Result<Vec<_>, CborError>>()?;
+
+        // Make two separate vecs out of the vec of tuples.
+        let (mdoc_models, copy_models): (Vec<_>, Vec<_>) = mdoc_models.into_iter().unzip();
+
+        mdoc::Entity::insert_many(mdoc_models).exec(connection).await?;
+        mdoc_copy::Entity::insert_many(copy_models.into_iter().flatten())
+            .exec(connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_wallet_event(connection: &impl ConnectionTrait, event: WalletEvent) -> StorageResult<()> {
+        let event_doc_types = event.associated_doc_types();
+
+        // Find existing doc_type entities
+        let existing_doc_type_entities = history_doc_type::Entity::find()
+            .filter(history_doc_type::Column::DocType.is_in(event_doc_types.clone()))
+            .all(connection)
+            .await?;
+
+        // Get Vec of existing doc_types
+        let existing_doc_types = existing_doc_type_entities
+            .iter()
+            .map(|e| e.doc_type.as_str())
+            .collect::<Vec<_>>();
+
+        // Determine what new doc_type entries need to be inserted
+        let new_doc_type_entities = event_doc_types
+            .into_iter()
+            .filter(|doc_type| !existing_doc_types.contains(doc_type))
+            .map(|doc_type| history_doc_type::Model {
+                id: Uuid::new_v4(),
+                doc_type: doc_type.to_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        // Insert the history event
+        match WalletEventModel::try_from(event)? {
+            WalletEventModel::Issuance(event_entity) => {
+                Self::insert_history_event_and_doc_type_mappings(
+                    connection,
+                    issuance_history_event::ActiveModel::from(event_entity),
+                    new_doc_type_entities,
+                    existing_doc_type_entities,
+                    |(event, doc_type_id)| issuance_history_event_doc_type::ActiveModel {
+                        issuance_history_event_id: event.id.clone(),
+                        history_doc_type_id: Set(doc_type_id),
+                    },
+                )
+                .await?;
+            }
+            WalletEventModel::Disclosure(event_entity) => {
+                Self::insert_history_event_and_doc_type_mappings(
+                    connection,
+                    disclosure_history_event::ActiveModel::from(event_entity),
+                    new_doc_type_entities,
+                    existing_doc_type_entities,
+                    |(event, doc_type_id)| disclosure_history_event_doc_type::ActiveModel {
+                        disclosure_history_event_id: event.id.clone(),
+                        history_doc_type_id: Set(doc_type_id),
+                    },
+                )
+                .await?;
+            }
+            WalletEventModel::Deletion(event_entity) => {
+                Self::insert_history_event_and_doc_type_mappings(
+                    connection,
+                    deletion_history_event::ActiveModel::from(event_entity),
+                    new_doc_type_entities,
+                    existing_doc_type_entities,
+                    |(event, doc_type_id)| deletion_history_event_doc_type::ActiveModel {
+                        deletion_history_event_id: event.id.clone(),
+                        history_doc_type_id: Set(doc_type_id),
+                    },
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn insert_history_event_and_doc_type_mappings<
         EventEntity: EntityTrait,
         EventActiveModel: ActiveModelTrait<Entity = EventEntity>,
@@ -250,6 +400,7 @@ impl<K> DatabaseStorage<K> {
     fn combine_history_events(
         issuance_events: Vec<issuance_history_event::Model>,
         disclosure_events: Vec<disclosure_history_event::Model>,
+        deletion_events: Vec<deletion_history_event::Model>,
     ) -> StorageResult<Vec<WalletEvent>> {
         let mut issuance_events: Vec<WalletEvent> = issuance_events
             .into_iter()
@@ -259,8 +410,13 @@ impl<K> DatabaseStorage<K> {
             .into_iter()
             .map(WalletEvent::try_from)
             .collect::<Result<Vec<_>, _>>()?;
+        let mut deletion_events: Vec<WalletEvent> = deletion_events
+            .into_iter()
+            .map(WalletEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
 
         issuance_events.append(&mut disclosure_events);
+        issuance_events.append(&mut deletion_events);
         issuance_events.sort_by(|a, b| b.timestamp().cmp(a.timestamp()));
         Ok(issuance_events)
     }
@@ -411,65 +567,81 @@ where
         Ok(())
     }
 
-    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>) -> StorageResult<()> {
-        // Construct a vec of tuples of 1 `mdoc` and 1 or more `mdoc_copy` models,
-        // based on the unique `MdocCopies`, to be inserted into the database.
-        let mdoc_models = mdocs
-            .into_iter()
-            .map(|mdoc_copies| {
-                let mdoc_id = Uuid::new_v4();
-
-                let copy_models = mdoc_copies
-                    .as_ref()
-                    .iter()
-                    .map(|mdoc| {
-                        let model = mdoc_copy::ActiveModel {
-                            id: Set(Uuid::new_v4()),
-                            mdoc_id: Set(mdoc_id),
-                            mdoc: Set(cbor_serialize(&mdoc)?),
-                            ..Default::default()
-                        };
+    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()> {
+        let transaction = self.database()?.connection().begin().await?;
 
-                        Ok(model)
-                    })
-                    .collect::<Result<Vec<_>, CborError>>()?;
+        Self::insert_mdoc_copies(&transaction, mdocs, issuer_url).await?;
 
-                // `mdoc_copies.cred_copies` is guaranteed to contain at least one value because of the filter() above.
-                let doc_type = mdoc_copies.into_iter().next().unwrap().doc_type().clone();
-                let mdoc_model = mdoc::ActiveModel {
-                    id: Set(mdoc_id),
-                    doc_type: Set(doc_type),
-                };
+        transaction.commit().await?;
 
-                Ok((mdoc_model, copy_models))
-            })
-            .collect::<Result<Vec<_>, CborError>>()?;
+        Ok(())
+    }
 
-        // Make two separate vecs out of the vec of tuples.
-        let (mdoc_models, copy_models): (Vec<_>, Vec<_>) = mdoc_models.into_iter().unzip();
+    async fn replace_mdoc(&mut self, old_id: Uuid, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()> {
+        // Prepare the event before inserting the mdocs, to avoid cloning all of the copies.
+        let event = WalletEvent::new_renewal(
+            mdocs
+                .iter()
+                .map(|mdoc_copies| mdoc_copies.first().clone())
+                .collect::<Vec<_>>()
+                .try_into()?,
+        );
 
+        // Insert the new mdocs, delete the old one and log the event in a single transaction,
+        // so that there is no point in time at which neither of the mdocs is present.
         let transaction = self.database()?.connection().begin().await?;
 
-        mdoc::Entity::insert_many(mdoc_models).exec(&transaction).await?;
-        mdoc_copy::Entity::insert_many(copy_models.into_iter().flatten())
+        Self::insert_mdoc_copies(&transaction, mdocs, issuer_url).await?;
+
+        mdoc_copy::Entity::delete_many()
+            .filter(mdoc_copy::Column::MdocId.eq(old_id))
             .exec(&transaction)
             .await?;
+        let delete_result = mdoc::Entity::delete_by_id(old_id).exec(&transaction).await?;
+
+        // Dropping the transaction without committing it will roll back the insertion.
+        if delete_result.rows_affected == 0 {
+            return Err(StorageError::MdocNotFound(old_id));
+        }
+
+        Self::insert_wallet_event(&transaction, event).await?;
 
         transaction.commit().await?;
 
         Ok(())
     }
 
-    async fn increment_mdoc_copies_usage_count(&mut self, mdoc_copy_ids: Vec<Uuid>) -> StorageResult<()> {
+    async fn increment_mdoc_copies_usage_count(
+        &mut self,
+        mdoc_copy_ids: Vec<Uuid>,
+        used_at: DateTime<Utc>,
+    ) -> StorageResult<()> {
+        let transaction = self.database()?.connection().begin().await?;
+
         mdoc_copy::Entity::update_many()
             .col_expr(
                 mdoc_copy::Column::DisclosureCount,
                 Expr::col(mdoc_copy::Column::DisclosureCount).add(1),
             )
-            .filter(mdoc_copy::Column::Id.is_in(mdoc_copy_ids))
-            .exec(self.database()?.connection())
+            .filter(mdoc_copy::Column::Id.is_in(mdoc_copy_ids.clone()))
+            .exec(&transaction)
+            .await?;
+
+        // Record the time of use on the mdocs that the copies belong to.
+        let mdoc_ids = Query::select()
+            .column(mdoc_copy::Column::MdocId)
+            .from(mdoc_copy::Entity)
+            .and_where(mdoc_copy::Column::Id.is_in(mdoc_copy_ids))
+            .to_owned();
+
+        mdoc::Entity::update_many()
+            .col_expr(mdoc::Column::LastUsedAt, Expr::value(used_at))
+            .filter(mdoc::Column::Id.in_subquery(mdoc_ids))
+            .exec(&transaction)
             .await?;
 
+        transaction.commit().await?;
+
         Ok(())
     }
 
@@ -480,12 +652,8 @@ where
     async fn fetch_unique_mdocs_by_doctypes(&self, doc_types: &HashSet<&str>) -> StorageResult<Vec<StoredMdocCopy>> {
         let doc_types_iter = doc_types.iter().copied();
 
-        self.query_unique_mdocs(move |select| {
-            select
-                .inner_join(mdoc::Entity)
-                .filter(mdoc::Column::DocType.is_in(doc_types_iter))
-        })
-        .await
+        self.query_unique_mdocs(move |select| select.filter(mdoc::Column::DocType.is_in(doc_types_iter)))
+            .await
     }
 
     async fn has_any_mdocs_with_doctype(&self, doc_type: &str) -> StorageResult<bool> {
@@ -493,62 +661,27 @@ where
         Ok(!result.is_empty())
     }
 
-    async fn log_wallet_event(&mut self, event: WalletEvent) -> StorageResult<()> {
+    async fn delete_mdocs(&mut self, mdoc_ids: Vec<Uuid>) -> StorageResult<()> {
         let transaction = self.database()?.connection().begin().await?;
 
-        let event_doc_types = event.associated_doc_types();
-
-        // Find existing doc_type entities
-        let existing_doc_type_entities = history_doc_type::Entity::find()
-            .filter(history_doc_type::Column::DocType.is_in(event_doc_types.clone()))
-            .all(&transaction)
+        mdoc_copy::Entity::delete_many()
+            .filter(mdoc_copy::Column::MdocId.is_in(mdoc_ids.clone()))
+            .exec(&transaction)
+            .await?;
+        mdoc::Entity::delete_many()
+            .filter(mdoc::Column::Id.is_in(mdoc_ids))
+            .exec(&transaction)
             .await?;
 
-        // Get Vec of existing doc_types
-        let existing_doc_types = existing_doc_type_entities
-            .iter()
-            .map(|e| e.doc_type.as_str())
-            .collect::<Vec<_>>();
+        transaction.commit().await?;
 
-        // Determine what new doc_type entries need to be inserted
-        let new_doc_type_entities = event_doc_types
-            .into_iter()
-            .filter(|doc_type| !existing_doc_types.contains(doc_type))
-            .map(|doc_type| history_doc_type::Model {
-                id: Uuid::new_v4(),
-                doc_type: doc_type.to_owned(),
-            })
-            .collect::<Vec<_>>();
+        Ok(())
+    }
 
-        // Insert the history event
-        match WalletEventModel::try_from(event)? {
-            WalletEventModel::Issuance(event_entity) => {
-                Self::insert_history_event_and_doc_type_mappings(
-                    &transaction,
-                    issuance_history_event::ActiveModel::from(event_entity),
-                    new_doc_type_entities,
-                    existing_doc_type_entities,
-                    |(event, doc_type_id)| issuance_history_event_doc_type::ActiveModel {
-                        issuance_history_event_id: event.id.clone(),
-                        history_doc_type_id: Set(doc_type_id),
-                    },
-                )
-                .await?;
-            }
-            WalletEventModel::Disclosure(event_entity) => {
-                Self::insert_history_event_and_doc_type_mappings(
-                    &transaction,
-                    disclosure_history_event::ActiveModel::from(event_entity),
-                    new_doc_type_entities,
-                    existing_doc_type_entities,
-                    |(event, doc_type_id)| disclosure_history_event_doc_type::ActiveModel {
-                        disclosure_history_event_id: event.id.clone(),
-                        history_doc_type_id: Set(doc_type_id),
-                    },
-                )
-                .await?;
-            }
-        }
+    async fn log_wallet_event(&mut self, event: WalletEvent) -> StorageResult<()> {
+        let transaction = self.database()?.connection().begin().await?;
+
+        Self::insert_wallet_event(&transaction, event).await?;
 
         transaction.commit().await?;
 
@@ -566,9 +699,14 @@ where
             .order_by_desc(disclosure_history_event::Column::Timestamp)
             .all(connection);
 
-        let (issuance_events, disclosure_events) = try_join!(fetch_issuance_events, fetch_disclosure_events)?;
+        let fetch_deletion_events = deletion_history_event::Entity::find()
+            .order_by_desc(deletion_history_event::Column::Timestamp)
+            .all(connection);
+
+        let (issuance_events, disclosure_events, deletion_events) =
+            try_join!(fetch_issuance_events, fetch_disclosure_events, fetch_deletion_events)?;
 
-        Self::combine_history_events(issuance_events, disclosure_events)
+        Self::combine_history_events(issuance_events, disclosure_events, deletion_events)
     }
 
     async fn fetch_recent_wallet_events(&self) -> StorageResult<Vec<WalletEvent>> {
@@ -584,9 +722,15 @@ where
             .order_by_desc(disclosure_history_event::Column::Timestamp)
             .all(connection);
 
-        let (issuance_events, disclosure_events) = try_join!(fetch_issuance_events, fetch_disclosure_events)?;
+        let fetch_deletion_events = deletion_history_event::Entity::find()
+            .filter(Self::newer_than_31_days(deletion_history_event::Column::Timestamp))
+            .order_by_desc(deletion_history_event::Column::Timestamp)
+            .all(connection);
+
+        let (issuance_events, disclosure_events, deletion_events) =
+            try_join!(fetch_issuance_events, fetch_disclosure_events, fetch_deletion_events)?;
 
-        Self::combine_history_events(issuance_events, disclosure_events)
+        Self::combine_history_events(issuance_events, disclosure_events, deletion_events)
     }
 
     async fn fetch_wallet_events_by_doc_type(&self, doc_type: &str) -> StorageResult<Vec<WalletEvent>> {
@@ -606,10 +750,18 @@ where
             disclosure_history_event_doc_type::Relation::HistoryDocType.def(),
             disclosure_history_event::Column::Timestamp,
         );
+        let fetch_deletion_events = Self::query_history_events_by_doc_type(
+            doc_type,
+            connection,
+            deletion_history_event_doc_type::Relation::HistoryEvent.def(),
+            deletion_history_event_doc_type::Relation::HistoryDocType.def(),
+            deletion_history_event::Column::Timestamp,
+        );
 
-        let (issuance_events, disclosure_events) = try_join!(fetch_issuance_events, fetch_disclosure_events)?;
+        let (issuance_events, disclosure_events, deletion_events) =
+            try_join!(fetch_issuance_events, fetch_disclosure_events, fetch_deletion_events)?;
 
-        Self::combine_history_events(issuance_events, disclosure_events)
+        Self::combine_history_events(issuance_events, disclosure_events, deletion_events)
     }
 
     async fn did_share_data_with_relying_party(&self, certificate: &BorrowingCertificate) -> StorageResult<bool> {
@@ -634,13 +786,111 @@ where
 
         Ok(exists)
     }
+
+    async fn record_consent(
+        &mut self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        granted_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> StorageResult<()> {
+        let model = consent_decision::ActiveModel {
+            relying_party_certificate: Set(certificate.as_ref().to_vec()),
+            attributes_hash: Set(requested_attributes_hash(requests)?),
+            granted_at: Set(granted_at),
+            expires_at: Set(expires_at),
+        };
+
+        let transaction = self.database()?.connection().begin().await?;
+
+        // Clean up any consent decisions that have expired in the meantime, as these can never be used again.
+        consent_decision::Entity::delete_many()
+            .filter(consent_decision::Column::ExpiresAt.lte(granted_at))
+            .exec(&transaction)
+            .await?;
+
+        consent_decision::Entity::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    consent_decision::Column::RelyingPartyCertificate,
+                    consent_decision::Column::AttributesHash,
+                ])
+                .update_columns([consent_decision::Column::GrantedAt, consent_decision::Column::ExpiresAt])
+                .to_owned(),
+            )
+            .exec(&transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_consent(
+        &self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        time: DateTime<Utc>,
+    ) -> StorageResult<Option<ConsentDecision>> {
+        let consent = consent_decision::Entity::find()
+            .filter(consent_decision::Column::RelyingPartyCertificate.eq(certificate.as_ref()))
+            .filter(consent_decision::Column::AttributesHash.eq(requested_attributes_hash(requests)?))
+            .filter(consent_decision::Column::ExpiresAt.gt(time))
+            .one(self.database()?.connection())
+            .await?
+            .map(ConsentDecision::from);
+
+        Ok(consent)
+    }
+
+    async fn revoke_consents(&mut self, certificate: &BorrowingCertificate) -> StorageResult<()> {
+        consent_decision::Entity::delete_many()
+            .filter(consent_decision::Column::RelyingPartyCertificate.eq(certificate.as_ref()))
+            .exec(self.database()?.connection())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_consents(&mut self) -> StorageResult<()> {
+        consent_decision::Entity::delete_many()
+            .exec(self.database()?.connection())
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// A single copy of a unique mdoc, including the last time any of its copies was used and its issuer.
+#[derive(FromQueryResult)]
+struct UniqueMdocCopyModel {
+    id: Uuid,
+    mdoc_id: Uuid,
+    mdoc: Vec<u8>,
+    last_used_at: Option<DateTime<Utc>>,
+    issuer_url: Option<String>,
+}
+
+/// Checks that all copies have the same doctype and attributes, where the latter ignores the `random` of the items.
+fn mdoc_copies_are_interchangeable(mdoc_copies: &MdocCopies) -> bool {
+    let first = mdoc_copies.first();
+    let first_attributes = first.attributes();
+
+    mdoc_copies
+        .as_ref()
+        .iter()
+        .skip(1)
+        .all(|mdoc| mdoc.doc_type() == first.doc_type() && mdoc.attributes() == first_attributes)
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use std::mem;
+    use std::num::NonZeroU8;
     use std::sync::LazyLock;
 
+    use assert_matches::assert_matches;
+    use chrono::Duration;
     use chrono::TimeZone;
     use chrono::Utc;
     use tokio::fs;
@@ -648,12 +898,14 @@ pub(crate) mod tests {
     use nl_wallet_mdoc::holder::Mdoc;
     use nl_wallet_mdoc::server_keys::generate::Ca;
     use nl_wallet_mdoc::server_keys::KeyPair;
+    use nl_wallet_mdoc::test::data;
     use nl_wallet_mdoc::utils::issuer_auth::IssuerRegistration;
     use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
     use platform_support::utils::mock::MockHardwareUtilities;
     use platform_support::utils::PlatformUtilities;
     use wallet_common::account::messages::auth::WalletCertificate;
     use wallet_common::keys::mock_hardware::MockHardwareEncryptionKey;
+    use wallet_common::keys::mock_remote::MockRemoteKeyFactory;
     use wallet_common::utils::random_bytes;
 
     use crate::storage::data::RegistrationData;
@@ -677,6 +929,8 @@ pub(crate) mod tests {
             .unwrap()
     });
 
+    static ISSUER_URL: LazyLock<BaseUrl> = LazyLock::new(|| "https://issuer.example.com/".parse().unwrap());
+
     #[test]
     fn test_key_file_alias_for_name() {
         assert_eq!(key_file_alias_for_name("test_database"), "test_database_db");
@@ -883,7 +1137,7 @@ pub(crate) mod tests {
 
         // Insert mdocs
         storage
-            .insert_mdocs(vec![mdoc_copies.clone()])
+            .insert_mdocs(vec![mdoc_copies.clone()], &ISSUER_URL)
             .await
             .expect("Could not insert mdocs");
 
@@ -893,14 +1147,15 @@ pub(crate) mod tests {
             .await
             .expect("Could not fetch unique mdocs");
 
-        // Only one unique `Mdoc` should be returned and it should match all copies.
+        // Only one unique `Mdoc` should be returned and it should match all copies, as well as the issuer.
         assert_eq!(fetched_unique.len(), 1);
         let mdoc_copy1 = fetched_unique.first().unwrap();
         assert_eq!(&mdoc_copy1.mdoc, mdoc_copies.first());
+        assert_eq!(mdoc_copy1.issuer_url.as_ref(), Some(&*ISSUER_URL));
 
         // Increment the usage count for this mdoc.
         storage
-            .increment_mdoc_copies_usage_count(vec![mdoc_copy1.mdoc_copy_id])
+            .increment_mdoc_copies_usage_count(vec![mdoc_copy1.mdoc_copy_id], Utc::now())
             .await
             .expect("Could not increment usage count for mdoc copy");
 
@@ -918,7 +1173,7 @@ pub(crate) mod tests {
 
         // Increment the usage count for this mdoc.
         storage
-            .increment_mdoc_copies_usage_count(vec![mdoc_copy2.mdoc_copy_id])
+            .increment_mdoc_copies_usage_count(vec![mdoc_copy2.mdoc_copy_id], Utc::now())
             .await
             .expect("Could not increment usage count for mdoc copy");
 
@@ -954,6 +1209,206 @@ pub(crate) mod tests {
         assert!(fetched_unique_doctype_mismatch.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_mdoc_storage_last_used_at() {
+        let mut storage = open_test_database_storage().await;
+
+        let mdoc = Mdoc::new_example_mock();
+        let mdoc_copies = MdocCopies::try_from([mdoc.clone(), mdoc].to_vec()).unwrap();
+
+        storage
+            .insert_mdocs(vec![mdoc_copies], &ISSUER_URL)
+            .await
+            .expect("Could not insert mdocs");
+
+        // A newly inserted mdoc has never been used.
+        let mdoc_copy = storage.fetch_unique_mdocs().await.unwrap().into_iter().next().unwrap();
+        assert!(mdoc_copy.last_used_at.is_none());
+
+        // Using a copy of the mdoc should record the time of use.
+        let first_used_at = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        storage
+            .increment_mdoc_copies_usage_count(vec![mdoc_copy.mdoc_copy_id], first_used_at)
+            .await
+            .expect("Could not increment usage count for mdoc copy");
+
+        let fetched = storage.fetch_unique_mdocs().await.unwrap().into_iter().next().unwrap();
+        assert_ne!(fetched.mdoc_copy_id, mdoc_copy.mdoc_copy_id);
+        assert_eq!(fetched.last_used_at, Some(first_used_at));
+
+        // Using another copy should update the timestamp, which is also returned when fetching by doctype.
+        let second_used_at = first_used_at + Duration::days(1);
+        storage
+            .increment_mdoc_copies_usage_count(vec![fetched.mdoc_copy_id], second_used_at)
+            .await
+            .expect("Could not increment usage count for mdoc copy");
+
+        let fetched = storage
+            .fetch_unique_mdocs_by_doctypes(&HashSet::from(["org.iso.18013.5.1.mDL"]))
+            .await
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert_eq!(fetched.last_used_at, Some(second_used_at));
+    }
+
+    #[tokio::test]
+    async fn test_mdoc_storage_copies_mismatch() {
+        let mut storage = open_test_database_storage().await;
+
+        // Create two mdocs of the same doctype, but with different attributes.
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let key_factory = MockRemoteKeyFactory::default();
+        let mdoc1 = data::pid_family_name()
+            .into_first()
+            .unwrap()
+            .sign(&ca, &key_factory, NonZeroU8::MIN)
+            .await;
+        let mdoc2 = data::pid_given_name()
+            .into_first()
+            .unwrap()
+            .sign(&ca, &key_factory, NonZeroU8::MIN)
+            .await;
+        assert_eq!(mdoc1.doc_type(), mdoc2.doc_type());
+
+        // Inserting these as copies of the same mdoc should fail, without inserting any of the mdocs.
+        let error = storage
+            .insert_mdocs(
+                vec![
+                    MdocCopies::try_from(vec![Mdoc::new_example_mock()]).unwrap(),
+                    MdocCopies::try_from(vec![mdoc1, mdoc2]).unwrap(),
+                ],
+                &ISSUER_URL,
+            )
+            .await
+            .expect_err("inserting mdoc copies with different attributes should fail");
+
+        assert_matches!(error, StorageError::MdocCopiesMismatch(doc_type) if doc_type == PID_DOCTYPE);
+        assert!(storage.fetch_unique_mdocs().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mdoc_storage_replace_mdoc() {
+        let mut storage = open_test_database_storage().await;
+
+        let ca = Ca::generate_issuer_mock_ca().unwrap();
+        let key_factory = MockRemoteKeyFactory::default();
+        let old_mdoc = data::pid_family_name()
+            .into_first()
+            .unwrap()
+            .sign(&ca, &key_factory, NonZeroU8::MIN)
+            .await;
+        let new_mdoc = data::pid_given_name()
+            .into_first()
+            .unwrap()
+            .sign(&ca, &key_factory, NonZeroU8::MIN)
+            .await;
+
+        storage
+            .insert_mdocs(vec![MdocCopies::try_from(vec![old_mdoc]).unwrap()], &ISSUER_URL)
+            .await
+            .unwrap();
+        let old_mdoc_id = storage.fetch_unique_mdocs().await.unwrap().first().unwrap().mdoc_id;
+
+        // Replacing an mdoc that does not exist should fail, without inserting the new mdoc.
+        let error = storage
+            .replace_mdoc(
+                Uuid::new_v4(),
+                vec![MdocCopies::try_from(vec![new_mdoc.clone(), new_mdoc.clone()]).unwrap()],
+                &ISSUER_URL,
+            )
+            .await
+            .expect_err("replacing a non-existent mdoc should fail");
+
+        assert_matches!(error, StorageError::MdocNotFound(_));
+        let fetched_mdocs = storage.fetch_unique_mdocs().await.unwrap();
+        assert_eq!(fetched_mdocs.len(), 1);
+        assert_eq!(fetched_mdocs.first().unwrap().mdoc_id, old_mdoc_id);
+        assert!(storage.fetch_wallet_events().await.unwrap().is_empty());
+
+        // Replacing the existing mdoc should result in only the new mdoc being present, along with its issuer.
+        let new_issuer_url: BaseUrl = "https://other-issuer.example.com/".parse().unwrap();
+        storage
+            .replace_mdoc(
+                old_mdoc_id,
+                vec![MdocCopies::try_from(vec![new_mdoc.clone(), new_mdoc.clone()]).unwrap()],
+                &new_issuer_url,
+            )
+            .await
+            .expect("replacing an mdoc should succeed");
+
+        let fetched_mdocs = storage.fetch_unique_mdocs().await.unwrap();
+        assert_eq!(fetched_mdocs.len(), 1);
+        let fetched_mdoc = fetched_mdocs.into_iter().next().unwrap();
+        assert_ne!(fetched_mdoc.mdoc_id, old_mdoc_id);
+        assert_eq!(fetched_mdoc.mdoc.attributes(), new_mdoc.attributes());
+        assert_eq!(fetched_mdoc.issuer_url, Some(new_issuer_url));
+
+        // A renewed issuance event should have been logged for the new mdoc.
+        let events = storage.fetch_wallet_events().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            events.first().unwrap(),
+            WalletEvent::Issuance { mdocs, renewed: true, .. } if mdocs.0.contains_key(PID_DOCTYPE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mdoc_storage_fetch_and_delete_mdocs_by_issuer() {
+        let mut storage = open_test_database_storage().await;
+
+        let key_factory = MockRemoteKeyFactory::default();
+        let revoked_mdoc = data::pid_family_name()
+            .into_first()
+            .unwrap()
+            .sign(&Ca::generate_issuer_mock_ca().unwrap(), &key_factory, NonZeroU8::MIN)
+            .await;
+        let other_mdoc = data::pid_given_name()
+            .into_first()
+            .unwrap()
+            .sign(&Ca::generate_issuer_mock_ca().unwrap(), &key_factory, NonZeroU8::MIN)
+            .await;
+        let revoked_certificate = revoked_mdoc.issuer_certificate().unwrap();
+
+        storage
+            .insert_mdocs(
+                vec![
+                    MdocCopies::try_from(vec![revoked_mdoc.clone(), revoked_mdoc]).unwrap(),
+                    MdocCopies::try_from(vec![other_mdoc.clone()]).unwrap(),
+                ],
+                &ISSUER_URL,
+            )
+            .await
+            .unwrap();
+
+        // Only the mdoc signed using the revoked certificate should be returned.
+        let revoked_mdocs = storage.fetch_mdocs_by_issuer(&revoked_certificate).await.unwrap();
+        assert_eq!(revoked_mdocs.len(), 1);
+        assert_eq!(
+            revoked_mdocs.first().unwrap().mdoc.issuer_certificate().unwrap(),
+            revoked_certificate
+        );
+
+        // Deleting that mdoc should remove all of its copies, leaving only the other mdoc.
+        storage
+            .delete_mdocs(revoked_mdocs.into_iter().map(|stored| stored.mdoc_id).collect())
+            .await
+            .expect("deleting mdocs should succeed");
+
+        assert!(storage
+            .fetch_mdocs_by_issuer(&revoked_certificate)
+            .await
+            .unwrap()
+            .is_empty());
+        let fetched_mdocs = storage.fetch_unique_mdocs().await.unwrap();
+        assert_eq!(fetched_mdocs.len(), 1);
+        assert_eq!(
+            fetched_mdocs.first().unwrap().mdoc.attributes(),
+            other_mdoc.attributes()
+        );
+    }
+
     #[tokio::test]
     async fn test_event_log_storage_ordering() {
         let mut storage = open_test_database_storage().await;
@@ -1077,6 +1532,110 @@ pub(crate) mod tests {
             .unwrap());
     }
 
+    #[tokio::test]
+    async fn test_consent_decision_storage() {
+        let mut storage = open_test_database_storage().await;
+
+        let requests = ItemsRequests::from(data::pid_family_name());
+        let other_requests = ItemsRequests::from(data::pid_given_name());
+        let other_certificate = Ca::generate_reader_mock_ca()
+            .unwrap()
+            .generate_reader_mock(ReaderRegistration::new_mock().into())
+            .unwrap()
+            .certificate()
+            .clone();
+        let now = Utc.with_ymd_and_hms(2025, 2, 10, 12, 0, 0).unwrap();
+
+        // No consent should be remembered initially.
+        assert!(storage
+            .fetch_consent(READER_KEY.certificate(), &requests, now)
+            .await
+            .unwrap()
+            .is_none());
+
+        let expires_at = now + chrono::Duration::days(1);
+        storage
+            .record_consent(READER_KEY.certificate(), &requests, now, expires_at)
+            .await
+            .expect("recording consent should succeed");
+        storage
+            .record_consent(&other_certificate, &requests, now, expires_at)
+            .await
+            .expect("recording consent should succeed");
+
+        // The consent should only apply to the same relying party and the same set of attributes.
+        let consent = storage
+            .fetch_consent(READER_KEY.certificate(), &requests, now)
+            .await
+            .unwrap()
+            .expect("consent should be remembered");
+        assert_eq!(consent.expires_at, expires_at);
+        assert!(storage
+            .fetch_consent(READER_KEY.certificate(), &other_requests, now)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Recording consent again should replace the expiry, which means it can no longer be fetched once expired.
+        storage
+            .record_consent(
+                READER_KEY.certificate(),
+                &requests,
+                now,
+                now + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("recording consent should succeed");
+        let later = now + chrono::Duration::hours(2);
+        assert!(storage
+            .fetch_consent(READER_KEY.certificate(), &requests, later)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Recording another consent after that should delete the expired consent from the database.
+        storage
+            .record_consent(READER_KEY.certificate(), &other_requests, later, expires_at)
+            .await
+            .expect("recording consent should succeed");
+        let consents = consent_decision::Entity::find()
+            .all(storage.database().unwrap().connection())
+            .await
+            .unwrap();
+        assert_eq!(consents.len(), 2);
+        assert!(consents.iter().all(|consent| consent.expires_at == expires_at));
+
+        // Revoking the consents of one relying party should leave those of other relying parties intact.
+        storage
+            .record_consent(READER_KEY.certificate(), &requests, now, expires_at)
+            .await
+            .expect("recording consent should succeed");
+        storage
+            .revoke_consents(READER_KEY.certificate())
+            .await
+            .expect("revoking consents should succeed");
+        assert!(storage
+            .fetch_consent(READER_KEY.certificate(), &requests, now)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .fetch_consent(&other_certificate, &requests, now)
+            .await
+            .unwrap()
+            .is_some());
+
+        storage
+            .revoke_all_consents()
+            .await
+            .expect("revoking consents should succeed");
+        assert!(storage
+            .fetch_consent(&other_certificate, &requests, now)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     pub(crate) async fn test_history_ordering(storage: &mut impl Storage) {
         let timestamp = Utc.with_ymd_and_hms(2023, 11, 29, 10, 50, 45).unwrap();
         let timestamp_older = Utc.with_ymd_and_hms(2023, 11, 21, 13, 37, 00).unwrap();
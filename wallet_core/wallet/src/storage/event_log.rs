@@ -8,6 +8,7 @@ use serde_with::base64::Base64;
 use serde_with::serde_as;
 use uuid::Uuid;
 
+pub use entity::deletion_history_event;
 pub use entity::disclosure_history_event;
 pub use entity::issuance_history_event;
 use nl_wallet_mdoc::holder::Mdoc;
@@ -76,6 +77,8 @@ pub enum WalletEvent {
         id: Uuid,
         mdocs: EventDocuments,
         timestamp: DateTime<Utc>,
+        /// Whether these mdocs were issued to replace previously issued mdocs, rather than being newly added.
+        renewed: bool,
     },
     Disclosure {
         id: Uuid,
@@ -85,6 +88,11 @@ pub enum WalletEvent {
         status: EventStatus,
         r#type: DisclosureType,
     },
+    Deletion {
+        id: Uuid,
+        mdocs: EventDocuments,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 impl WalletEvent {
@@ -93,6 +101,16 @@ impl WalletEvent {
             id: Uuid::new_v4(),
             mdocs,
             timestamp: Utc::now(),
+            renewed: false,
+        }
+    }
+
+    pub fn new_renewal(mdocs: EventDocuments) -> Self {
+        Self::Issuance {
+            id: Uuid::new_v4(),
+            mdocs,
+            timestamp: Utc::now(),
+            renewed: true,
         }
     }
 
@@ -112,6 +130,14 @@ impl WalletEvent {
         }
     }
 
+    pub fn new_deletion(mdocs: EventDocuments) -> Self {
+        Self::Deletion {
+            id: Uuid::new_v4(),
+            mdocs,
+            timestamp: Utc::now(),
+        }
+    }
+
     /// Returns the associated doc_types for this event. Will return an empty set if there are no attributes.
     pub fn associated_doc_types(&self) -> IndexSet<&str> {
         match self {
@@ -122,6 +148,10 @@ impl WalletEvent {
             | Self::Disclosure {
                 documents: Some(EventDocuments(mdocs)),
                 ..
+            }
+            | Self::Deletion {
+                mdocs: EventDocuments(mdocs),
+                ..
             } => mdocs.keys().map(String::as_str).collect(),
             Self::Disclosure { documents: None, .. } => Default::default(),
         }
@@ -131,6 +161,7 @@ impl WalletEvent {
         match self {
             Self::Issuance { timestamp, .. } => timestamp,
             Self::Disclosure { timestamp, .. } => timestamp,
+            Self::Deletion { timestamp, .. } => timestamp,
         }
     }
 }
@@ -157,6 +188,19 @@ impl TryFrom<issuance_history_event::Model> for WalletEvent {
             id: event.id,
             mdocs: serde_json::from_value(event.attributes)?,
             timestamp: event.timestamp,
+            renewed: event.renewed,
+        };
+        Ok(result)
+    }
+}
+
+impl TryFrom<deletion_history_event::Model> for WalletEvent {
+    type Error = serde_json::Error;
+    fn try_from(event: deletion_history_event::Model) -> Result<Self, Self::Error> {
+        let result = Self::Deletion {
+            id: event.id,
+            mdocs: serde_json::from_value(event.attributes)?,
+            timestamp: event.timestamp,
         };
         Ok(result)
     }
@@ -166,16 +210,23 @@ impl TryFrom<issuance_history_event::Model> for WalletEvent {
 pub(crate) enum WalletEventModel {
     Issuance(issuance_history_event::Model),
     Disclosure(disclosure_history_event::Model),
+    Deletion(deletion_history_event::Model),
 }
 
 impl TryFrom<WalletEvent> for WalletEventModel {
     type Error = serde_json::Error;
     fn try_from(source: WalletEvent) -> Result<Self, Self::Error> {
         let result = match source {
-            WalletEvent::Issuance { id, mdocs, timestamp } => Self::Issuance(issuance_history_event::Model {
+            WalletEvent::Issuance {
+                id,
+                mdocs,
+                timestamp,
+                renewed,
+            } => Self::Issuance(issuance_history_event::Model {
                 attributes: serde_json::to_value(mdocs)?,
                 id,
                 timestamp,
+                renewed,
             }),
             WalletEvent::Disclosure {
                 id,
@@ -192,6 +243,11 @@ impl TryFrom<WalletEvent> for WalletEventModel {
                 status: status.into(),
                 r#type: r#type.into(),
             }),
+            WalletEvent::Deletion { id, mdocs, timestamp } => Self::Deletion(deletion_history_event::Model {
+                attributes: serde_json::to_value(mdocs)?,
+                id,
+                timestamp,
+            }),
         };
         Ok(result)
     }
@@ -320,6 +376,7 @@ mod test {
                 id: Uuid::new_v4(),
                 mdocs,
                 timestamp,
+                renewed: false,
             }
         }
 
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use chrono::DateTime;
 use chrono::Duration;
 use chrono::Utc;
 use indexmap::IndexMap;
@@ -8,15 +9,20 @@ use sea_orm::DbErr;
 use uuid::Uuid;
 
 use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use nl_wallet_mdoc::verifier::ItemsRequests;
 use nl_wallet_mdoc::DocType;
 use openid4vc::credential::MdocCopies;
+use wallet_common::urls::BaseUrl;
 
 use crate::storage::event_log::WalletEventModel;
 
+use super::consent::requested_attributes_hash;
+use super::consent::ConsentDecision;
 use super::data::KeyedData;
 use super::data::RegistrationData;
 use super::event_log::WalletEvent;
 use super::Storage;
+use super::StorageError;
 use super::StorageResult;
 use super::StorageState;
 use super::StoredMdocCopy;
@@ -27,14 +33,38 @@ pub enum KeyedDataResult {
     Error,
 }
 
+/// An mdoc stored in [`MockStorage`], along with the data that [`Storage`] stores alongside it.
+#[derive(Debug)]
+pub struct MockStoredMdoc {
+    pub mdoc_id: Uuid,
+    /// The id of the copy that is returned when fetching this mdoc, as the mock does not track individual copies.
+    pub mdoc_copy_id: Uuid,
+    pub issuer_url: Option<BaseUrl>,
+    pub mdoc_copies: MdocCopies,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<MdocCopies> for MockStoredMdoc {
+    fn from(value: MdocCopies) -> Self {
+        Self {
+            mdoc_id: Uuid::new_v4(),
+            mdoc_copy_id: Uuid::new_v4(),
+            issuer_url: None,
+            mdoc_copies: value,
+            last_used_at: None,
+        }
+    }
+}
+
 /// This is a mock implementation of [`Storage`], used for testing [`crate::Wallet`].
 #[derive(Debug)]
 pub struct MockStorage {
     pub state: StorageState,
     pub data: HashMap<&'static str, KeyedDataResult>,
-    pub mdocs: IndexMap<DocType, Vec<MdocCopies>>,
+    pub mdocs: IndexMap<DocType, Vec<MockStoredMdoc>>,
     pub mdoc_copies_usage_counts: HashMap<Uuid, u32>,
     pub event_log: Vec<WalletEvent>,
+    pub consent_decisions: HashMap<(Vec<u8>, Vec<u8>), ConsentDecision>,
     pub has_query_error: bool,
 }
 
@@ -55,6 +85,7 @@ impl MockStorage {
             mdocs: IndexMap::new(),
             mdoc_copies_usage_counts: HashMap::new(),
             event_log: vec![],
+            consent_decisions: HashMap::new(),
             has_query_error: false,
         }
     }
@@ -134,20 +165,60 @@ impl Storage for MockStorage {
         Ok(())
     }
 
-    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>) -> StorageResult<()> {
+    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()> {
         self.check_query_error()?;
 
         for mdoc_copies in mdocs {
             self.mdocs
                 .entry(mdoc_copies.first().doc_type().clone())
                 .or_default()
-                .push(mdoc_copies);
+                .push(MockStoredMdoc {
+                    issuer_url: Some(issuer_url.clone()),
+                    ..mdoc_copies.into()
+                });
         }
 
         Ok(())
     }
 
-    async fn increment_mdoc_copies_usage_count(&mut self, mdoc_copy_ids: Vec<Uuid>) -> StorageResult<()> {
+    async fn replace_mdoc(&mut self, old_id: Uuid, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()> {
+        self.check_query_error()?;
+
+        let (doc_type, position) = self
+            .mdocs
+            .iter()
+            .find_map(|(doc_type, mdocs)| {
+                mdocs
+                    .iter()
+                    .position(|stored| stored.mdoc_id == old_id)
+                    .map(|position| (doc_type.clone(), position))
+            })
+            .ok_or(StorageError::MdocNotFound(old_id))?;
+
+        let event = WalletEvent::new_renewal(
+            mdocs
+                .iter()
+                .map(|mdoc_copies| mdoc_copies.first().clone())
+                .collect::<Vec<_>>()
+                .try_into()?,
+        );
+
+        self.mdocs.get_mut(&doc_type).unwrap().remove(position);
+        self.insert_mdocs(mdocs, issuer_url).await?;
+        self.log_wallet_event(event).await
+    }
+
+    async fn increment_mdoc_copies_usage_count(
+        &mut self,
+        mdoc_copy_ids: Vec<Uuid>,
+        used_at: DateTime<Utc>,
+    ) -> StorageResult<()> {
+        self.mdocs
+            .values_mut()
+            .flatten()
+            .filter(|stored| mdoc_copy_ids.contains(&stored.mdoc_copy_id))
+            .for_each(|stored| stored.last_used_at = Some(used_at));
+
         mdoc_copy_ids.into_iter().for_each(|mdoc_copy_id| {
             self.mdoc_copies_usage_counts
                 .entry(mdoc_copy_id)
@@ -161,15 +232,17 @@ impl Storage for MockStorage {
     async fn fetch_unique_mdocs(&self) -> StorageResult<Vec<StoredMdocCopy>> {
         self.check_query_error()?;
 
-        // Get a single copy of every unique Mdoc, along with a random `Uuid`.
+        // Get a single copy of every unique Mdoc, along with its `Uuid` and the `Uuid` of that copy.
         let mdocs = self
             .mdocs
             .values()
             .flatten()
-            .map(|mdoc_copies| StoredMdocCopy {
-                mdoc_id: Uuid::new_v4(),
-                mdoc_copy_id: Uuid::new_v4(),
-                mdoc: mdoc_copies.first().clone(),
+            .map(|stored| StoredMdocCopy {
+                mdoc_id: stored.mdoc_id,
+                mdoc_copy_id: stored.mdoc_copy_id,
+                mdoc: stored.mdoc_copies.first().clone(),
+                issuer_url: stored.issuer_url.clone(),
+                last_used_at: stored.last_used_at,
             })
             .collect();
 
@@ -198,11 +271,23 @@ impl Storage for MockStorage {
         Ok(result)
     }
 
+    async fn delete_mdocs(&mut self, mdoc_ids: Vec<Uuid>) -> StorageResult<()> {
+        self.check_query_error()?;
+
+        for mdocs in self.mdocs.values_mut() {
+            mdocs.retain(|stored| !mdoc_ids.contains(&stored.mdoc_id));
+        }
+        self.mdocs.retain(|_, mdocs| !mdocs.is_empty());
+
+        Ok(())
+    }
+
     async fn log_wallet_event(&mut self, event: WalletEvent) -> StorageResult<()> {
         // Convert to database entity and back to check whether the `TryFrom` implementations are complete.
         let converted_event = match WalletEventModel::try_from(event.clone())? {
             WalletEventModel::Issuance(entity) => entity.try_into()?,
             WalletEventModel::Disclosure(entity) => entity.try_into()?,
+            WalletEventModel::Deletion(entity) => entity.try_into()?,
         };
         assert_eq!(event, converted_event);
         self.event_log.push(converted_event);
@@ -247,18 +332,78 @@ impl Storage for MockStorage {
         self.check_query_error()?;
 
         let exists = self.event_log.iter().any(|event| match event {
-            WalletEvent::Issuance { .. } => false,
+            WalletEvent::Issuance { .. } | WalletEvent::Deletion { .. } => false,
             WalletEvent::Disclosure { reader_certificate, .. } => reader_certificate.as_ref() == certificate,
         });
         Ok(exists)
     }
+
+    async fn record_consent(
+        &mut self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        granted_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> StorageResult<()> {
+        self.check_query_error()?;
+
+        self.consent_decisions
+            .retain(|_, consent| !consent.is_expired_at(granted_at));
+
+        let key = (certificate.as_ref().to_vec(), requested_attributes_hash(requests)?);
+        let consent = ConsentDecision { granted_at, expires_at };
+        self.consent_decisions.insert(key, consent);
+
+        Ok(())
+    }
+
+    async fn fetch_consent(
+        &self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        time: DateTime<Utc>,
+    ) -> StorageResult<Option<ConsentDecision>> {
+        self.check_query_error()?;
+
+        let key = (certificate.as_ref().to_vec(), requested_attributes_hash(requests)?);
+        let consent = self
+            .consent_decisions
+            .get(&key)
+            .filter(|consent| !consent.is_expired_at(time))
+            .cloned();
+
+        Ok(consent)
+    }
+
+    async fn revoke_consents(&mut self, certificate: &BorrowingCertificate) -> StorageResult<()> {
+        self.check_query_error()?;
+
+        self.consent_decisions
+            .retain(|(consent_certificate, _), _| consent_certificate != certificate.as_ref());
+
+        Ok(())
+    }
+
+    async fn revoke_all_consents(&mut self) -> StorageResult<()> {
+        self.check_query_error()?;
+
+        self.consent_decisions.clear();
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
     use serde::Deserialize;
     use serde::Serialize;
 
+    use nl_wallet_mdoc::holder::Mdoc;
+    use openid4vc::credential::MdocCopies;
+    use wallet_common::urls::BaseUrl;
+
     use crate::storage::database_storage::tests::test_history_by_doc_type;
     use crate::storage::database_storage::tests::test_history_ordering;
     use crate::storage::KeyedData;
@@ -318,4 +463,28 @@ mod tests {
         storage.open().await.unwrap();
         test_history_by_doc_type(&mut storage).await;
     }
+
+    #[tokio::test]
+    async fn mdoc_last_used_at() {
+        let mut storage = MockStorage::default();
+        storage.open().await.unwrap();
+
+        let mdoc_copies = MdocCopies::try_from(vec![Mdoc::new_example_mock()]).unwrap();
+        let issuer_url: BaseUrl = "https://example.com".parse().unwrap();
+        storage.insert_mdocs(vec![mdoc_copies], &issuer_url).await.unwrap();
+
+        let mdoc_copy = storage.fetch_unique_mdocs().await.unwrap().into_iter().next().unwrap();
+        assert!(mdoc_copy.last_used_at.is_none());
+
+        let used_at = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        storage
+            .increment_mdoc_copies_usage_count(vec![mdoc_copy.mdoc_copy_id], used_at)
+            .await
+            .unwrap();
+
+        let fetched = storage.fetch_unique_mdocs().await.unwrap().into_iter().next().unwrap();
+        assert_eq!(fetched.mdoc_copy_id, mdoc_copy.mdoc_copy_id);
+        assert_eq!(fetched.last_used_at, Some(used_at));
+        assert_eq!(storage.mdoc_copies_usage_counts.get(&mdoc_copy.mdoc_copy_id), Some(&1));
+    }
 }
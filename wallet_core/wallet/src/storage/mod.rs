@@ -1,3 +1,4 @@
+mod consent;
 mod data;
 mod database;
 mod database_storage;
@@ -15,15 +16,22 @@ use std::array::TryFromSliceError;
 use std::collections::HashSet;
 use std::io;
 
+use chrono::DateTime;
+use chrono::Utc;
 use sea_orm::DbErr;
 use uuid::Uuid;
 
 use error_category::ErrorCategory;
 use nl_wallet_mdoc::holder::Mdoc;
+use nl_wallet_mdoc::utils::cose::CoseError;
 use nl_wallet_mdoc::utils::serialization::CborError;
 use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use nl_wallet_mdoc::verifier::ItemsRequests;
 use openid4vc::credential::MdocCopies;
+use wallet_common::urls::BaseUrl;
+use wallet_common::urls::BaseUrlParseError;
 
+pub use self::consent::ConsentDecision;
 pub use self::data::ChangePinData;
 pub use self::data::InstructionData;
 pub use self::data::KeyData;
@@ -39,6 +47,8 @@ pub use self::key_file::KeyFileError;
 
 #[cfg(any(test, feature = "mock"))]
 pub use self::mock_storage::MockStorage;
+#[cfg(any(test, feature = "mock"))]
+pub use self::mock_storage::MockStoredMdoc;
 
 /// This represents the current start of [`Storage`].
 #[derive(Debug, Clone, Copy)]
@@ -76,6 +86,17 @@ pub enum StorageError {
     SqlCipherKey(#[from] TryFromSliceError),
     #[error("{0}")]
     KeyFile(#[from] KeyFileError),
+    #[error("copies of mdoc with doctype {0} do not contain identical attributes")]
+    #[category(critical)]
+    MdocCopiesMismatch(String),
+    #[error("mdoc with id {0} not found")]
+    #[category(critical)]
+    MdocNotFound(Uuid),
+    #[error("could not read issuer certificate from mdoc: {0}")]
+    IssuerCertificate(#[from] CoseError),
+    #[error("could not parse issuer URL of mdoc: {0}")]
+    #[category(critical)]
+    IssuerUrl(#[from] BaseUrlParseError),
 }
 
 pub type StorageResult<T> = Result<T, StorageError>;
@@ -85,6 +106,11 @@ pub struct StoredMdocCopy {
     pub mdoc_id: Uuid,
     pub mdoc_copy_id: Uuid,
     pub mdoc: Mdoc,
+    /// The URL of the Credential Issuer from which this mdoc was obtained. This is absent for mdocs that were stored
+    /// before this was recorded, all of which were issued by the PID issuer.
+    pub issuer_url: Option<BaseUrl>,
+    /// The last time any copy of this mdoc was disclosed, if ever.
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 /// This trait abstracts the persistent storage for the wallet.
@@ -107,15 +133,66 @@ pub trait Storage {
     async fn upsert_data<D: KeyedData>(&mut self, data: &D) -> StorageResult<()>;
     async fn delete_data<D: KeyedData>(&mut self) -> StorageResult<()>;
 
-    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>) -> StorageResult<()>;
-    async fn increment_mdoc_copies_usage_count(&mut self, mdoc_copy_ids: Vec<Uuid>) -> StorageResult<()>;
+    /// Insert `mdocs`, which were obtained from the Credential Issuer at `issuer_url`.
+    async fn insert_mdocs(&mut self, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()>;
+    /// Atomically replace the mdoc with id `old_id` by `mdocs`, which were obtained from the Credential Issuer at
+    /// `issuer_url`, logging a renewed issuance event for the latter.
+    async fn replace_mdoc(&mut self, old_id: Uuid, mdocs: Vec<MdocCopies>, issuer_url: &BaseUrl) -> StorageResult<()>;
+    /// Increment the usage count of the mdoc copies with the provided ids and record `used_at` as the time at which
+    /// the mdocs they belong to were last used.
+    async fn increment_mdoc_copies_usage_count(
+        &mut self,
+        mdoc_copy_ids: Vec<Uuid>,
+        used_at: DateTime<Utc>,
+    ) -> StorageResult<()>;
     async fn fetch_unique_mdocs(&self) -> StorageResult<Vec<StoredMdocCopy>>;
     async fn fetch_unique_mdocs_by_doctypes(&self, doc_types: &HashSet<&str>) -> StorageResult<Vec<StoredMdocCopy>>;
     async fn has_any_mdocs_with_doctype(&self, doc_type: &str) -> StorageResult<bool>;
+    /// Atomically delete the mdocs with the provided ids, including all of their copies.
+    async fn delete_mdocs(&mut self, mdoc_ids: Vec<Uuid>) -> StorageResult<()>;
+
+    /// Fetch a single copy of every unique mdoc that was signed using the provided issuer certificate.
+    async fn fetch_mdocs_by_issuer(
+        &self,
+        issuer_certificate: &BorrowingCertificate,
+    ) -> StorageResult<Vec<StoredMdocCopy>> {
+        let mut mdocs = Vec::new();
+
+        for stored_mdoc in self.fetch_unique_mdocs().await? {
+            if stored_mdoc.mdoc.issuer_certificate()? == *issuer_certificate {
+                mdocs.push(stored_mdoc);
+            }
+        }
+
+        Ok(mdocs)
+    }
 
     async fn log_wallet_event(&mut self, event: WalletEvent) -> StorageResult<()>;
     async fn fetch_wallet_events(&self) -> StorageResult<Vec<WalletEvent>>;
     async fn fetch_recent_wallet_events(&self) -> StorageResult<Vec<WalletEvent>>;
     async fn fetch_wallet_events_by_doc_type(&self, doc_type: &str) -> StorageResult<Vec<WalletEvent>>;
     async fn did_share_data_with_relying_party(&self, certificate: &BorrowingCertificate) -> StorageResult<bool>;
+
+    /// Remember that the user consented at `granted_at` to disclosing the attributes in `requests` to the relying party
+    /// identified by `certificate`, until `expires_at`. This replaces any earlier decision for the same set of
+    /// attributes and deletes all decisions that have expired at `granted_at`.
+    async fn record_consent(
+        &mut self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        granted_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> StorageResult<()>;
+    /// Fetch the remembered consent decision for disclosing exactly the attributes in `requests` to the relying party
+    /// identified by `certificate`, if present and not yet expired at `time`.
+    async fn fetch_consent(
+        &self,
+        certificate: &BorrowingCertificate,
+        requests: &ItemsRequests,
+        time: DateTime<Utc>,
+    ) -> StorageResult<Option<ConsentDecision>>;
+    /// Forget all remembered consent decisions for the relying party identified by `certificate`.
+    async fn revoke_consents(&mut self, certificate: &BorrowingCertificate) -> StorageResult<()>;
+    /// Forget all remembered consent decisions, for any relying party.
+    async fn revoke_all_consents(&mut self) -> StorageResult<()>;
 }
@@ -1,6 +1,10 @@
 use std::array::TryFromSliceError;
 use std::fmt::Write;
 
+use derive_more::Debug;
+use subtle::Choice;
+use subtle::ConstantTimeEq;
+
 // Utility function for converting bytes to uppercase hex.
 fn bytes_to_hex(bytes: &[u8]) -> String {
     bytes
@@ -16,9 +20,13 @@ const SALT_LENGTH: usize = 16;
 
 /// This represents a 32-bytes encryption key and 16-byte salt. See:
 /// https://www.zetetic.net/sqlcipher/sqlcipher-api/#example-3-raw-key-data-with-explicit-salt-without-key-derivation
-#[derive(Clone, Copy)]
+///
+/// Note that the key material is never included in the [`Debug`] output and that keys are compared in constant time.
+#[derive(Clone, Copy, Debug)]
 pub struct SqlCipherKey {
+    #[debug(skip)]
     key: [u8; KEY_LENGTH],
+    #[debug(skip)]
     salt: Option<[u8; SALT_LENGTH]>,
 }
 
@@ -36,6 +44,27 @@ impl SqlCipherKey {
     }
 }
 
+impl ConstantTimeEq for SqlCipherKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Only the presence of the salt is not compared in constant time, as its length is not secret.
+        let salt_eq = match (&self.salt, &other.salt) {
+            (Some(salt), Some(other_salt)) => salt.as_slice().ct_eq(other_salt.as_slice()),
+            (None, None) => Choice::from(1),
+            _ => Choice::from(0),
+        };
+
+        self.key.as_slice().ct_eq(other.key.as_slice()) & salt_eq
+    }
+}
+
+impl PartialEq for SqlCipherKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SqlCipherKey {}
+
 /// Conversion from bytes by implementing TryFrom, which accepts a byte slice
 /// of either N (no salt) or N + M (with salt) length.
 impl TryFrom<&[u8]> for SqlCipherKey {
@@ -108,4 +137,37 @@ mod tests {
             "x'BEEFBABEBEEFBABEBEEFBABE01010101BEEFBABEBEEFBABEBEEFBABEBEEFBABECAFEBABE01010101CAFEBABECAFEBABE'"
         );
     }
+
+    #[test]
+    fn test_sql_cipher_key_eq() {
+        let key_data = [1; 32];
+        let other_key_data = [2; 32];
+        let salt_data = [3; 16];
+
+        let key = SqlCipherKey::new(key_data, None);
+        let key_with_salt = SqlCipherKey::new(key_data, Some(salt_data));
+
+        assert!(bool::from(key.ct_eq(&SqlCipherKey::new(key_data, None))));
+        assert!(bool::from(
+            key_with_salt.ct_eq(&SqlCipherKey::new(key_data, Some(salt_data)))
+        ));
+        assert!(!bool::from(key.ct_eq(&SqlCipherKey::new(other_key_data, None))));
+        assert!(!bool::from(key.ct_eq(&key_with_salt)));
+        assert!(!bool::from(
+            key_with_salt.ct_eq(&SqlCipherKey::new(key_data, Some([4; 16])))
+        ));
+
+        assert_eq!(key, SqlCipherKey::new(key_data, None));
+        assert_ne!(key, key_with_salt);
+    }
+
+    #[test]
+    fn test_sql_cipher_key_debug() {
+        let key = SqlCipherKey::new([0xAB; 32], Some([0xCD; 16]));
+        let debug = format!("{:?}", key);
+
+        assert!(!debug.contains("171"));
+        assert!(!debug.contains("205"));
+        assert!(!debug.to_uppercase().contains("ABAB"));
+    }
 }
@@ -105,6 +105,8 @@ mod tests {
 
     use assert_matches::assert_matches;
 
+    use openid4vc::credential::MdocCopies;
+
     use super::super::test;
     use super::super::test::WalletDeviceVendor;
     use super::super::test::WalletWithMocks;
@@ -146,12 +148,10 @@ mod tests {
         // The database contains a single `Mdoc`.
         let mdoc = test::create_full_pid_mdoc();
         let mdoc_doc_type = mdoc.doc_type().clone();
-        wallet
-            .storage
-            .write()
-            .await
-            .mdocs
-            .insert(mdoc.doc_type().clone(), vec![vec![mdoc].try_into().unwrap()]);
+        wallet.storage.write().await.mdocs.insert(
+            mdoc.doc_type().clone(),
+            vec![MdocCopies::try_from(vec![mdoc]).unwrap().into()],
+        );
 
         // Register mock document_callback
         let attestations = test::setup_mock_attestations_callback(&mut wallet)
@@ -188,12 +188,10 @@ mod tests {
 
         // The database contains a single `Mdoc`, without Issuer registration.
         let mdoc = test::create_full_pid_mdoc_unauthenticated();
-        wallet
-            .storage
-            .write()
-            .await
-            .mdocs
-            .insert(mdoc.doc_type().clone(), vec![vec![mdoc].try_into().unwrap()]);
+        wallet.storage.write().await.mdocs.insert(
+            mdoc.doc_type().clone(),
+            vec![MdocCopies::try_from(vec![mdoc]).unwrap().into()],
+        );
 
         // Register mock attestation_callback
         let (attestations, error) = test::setup_mock_attestations_callback(&mut wallet)
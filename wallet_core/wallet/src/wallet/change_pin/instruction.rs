@@ -39,6 +39,8 @@ impl ChangePinClientError for AccountProviderError {
             Self::Response(error) => error.is_network_error(),
             Self::Networking(_) => true,
             Self::BaseUrl(_) => false,
+            // The request was not sent, but the Wallet Provider is unavailable, so retrying later is appropriate.
+            Self::CircuitOpen => true,
         }
     }
 }
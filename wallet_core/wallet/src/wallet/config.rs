@@ -1,9 +1,16 @@
 use std::sync::Arc;
 
+use tracing::info;
+use tracing::instrument;
+
+use error_category::sentry_capture_error;
 use platform_support::attested_key::AttestedKeyHolder;
 use wallet_common::config::wallet_config::WalletConfiguration;
 use wallet_common::update_policy::VersionState;
 
+use crate::config::ConfigSignatureInfo;
+use crate::config::ConfigSignatureRepository;
+use crate::config::ConfigurationError;
 use crate::repository::ObservableRepository;
 use crate::repository::Repository;
 use crate::repository::RepositoryCallback;
@@ -51,6 +58,22 @@ where
     }
 }
 
+impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
+where
+    CR: ConfigSignatureRepository,
+    AKH: AttestedKeyHolder,
+{
+    /// Re-verify the signature of the active configuration against the configured public key, for diagnostic
+    /// purposes. This is separate from the verification that takes place whenever the configuration is fetched.
+    #[instrument(skip_all)]
+    #[sentry_capture_error]
+    pub fn verify_config_signature(&self) -> Result<ConfigSignatureInfo, ConfigurationError> {
+        info!("Verifying configuration signature");
+
+        self.config_repository.verify_signature()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -61,6 +84,7 @@ mod tests {
     use wallet_common::config::wallet_config::WalletConfiguration;
 
     use crate::config::default_wallet_config;
+    use crate::config::ConfigSignatureInfo;
 
     use super::super::test::WalletDeviceVendor;
     use super::super::test::WalletWithMocks;
@@ -110,4 +134,21 @@ mod tests {
 
         assert_eq!(Arc::strong_count(&configs), 1);
     }
+
+    #[tokio::test]
+    async fn test_wallet_verify_config_signature() {
+        let wallet = WalletWithMocks::new_unregistered(WalletDeviceVendor::Apple);
+
+        // The mock configuration repository only contains the configuration bundled with the app.
+        let info = wallet
+            .verify_config_signature()
+            .expect("verifying configuration signature should succeed");
+
+        assert_eq!(
+            info,
+            ConfigSignatureInfo::Unsigned {
+                version: default_wallet_config().version
+            }
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use tracing::info;
+use tracing::instrument;
+use tracing::warn;
+use uuid::Uuid;
+
+use error_category::sentry_capture_error;
+use error_category::ErrorCategory;
+use openid4vc::issuance_session::IssuanceSession;
+use openid4vc::issuance_session::IssuanceSessionError;
+use openid4vc::metadata::CredentialMetadataDrift;
+use platform_support::attested_key::AttestedKeyHolder;
+use wallet_common::config::wallet_config::WalletConfiguration;
+use wallet_common::update_policy::VersionState;
+
+use crate::repository::Repository;
+use crate::storage::Storage;
+use crate::storage::StorageError;
+use crate::storage::StoredMdocCopy;
+
+use super::issuance::pid_issuer_http_client;
+use super::Wallet;
+
+#[derive(Debug, thiserror::Error, ErrorCategory)]
+#[category(defer)]
+pub enum CredentialMetadataError {
+    #[category(expected)]
+    #[error("app version is blocked")]
+    VersionBlocked,
+    #[error("wallet is not registered")]
+    #[category(expected)]
+    NotRegistered,
+    #[error("wallet is locked")]
+    #[category(expected)]
+    Locked,
+    #[error("could not fetch mdoc from database storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("mdoc with id {0} not found")]
+    #[category(critical)]
+    MdocNotFound(Uuid),
+    #[error("could not discover issuer metadata: {0}")]
+    Metadata(#[from] IssuanceSessionError),
+}
+
+impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
+where
+    CR: Repository<Arc<WalletConfiguration>>,
+    UR: Repository<VersionState>,
+    S: Storage,
+    AKH: AttestedKeyHolder,
+    IS: IssuanceSession,
+{
+    /// Fetch the current metadata of the Credential Issuer of the stored mdoc with the given id and check whether the
+    /// attributes of the mdoc still conform to it. Any differences found are returned, an empty result means no drift
+    /// was detected.
+    #[instrument(skip_all)]
+    #[sentry_capture_error]
+    pub async fn revalidate_credential_metadata(
+        &self,
+        mdoc_id: Uuid,
+    ) -> Result<Vec<CredentialMetadataDrift>, CredentialMetadataError> {
+        info!("Revalidating credential metadata");
+
+        info!("Checking if blocked");
+        if self.is_blocked() {
+            return Err(CredentialMetadataError::VersionBlocked);
+        }
+
+        info!("Checking if registered");
+        if !self.registration.is_registered() {
+            return Err(CredentialMetadataError::NotRegistered);
+        }
+
+        info!("Checking if locked");
+        if self.lock.is_locked() {
+            return Err(CredentialMetadataError::Locked);
+        }
+
+        info!("Fetching mdoc from storage");
+        let StoredMdocCopy { mdoc, issuer_url, .. } = self
+            .storage
+            .read()
+            .await
+            .fetch_unique_mdocs()
+            .await?
+            .into_iter()
+            .find(|stored| stored.mdoc_id == mdoc_id)
+            .ok_or(CredentialMetadataError::MdocNotFound(mdoc_id))?;
+
+        info!("Discovering Credential Issuer metadata");
+        // Mdocs for which the issuer was not recorded can only have been issued by the PID issuer.
+        let issuer_url = issuer_url.unwrap_or_else(|| self.config_repository.get().pid_issuance.pid_issuer_url.clone());
+        let metadata = IS::discover_metadata(pid_issuer_http_client().into(), issuer_url).await?;
+
+        let drift = metadata
+            .issuer_config
+            .mdoc_metadata_drift(mdoc.doc_type(), &mdoc.attributes());
+
+        if !drift.is_empty() {
+            warn!("Stored mdoc no longer conforms to the Credential Issuer metadata: {drift:?}");
+        }
+
+        Ok(drift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use assert_matches::assert_matches;
+    use rstest::rstest;
+    use serial_test::serial;
+
+    use openid4vc::credential::MdocCopies;
+    use openid4vc::metadata::CredentialMetadata;
+    use openid4vc::metadata::IssuerMetadata;
+    use openid4vc::mock::MockIssuanceSession;
+
+    use crate::document::PID_DOCTYPE;
+    use crate::storage::MockStoredMdoc;
+
+    use super::super::test;
+    use super::super::test::WalletDeviceVendor;
+    use super::super::test::WalletWithMocks;
+    use super::*;
+
+    async fn setup_wallet_with_pid() -> WalletWithMocks {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        let mdoc = test::create_full_pid_mdoc();
+        wallet
+            .storage
+            .write()
+            .await
+            .insert_mdocs(vec![vec![mdoc].try_into().unwrap()], &test::ISSUER_URL)
+            .await
+            .unwrap();
+
+        wallet
+    }
+
+    #[tokio::test]
+    #[serial(MockIssuanceSession)]
+    async fn test_revalidate_credential_metadata() {
+        let wallet = setup_wallet_with_pid().await;
+        let mdoc_id = wallet.storage.read().await.fetch_unique_mdocs().await.unwrap()[0].mdoc_id;
+
+        // Have the issuer announce the PID doctype without any claims.
+        let metadata_context = MockIssuanceSession::metadata_context();
+        metadata_context.expect().return_once(|base_url| {
+            let credential_metadata: CredentialMetadata = serde_json::from_value(serde_json::json!({
+                "format": "mso_mdoc",
+                "doctype": PID_DOCTYPE,
+                "claims": {},
+            }))
+            .unwrap();
+
+            let mut metadata = IssuerMetadata::new_mock(&base_url);
+            metadata.issuer_config.credential_configurations_supported =
+                HashMap::from([("pid".to_string(), credential_metadata)]);
+
+            Ok(metadata)
+        });
+
+        let drift = wallet
+            .revalidate_credential_metadata(mdoc_id)
+            .await
+            .expect("could not revalidate credential metadata");
+
+        // All of the attributes of the PID should be flagged as unknown.
+        assert!(!drift.is_empty());
+        assert!(drift
+            .iter()
+            .all(|drift| matches!(drift, CredentialMetadataDrift::UnknownAttribute { .. })));
+    }
+
+    #[tokio::test]
+    #[serial(MockIssuanceSession)]
+    async fn test_revalidate_credential_metadata_doc_type_not_supported() {
+        let wallet = setup_wallet_with_pid().await;
+        let mdoc_id = wallet.storage.read().await.fetch_unique_mdocs().await.unwrap()[0].mdoc_id;
+
+        // The mock metadata does not announce any credential configurations.
+        let metadata_context = MockIssuanceSession::metadata_context();
+        metadata_context
+            .expect()
+            .return_once(|base_url| Ok(IssuerMetadata::new_mock(&base_url)));
+
+        let drift = wallet
+            .revalidate_credential_metadata(mdoc_id)
+            .await
+            .expect("could not revalidate credential metadata");
+
+        assert_eq!(drift, vec![CredentialMetadataDrift::DocTypeNotSupported]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[serial(MockIssuanceSession)]
+    async fn test_revalidate_credential_metadata_issuer_url(#[values(true, false)] issuer_url_recorded: bool) {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Store an mdoc for which the issuer may not have been recorded, in which case the PID issuer should be used.
+        let stored_mdoc = MockStoredMdoc {
+            issuer_url: issuer_url_recorded.then(|| test::ISSUER_URL.clone()),
+            ..MdocCopies::try_from(vec![test::create_full_pid_mdoc()]).unwrap().into()
+        };
+        let mdoc_id = stored_mdoc.mdoc_id;
+        wallet
+            .storage
+            .write()
+            .await
+            .mdocs
+            .insert(PID_DOCTYPE.to_string(), vec![stored_mdoc]);
+
+        let expected_issuer_url = if issuer_url_recorded {
+            test::ISSUER_URL.clone()
+        } else {
+            wallet.config_repository.get().pid_issuance.pid_issuer_url.clone()
+        };
+
+        // The metadata should be discovered at the issuer of the mdoc.
+        let metadata_context = MockIssuanceSession::metadata_context();
+        metadata_context.expect().return_once(move |base_url| {
+            assert_eq!(base_url, expected_issuer_url);
+
+            Ok(IssuerMetadata::new_mock(&base_url))
+        });
+
+        wallet
+            .revalidate_credential_metadata(mdoc_id)
+            .await
+            .expect("could not revalidate credential metadata");
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_credential_metadata_error_mdoc_not_found() {
+        let wallet = setup_wallet_with_pid().await;
+
+        let error = wallet
+            .revalidate_credential_metadata(Uuid::new_v4())
+            .await
+            .expect_err("revalidating credential metadata should fail");
+
+        assert_matches!(error, CredentialMetadataError::MdocNotFound(_));
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_credential_metadata_error_locked() {
+        let mut wallet = setup_wallet_with_pid().await;
+
+        wallet.lock().await;
+
+        let error = wallet
+            .revalidate_credential_metadata(Uuid::new_v4())
+            .await
+            .expect_err("revalidating credential metadata should fail");
+
+        assert_matches!(error, CredentialMetadataError::Locked);
+    }
+}
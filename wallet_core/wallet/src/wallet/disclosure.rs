@@ -1,7 +1,10 @@
 use std::collections::HashSet;
+use std::convert::Infallible;
 use std::sync::Arc;
 
+use chrono::Utc;
 use indexmap::IndexMap;
+use itertools::Itertools;
 use tracing::error;
 use tracing::info;
 use tracing::instrument;
@@ -10,12 +13,17 @@ use uuid::Uuid;
 
 use error_category::sentry_capture_error;
 use error_category::ErrorCategory;
+use nl_wallet_mdoc::holder::DisclosureRequestMatch;
 use nl_wallet_mdoc::holder::MdocDataSource;
 use nl_wallet_mdoc::holder::ProposedAttributes;
 use nl_wallet_mdoc::holder::StoredMdoc;
+use nl_wallet_mdoc::identifiers::AttributeIdentifier;
+use nl_wallet_mdoc::identifiers::AttributeIdentifierHolder;
 use nl_wallet_mdoc::utils::cose::CoseError;
 use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
 use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use nl_wallet_mdoc::verifier::ItemsRequests;
+use nl_wallet_mdoc::SessionTranscript;
 use openid4vc::disclosure_session::VpClientError;
 use openid4vc::verifier::SessionType;
 use platform_support::attested_key::AttestedKeyHolder;
@@ -25,6 +33,7 @@ use wallet_common::update_policy::VersionState;
 use wallet_common::urls;
 
 use crate::account_provider::AccountProviderClient;
+use crate::attestation::LocalizedString;
 use crate::config::UNIVERSAL_LINK_BASE_URL;
 use crate::disclosure::DisclosureUriError;
 use crate::disclosure::DisclosureUriSource;
@@ -62,6 +71,66 @@ pub struct DisclosureProposal {
     pub is_login_flow: bool,
 }
 
+/// The result of matching a disclosure request against the stored mdocs, without contacting the relying party. This
+/// mirrors the outcomes of matching the request during an actual disclosure session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisclosurePreview {
+    /// All of the requested attributes are available, each from the listed mdoc.
+    Proposal(Vec<DisclosurePreviewAttribute>),
+    /// Not all of the requested attributes are available in any single mdoc of the requested doc type.
+    MissingAttributes(Vec<AttributeIdentifier>),
+    /// More than one mdoc is available for these requested doc types, which disclosure does not support yet.
+    MultipleCandidates(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisclosurePreviewAttribute {
+    pub attribute: AttributeIdentifier,
+    /// The identifier of the stored mdoc from which the attribute would be disclosed.
+    pub mdoc_id: Uuid,
+}
+
+impl DisclosurePreview {
+    pub fn is_satisfiable(&self) -> bool {
+        matches!(self, Self::Proposal(_))
+    }
+}
+
+/// Provides mdocs that were fetched from storage to [`DisclosureRequestMatch`], identified by their mdoc id instead of
+/// by the id of the particular copy, so that [`Wallet::preview_disclosure()`] can report the mdoc for each attribute.
+struct PreviewMdocDataSource(Vec<StoredMdocCopy>);
+
+impl MdocDataSource for PreviewMdocDataSource {
+    type MdocIdentifier = Uuid;
+    type Error = Infallible;
+
+    async fn mdoc_by_doc_types(
+        &self,
+        doc_types: &HashSet<&str>,
+    ) -> std::result::Result<Vec<Vec<StoredMdoc<Self::MdocIdentifier>>>, Self::Error> {
+        let mdocs_by_doc_type = self
+            .0
+            .iter()
+            .filter(|StoredMdocCopy { mdoc, .. }| doc_types.contains(mdoc.doc_type().as_str()))
+            .fold(
+                IndexMap::<_, Vec<_>>::with_capacity(doc_types.len()),
+                |mut mdocs_by_doc_type, StoredMdocCopy { mdoc_id, mdoc, .. }| {
+                    mdocs_by_doc_type
+                        .entry(mdoc.doc_type().as_str())
+                        .or_default()
+                        .push(StoredMdoc {
+                            id: *mdoc_id,
+                            mdoc: mdoc.clone(),
+                        });
+
+                    mdocs_by_doc_type
+                },
+            );
+
+        Ok(mdocs_by_doc_type.into_values().collect())
+    }
+}
+
 #[derive(Debug, thiserror::Error, ErrorCategory)]
 #[category(defer)]
 pub enum DisclosureError {
@@ -77,12 +146,22 @@ pub enum DisclosureError {
     #[error("disclosure session is not in the correct state")]
     #[category(expected)]
     SessionState,
+    #[error("disclosure session was abandoned because the wallet was locked")]
+    #[category(expected)]
+    SessionAbandoned,
+    #[error("disclosure is currently disabled by the Wallet Provider")]
+    #[category(expected)]
+    DisclosureDisabled { reason: Vec<LocalizedString> },
     #[error("could not parse disclosure URI: {0}")]
     DisclosureUri(#[source] DisclosureUriError),
     #[error("error in OpenID4VP disclosure session: {0}")]
     VpDisclosureSession(#[from] VpClientError),
     #[error("could not fetch if attributes were shared before: {0}")]
     HistoryRetrieval(#[source] StorageError),
+    #[error("could not fetch mdocs from database storage: {0}")]
+    MdocRetrieval(#[source] StorageError),
+    #[error("could not match requested attributes against stored mdocs: {0}")]
+    MatchRequestedAttributes(#[source] nl_wallet_mdoc::Error),
     #[error("not all requested attributes are available, missing: {missing_attributes:?}")]
     #[category(pd)] // Might reveal information about what attributes are stored in the Wallet
     AttributesNotAvailable {
@@ -176,6 +255,9 @@ where
             return Err(DisclosureError::SessionState);
         }
 
+        // Starting a new session supersedes any session that was abandoned when locking the wallet.
+        self.disclosure_session_abandoned = false;
+
         let config = &self.config_repository.get().disclosure;
 
         let disclosure_uri = MDS::parse_url(uri, urls::disclosure_base_uri(&UNIVERSAL_LINK_BASE_URL).as_ref())
@@ -236,6 +318,28 @@ where
         // Prepare a `IndexMap<DocType, ProposedDocumentAttributes>`.
         let proposed_attributes = proposal_session.proposed_attributes();
 
+        info!("Checking if disclosure is disabled");
+        if config.is_disclosure_disabled(
+            proposed_attributes.keys().map(String::as_str),
+            &session.rp_certificate().fingerprint_sha256_hex(),
+        ) {
+            // Let the relying party know that the session will not be completed.
+            if let Err(error) = session.terminate().await {
+                error!("Could not terminate disclosure session after disclosure was disabled: {error}");
+            }
+
+            let reason = config
+                .disclosure_disabled_reason
+                .iter()
+                .map(|(language, value)| LocalizedString {
+                    language: language.clone(),
+                    value: value.clone(),
+                })
+                .collect();
+
+            return Err(DisclosureError::DisclosureDisabled { reason });
+        }
+
         let is_login_flow = DisclosureType::from_proposed_attributes(&proposed_attributes).is_login_flow();
 
         // Prepare a `Vec<ProposedDisclosureDocument>` to report to the caller.
@@ -260,10 +364,89 @@ where
         Ok(proposal)
     }
 
+    /// Match the requested attributes against the stored mdocs, in order to show which attributes would be disclosed
+    /// from which mdoc. This does not contact the relying party, nor does it sign anything.
+    #[instrument(skip_all)]
+    #[sentry_capture_error]
+    pub async fn preview_disclosure(&self, requests: &ItemsRequests) -> Result<DisclosurePreview, DisclosureError> {
+        info!("Previewing disclosure");
+
+        info!("Checking if blocked");
+        if self.is_blocked() {
+            return Err(DisclosureError::VersionBlocked);
+        }
+
+        info!("Checking if registered");
+        if !self.registration.is_registered() {
+            return Err(DisclosureError::NotRegistered);
+        }
+
+        info!("Checking if locked");
+        if self.lock.is_locked() {
+            return Err(DisclosureError::Locked);
+        }
+
+        let doc_types = requests
+            .0
+            .iter()
+            .map(|items_request| items_request.doc_type.as_str())
+            .collect::<HashSet<_>>();
+
+        let stored_mdocs = self
+            .storage
+            .read()
+            .await
+            .fetch_unique_mdocs_by_doctypes(&doc_types)
+            .await
+            .map_err(DisclosureError::MdocRetrieval)?;
+
+        // As nothing is signed, the challenges that are computed from the session transcript are never used,
+        // so any session transcript will do here.
+        let session_transcript = SessionTranscript::new_oid4vp(&UNIVERSAL_LINK_BASE_URL, "", String::new(), "");
+
+        let candidates_by_doc_type = match DisclosureRequestMatch::new(
+            requests.0.iter(),
+            &PreviewMdocDataSource(stored_mdocs),
+            &session_transcript,
+        )
+        .await
+        .map_err(DisclosureError::MatchRequestedAttributes)?
+        {
+            DisclosureRequestMatch::Candidates(candidates) => candidates,
+            DisclosureRequestMatch::MissingAttributes(missing_attributes) => {
+                return Ok(DisclosurePreview::MissingAttributes(missing_attributes));
+            }
+        };
+
+        let duplicate_doc_types = candidates_by_doc_type
+            .iter()
+            .filter(|(_, candidates)| candidates.len() > 1)
+            .map(|(doc_type, _)| doc_type.clone())
+            .collect_vec();
+        if !duplicate_doc_types.is_empty() {
+            return Ok(DisclosurePreview::MultipleCandidates(duplicate_doc_types));
+        }
+
+        // There is now exactly one candidate per doc type, from which all of its requested attributes are disclosed.
+        let attributes = requests
+            .0
+            .iter()
+            .attribute_identifiers()
+            .into_iter()
+            .map(|attribute| {
+                let mdoc_id = candidates_by_doc_type[attribute.credential_type.as_str()][0].source_identifier;
+
+                DisclosurePreviewAttribute { attribute, mdoc_id }
+            })
+            .collect();
+
+        Ok(DisclosurePreview::Proposal(attributes))
+    }
+
     /// When we have missing attributes, we don't have a proposal -> empty proposed_attributes.
     /// When we do have a proposal, give us the proposed attributes then. In both cases, empty
     /// or "real", use from_proposed_attributes to determine the disclosure_type.
-    async fn terminate_disclosure_session(&mut self, session: MDS) -> Result<Option<Url>, DisclosureError> {
+    pub(super) async fn terminate_disclosure_session(&mut self, session: MDS) -> Result<Option<Url>, DisclosureError> {
         let proposed_attributes = match session.session_state() {
             MdocDisclosureSessionState::MissingAttributes(_) => None,
             MdocDisclosureSessionState::Proposal(proposal_session) => Some(proposal_session.proposed_attributes()),
@@ -391,6 +574,11 @@ where
             return Err(DisclosureError::Locked);
         }
 
+        info!("Checking if the disclosure session was abandoned");
+        if self.disclosure_session_abandoned {
+            return Err(DisclosureError::SessionAbandoned);
+        }
+
         info!("Checking if a disclosure session is present");
         let session = self.disclosure_session.as_ref().ok_or(DisclosureError::SessionState)?;
 
@@ -412,7 +600,7 @@ where
             .storage
             .write()
             .await
-            .increment_mdoc_copies_usage_count(session_proposal.proposed_source_identifiers())
+            .increment_mdoc_copies_usage_count(session_proposal.proposed_source_identifiers(), Utc::now())
             .await;
 
         if let Err(error) = result {
@@ -581,6 +769,7 @@ mod tests {
     use nl_wallet_mdoc::holder::ProposedDocumentAttributes;
     use nl_wallet_mdoc::unsigned::Entry;
     use nl_wallet_mdoc::DataElementValue;
+    use nl_wallet_mdoc::ItemsRequest;
     use openid4vc::disclosure_session::VpMessageClientError;
     use openid4vc::DisclosureErrorResponse;
     use openid4vc::ErrorResponse;
@@ -588,12 +777,16 @@ mod tests {
     use openid4vc::PostAuthResponseErrorCode;
     use sd_jwt::metadata::TypeMetadata;
 
+    use crate::config::default_config_server_config;
+    use crate::config::LocalConfigurationRepository;
+    use crate::config::UpdatingConfigurationRepository;
     use crate::config::UNIVERSAL_LINK_BASE_URL;
     use crate::disclosure::MockMdocDisclosureMissingAttributes;
     use crate::disclosure::MockMdocDisclosureProposal;
     use crate::disclosure::MockMdocDisclosureSession;
     use crate::document::Attribute;
     use crate::document::AttributeValue;
+    use crate::document::PID_DOCTYPE;
     use crate::EventStatus;
     use crate::HistoryEvent;
 
@@ -618,6 +811,21 @@ mod tests {
         )])
     }
 
+    /// Create an [`ItemsRequest`] for attributes in the name space that is equal to the doc type.
+    fn create_items_request(doc_type: &str, attributes: &[&str]) -> ItemsRequest {
+        ItemsRequest {
+            doc_type: doc_type.to_string(),
+            name_spaces: IndexMap::from([(
+                doc_type.to_string(),
+                attributes
+                    .iter()
+                    .map(|attribute| (attribute.to_string(), false))
+                    .collect(),
+            )]),
+            request_info: None,
+        }
+    }
+
     #[tokio::test]
     #[serial(MockMdocDisclosureSession)]
     async fn test_wallet_start_disclosure() {
@@ -676,7 +884,7 @@ mod tests {
     async fn test_wallet_start_disclosure_error_locked() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Starting disclosure on a locked wallet should result in an error.
         let error = wallet
@@ -720,6 +928,89 @@ mod tests {
         assert!(wallet.disclosure_session.is_some());
     }
 
+    /// The relying parties for which disclosure is disabled in `test_wallet_start_disclosure_disabled()`.
+    #[derive(Debug, Clone, Copy)]
+    enum DisabledRpScope {
+        AllRps,
+        SessionRp,
+        OtherRp,
+    }
+
+    #[rstest]
+    #[case(true, vec![], DisabledRpScope::AllRps, true)]
+    #[case(false, vec![], DisabledRpScope::AllRps, false)]
+    #[case(true, vec!["com.example.pid"], DisabledRpScope::AllRps, true)]
+    #[case(true, vec!["com.example.other"], DisabledRpScope::AllRps, false)]
+    #[case(true, vec![], DisabledRpScope::SessionRp, true)]
+    #[case(true, vec!["com.example.pid"], DisabledRpScope::SessionRp, true)]
+    #[case(true, vec!["com.example.other"], DisabledRpScope::SessionRp, false)]
+    #[case(true, vec![], DisabledRpScope::OtherRp, false)]
+    #[case(true, vec!["com.example.pid"], DisabledRpScope::OtherRp, false)]
+    #[tokio::test]
+    #[serial(MockMdocDisclosureSession)]
+    async fn test_wallet_start_disclosure_disabled(
+        #[case] disclosure_disabled: bool,
+        #[case] disabled_doc_types: Vec<&str>,
+        #[case] disabled_rp_scope: DisabledRpScope,
+        #[case] expect_disabled: bool,
+    ) {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Configure disclosure to be disabled, either for all relying parties or scoped to a specific one.
+        let disabled_rp_certificates = match disabled_rp_scope {
+            DisabledRpScope::AllRps => vec![],
+            DisabledRpScope::SessionRp => vec![MockMdocDisclosureSession::default()
+                .certificate
+                .fingerprint_sha256_hex()],
+            DisabledRpScope::OtherRp => vec!["00".repeat(32)],
+        };
+        let mut config = wallet.config_repository.get().as_ref().clone();
+        config.disclosure.disclosure_disabled = disclosure_disabled;
+        config.disclosure.disclosure_disabled_doc_types = disabled_doc_types.into_iter().map(str::to_string).collect();
+        config.disclosure.disclosure_disabled_rp_certificates = disabled_rp_certificates;
+        config.disclosure.disclosure_disabled_reason = [("nl", "Delen is tijdelijk niet mogelijk")]
+            .into_iter()
+            .map(|(language, value)| (language.to_string(), value.to_string()))
+            .collect();
+        wallet.config_repository = UpdatingConfigurationRepository::new(
+            LocalConfigurationRepository::new(config),
+            default_config_server_config(),
+        )
+        .await;
+
+        MockMdocDisclosureSession::next_fields(
+            ReaderRegistration::new_mock(),
+            MdocDisclosureSessionState::Proposal(MockMdocDisclosureProposal {
+                proposed_source_identifiers: vec![PROPOSED_ID],
+                proposed_attributes: setup_proposed_attributes("age_over_18".to_string(), DataElementValue::Bool(true)),
+                ..Default::default()
+            }),
+            None,
+        );
+
+        let result = wallet
+            .start_disclosure(&DISCLOSURE_URI, DisclosureUriSource::Link)
+            .await;
+
+        if expect_disabled {
+            let error = result.expect_err("Starting disclosure should have resulted in an error");
+
+            assert_matches!(
+                error,
+                DisclosureError::DisclosureDisabled { reason }
+                    if reason == vec![LocalizedString {
+                        language: "nl".to_string(),
+                        value: "Delen is tijdelijk niet mogelijk".to_string(),
+                    }]
+            );
+            assert!(wallet.disclosure_session.is_none());
+        } else {
+            result.expect("Could not start disclosure");
+
+            assert!(wallet.disclosure_session.is_some());
+        }
+    }
+
     #[tokio::test]
     async fn test_wallet_start_disclosure_error_disclosure_uri() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
@@ -903,6 +1194,138 @@ mod tests {
         assert!(wallet.disclosure_session.is_none());
     }
 
+    #[tokio::test]
+    async fn test_wallet_preview_disclosure() {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        wallet
+            .storage
+            .write()
+            .await
+            .insert_mdocs(
+                vec![vec![test::create_full_pid_mdoc()].try_into().unwrap()],
+                &test::ISSUER_URL,
+            )
+            .await
+            .unwrap();
+        let mdoc_id = wallet.storage.read().await.fetch_unique_mdocs().await.unwrap()[0].mdoc_id;
+
+        let requests = vec![
+            create_items_request(PID_DOCTYPE, &["family_name", "birth_city"]),
+            create_items_request(PID_DOCTYPE, &["family_name"]),
+        ]
+        .into();
+
+        let preview = wallet
+            .preview_disclosure(&requests)
+            .await
+            .expect("previewing disclosure should succeed");
+
+        // The PID attributes should be disclosed from the stored PID.
+        assert!(preview.is_satisfiable());
+        assert_eq!(
+            preview,
+            DisclosurePreview::Proposal(vec![
+                DisclosurePreviewAttribute {
+                    attribute: format!("{PID_DOCTYPE}/{PID_DOCTYPE}/family_name").parse().unwrap(),
+                    mdoc_id,
+                },
+                DisclosurePreviewAttribute {
+                    attribute: format!("{PID_DOCTYPE}/{PID_DOCTYPE}/birth_city").parse().unwrap(),
+                    mdoc_id,
+                },
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case(
+        vec![
+            create_items_request(PID_DOCTYPE, &["family_name"]),
+            create_items_request("com.example.address", &["city"]),
+        ],
+        vec!["com.example.address/com.example.address/city".parse().unwrap()]
+    )]
+    #[case(
+        vec![create_items_request(PID_DOCTYPE, &["family_name", "missing_attribute"])],
+        vec![format!("{PID_DOCTYPE}/{PID_DOCTYPE}/missing_attribute").parse().unwrap()]
+    )]
+    #[tokio::test]
+    async fn test_wallet_preview_disclosure_missing_attributes(
+        #[case] requests: Vec<ItemsRequest>,
+        #[case] expected_missing_attributes: Vec<AttributeIdentifier>,
+    ) {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        wallet
+            .storage
+            .write()
+            .await
+            .insert_mdocs(
+                vec![vec![test::create_full_pid_mdoc()].try_into().unwrap()],
+                &test::ISSUER_URL,
+            )
+            .await
+            .unwrap();
+
+        let preview = wallet
+            .preview_disclosure(&requests.into())
+            .await
+            .expect("previewing disclosure should succeed");
+
+        assert!(!preview.is_satisfiable());
+        assert_eq!(
+            preview,
+            DisclosurePreview::MissingAttributes(expected_missing_attributes)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wallet_preview_disclosure_multiple_candidates() {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Store two separate PIDs, which could both be disclosed.
+        wallet
+            .storage
+            .write()
+            .await
+            .insert_mdocs(
+                vec![
+                    vec![test::create_full_pid_mdoc()].try_into().unwrap(),
+                    vec![test::create_full_pid_mdoc()].try_into().unwrap(),
+                ],
+                &test::ISSUER_URL,
+            )
+            .await
+            .unwrap();
+
+        let preview = wallet
+            .preview_disclosure(&vec![create_items_request(PID_DOCTYPE, &["family_name"])].into())
+            .await
+            .expect("previewing disclosure should succeed");
+
+        // This should be reported, as actual disclosure would fail for this request.
+        assert!(!preview.is_satisfiable());
+        assert_eq!(
+            preview,
+            DisclosurePreview::MultipleCandidates(vec![PID_DOCTYPE.to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wallet_preview_disclosure_error_locked() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        wallet.lock().await;
+
+        let error = wallet
+            .preview_disclosure(&vec![create_items_request(PID_DOCTYPE, &["family_name"])].into())
+            .await
+            .expect_err("previewing disclosure should not succeed");
+
+        assert_matches!(error, DisclosureError::Locked);
+    }
+
     #[tokio::test]
     #[serial(MockMdocDisclosureSession)]
     async fn test_wallet_cancel_disclosure() {
@@ -1039,9 +1462,15 @@ mod tests {
         // Prepare a registered and unlocked wallet with an active disclosure session.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.disclosure_session = MockMdocDisclosureSession::default().into();
+        let session = MockMdocDisclosureSession::default();
+        let was_terminated = Arc::clone(&session.was_terminated);
+        wallet.disclosure_session = session.into();
 
-        wallet.lock();
+        // Locking the wallet should terminate the disclosure session.
+        wallet.lock().await;
+
+        assert!(wallet.disclosure_session.is_none());
+        assert!(was_terminated.load(Ordering::Relaxed));
 
         // Cancelling disclosure on a locked wallet should result in an error.
         let error = wallet
@@ -1050,7 +1479,6 @@ mod tests {
             .expect_err("Cancelling disclosure should have resulted in an error");
 
         assert_matches!(error, DisclosureError::Locked);
-        assert!(wallet.disclosure_session.is_some());
     }
 
     #[tokio::test]
@@ -1169,9 +1597,15 @@ mod tests {
         // Prepare a registered and unlocked wallet with an active disclosure session.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.disclosure_session = MockMdocDisclosureSession::default().into();
+        let session = MockMdocDisclosureSession::default();
+        let disclosure_count = match session.session_state {
+            MdocDisclosureSessionState::Proposal(ref proposal) => Arc::clone(&proposal.disclosure_count),
+            _ => unreachable!(),
+        };
+        wallet.disclosure_session = session.into();
 
-        wallet.lock();
+        // Locking the wallet should terminate the disclosure session.
+        wallet.lock().await;
 
         // Accepting disclosure on a locked wallet should result in an error.
         let error = wallet
@@ -1180,27 +1614,23 @@ mod tests {
             .expect_err("Accepting disclosure should have resulted in an error");
 
         assert_matches!(error, DisclosureError::Locked);
-        assert!(wallet.disclosure_session.is_some());
+        assert!(wallet.disclosure_session.is_none());
         assert!(wallet.is_locked());
-        match wallet.disclosure_session.as_ref().unwrap().session_state {
-            MdocDisclosureSessionState::Proposal(ref proposal) => {
-                assert_eq!(proposal.disclosure_count.load(Ordering::Relaxed), 0);
-            }
-            _ => unreachable!(),
-        };
+        assert_eq!(disclosure_count.load(Ordering::Relaxed), 0);
 
         // The mdoc copy usage counts should not be incremented.
         assert!(wallet.storage.read().await.mdoc_copies_usage_counts.is_empty());
 
-        // Verify no Disclosure events are logged
-        assert!(wallet
-            .storage
-            .read()
-            .await
-            .fetch_wallet_events()
-            .await
-            .unwrap()
-            .is_empty());
+        // Verify that only the cancellation of the disclosure is logged.
+        let events = wallet.storage.read().await.fetch_wallet_events().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            events.first().unwrap(),
+            WalletEvent::Disclosure {
+                status: EventStatus::Cancelled,
+                ..
+            }
+        );
     }
 
     #[tokio::test]
@@ -1610,10 +2040,13 @@ mod tests {
             .storage
             .write()
             .await
-            .insert_mdocs(vec![
-                vec![mdoc1.clone(), mdoc1.clone(), mdoc1.clone()].try_into().unwrap(),
-                vec![mdoc2.clone(), mdoc2.clone(), mdoc2.clone()].try_into().unwrap(),
-            ])
+            .insert_mdocs(
+                vec![
+                    vec![mdoc1.clone(), mdoc1.clone(), mdoc1.clone()].try_into().unwrap(),
+                    vec![mdoc2.clone(), mdoc2.clone(), mdoc2.clone()].try_into().unwrap(),
+                ],
+                &test::ISSUER_URL,
+            )
             .await
             .unwrap();
 
@@ -196,6 +196,7 @@ pub enum HistoryEvent {
     Issuance {
         timestamp: DateTime<Utc>,
         mdocs: Vec<Document>,
+        renewed: bool,
     },
     Disclosure {
         status: EventStatus,
@@ -204,6 +205,10 @@ pub enum HistoryEvent {
         reader_registration: Box<ReaderRegistration>,
         attributes: Option<Vec<DisclosureDocument>>,
     },
+    Deletion {
+        timestamp: DateTime<Utc>,
+        mdocs: Vec<Document>,
+    },
 }
 
 impl TryFrom<WalletEvent> for HistoryEvent {
@@ -215,24 +220,11 @@ impl TryFrom<WalletEvent> for HistoryEvent {
                 id: _,
                 timestamp,
                 mdocs,
+                renewed,
             } => Self::Issuance {
                 timestamp,
-                mdocs: mdocs
-                    .0
-                    .into_iter()
-                    .map(|(doc_type, proposed_card)| {
-                        let issuer_registration = IssuerRegistration::from_certificate(&proposed_card.issuer)?
-                            .ok_or(EventConversionError::NoIssuerRegistrationFound)?;
-
-                        let document = Document::from_mdoc_attributes(
-                            DocumentPersistence::InMemory,
-                            &doc_type,
-                            proposed_card.into(),
-                            issuer_registration,
-                        )?;
-                        Ok(document)
-                    })
-                    .collect::<Result<_, EventConversionError>>()?,
+                mdocs: event_documents_to_documents(mdocs)?,
+                renewed,
             },
             WalletEvent::Disclosure {
                 id: _,
@@ -268,11 +260,37 @@ impl TryFrom<WalletEvent> for HistoryEvent {
                     Box::new(reader_registration)
                 },
             },
+            WalletEvent::Deletion {
+                id: _,
+                timestamp,
+                mdocs,
+            } => Self::Deletion {
+                timestamp,
+                mdocs: event_documents_to_documents(mdocs)?,
+            },
         };
         Ok(result)
     }
 }
 
+fn event_documents_to_documents(EventDocuments(mdocs): EventDocuments) -> Result<Vec<Document>, EventConversionError> {
+    mdocs
+        .into_iter()
+        .map(|(doc_type, proposed_card)| {
+            let issuer_registration = IssuerRegistration::from_certificate(&proposed_card.issuer)?
+                .ok_or(EventConversionError::NoIssuerRegistrationFound)?;
+
+            let document = Document::from_mdoc_attributes(
+                DocumentPersistence::InMemory,
+                &doc_type,
+                proposed_card.into(),
+                issuer_registration,
+            )?;
+            Ok(document)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -321,7 +339,7 @@ mod tests {
     async fn test_history_fails_when_locked() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         let error = wallet
             .get_history()
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use cfg_if::cfg_if;
 use futures::try_join;
 use tokio::sync::RwLock;
+use tracing::info;
 
 use error_category::sentry_capture_error;
 use error_category::ErrorCategory;
@@ -15,8 +16,12 @@ use wallet_common::config::http::TlsPinningConfig;
 use wallet_common::config::wallet_config::WalletConfiguration;
 use wallet_common::update_policy::VersionState;
 
+use crate::account_provider::CircuitBreakerAccountProviderClient;
+use crate::account_provider::CircuitBreakerConfig;
+use crate::config::current_config_environment;
 use crate::config::default_config_server_config;
 use crate::config::default_wallet_config;
+use crate::config::init_config_environment;
 use crate::config::init_universal_link_base_url;
 use crate::config::ConfigurationError;
 use crate::config::UpdatingConfigurationRepository;
@@ -33,6 +38,7 @@ use crate::storage::StorageState;
 use crate::update_policy::UpdatePolicyRepository;
 
 use super::KeyHolderType;
+use super::RemoveCredentialsError;
 use super::Wallet;
 use super::WalletRegistration;
 
@@ -45,6 +51,8 @@ pub enum WalletInitError {
     Utilities(#[from] UtilitiesError),
     #[error("could not initialize database: {0}")]
     Database(#[from] StorageError),
+    #[error("could not remove credentials of revoked issuers: {0}")]
+    RevokedIssuerCredentials(#[from] RemoveCredentialsError),
 }
 
 #[cfg(feature = "fake_attestation")]
@@ -80,25 +88,30 @@ async fn init_mock_key_holder() -> platform_support::attested_key::mock::Persist
     PersistentMockAttestedKeyHolder::new_mock_xcode(apple_attestation_environment)
 }
 
-impl<APC, DS, IS, MDS, WIC>
+impl<C, DS, IS, MDS, WIC>
     Wallet<
         WalletConfigurationRepository,
         UpdatePolicyRepository,
         DatabaseStorage<HardwareEncryptionKey>,
         KeyHolderType,
-        APC,
+        CircuitBreakerAccountProviderClient<C>,
         DS,
         IS,
         MDS,
         WIC,
     >
 where
-    APC: Default,
+    C: Default,
     WIC: Default,
 {
     #[sentry_capture_error]
     pub async fn init_all() -> Result<Self, WalletInitError> {
-        init_universal_link_base_url();
+        let storage_path = HardwareUtilities::storage_path().await?;
+
+        // This needs to happen before the default configuration or the universal link base URL is used.
+        let previous_environment = init_config_environment(storage_path.as_path()).await?;
+
+        init_universal_link_base_url()?;
 
         // When using fake attestations, initialize the key holder, but make sure this happens only once.
         cfg_if! {
@@ -111,7 +124,6 @@ where
 
         let update_policy_repository = UpdatePolicyRepository::init();
 
-        let storage_path = HardwareUtilities::storage_path().await?;
         let storage = DatabaseStorage::<HardwareEncryptionKey>::new(storage_path.clone());
         let config_repository = UpdatingConfigurationRepository::init(
             storage_path.clone(),
@@ -120,17 +132,42 @@ where
         )
         .await?;
 
-        Self::init_registration(
+        let circuit_breaker_config = CircuitBreakerConfig::from(&config_repository.get().account_server);
+        let account_provider_client = CircuitBreakerAccountProviderClient::new(C::default(), circuit_breaker_config);
+
+        let mut wallet = Self::init_registration(
             config_repository,
             update_policy_repository,
             storage,
             key_holder,
-            APC::default(),
+            account_provider_client,
         )
-        .await
+        .await?;
+
+        // Any registration and data that is present belongs to the previous environment and cannot be used.
+        if let Some(previous_environment) = previous_environment {
+            info!(
+                "Configuration environment changed from \"{}\" to \"{}\", resetting wallet",
+                previous_environment,
+                current_config_environment()
+            );
+
+            wallet.reset_to_initial_state().await;
+        }
+
+        Ok(wallet)
     }
 }
 
+/// Select one of the embedded configuration environments, which takes effect when the wallet is initialized the next
+/// time the app is started. At that point, the registration and all data of the wallet are removed.
+pub async fn select_config_environment(environment: &str) -> Result<(), WalletInitError> {
+    let storage_path = HardwareUtilities::storage_path().await?;
+    crate::config::select_config_environment(storage_path.as_path(), environment).await?;
+
+    Ok(())
+}
+
 impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
 where
     AKH: AttestedKeyHolder,
@@ -174,6 +211,8 @@ where
             account_provider_client: Arc::new(account_provider_client),
             issuance_session: None,
             disclosure_session: None,
+            issuance_session_abandoned: false,
+            disclosure_session_abandoned: false,
             wte_issuance_client: WIC::default(),
             lock: WalletLock::new(true),
             attestations_callback: None,
@@ -199,7 +238,7 @@ where
 
         let registration_status = Self::fetch_registration_status(&mut storage).await?;
 
-        let wallet = Self::new(
+        let mut wallet = Self::new(
             config_repository,
             update_policy_repository,
             storage,
@@ -208,6 +247,12 @@ where
             registration_status,
         );
 
+        // Remove any credentials of issuers that have been revoked in the configuration at launch, before these can
+        // be shown to the user or disclosed to a relying party.
+        if wallet.registration.is_registered() {
+            wallet.remove_revoked_issuer_credentials().await?;
+        }
+
         Ok(wallet)
     }
 }
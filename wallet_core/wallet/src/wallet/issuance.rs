@@ -1,13 +1,17 @@
 use std::mem;
 use std::sync::Arc;
 
+use chrono::TimeDelta;
 use http::header;
 use http::HeaderMap;
 use http::HeaderValue;
 use http::Uri;
 use p256::ecdsa::signature;
+use tokio::time;
+use tokio::time::Instant;
 use tracing::info;
 use tracing::instrument;
+use tracing::warn;
 use url::Url;
 
 use error_category::sentry_capture_error;
@@ -16,14 +20,18 @@ use nl_wallet_mdoc::utils::cose::CoseError;
 use nl_wallet_mdoc::utils::issuer_auth::IssuerRegistration;
 use nl_wallet_mdoc::utils::x509::MdocCertificateExtension;
 use openid4vc::credential::MdocCopies;
+use openid4vc::credential::NotificationEvent;
+use openid4vc::credential_formats::CredentialFormat;
 use openid4vc::credential_payload::CredentialPayload;
 use openid4vc::credential_payload::CredentialPayloadError;
+use openid4vc::issuance_session::CredentialSizeLimits;
 use openid4vc::issuance_session::HttpIssuanceSession;
 use openid4vc::issuance_session::IssuanceSession;
 use openid4vc::issuance_session::IssuanceSessionError;
 use openid4vc::jwt::JwtCredentialError;
 use openid4vc::token::CredentialPreview;
 use openid4vc::token::CredentialPreviewError;
+use openid4vc::Format;
 use platform_support::attested_key::AttestedKeyHolder;
 use sd_jwt::metadata::TypeMetadataError;
 use wallet_common::config::http::TlsPinningConfig;
@@ -32,6 +40,7 @@ use wallet_common::jwt::JwtError;
 use wallet_common::reqwest::default_reqwest_client_builder;
 use wallet_common::update_policy::VersionState;
 use wallet_common::urls;
+use wallet_common::urls::BaseUrl;
 
 use crate::account_provider::AccountProviderClient;
 use crate::attestation::Attestation;
@@ -61,7 +70,22 @@ use super::Wallet;
 
 pub(super) enum PidIssuanceSession<DS = HttpDigidSession, IS = HttpIssuanceSession> {
     Digid(DS),
-    Openid4vci(IS),
+    /// The issuance session at the PID issuer, along with the deadline of the PID issuance flow.
+    Openid4vci {
+        pid_issuer: IS,
+        deadline: Instant,
+    },
+}
+
+#[cfg(test)]
+impl<DS, IS> PidIssuanceSession<DS, IS> {
+    /// Wrap an issuance session at the PID issuer, with a deadline that will not expire during a test.
+    pub(super) fn new_openid4vci(pid_issuer: IS) -> Self {
+        Self::Openid4vci {
+            pid_issuer,
+            deadline: Instant::now() + std::time::Duration::from_secs(3600),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error, ErrorCategory)]
@@ -79,9 +103,18 @@ pub enum PidIssuanceError {
     #[error("issuance session is not in the correct state")]
     #[category(expected)]
     SessionState,
+    #[error("issuance session was abandoned because the wallet was locked")]
+    #[category(expected)]
+    SessionAbandoned,
+    #[error("PID issuance flow timed out")]
+    #[category(expected)]
+    Timeout,
     #[error("PID already present")]
     #[category(expected)]
     PidAlreadyPresent,
+    #[error("issuer offered credential in format that is not allowed: {0}")]
+    #[category(critical)]
+    CredentialFormatNotAllowed(Format),
     #[error("could not start DigiD session: {0}")]
     DigidSessionStart(#[source] DigidSessionError),
     #[error("could not finish DigiD session: {0}")]
@@ -130,6 +163,17 @@ pub enum PidIssuanceError {
     Attestation(#[from] AttestationError),
 }
 
+/// Create the HTTP client used to communicate with the PID issuer.
+pub(super) fn pid_issuer_http_client() -> reqwest::Client {
+    default_reqwest_client_builder()
+        .default_headers(HeaderMap::from_iter([(
+            header::ACCEPT,
+            HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+        )]))
+        .build()
+        .expect("Could not build reqwest HTTP client")
+}
+
 impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
 where
     CR: Repository<Arc<WalletConfiguration>>,
@@ -165,6 +209,9 @@ where
             return Err(PidIssuanceError::SessionState);
         }
 
+        // Starting a new session supersedes any session that was abandoned when locking the wallet.
+        self.issuance_session_abandoned = false;
+
         info!("Checking if a pid is already present");
         let has_pid = self
             .storage
@@ -240,7 +287,7 @@ where
         info!("Checking if there is an active issuance session");
         let issuance_session = self.issuance_session.take().ok_or(PidIssuanceError::SessionState)?;
 
-        if let PidIssuanceSession::Openid4vci(pid_issuer) = issuance_session {
+        if let PidIssuanceSession::Openid4vci { pid_issuer, .. } = issuance_session {
             info!("Rejecting PID");
             pid_issuer.reject_issuance().await?;
         }
@@ -268,6 +315,11 @@ where
             return Err(PidIssuanceError::Locked);
         }
 
+        info!("Checking if the issuance session was abandoned");
+        if self.issuance_session_abandoned {
+            return Err(PidIssuanceError::SessionAbandoned);
+        }
+
         info!("Checking if there is an active DigiD issuance session");
         if !matches!(self.issuance_session, Some(PidIssuanceSession::Digid(_))) {
             return Err(PidIssuanceError::SessionState);
@@ -276,30 +328,70 @@ where
         // Take ownership of the active session, now that we know that it exists.
         let session = match self.issuance_session.take().unwrap() {
             PidIssuanceSession::Digid(session) => session,
-            PidIssuanceSession::Openid4vci(_) => panic!(),
+            PidIssuanceSession::Openid4vci { .. } => panic!(),
         };
 
-        let token_request = session
-            .into_token_request(redirect_uri)
-            .await
-            .map_err(PidIssuanceError::DigidSessionFinish)?;
-
-        let pid_issuer_http_client = default_reqwest_client_builder()
-            .default_headers(HeaderMap::from_iter([(
-                header::ACCEPT,
-                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
-            )]))
-            .build()
-            .expect("Could not build reqwest HTTP client");
         let config = self.config_repository.get();
 
-        let (pid_issuer, attestation_previews) = IS::start_issuance(
-            pid_issuer_http_client.into(),
-            config.pid_issuance.pid_issuer_url.clone(),
-            token_request,
-            &config.mdoc_trust_anchors(),
-        )
-        .await?;
+        // The configured flow timeout covers the remainder of the PID issuance flow, up until the PID is accepted. If
+        // it expires here, the DigiD session has already been consumed and no issuance session exists yet, so
+        // there is nothing to reject at the issuer.
+        let deadline = Instant::now() + config.pid_issuance.flow_timeout;
+
+        let (pid_issuer, attestation_previews) = time::timeout_at(deadline, async {
+            let token_request = session
+                .into_token_request(redirect_uri)
+                .await
+                .map_err(PidIssuanceError::DigidSessionFinish)?;
+
+            let (pid_issuer, attestation_previews) = IS::start_issuance(
+                pid_issuer_http_client().into(),
+                config.pid_issuance.pid_issuer_url.clone(),
+                token_request,
+                &config.mdoc_trust_anchors(),
+            )
+            .await?;
+
+            let pid_issuer = pid_issuer
+                .with_concurrency_limit(config.pid_issuance.concurrency_limit)
+                .with_allowed_formats(config.pid_issuance.allowed_credential_formats.clone())
+                .with_size_limits(CredentialSizeLimits {
+                    max_attribute_size: config.pid_issuance.max_attribute_size,
+                    max_credential_size: config.pid_issuance.max_credential_size,
+                })
+                .with_max_future_validity(
+                    TimeDelta::from_std(config.pid_issuance.max_future_validity).unwrap_or(TimeDelta::MAX),
+                )
+                .with_single_holder_key(config.pid_issuance.single_holder_key);
+
+            let pid_issuer = match config.pid_issuance.pid_issuer_jwks_url.as_ref() {
+                Some(jwks_url) => pid_issuer.with_jwks_verification(jwks_url.clone()),
+                None => pid_issuer,
+            };
+
+            Ok::<_, PidIssuanceError>((pid_issuer, attestation_previews))
+        })
+        .await
+        .map_err(|_| {
+            warn!("PID issuance flow timed out while processing DigiD redirect URI");
+            PidIssuanceError::Timeout
+        })??;
+
+        // Only request credentials in formats that the wallet is configured to accept, so reject the session before
+        // any credential is requested if the issuer offers anything else.
+        let disallowed_format = attestation_previews
+            .iter()
+            .flat_map(|formats| formats.as_ref().as_slice())
+            .map(CredentialFormat::format)
+            .find(|format| !config.pid_issuance.is_credential_format_allowed(*format));
+
+        if let Some(format) = disallowed_format {
+            if let Err(error) = pid_issuer.reject_issuance().await {
+                warn!("Could not reject issuance session with disallowed credential format: {error}");
+            }
+
+            return Err(PidIssuanceError::CredentialFormatNotAllowed(format));
+        }
 
         info!("PID received successfully from issuer, returning preview documents");
         let attestations = attestation_previews
@@ -332,7 +424,7 @@ where
             .collect::<Result<Vec<_>, PidIssuanceError>>()?;
 
         self.issuance_session
-            .replace(PidIssuanceSession::Openid4vci(pid_issuer));
+            .replace(PidIssuanceSession::Openid4vci { pid_issuer, deadline });
 
         Ok(attestations)
     }
@@ -369,30 +461,54 @@ where
             return Err(PidIssuanceError::Locked);
         }
 
+        info!("Checking if the issuance session was abandoned");
+        if self.issuance_session_abandoned {
+            return Err(PidIssuanceError::SessionAbandoned);
+        }
+
         info!("Checking if there is an active PID issuance session");
-        let pid_issuer = match self.issuance_session.as_ref().ok_or(PidIssuanceError::SessionState)? {
+        let (pid_issuer, deadline) = match self.issuance_session.as_ref().ok_or(PidIssuanceError::SessionState)? {
             PidIssuanceSession::Digid(_) => Err(PidIssuanceError::SessionState)?,
-            PidIssuanceSession::Openid4vci(pid_issuer) => pid_issuer,
+            PidIssuanceSession::Openid4vci { pid_issuer, deadline } => (pid_issuer, *deadline),
         };
 
         let config = self.config_repository.get();
 
         let instruction_result_public_key = config.account_server.instruction_result_public_key.clone().into();
 
-        let remote_instruction = self
-            .new_instruction_client(
+        let Ok(remote_instruction) = time::timeout_at(
+            deadline,
+            self.new_instruction_client(
                 pin,
                 Arc::clone(attested_key),
                 registration_data.clone(),
                 config.account_server.http_config.clone(),
                 instruction_result_public_key,
-            )
-            .await?;
+            ),
+        )
+        .await
+        else {
+            return Err(self.abort_pid_issuance_after_timeout().await);
+        };
+        let remote_instruction = remote_instruction?;
 
-        let wte = self
-            .wte_issuance_client
-            .obtain_wte(&config.account_server.wte_public_key.0, remote_instruction.clone())
-            .await?;
+        let Ok(wte) = time::timeout_at(
+            deadline,
+            self.wte_issuance_client
+                .obtain_wte(&config.account_server.wte_public_key.0, remote_instruction.clone()),
+        )
+        .await
+        else {
+            return Err(self.abort_pid_issuance_after_timeout().await);
+        };
+        let wte = wte?;
+
+        // Once the credentials are requested the issuer may issue them at any moment, so aborting after that point
+        // could lose credentials that were already issued. This is why the deadline is checked for the last
+        // time here.
+        if Instant::now() >= deadline {
+            return Err(self.abort_pid_issuance_after_timeout().await);
+        }
 
         let remote_key_factory = RemoteEcdsaKeyFactory::new(remote_instruction);
 
@@ -405,23 +521,24 @@ where
                 Some(wte),
                 config.pid_issuance.pid_issuer_url.clone(),
             )
-            .await
-            .map_err(|error| {
-                match error {
-                    // We knowingly call unwrap() on the downcast to `RemoteEcdsaKeyError` here because we know
-                    // that it is the error type of the `RemoteEcdsaKeyFactory` we provide above.
-                    IssuanceSessionError::PrivateKeyGeneration(error)
-                    | IssuanceSessionError::Jwt(JwtError::Signing(error)) => {
-                        match *error.downcast::<RemoteEcdsaKeyError>().unwrap() {
-                            RemoteEcdsaKeyError::Instruction(error) => PidIssuanceError::Instruction(error),
-                            RemoteEcdsaKeyError::Signature(error) => PidIssuanceError::Signature(error),
-                            RemoteEcdsaKeyError::KeyNotFound(identifier) => PidIssuanceError::KeyNotFound(identifier),
-                            RemoteEcdsaKeyError::MissingSignature => PidIssuanceError::MissingSignature,
-                        }
+            .await;
+
+        let issuance_result = issuance_result.map_err(|error| {
+            match error {
+                // We knowingly call unwrap() on the downcast to `RemoteEcdsaKeyError` here because we know
+                // that it is the error type of the `RemoteEcdsaKeyFactory` we provide above.
+                IssuanceSessionError::PrivateKeyGeneration(error)
+                | IssuanceSessionError::Jwt(JwtError::Signing(error)) => {
+                    match *error.downcast::<RemoteEcdsaKeyError>().unwrap() {
+                        RemoteEcdsaKeyError::Instruction(error) => PidIssuanceError::Instruction(error),
+                        RemoteEcdsaKeyError::Signature(error) => PidIssuanceError::Signature(error),
+                        RemoteEcdsaKeyError::KeyNotFound(identifier) => PidIssuanceError::KeyNotFound(identifier),
+                        RemoteEcdsaKeyError::MissingSignature => PidIssuanceError::MissingSignature,
                     }
-                    _ => PidIssuanceError::PidIssuer(error),
                 }
-            });
+                _ => PidIssuanceError::PidIssuer(error),
+            }
+        });
 
         // Make sure there are no remaining references to the `AttestedKey` value.
         mem::drop(remote_key_factory);
@@ -442,8 +559,45 @@ where
             .collect::<Result<Vec<_>, _>>()?;
 
         info!("Isuance succeeded; removing issuance session state");
-        self.issuance_session.take();
+        let issuance_session = self.issuance_session.take();
+
+        let store_result = self
+            .store_issued_mdocs(issued_mdocs, &config.pid_issuance.pid_issuer_url)
+            .await;
+
+        // Let the issuer know whether the credentials were stored. As this is best effort, failing to send the
+        // notification does not affect the outcome of the issuance.
+        if let Some(PidIssuanceSession::Openid4vci { pid_issuer, .. }) = &issuance_session {
+            let event = match store_result {
+                Ok(_) => NotificationEvent::CredentialAccepted,
+                Err(_) => NotificationEvent::CredentialFailure,
+            };
+
+            if let Err(error) = pid_issuer.notify(event).await {
+                warn!("Could not notify PID issuer: {error}");
+            }
+        }
+
+        let event = store_result?;
+
+        self.store_history_event(event)
+            .await
+            .map_err(PidIssuanceError::EventStorage)?;
 
+        self.emit_attestations().await.map_err(PidIssuanceError::Attestations)?;
+
+        Ok(())
+    }
+
+    /// Validate and store the issued mdocs, returning the history event to be logged for their issuance.
+    async fn store_issued_mdocs(
+        &self,
+        issued_mdocs: Vec<MdocCopies>,
+        issuer_url: &BaseUrl,
+    ) -> Result<WalletEvent, PidIssuanceError>
+    where
+        S: Storage,
+    {
         // Prepare events before storing mdocs, to avoid cloning mdocs
         let event = {
             // Extract first copy from each issued mdoc
@@ -470,22 +624,32 @@ where
         self.storage
             .write()
             .await
-            .insert_mdocs(issued_mdocs)
+            .insert_mdocs(issued_mdocs, issuer_url)
             .await
             .map_err(PidIssuanceError::MdocStorage)?;
 
-        self.store_history_event(event)
-            .await
-            .map_err(PidIssuanceError::EventStorage)?;
+        Ok(event)
+    }
 
-        self.emit_attestations().await.map_err(PidIssuanceError::Attestations)?;
+    /// Remove the active PID issuance session after the flow timeout has expired, rejecting it at the issuer if
+    /// possible. Returns [`PidIssuanceError::Timeout`] for convenience.
+    async fn abort_pid_issuance_after_timeout(&mut self) -> PidIssuanceError {
+        warn!("PID issuance flow timed out, aborting issuance session");
 
-        Ok(())
+        if let Some(PidIssuanceSession::Openid4vci { pid_issuer, .. }) = self.issuance_session.take() {
+            if let Err(error) = pid_issuer.reject_issuance().await {
+                warn!("Could not reject timed out issuance session: {error}");
+            }
+        }
+
+        PidIssuanceError::Timeout
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use assert_matches::assert_matches;
     use mockall::predicate::*;
     use openid4vc::credential_formats::CredentialFormats;
@@ -504,6 +668,9 @@ mod tests {
     use wallet_common::config::http::TlsPinningConfig;
     use wallet_common::vec_at_least::VecNonEmpty;
 
+    use crate::config::default_config_server_config;
+    use crate::config::LocalConfigurationRepository;
+    use crate::config::UpdatingConfigurationRepository;
     use crate::document;
     use crate::issuance::MockDigidSession;
     use crate::storage::StorageState;
@@ -515,7 +682,7 @@ mod tests {
     use super::super::test::ISSUER_KEY;
     use super::*;
 
-    fn mock_issuance_session(mdoc: Mdoc) -> MockIssuanceSession {
+    fn mock_issuance_session(mdoc: Mdoc, expected_notification: NotificationEvent) -> MockIssuanceSession {
         let mut client = MockIssuanceSession::new();
         client.expect_accept().return_once(|| {
             Ok(vec![vec![IssuedCredential::MsoMdoc(Box::new(mdoc))]
@@ -523,6 +690,11 @@ mod tests {
                 .unwrap()])
         });
         client
+            .expect_notify_event()
+            .with(eq(expected_notification))
+            .times(1)
+            .return_once(|_| Ok(()));
+        client
     }
 
     #[tokio::test]
@@ -555,7 +727,7 @@ mod tests {
     async fn test_create_pid_issuance_auth_url_error_locked() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Creating a DigiD authentication URL on
         // a locked wallet should result in an error.
@@ -604,7 +776,7 @@ mod tests {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
         // Setup a mock OpenID4VCI session.
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(MockIssuanceSession::default()));
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(MockIssuanceSession::default()));
 
         // Creating a DigiD authentication URL on a `Wallet` that has
         // an active OpenID4VCI session should return an error.
@@ -665,7 +837,7 @@ mod tests {
             client.expect_reject().return_once(|| Ok(()));
             client
         };
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Cancelling PID issuance should not fail.
         wallet
@@ -681,7 +853,7 @@ mod tests {
         // Prepare a registered and locked wallet.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Cancelling PID issuance on a locked wallet should result in an error.
         let error = wallet
@@ -757,12 +929,57 @@ mod tests {
         assert_matches!(attestations[0].identity, AttestationIdentity::Ephemeral);
     }
 
+    #[tokio::test]
+    async fn test_continue_pid_issuance_error_credential_format_not_allowed() {
+        let mut wallet = setup_wallet_with_digid_session();
+
+        // Configure the wallet to not accept any credential formats.
+        let mut config = wallet.config_repository.get().as_ref().clone();
+        config.pid_issuance.allowed_credential_formats = vec![];
+        wallet.config_repository = UpdatingConfigurationRepository::new(
+            LocalConfigurationRepository::new(config),
+            default_config_server_config(),
+        )
+        .await;
+
+        let (unsigned_mdoc, metadata) = document::create_full_unsigned_pid_mdoc();
+        let metadata_chain = TypeMetadataChain::create(metadata, vec![]).unwrap();
+        // Set up the `MockIssuanceSession` to return one `AttestationPreview` and expect to be rejected.
+        let start_context = MockIssuanceSession::start_context();
+        start_context.expect().return_once(|| {
+            let mut client = MockIssuanceSession::new();
+            client.expect_reject().return_once(|| Ok(()));
+
+            Ok((
+                client,
+                vec![CredentialFormats::try_new(
+                    VecNonEmpty::try_from(vec![CredentialPreview::MsoMdoc {
+                        unsigned_mdoc,
+                        issuer_certificate: ISSUER_KEY.issuance_key.certificate().clone(),
+                        metadata_chain,
+                    }])
+                    .unwrap(),
+                )
+                .unwrap()],
+            ))
+        });
+
+        // Continuing PID issuance should result in an error, as the offered format is not allowed.
+        let error = wallet
+            .continue_pid_issuance(Url::parse(REDIRECT_URI).unwrap())
+            .await
+            .expect_err("Continuing PID issuance should have resulted in error");
+
+        assert_matches!(error, PidIssuanceError::CredentialFormatNotAllowed(Format::MsoMdoc));
+        assert!(wallet.issuance_session.is_none());
+    }
+
     #[tokio::test]
     async fn test_continue_pid_issuance_error_locked() {
         // Prepare a registered and locked wallet.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Continuing PID issuance on a locked wallet should result in an error.
         let error = wallet
@@ -860,7 +1077,7 @@ mod tests {
                 .return_once(|| Err(IssuanceSessionError::MissingNonce));
             client
         };
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Canceling PID issuance on a wallet should forward this error.
         let error = wallet
@@ -887,8 +1104,8 @@ mod tests {
         // Create a mock OpenID4VCI session that accepts the PID with a single
         // instance of `MdocCopies`, which contains a single valid `Mdoc`.
         let mdoc = test::create_full_pid_mdoc();
-        let pid_issuer = mock_issuance_session(mdoc);
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        let pid_issuer = mock_issuance_session(mdoc, NotificationEvent::CredentialAccepted);
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Accept the PID issuance with the PIN.
         wallet
@@ -937,6 +1154,35 @@ mod tests {
         assert_matches!(err, PidIssuanceError::PidAlreadyPresent);
     }
 
+    #[rstest]
+    #[case::wte_exceeds_deadline(Duration::from_secs(60), Some(Duration::from_secs(120)))]
+    #[case::deadline_expired_before_accepting(Duration::ZERO, None)]
+    #[tokio::test(start_paused = true)]
+    async fn test_accept_pid_issuance_error_timeout(#[case] remaining: Duration, #[case] wte_delay: Option<Duration>) {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Optionally have the Wallet Provider take longer to issue the WTE than the remaining time of the flow.
+        wallet.wte_issuance_client.delay = wte_delay;
+
+        // Set up a mock OpenID4VCI session that expects to be rejected, without credentials ever being requested.
+        let mut pid_issuer = MockIssuanceSession::new();
+        pid_issuer.expect_reject().return_once(|| Ok(()));
+
+        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci {
+            pid_issuer,
+            deadline: Instant::now() + remaining,
+        });
+
+        // Accepting PID issuance should time out and remove the issuance session.
+        let error = wallet
+            .accept_pid_issuance(PIN.to_string())
+            .await
+            .expect_err("Accepting PID issuance should have resulted in an error");
+
+        assert_matches!(error, PidIssuanceError::Timeout);
+        assert!(wallet.issuance_session.is_none());
+    }
+
     #[tokio::test]
     async fn test_accept_pid_issuance_missing_issuer_registration() {
         // Prepare a registered and unlocked wallet.
@@ -945,8 +1191,8 @@ mod tests {
         // Create a mock OpenID4VCI session that accepts the PID with a single instance of `MdocCopies`, which contains
         // a single valid `Mdoc`, but signed with a Certificate that is missing IssuerRegistration
         let mdoc = test::create_full_pid_mdoc_unauthenticated();
-        let pid_issuer = mock_issuance_session(mdoc);
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        let pid_issuer = mock_issuance_session(mdoc, NotificationEvent::CredentialFailure);
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Accept the PID issuance with the PIN.
         let error = wallet
@@ -983,7 +1229,7 @@ mod tests {
         // Prepare a registered and locked wallet.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Accepting PID issuance on a locked wallet should result in an error.
         let error = wallet
@@ -1029,7 +1275,7 @@ mod tests {
                 .return_once(|| Err(IssuanceSessionError::Jwt(JwtError::Signing(Box::new(key_error)))));
             client
         };
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Accepting PID issuance should result in an error.
         let error = wallet
@@ -1108,7 +1354,7 @@ mod tests {
                 .return_once(|| Err(IssuanceSessionError::MissingNonce));
             client
         };
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Accepting PID issuance should result in an error.
         let error = wallet
@@ -1129,8 +1375,8 @@ mod tests {
 
         // Have the mock OpenID4VCI session report some mdocs upon accepting.
         let mdoc = test::create_full_pid_mdoc();
-        let pid_issuer = mock_issuance_session(mdoc);
-        wallet.issuance_session = Some(PidIssuanceSession::Openid4vci(pid_issuer));
+        let pid_issuer = mock_issuance_session(mdoc, NotificationEvent::CredentialFailure);
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
 
         // Have the mdoc storage return an error on query.
         wallet.storage.write().await.has_query_error = true;
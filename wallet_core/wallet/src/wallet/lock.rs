@@ -2,9 +2,11 @@ use std::sync::Arc;
 
 use tracing::info;
 use tracing::instrument;
+use tracing::warn;
 
 use error_category::sentry_capture_error;
 use error_category::ErrorCategory;
+use openid4vc::issuance_session::IssuanceSession;
 use platform_support::attested_key::AttestedKeyHolder;
 use wallet_common::account::messages::instructions::CheckPin;
 use wallet_common::config::http::TlsPinningConfig;
@@ -15,6 +17,7 @@ pub use crate::lock::LockCallback;
 pub use crate::storage::UnlockMethod;
 
 use crate::account_provider::AccountProviderClient;
+use crate::disclosure::MdocDisclosureSession;
 use crate::errors::ChangePinError;
 use crate::errors::StorageError;
 use crate::instruction::InstructionError;
@@ -24,6 +27,7 @@ use crate::storage::Storage;
 use crate::storage::UnlockData;
 use crate::update_policy::UpdatePolicyError;
 
+use super::PidIssuanceSession;
 use super::Wallet;
 
 #[derive(Debug, thiserror::Error, ErrorCategory)]
@@ -124,9 +128,41 @@ where
         Ok(())
     }
 
+    /// Lock the wallet, abandoning any in-flight PID issuance (including DigiD) or disclosure session. These sessions
+    /// are terminated at the issuer or verifier, so they can not be continued or accepted after unlocking the wallet.
+    /// Any error that occurs while terminating a session is logged, as locking the wallet should always succeed.
     #[instrument(skip_all)]
-    pub fn lock(&mut self) {
+    pub async fn lock(&mut self)
+    where
+        CR: Repository<Arc<WalletConfiguration>>,
+        UR: Repository<VersionState>,
+        S: Storage,
+        IS: IssuanceSession,
+        MDS: MdocDisclosureSession<Self>,
+    {
         self.lock.lock();
+
+        if let Some(issuance_session) = self.issuance_session.take() {
+            info!("Abandoning active PID issuance session");
+            self.issuance_session_abandoned = true;
+
+            if let PidIssuanceSession::Openid4vci { pid_issuer, .. } = issuance_session {
+                info!("Rejecting PID");
+                if let Err(error) = pid_issuer.reject_issuance().await {
+                    warn!("Error while rejecting abandoned PID issuance session: {error}");
+                }
+            }
+        }
+
+        if let Some(session) = self.disclosure_session.take() {
+            info!("Abandoning active disclosure session");
+            self.disclosure_session_abandoned = true;
+
+            // This also logs the disclosure as cancelled in the history.
+            if let Err(error) = self.terminate_disclosure_session(session).await {
+                warn!("Error while terminating abandoned disclosure session: {error}");
+            }
+        }
     }
 
     async fn send_check_pin_instruction(&self, pin: String) -> Result<(), WalletUnlockError>
@@ -261,6 +297,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::Ordering;
     use std::sync::Arc;
 
     use assert_matches::assert_matches;
@@ -272,6 +309,7 @@ mod tests {
     use rstest::rstest;
 
     use apple_app_attest::AssertionCounter;
+    use openid4vc::mock::MockIssuanceSession;
     use platform_support::attested_key::AttestedKey;
     use wallet_common::account::messages::errors::AccountError;
     use wallet_common::account::messages::errors::IncorrectPinData;
@@ -284,13 +322,20 @@ mod tests {
     use wallet_common::utils;
 
     use crate::account_provider::AccountProviderResponseError;
+    use crate::disclosure::MockMdocDisclosureSession;
+    use crate::issuance::MockDigidSession;
     use crate::pin::key::PinKey;
+    use crate::storage::EventStatus;
     use crate::storage::InstructionData;
     use crate::storage::KeyedData;
+    use crate::storage::WalletEvent;
 
+    use super::super::issuance::PidIssuanceSession;
     use super::super::test::WalletDeviceVendor;
     use super::super::test::WalletWithMocks;
     use super::super::test::ACCOUNT_SERVER_KEYS;
+    use super::super::DisclosureError;
+    use super::super::PidIssuanceError;
     use super::super::WalletRegistration;
     use super::*;
 
@@ -313,8 +358,8 @@ mod tests {
         wallet.set_lock_callback(Box::new(move |is_locked| callback_is_locked_vec.lock().push(is_locked)));
 
         // Lock the `Wallet`, then lock it again.
-        wallet.lock();
-        wallet.lock();
+        wallet.lock().await;
+        wallet.lock().await;
 
         // Mock the responses of the account server for both the instruction challenge
         // and the actual instruction and check the contents of those messages.
@@ -452,12 +497,81 @@ mod tests {
         assert_eq!(Arc::strong_count(&is_locked_vec), 1);
 
         // Lock the `Wallet` again.
-        wallet.lock();
+        wallet.lock().await;
 
         // Test that the callback was not called.
         assert_eq!(is_locked_vec.lock().len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_wallet_lock_abandons_sessions() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Start both an issuance and a disclosure session, then lock the `Wallet`.
+        wallet.issuance_session = Some(PidIssuanceSession::Digid(MockDigidSession::default()));
+        wallet.disclosure_session = Some(MockMdocDisclosureSession::default());
+
+        wallet.lock().await;
+
+        // Both sessions should have been dropped.
+        assert!(wallet.issuance_session.is_none());
+        assert!(wallet.disclosure_session.is_none());
+
+        // After unlocking, continuing or accepting either session should
+        // result in an error that reports the session as abandoned.
+        wallet.lock.unlock();
+
+        let error = wallet
+            .continue_pid_issuance("http://example.com".parse().unwrap())
+            .await
+            .expect_err("Continuing PID issuance should have resulted in error");
+        assert_matches!(error, PidIssuanceError::SessionAbandoned);
+
+        let error = wallet
+            .accept_pid_issuance(PIN.to_string())
+            .await
+            .expect_err("Accepting PID issuance should have resulted in error");
+        assert_matches!(error, PidIssuanceError::SessionAbandoned);
+
+        let error = wallet
+            .accept_disclosure(PIN.to_string())
+            .await
+            .expect_err("Accepting disclosure should have resulted in error");
+        assert_matches!(error, DisclosureError::SessionAbandoned);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_lock_terminates_sessions() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Start both an issuance and a disclosure session, of which the issuance session should be rejected.
+        let pid_issuer = {
+            let mut client = MockIssuanceSession::new();
+            client.expect_reject().times(1).return_once(|| Ok(()));
+            client
+        };
+        let disclosure_session = MockMdocDisclosureSession::default();
+        let was_terminated = Arc::clone(&disclosure_session.was_terminated);
+
+        wallet.issuance_session = Some(PidIssuanceSession::new_openid4vci(pid_issuer));
+        wallet.disclosure_session = Some(disclosure_session);
+
+        wallet.lock().await;
+
+        // The disclosure session should have been terminated and logged as cancelled.
+        assert!(was_terminated.load(Ordering::Relaxed));
+
+        let events = wallet.storage.read().await.fetch_wallet_events().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            events.first().unwrap(),
+            WalletEvent::Disclosure {
+                status: EventStatus::Cancelled,
+                ..
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_wallet_unlock_error_not_registered() {
         // Prepare an unregistered wallet
@@ -489,7 +603,7 @@ mod tests {
     async fn test_wallet_unlock_error_instruction_server_challenge_404() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // A 404 response from the account server when requesting the instruction
         // challenge for unlocking should result in an `InstructionError::ServerError`.
@@ -513,7 +627,7 @@ mod tests {
     ) -> WalletUnlockError {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         let account_provider_client = Arc::get_mut(&mut wallet.account_provider_client).unwrap();
 
@@ -605,7 +719,7 @@ mod tests {
     async fn test_wallet_unlock_error_instruction_signing() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Have the hardware key signing fail.
         match &mut wallet.registration {
@@ -628,7 +742,7 @@ mod tests {
     async fn test_wallet_unlock_error_instruction_result_validation() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         let account_provider_client = Arc::get_mut(&mut wallet.account_provider_client).unwrap();
         account_provider_client
@@ -666,7 +780,7 @@ mod tests {
     async fn test_wallet_unlock_error_instruction_store() {
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
 
-        wallet.lock();
+        wallet.lock().await;
 
         // Have the database return an error when fetching the sequence number.
         wallet.storage.write().await.set_keyed_data_error(InstructionData::KEY);
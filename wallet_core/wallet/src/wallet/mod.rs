@@ -1,6 +1,7 @@
 mod attestations;
 mod change_pin;
 mod config;
+mod credential_metadata;
 mod disclosure;
 mod history;
 mod init;
@@ -9,6 +10,8 @@ mod issuance;
 mod lock;
 mod registration;
 mod reset;
+mod revocation;
+mod trust_anchors;
 mod uri;
 
 #[cfg(test)]
@@ -27,6 +30,7 @@ use platform_support::attested_key::AttestedKey;
 use platform_support::attested_key::AttestedKeyHolder;
 use platform_support::hw_keystore::hardware::HardwareEncryptionKey;
 
+use crate::account_provider::CircuitBreakerAccountProviderClient;
 use crate::account_provider::HttpAccountProviderClient;
 use crate::config::WalletConfigurationRepository;
 use crate::issuance::HttpDigidSession;
@@ -37,7 +41,10 @@ use crate::update_policy::UpdatePolicyRepository;
 use crate::wallet::attestations::AttestationsCallback;
 use crate::wte::WpWteIssuanceClient;
 
+pub use self::credential_metadata::CredentialMetadataError;
 pub use self::disclosure::DisclosureError;
+pub use self::disclosure::DisclosurePreview;
+pub use self::disclosure::DisclosurePreviewAttribute;
 pub use self::disclosure::DisclosureProposal;
 pub use self::history::EventConversionError;
 pub use self::history::EventStatus;
@@ -45,13 +52,21 @@ pub use self::history::EventStorageError;
 pub use self::history::HistoryError;
 pub use self::history::HistoryEvent;
 pub use self::history::RecentHistoryCallback;
+pub use self::init::select_config_environment;
 pub use self::init::WalletInitError;
 pub use self::issuance::PidIssuanceError;
 pub use self::lock::LockCallback;
 pub use self::lock::UnlockMethod;
 pub use self::lock::WalletUnlockError;
+pub use self::registration::AttestedKeyContinuity;
+pub use self::registration::AttestedKeyContinuityError;
+pub use self::registration::CertificateValidationError;
 pub use self::registration::WalletRegistrationError;
 pub use self::reset::ResetError;
+pub use self::revocation::RemoveCredentialsError;
+pub use self::trust_anchors::TrustAnchorError;
+pub use self::trust_anchors::TrustAnchorInfo;
+pub use self::trust_anchors::TrustAnchorSummary;
 pub use self::uri::UriIdentificationError;
 pub use self::uri::UriType;
 
@@ -93,15 +108,15 @@ impl<A, G> WalletRegistration<A, G> {
 }
 
 pub struct Wallet<
-    CR = WalletConfigurationRepository,                 // Repository<WalletConfiguration>
-    UR = UpdatePolicyRepository,                        // Repository<VersionState>
-    S = DatabaseStorage<HardwareEncryptionKey>,         // Storage
-    AKH = KeyHolderType,                                // AttestedKeyHolder
-    APC = HttpAccountProviderClient,                    // AccountProviderClient
-    DS = HttpDigidSession,                              // DigidSession
-    IS = HttpIssuanceSession,                           // IssuanceSession
-    MDS = DisclosureSession<HttpVpMessageClient, Uuid>, // MdocDisclosureSession
-    WIC = WpWteIssuanceClient,                          // WteIssuanceClient
+    CR = WalletConfigurationRepository,                                   // Repository<WalletConfiguration>
+    UR = UpdatePolicyRepository,                                          // Repository<VersionState>
+    S = DatabaseStorage<HardwareEncryptionKey>,                           // Storage
+    AKH = KeyHolderType,                                                  // AttestedKeyHolder
+    APC = CircuitBreakerAccountProviderClient<HttpAccountProviderClient>, // AccountProviderClient
+    DS = HttpDigidSession,                                                // DigidSession
+    IS = HttpIssuanceSession,                                             // IssuanceSession
+    MDS = DisclosureSession<HttpVpMessageClient, Uuid>,                   // MdocDisclosureSession
+    WIC = WpWteIssuanceClient,                                            // WteIssuanceClient
 > where
     AKH: AttestedKeyHolder,
 {
@@ -113,6 +128,10 @@ pub struct Wallet<
     account_provider_client: Arc<APC>,
     issuance_session: Option<PidIssuanceSession<DS, IS>>,
     disclosure_session: Option<MDS>,
+    /// Whether an in-flight issuance or disclosure session was abandoned when the wallet was locked, see
+    /// [`Wallet::lock()`]. This is reset when a new session of that type is started.
+    issuance_session_abandoned: bool,
+    disclosure_session_abandoned: bool,
     wte_issuance_client: WIC,
     lock: WalletLock,
     attestations_callback: Option<AttestationsCallback>,
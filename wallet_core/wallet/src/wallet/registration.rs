@@ -14,6 +14,7 @@ use platform_support::attested_key::AttestedKeyHolder;
 use platform_support::attested_key::KeyWithAttestation;
 use wallet_common::account::messages::auth::Registration;
 use wallet_common::account::signed::ChallengeResponse;
+use wallet_common::apple::AppleAttestedKey;
 use wallet_common::config::http::TlsPinningConfig;
 use wallet_common::config::wallet_config::WalletConfiguration;
 use wallet_common::jwt::JwtError;
@@ -24,6 +25,8 @@ use wallet_common::vec_at_least::VecAtLeastNError;
 
 use crate::account_provider::AccountProviderClient;
 use crate::account_provider::AccountProviderError;
+use crate::account_provider::CircuitBreakerAccountProviderClient;
+use crate::account_provider::CircuitState;
 use crate::errors::UpdatePolicyError;
 use crate::pin::key::PinKey;
 use crate::pin::key::{self as pin_key};
@@ -39,6 +42,9 @@ use crate::storage::StorageError;
 use super::Wallet;
 use super::WalletRegistration;
 
+/// The payload that is signed using an Apple attested key to check that the key is still present.
+const ATTESTED_KEY_CONTINUITY_PAYLOAD: &[u8] = b"attested_key_continuity";
+
 #[derive(Debug, thiserror::Error, ErrorCategory)]
 #[category(defer)]
 pub enum WalletRegistrationError {
@@ -69,7 +75,7 @@ pub enum WalletRegistrationError {
     #[error("could not request registration from Wallet Provider: {0}")]
     RegistrationRequest(#[source] AccountProviderError),
     #[error("could not validate registration certificate received from Wallet Provider: {0}")]
-    CertificateValidation(#[source] JwtError),
+    CertificateValidation(#[source] CertificateValidationError),
     #[error("public key in registration certificate received from Wallet Provider does not match hardware public key")]
     #[category(expected)]
     PublicKeyMismatch,
@@ -79,6 +85,38 @@ pub enum WalletRegistrationError {
     UpdatePolicy(#[from] UpdatePolicyError),
 }
 
+#[derive(Debug, thiserror::Error, ErrorCategory)]
+pub enum CertificateValidationError {
+    #[error("{0}")]
+    #[category(defer)]
+    Jwt(#[from] JwtError),
+    #[error("registration certificate issuer does not match Wallet Provider, expected: {expected}, found: {found}")]
+    #[category(critical)]
+    IssuerMismatch { expected: String, found: String },
+}
+
+/// The outcome of [`Wallet::verify_attested_key_continuity()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttestedKeyContinuity {
+    /// The wallet is not registered, so there is no attested key to verify.
+    Unregistered,
+    /// The attested key is still usable and, where this can be checked, matches the registration.
+    Intact,
+    /// The attested key is no longer usable or does not match the registration, in which case the wallet cannot be
+    /// used and should be registered again.
+    Lost,
+}
+
+#[derive(Debug, thiserror::Error, ErrorCategory)]
+#[category(defer)]
+pub enum AttestedKeyContinuityError {
+    #[error("could not use attested key: {0}")]
+    #[category(pd)]
+    AttestedKey(#[source] Box<dyn Error + Send + Sync>),
+    #[error("could not validate stored registration certificate: {0}")]
+    CertificateValidation(#[source] CertificateValidationError),
+}
+
 impl WalletRegistrationError {
     pub fn is_attestation_not_supported(&self) -> bool {
         match self {
@@ -95,6 +133,15 @@ impl WalletRegistrationError {
     }
 }
 
+/// Only an attested key that no longer exists means that continuity is lost. Any other error, e.g. a transient one from
+/// the platform, should not result in the wallet having to be registered again.
+fn is_attested_key_not_found(error: &(dyn Error + 'static)) -> bool {
+    matches!(
+        error.downcast_ref::<HardwareAttestedKeyError>(),
+        Some(HardwareAttestedKeyError::Platform(AttestedKeyError::KeyNotFound))
+    )
+}
+
 impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
 where
     AKH: AttestedKeyHolder,
@@ -268,7 +315,18 @@ where
         // Note that this public key is only available on Android, on iOS all we have is opaque attestation data.
         let cert_claims = wallet_certificate
             .parse_and_verify_with_sub(&config.account_server.certificate_public_key.clone().into())
-            .map_err(WalletRegistrationError::CertificateValidation)?;
+            .map_err(|error| WalletRegistrationError::CertificateValidation(error.into()))?;
+
+        // Make sure that the wallet certificate was issued by the Wallet Provider we expect, so that a certificate that
+        // was validly signed for another instance of the Wallet Provider is not accepted.
+        if cert_claims.iss != config.account_server.certificate_issuer {
+            return Err(WalletRegistrationError::CertificateValidation(
+                CertificateValidationError::IssuerMismatch {
+                    expected: config.account_server.certificate_issuer.clone(),
+                    found: cert_claims.iss,
+                },
+            ));
+        }
 
         if let AttestedKey::Google(key) = &attested_key {
             let attested_pub_key = key
@@ -312,6 +370,83 @@ where
 
         Ok(())
     }
+
+    /// Verify that the attested key referenced by the stored registration still exists and, on Android, that its
+    /// public key matches the one in the wallet certificate. As the OS does not necessarily preserve the attested key,
+    /// e.g. across app updates, this should be checked on launch to determine if the wallet needs to be registered
+    /// again.
+    #[instrument(skip_all)]
+    #[sentry_capture_error]
+    pub async fn verify_attested_key_continuity(&self) -> Result<AttestedKeyContinuity, AttestedKeyContinuityError>
+    where
+        CR: Repository<Arc<WalletConfiguration>>,
+    {
+        info!("Verifying continuity of attested key");
+
+        let Some((attested_key, registration_data)) = self.registration.as_key_and_registration_data() else {
+            info!("Wallet is not registered, no attested key to verify");
+
+            return Ok(AttestedKeyContinuity::Unregistered);
+        };
+
+        let is_intact = match attested_key.as_ref() {
+            // The public key of an Apple attested key is not available, so the best we can do is to check that the key
+            // is still able to generate an assertion.
+            AttestedKey::Apple(key) => match key.sign(ATTESTED_KEY_CONTINUITY_PAYLOAD.to_vec()).await {
+                Ok(_) => true,
+                Err(error) if is_attested_key_not_found(&error) => {
+                    warn!("Apple attested key no longer exists");
+
+                    false
+                }
+                Err(error) => return Err(AttestedKeyContinuityError::AttestedKey(Box::new(error))),
+            },
+            AttestedKey::Google(key) => match key.verifying_key().await {
+                Ok(public_key) => {
+                    let config = self.config_repository.get();
+                    let cert_claims = registration_data
+                        .wallet_certificate
+                        .parse_and_verify_with_sub(&config.account_server.certificate_public_key.clone().into())
+                        .map_err(|error| AttestedKeyContinuityError::CertificateValidation(error.into()))?;
+
+                    let is_matching = cert_claims.hw_pubkey.0 == public_key;
+                    if !is_matching {
+                        warn!("Public key of Google attested key does not match registration certificate");
+                    }
+
+                    is_matching
+                }
+                Err(error) if is_attested_key_not_found(&error) => {
+                    warn!("Google attested key no longer exists");
+
+                    false
+                }
+                Err(error) => return Err(AttestedKeyContinuityError::AttestedKey(Box::new(error))),
+            },
+        };
+
+        let continuity = if is_intact {
+            AttestedKeyContinuity::Intact
+        } else {
+            AttestedKeyContinuity::Lost
+        };
+
+        info!("Continuity of attested key: {continuity:?}");
+
+        Ok(continuity)
+    }
+}
+
+impl<CR, UR, S, AKH, C, DS, IS, MDS, WIC>
+    Wallet<CR, UR, S, AKH, CircuitBreakerAccountProviderClient<C>, DS, IS, MDS, WIC>
+where
+    AKH: AttestedKeyHolder,
+{
+    /// Returns the state of the circuit breaker in front of the Wallet Provider. When this is
+    /// [`CircuitState::Open`], the Wallet Provider should be presented as unavailable.
+    pub fn account_provider_state(&self) -> CircuitState {
+        self.account_provider_client.state()
+    }
 }
 
 #[cfg(test)]
@@ -333,6 +468,7 @@ mod tests {
     use platform_support::attested_key::mock::KeyHolderType;
     use wallet_common::account::messages::auth::RegistrationAttestation;
     use wallet_common::account::messages::auth::WalletCertificate;
+    use wallet_common::account::messages::auth::WalletCertificateClaims;
     use wallet_common::account::signed::SequenceNumberComparison;
     use wallet_common::jwt::Jwt;
     use wallet_common::utils;
@@ -343,6 +479,7 @@ mod tests {
 
     use super::super::test::WalletDeviceVendor;
     use super::super::test::WalletWithMocks;
+    use super::super::test::ACCOUNT_SERVER_KEYS;
     use super::*;
 
     const PIN: &str = "051097";
@@ -736,6 +873,49 @@ mod tests {
         assert!(wallet.storage.read().await.data.is_empty());
     }
 
+    #[tokio::test]
+    #[rstest]
+    async fn test_wallet_register_error_certificate_issuer_mismatch(
+        #[values(WalletDeviceVendor::Apple, WalletDeviceVendor::Google)] vendor: WalletDeviceVendor,
+    ) {
+        let mut wallet = unregistered_wallet_with_registration_challenge(vendor);
+
+        // Have the account server respond with a validly signed certificate,
+        // that was issued by a different instance of the Wallet Provider.
+        Arc::get_mut(&mut wallet.account_provider_client)
+            .unwrap()
+            .expect_register()
+            .return_once(|_, _| {
+                let random_pubkey = *SigningKey::random(&mut OsRng).verifying_key();
+                let claims = WalletCertificateClaims {
+                    iss: "other_account_server".to_string(),
+                    ..WalletWithMocks::valid_certificate_claims(None, random_pubkey)
+                };
+
+                let certificate = Jwt::sign_with_sub(&claims, &ACCOUNT_SERVER_KEYS.certificate_signing_key)
+                    .now_or_never()
+                    .unwrap()
+                    .unwrap();
+
+                Ok(certificate)
+            });
+
+        let error = wallet
+            .register(PIN.to_string())
+            .await
+            .expect_err("Wallet registration should have resulted in error");
+
+        assert_matches!(
+            error,
+            WalletRegistrationError::CertificateValidation(CertificateValidationError::IssuerMismatch {
+                expected,
+                found,
+            }) if expected == "wallet_unit_test" && found == "other_account_server"
+        );
+        assert_matches!(wallet.registration, WalletRegistration::Unregistered);
+        assert!(wallet.storage.read().await.data.is_empty());
+    }
+
     #[tokio::test]
     async fn test_wallet_register_error_public_key_mismatch() {
         let mut wallet = unregistered_wallet_with_registration_challenge(WalletDeviceVendor::Google);
@@ -794,4 +974,110 @@ mod tests {
         assert_eq!(data.len(), 1);
         assert_matches!(data.get(RegistrationData::KEY), Some(KeyedDataResult::Error));
     }
+
+    #[tokio::test]
+    async fn test_wallet_verify_attested_key_continuity_unregistered() {
+        let wallet = WalletWithMocks::new_unregistered(WalletDeviceVendor::Apple);
+
+        let continuity = wallet
+            .verify_attested_key_continuity()
+            .await
+            .expect("verifying attested key continuity should succeed");
+
+        assert_eq!(continuity, AttestedKeyContinuity::Unregistered);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    async fn test_wallet_verify_attested_key_continuity_intact(
+        #[values(WalletDeviceVendor::Apple, WalletDeviceVendor::Google)] vendor: WalletDeviceVendor,
+    ) {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(vendor);
+
+        let continuity = wallet
+            .verify_attested_key_continuity()
+            .await
+            .expect("verifying attested key continuity should succeed");
+
+        assert_eq!(continuity, AttestedKeyContinuity::Intact);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_verify_attested_key_continuity_error_apple() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        // Have the attested key fail to sign for a reason other than the key not existing.
+        let WalletRegistration::Registered { attested_key, .. } = &mut wallet.registration else {
+            unreachable!();
+        };
+        let AttestedKey::Apple(key) = Arc::get_mut(attested_key).unwrap() else {
+            unreachable!();
+        };
+        key.has_error = true;
+
+        let error = wallet
+            .verify_attested_key_continuity()
+            .await
+            .expect_err("verifying attested key continuity should fail");
+
+        assert_matches!(error, AttestedKeyContinuityError::AttestedKey(_));
+    }
+
+    #[rstest]
+    #[case(HardwareAttestedKeyError::Platform(AttestedKeyError::KeyNotFound), true)]
+    #[case(HardwareAttestedKeyError::Platform(AttestedKeyError::Other { reason: "keychain".to_string() }), false)]
+    #[case(HardwareAttestedKeyError::IdentifierInUse("key".to_string()), false)]
+    fn test_is_attested_key_not_found(#[case] error: HardwareAttestedKeyError, #[case] expected: bool) {
+        assert_eq!(is_attested_key_not_found(&error), expected);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_verify_attested_key_continuity_lost_google() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Google);
+
+        // Replace the wallet certificate with one that contains a different public key.
+        let WalletRegistration::Registered { data, .. } = &mut wallet.registration else {
+            unreachable!();
+        };
+        data.wallet_certificate = WalletWithMocks::valid_certificate(
+            Some(data.wallet_id.clone()),
+            *SigningKey::random(&mut OsRng).verifying_key(),
+        );
+
+        let continuity = wallet
+            .verify_attested_key_continuity()
+            .await
+            .expect("verifying attested key continuity should succeed");
+
+        assert_eq!(continuity, AttestedKeyContinuity::Lost);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_verify_attested_key_continuity_error_certificate() {
+        let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Google);
+
+        // Replace the wallet certificate with one that is not signed by the Wallet Provider.
+        let WalletRegistration::Registered { attested_key, data } = &mut wallet.registration else {
+            unreachable!();
+        };
+        let AttestedKey::Google(key) = attested_key.as_ref() else {
+            unreachable!();
+        };
+        data.wallet_certificate = Jwt::sign_with_sub(
+            &WalletWithMocks::valid_certificate_claims(Some(data.wallet_id.clone()), *key.verifying_key()),
+            &SigningKey::random(&mut OsRng),
+        )
+        .await
+        .unwrap();
+
+        let error = wallet
+            .verify_attested_key_continuity()
+            .await
+            .expect_err("verifying attested key continuity should fail");
+
+        assert_matches!(
+            error,
+            AttestedKeyContinuityError::CertificateValidation(CertificateValidationError::Jwt(_))
+        );
+    }
 }
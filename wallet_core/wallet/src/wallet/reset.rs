@@ -166,7 +166,7 @@ mod tests {
     async fn test_wallet_reset_full() {
         // Create the impossible Wallet that is doing everything at once and reset it.
         let mut wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
-        wallet.issuance_session = PidIssuanceSession::Openid4vci(MockIssuanceSession::default()).into();
+        wallet.issuance_session = PidIssuanceSession::new_openid4vci(MockIssuanceSession::default()).into();
         wallet.disclosure_session = MockMdocDisclosureSession::default().into();
 
         wallet
@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use tracing::info;
+use tracing::instrument;
+use tracing::warn;
+
+use error_category::sentry_capture_error;
+use error_category::ErrorCategory;
+use nl_wallet_mdoc::utils::cose::CoseError;
+use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use platform_support::attested_key::AttestedKeyHolder;
+use wallet_common::config::wallet_config::WalletConfiguration;
+use wallet_common::update_policy::VersionState;
+
+use crate::repository::Repository;
+use crate::storage::EventDocuments;
+use crate::storage::Storage;
+use crate::storage::StorageError;
+use crate::storage::WalletEvent;
+
+use super::attestations::AttestationsError;
+use super::history::EventStorageError;
+use super::Wallet;
+
+#[derive(Debug, thiserror::Error, ErrorCategory)]
+#[category(defer)]
+pub enum RemoveCredentialsError {
+    #[category(expected)]
+    #[error("app version is blocked")]
+    VersionBlocked,
+    #[error("wallet is not registered")]
+    #[category(expected)]
+    NotRegistered,
+    #[error("wallet is locked")]
+    #[category(expected)]
+    Locked,
+    #[error("could not remove mdocs from database storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("invalid issuer certificate: {0}")]
+    InvalidIssuerCertificate(#[source] CoseError),
+    #[error("could not store event in history database: {0}")]
+    EventStorage(#[from] EventStorageError),
+    #[error("could not emit attestations after removing mdocs: {0}")]
+    Attestations(#[from] AttestationsError),
+}
+
+impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
+where
+    S: Storage,
+    AKH: AttestedKeyHolder,
+{
+    /// Remove all credentials that were signed using the provided issuer certificate, e.g. because that issuer has been
+    /// compromised. Returns the number of removed credentials.
+    #[instrument(skip_all)]
+    #[sentry_capture_error]
+    pub async fn remove_credentials_by_issuer(
+        &mut self,
+        issuer_certificate: &BorrowingCertificate,
+    ) -> Result<usize, RemoveCredentialsError>
+    where
+        UR: Repository<VersionState>,
+    {
+        info!("Removing credentials by issuer");
+
+        info!("Checking if blocked");
+        if self.is_blocked() {
+            return Err(RemoveCredentialsError::VersionBlocked);
+        }
+
+        info!("Checking if registered");
+        if !self.registration.is_registered() {
+            return Err(RemoveCredentialsError::NotRegistered);
+        }
+
+        info!("Checking if locked");
+        if self.lock.is_locked() {
+            return Err(RemoveCredentialsError::Locked);
+        }
+
+        let removed_count = self.delete_mdocs_by_issuer(issuer_certificate).await?;
+
+        if removed_count > 0 {
+            self.emit_attestations().await?;
+        }
+
+        Ok(removed_count)
+    }
+
+    /// Remove all credentials that were signed using any of the revoked issuer certificates in the configuration. As
+    /// this is performed when the wallet is initialized, it does not require the wallet to be unlocked.
+    /// Any entry in the configuration that cannot be parsed as a certificate is logged and skipped.
+    pub(super) async fn remove_revoked_issuer_credentials(&mut self) -> Result<usize, RemoveCredentialsError>
+    where
+        CR: Repository<Arc<WalletConfiguration>>,
+        UR: Repository<VersionState>,
+    {
+        let revoked_certificates = self
+            .config_repository
+            .get()
+            .revoked_issuer_certificates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, der)| {
+                BorrowingCertificate::from_der(der.as_slice())
+                    .inspect_err(|error| {
+                        warn!("Skipping revoked issuer certificate at index {index} in configuration: {error}")
+                    })
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        let mut removed_count = 0;
+        for issuer_certificate in &revoked_certificates {
+            removed_count += self.delete_mdocs_by_issuer(issuer_certificate).await?;
+        }
+
+        if removed_count > 0 {
+            self.emit_attestations().await?;
+        }
+
+        Ok(removed_count)
+    }
+
+    /// Delete all mdocs signed using the provided issuer certificate and record a deletion event in the history for
+    /// every one of them.
+    async fn delete_mdocs_by_issuer(
+        &mut self,
+        issuer_certificate: &BorrowingCertificate,
+    ) -> Result<usize, RemoveCredentialsError>
+    where
+        UR: Repository<VersionState>,
+    {
+        let stored_mdocs = self
+            .storage
+            .read()
+            .await
+            .fetch_mdocs_by_issuer(issuer_certificate)
+            .await?;
+
+        if stored_mdocs.is_empty() {
+            return Ok(0);
+        }
+
+        let mdoc_ids = stored_mdocs
+            .iter()
+            .map(|stored_mdoc| stored_mdoc.mdoc_id)
+            .collect::<Vec<_>>();
+
+        // Prepare the history events before deleting, as the attributes of the mdocs are no longer available
+        // afterwards.
+        let events = stored_mdocs
+            .into_iter()
+            .map(|stored_mdoc| EventDocuments::try_from(vec![stored_mdoc.mdoc]).map(WalletEvent::new_deletion))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RemoveCredentialsError::InvalidIssuerCertificate)?;
+
+        let removed_count = mdoc_ids.len();
+        warn!("Removing {removed_count} mdoc(s) signed using revoked issuer certificate: {mdoc_ids:?}");
+
+        self.storage.write().await.delete_mdocs(mdoc_ids).await?;
+
+        for event in events {
+            self.store_history_event(event).await?;
+        }
+
+        Ok(removed_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::config::default_config_server_config;
+    use crate::config::LocalConfigurationRepository;
+    use crate::config::UpdatingConfigurationRepository;
+    use crate::document::PID_DOCTYPE;
+
+    use super::super::test;
+    use super::super::test::WalletDeviceVendor;
+    use super::super::test::WalletWithMocks;
+    use super::*;
+
+    async fn setup_wallet_with_pid() -> (WalletWithMocks, BorrowingCertificate) {
+        let wallet = WalletWithMocks::new_registered_and_unlocked(WalletDeviceVendor::Apple);
+
+        let mdoc = test::create_full_pid_mdoc();
+        let issuer_certificate = mdoc.issuer_certificate().unwrap();
+        wallet
+            .storage
+            .write()
+            .await
+            .insert_mdocs(vec![vec![mdoc].try_into().unwrap()], &test::ISSUER_URL)
+            .await
+            .unwrap();
+
+        (wallet, issuer_certificate)
+    }
+
+    #[tokio::test]
+    async fn test_remove_credentials_by_issuer() {
+        let (mut wallet, issuer_certificate) = setup_wallet_with_pid().await;
+
+        let attestations = test::setup_mock_attestations_callback(&mut wallet).await.unwrap();
+
+        let removed_count = wallet
+            .remove_credentials_by_issuer(&issuer_certificate)
+            .await
+            .expect("removing credentials by issuer should succeed");
+
+        assert_eq!(removed_count, 1);
+        assert!(wallet
+            .storage
+            .read()
+            .await
+            .fetch_unique_mdocs()
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A deletion event should have been recorded for the removed PID.
+        let events = wallet.storage.read().await.fetch_wallet_events().await.unwrap();
+        assert_matches!(&events[..], [WalletEvent::Deletion { mdocs, .. }] if mdocs.0.contains_key(PID_DOCTYPE));
+
+        // The attestations should have been emitted again, without the removed PID.
+        let attestations = attestations.lock();
+        assert_eq!(attestations.len(), 2);
+        assert_eq!(attestations[0].len(), 1);
+        assert!(attestations[1].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remove_credentials_by_issuer_error_locked() {
+        let (mut wallet, issuer_certificate) = setup_wallet_with_pid().await;
+
+        wallet.lock().await;
+
+        let error = wallet
+            .remove_credentials_by_issuer(&issuer_certificate)
+            .await
+            .expect_err("removing credentials by issuer should fail");
+
+        assert_matches!(error, RemoveCredentialsError::Locked);
+        assert_eq!(wallet.storage.read().await.fetch_unique_mdocs().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove_revoked_issuer_credentials() {
+        let (mut wallet, issuer_certificate) = setup_wallet_with_pid().await;
+
+        // Lock the wallet, as this is also the case when it is initialized.
+        wallet.lock().await;
+
+        // Configure the issuer certificate of the PID as revoked, preceded by an entry that is not a certificate.
+        let mut config = wallet.config_repository.get().as_ref().clone();
+        config.revoked_issuer_certificates = vec![b"not a certificate".to_vec(), issuer_certificate.to_vec()];
+        wallet.config_repository = UpdatingConfigurationRepository::new(
+            LocalConfigurationRepository::new(config),
+            default_config_server_config(),
+        )
+        .await;
+
+        let removed_count = wallet
+            .remove_revoked_issuer_credentials()
+            .await
+            .expect("removing revoked issuer credentials should succeed");
+
+        assert_eq!(removed_count, 1);
+        assert!(wallet
+            .storage
+            .read()
+            .await
+            .fetch_unique_mdocs()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let events = wallet.storage.read().await.fetch_wallet_events().await.unwrap();
+        assert_matches!(&events[..], [WalletEvent::Deletion { mdocs, .. }] if mdocs.0.contains_key(PID_DOCTYPE));
+    }
+}
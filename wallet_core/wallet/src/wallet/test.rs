@@ -27,6 +27,7 @@ use wallet_common::generator::TimeGenerator;
 use wallet_common::jwt::Jwt;
 use wallet_common::keys::mock_remote::MockRemoteEcdsaKey;
 use wallet_common::trust_anchor::BorrowingTrustAnchor;
+use wallet_common::urls::BaseUrl;
 use wallet_common::utils;
 
 use crate::account_provider::MockAccountProviderClient;
@@ -116,6 +117,9 @@ pub static ISSUER_KEY_UNAUTHENTICATED: LazyLock<IssuerKey> = LazyLock::new(|| {
     }
 });
 
+/// The URL of the Credential Issuer from which the mdocs generated for testing are obtained.
+pub static ISSUER_URL: LazyLock<BaseUrl> = LazyLock::new(|| "https://issuer.example.com/".parse().unwrap());
+
 /// Generates a valid `Mdoc` that contains a full PID.
 pub fn create_full_pid_mdoc() -> Mdoc {
     let (unsigned_mdoc, metadata) = document::create_full_unsigned_pid_mdoc();
@@ -170,6 +174,7 @@ impl WalletWithMocks {
         let config = {
             let mut config = default_wallet_config();
 
+            config.account_server.certificate_issuer = "wallet_unit_test".to_string();
             config.account_server.certificate_public_key = (*keys.certificate_signing_key.verifying_key()).into();
             config.account_server.instruction_result_public_key =
                 (*keys.instruction_result_signing_key.verifying_key()).into();
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use tracing::info;
+
+use error_category::ErrorCategory;
+use nl_wallet_mdoc::utils::x509::BorrowingCertificate;
+use nl_wallet_mdoc::utils::x509::CertificateError;
+use platform_support::attested_key::AttestedKeyHolder;
+use wallet_common::config::wallet_config::WalletConfiguration;
+use wallet_common::trust_anchor::BorrowingTrustAnchor;
+
+use crate::repository::Repository;
+
+use super::Wallet;
+
+#[derive(Debug, thiserror::Error, ErrorCategory)]
+#[category(defer)]
+pub enum TrustAnchorError {
+    #[error("could not parse trust anchor certificate: {0}")]
+    Certificate(#[from] CertificateError),
+}
+
+/// The trust anchors from the wallet configuration that are currently in effect, split up by their purpose.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustAnchorSummary {
+    /// The CAs that are trusted to issue certificates to issuers of mdocs.
+    pub issuer_anchors: Vec<TrustAnchorInfo>,
+    /// The CAs that are trusted to issue certificates to relying parties.
+    pub reader_anchors: Vec<TrustAnchorInfo>,
+}
+
+/// Human-readable information about a single trust anchor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustAnchorInfo {
+    pub common_names: Vec<String>,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// Hex encoded SHA-256 digest over the DER encoding of the certificate.
+    pub fingerprint: String,
+}
+
+impl TryFrom<&BorrowingTrustAnchor> for TrustAnchorInfo {
+    type Error = CertificateError;
+
+    fn try_from(value: &BorrowingTrustAnchor) -> Result<Self, Self::Error> {
+        let certificate = BorrowingCertificate::from_der(value.as_ref())?;
+        let validity = certificate.x509_certificate().validity();
+
+        let info = TrustAnchorInfo {
+            common_names: certificate.common_names()?.into_iter().map(String::from).collect(),
+            // These unwraps are safe, as the timestamps in an X.509 certificate are always within the range of chrono.
+            not_before: DateTime::from_timestamp(validity.not_before.timestamp(), 0).unwrap(),
+            not_after: DateTime::from_timestamp(validity.not_after.timestamp(), 0).unwrap(),
+            fingerprint: certificate.fingerprint_sha256_hex(),
+        };
+
+        Ok(info)
+    }
+}
+
+impl TryFrom<&WalletConfiguration> for TrustAnchorSummary {
+    type Error = CertificateError;
+
+    fn try_from(value: &WalletConfiguration) -> Result<Self, Self::Error> {
+        let issuer_anchors = value
+            .mdoc_trust_anchors
+            .iter()
+            .map(TrustAnchorInfo::try_from)
+            .collect::<Result<_, _>>()?;
+        let reader_anchors = value
+            .disclosure
+            .rp_trust_anchors
+            .iter()
+            .map(TrustAnchorInfo::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let summary = TrustAnchorSummary {
+            issuer_anchors,
+            reader_anchors,
+        };
+
+        Ok(summary)
+    }
+}
+
+impl<CR, UR, S, AKH, APC, DS, IS, MDS, WIC> Wallet<CR, UR, S, AKH, APC, DS, IS, MDS, WIC>
+where
+    CR: Repository<Arc<WalletConfiguration>>,
+    AKH: AttestedKeyHolder,
+{
+    /// Returns a summary of the issuer and reader trust anchors in the currently active configuration.
+    pub fn effective_trust_anchors(&self) -> Result<TrustAnchorSummary, TrustAnchorError> {
+        info!("Summarizing effective trust anchors");
+
+        let config = self.config_repository.get();
+        let summary = TrustAnchorSummary::try_from(config.as_ref())?;
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Digest;
+    use sha2::Sha256;
+
+    use crate::config::default_wallet_config;
+
+    use super::super::test::WalletDeviceVendor;
+    use super::super::test::WalletWithMocks;
+    use super::super::test::ISSUER_KEY;
+
+    #[tokio::test]
+    async fn test_wallet_effective_trust_anchors() {
+        let wallet = WalletWithMocks::new_unregistered(WalletDeviceVendor::Apple);
+
+        let summary = wallet
+            .effective_trust_anchors()
+            .expect("trust anchors should be summarized");
+
+        // The mock wallet only trusts the mock issuer CA.
+        assert_eq!(summary.issuer_anchors.len(), 1);
+        let issuer_anchor = summary.issuer_anchors.first().unwrap();
+        assert_eq!(issuer_anchor.common_names, vec!["ca.issuer.example.com".to_string()]);
+        assert!(issuer_anchor.not_before < issuer_anchor.not_after);
+        assert_eq!(
+            issuer_anchor.fingerprint,
+            hex::encode(Sha256::digest(ISSUER_KEY.trust_anchor.as_ref()))
+        );
+
+        // The reader trust anchors are taken from the default configuration.
+        assert_eq!(
+            summary.reader_anchors.len(),
+            default_wallet_config().disclosure.rp_trust_anchors.len()
+        );
+    }
+}
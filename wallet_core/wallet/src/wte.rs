@@ -60,6 +60,8 @@ impl Default for WpWteIssuanceClient {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::time::Duration;
+
     use p256::ecdsa::VerifyingKey;
 
     use openid4vc::jwt::JwtCredential;
@@ -76,7 +78,11 @@ pub(crate) mod tests {
 
     use super::WteIssuanceClient;
 
-    pub struct MockWteIssuanceClient;
+    #[derive(Default)]
+    pub struct MockWteIssuanceClient {
+        /// Optionally delay obtaining the WTE, in order to simulate a slow Wallet Provider.
+        pub delay: Option<Duration>,
+    }
 
     impl WteIssuanceClient for MockWteIssuanceClient {
         async fn obtain_wte<S, AK, GK, A>(
@@ -90,6 +96,10 @@ pub(crate) mod tests {
             GK: GoogleAttestedKey,
             A: AccountProviderClient,
         {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+
             let key_id = random_string(32);
             MockRemoteEcdsaKey::new_random(key_id.clone());
             let cred = JwtCredential::new_unverified::<MockRemoteEcdsaKey>(key_id, "header.body.signature".into());
@@ -97,10 +107,4 @@ pub(crate) mod tests {
             Ok(cred)
         }
     }
-
-    impl Default for MockWteIssuanceClient {
-        fn default() -> Self {
-            Self
-        }
-    }
 }
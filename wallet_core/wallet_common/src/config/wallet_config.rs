@@ -1,6 +1,10 @@
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::num::NonZeroU32;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use derive_more::Debug;
 use etag::EntityTag;
@@ -9,11 +13,14 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_with::base64::Base64;
 use serde_with::serde_as;
+use serde_with::DurationSeconds;
+use url::Url;
 
 use crate::account::serialization::DerVerifyingKey;
 use crate::config::digid::DigidApp2AppConfiguration;
 use crate::config::http::TlsPinningConfig;
 use crate::config::EnvironmentSpecific;
+use crate::format::Format;
 use crate::trust_anchor::BorrowingTrustAnchor;
 use crate::urls::BaseUrl;
 
@@ -28,6 +35,12 @@ pub struct WalletConfiguration {
     #[debug(skip)]
     #[serde_as(as = "Vec<Base64>")]
     pub mdoc_trust_anchors: Vec<BorrowingTrustAnchor>,
+    /// DER encoded issuer certificates that should no longer be trusted, e.g. because the issuer was compromised.
+    /// Any credentials signed using one of these certificates are removed from the wallet.
+    #[debug(skip)]
+    #[serde(default)]
+    #[serde_as(as = "Vec<Base64>")]
+    pub revoked_issuer_certificates: Vec<Vec<u8>>,
     pub update_policy_server: UpdatePolicyServerConfiguration,
     pub google_cloud_project_id: u64,
     pub version: u64,
@@ -77,15 +90,41 @@ impl Default for LockTimeoutConfiguration {
     }
 }
 
+#[serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AccountServerConfiguration {
     pub http_config: TlsPinningConfig,
+    /// The expected `iss` claim of the wallet certificate, which identifies the Wallet Provider that issued it.
+    #[serde(default = "default_certificate_issuer")]
+    pub certificate_issuer: String,
     #[debug(skip)]
     pub certificate_public_key: DerVerifyingKey,
     #[debug(skip)]
     pub instruction_result_public_key: DerVerifyingKey,
     #[debug(skip)]
     pub wte_public_key: DerVerifyingKey,
+    /// The amount of consecutive failed requests to the Wallet Provider after which the wallet stops sending requests.
+    #[serde(default = "default_circuit_breaker_failure_threshold")]
+    pub circuit_breaker_failure_threshold: NonZeroU32,
+    /// The time for which no requests are sent to the Wallet Provider once the failure threshold has been reached.
+    #[serde(
+        rename = "circuit_breaker_cooldown_in_sec",
+        default = "default_circuit_breaker_cooldown"
+    )]
+    #[serde_as(as = "DurationSeconds")]
+    pub circuit_breaker_cooldown: Duration,
+}
+
+fn default_certificate_issuer() -> String {
+    "account_server".to_string()
+}
+
+fn default_circuit_breaker_failure_threshold() -> NonZeroU32 {
+    NonZeroU32::new(5).unwrap()
+}
+
+fn default_circuit_breaker_cooldown() -> Duration {
+    Duration::from_secs(30)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -93,11 +132,75 @@ pub struct UpdatePolicyServerConfiguration {
     pub http_config: TlsPinningConfig,
 }
 
+#[serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PidIssuanceConfiguration {
     pub pid_issuer_url: BaseUrl,
+    /// When set, the signing keys of the PID issuer are additionally required to be present in the JWKS that it
+    /// publishes at this URL.
+    #[serde(default)]
+    pub pid_issuer_jwks_url: Option<Url>,
     pub digid: DigidConfiguration,
     pub digid_http_config: TlsPinningConfig,
+    /// The OpenID4VCI credential formats that the wallet is willing to request.
+    #[serde(default = "default_allowed_credential_formats")]
+    pub allowed_credential_formats: Vec<Format>,
+    /// The maximum duration of the PID issuance flow, starting when the DigiD redirect is received and ending when the
+    /// PID is requested from the issuer. This includes the time the user takes to review the PID and enter their PIN.
+    /// The flow is aborted when this is exceeded.
+    #[serde(rename = "flow_timeout_in_sec", default = "default_flow_timeout")]
+    #[serde_as(as = "DurationSeconds")]
+    pub flow_timeout: Duration,
+    /// The maximum amount of credential private keys that are used concurrently when accepting the PID, both when
+    /// generating them and when signing with them.
+    #[serde(default = "default_concurrency_limit")]
+    pub concurrency_limit: NonZeroUsize,
+    /// The maximum size in bytes of a single CBOR-encoded attribute value in an issued credential.
+    #[serde(default = "default_max_attribute_size")]
+    pub max_attribute_size: usize,
+    /// The maximum size in bytes of a single CBOR-encoded issued credential. As every copy of a credential is stored
+    /// separately, this bounds the storage used by a single issuance.
+    #[serde(default = "default_max_credential_size")]
+    pub max_credential_size: usize,
+    /// The maximum time between now and the start of the validity of an issued credential. Credentials whose validity
+    /// starts later than that are rejected, as this indicates an issuer bug.
+    #[serde(rename = "max_future_validity_in_sec", default = "default_max_future_validity")]
+    #[serde_as(as = "DurationSeconds")]
+    pub max_future_validity: Duration,
+    /// Whether to bind all copies of the PID to a single key, which makes the copies linkable to each other when
+    /// disclosed. This requires the PID issuer to accept this.
+    #[serde(default)]
+    pub single_holder_key: bool,
+}
+
+fn default_allowed_credential_formats() -> Vec<Format> {
+    vec![Format::MsoMdoc]
+}
+
+fn default_flow_timeout() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_concurrency_limit() -> NonZeroUsize {
+    NonZeroUsize::new(8).unwrap()
+}
+
+fn default_max_attribute_size() -> usize {
+    1024 * 1024
+}
+
+fn default_max_credential_size() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_max_future_validity() -> Duration {
+    Duration::from_secs(365 * 24 * 60 * 60)
+}
+
+impl PidIssuanceConfiguration {
+    pub fn is_credential_format_allowed(&self, format: Format) -> bool {
+        self.allowed_credential_formats.contains(&format)
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq, Hash)]
@@ -113,9 +216,40 @@ pub struct DisclosureConfiguration {
     #[debug(skip)]
     #[serde_as(as = "Vec<Base64>")]
     pub rp_trust_anchors: Vec<BorrowingTrustAnchor>,
+    /// Temporarily disables disclosure, e.g. during incident response. When either of the scopes below is non-empty,
+    /// disclosure is only disabled for sessions that match all of the non-empty scopes.
+    #[serde(default)]
+    pub disclosure_disabled: bool,
+    /// The doc types for which disclosure is disabled, which applies to all doc types when empty.
+    #[serde(default)]
+    pub disclosure_disabled_doc_types: Vec<String>,
+    /// The hex encoded SHA-256 fingerprints of the certificates of the relying parties for which disclosure is
+    /// disabled, which applies to all relying parties when empty.
+    #[serde(default)]
+    pub disclosure_disabled_rp_certificates: Vec<String>,
+    /// The reason for disabling disclosure that is shown to the user, keyed by language.
+    #[serde(default)]
+    pub disclosure_disabled_reason: BTreeMap<String, String>,
 }
 
 impl DisclosureConfiguration {
+    /// Returns whether disclosure of any of the `doc_types` to the relying party with the certificate identified by
+    /// `rp_certificate_fingerprint` is currently disabled.
+    pub fn is_disclosure_disabled<'a>(
+        &self,
+        mut doc_types: impl Iterator<Item = &'a str>,
+        rp_certificate_fingerprint: &str,
+    ) -> bool {
+        self.disclosure_disabled
+            && (self.disclosure_disabled_rp_certificates.is_empty()
+                || self
+                    .disclosure_disabled_rp_certificates
+                    .iter()
+                    .any(|fingerprint| fingerprint.eq_ignore_ascii_case(rp_certificate_fingerprint)))
+            && (self.disclosure_disabled_doc_types.is_empty()
+                || doc_types.any(|doc_type| self.disclosure_disabled_doc_types.iter().any(|d| d == doc_type)))
+    }
+
     pub fn rp_trust_anchors(&self) -> Vec<TrustAnchor> {
         self.rp_trust_anchors
             .iter()
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The format of a credential, as used in the OpenID4VCI and OpenID4VP protocols.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Format {
+    #[default]
+    MsoMdoc,
+    Jwt,
+
+    // Other formats we don't currently support; we include them here so we can give the appropriate error message
+    // when they might be requested by the wallet (as opposed to a deserialization error).
+    // The OpenID4VCI and OpenID4VP specs aim to be general and do not provide an exhaustive list; the formats below
+    // are found as examples in the specs.
+    LdpVc,
+    JwtVc,
+    JwtVcJson,
+    AcVc, // Anonymous Credentials i.e. Idemix
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::LazyLock;
 
@@ -8,6 +9,7 @@ use chrono::serde::ts_seconds;
 use chrono::DateTime;
 use chrono::Utc;
 use itertools::Itertools;
+use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::jwk;
 use jsonwebtoken::jwk::EllipticCurve;
 use jsonwebtoken::jwk::Jwk;
@@ -124,6 +126,12 @@ pub enum JwtError {
     #[error("cannot construct JSON-serialized JWT: received differing payloads: {0}, {1}")]
     #[category(pd)]
     DifferentPayloads(String, String),
+    #[error("detached JWS unexpectedly contains a payload")]
+    #[category(critical)]
+    PayloadNotDetached,
+    #[error("detached JWS header does not specify an unencoded payload")]
+    #[category(critical)]
+    PayloadNotUnencoded,
 }
 
 pub trait JwtSubject {
@@ -255,10 +263,12 @@ where
         Ok([message, encoded_signature].join(".").into())
     }
 
-    /// Bulk-sign the keys and JWT payloads into JWTs.
+    /// Bulk-sign the keys and JWT payloads into JWTs. The messages are signed in consecutive batches, so that no more
+    /// than `concurrency_limit` keys are used at the same time.
     pub async fn sign_bulk<K: CredentialEcdsaKey>(
         keys_and_messages: Vec<(K, (T, jsonwebtoken::Header))>,
         key_factory: &impl KeyFactory<Key = K>,
+        concurrency_limit: NonZeroUsize,
     ) -> Result<Vec<(K, Jwt<T>)>, JwtError> {
         let (keys, to_sign): (Vec<_>, Vec<_>) = keys_and_messages.into_iter().unzip();
 
@@ -275,16 +285,19 @@ where
             .collect::<Result<Vec<_>, JwtError>>()?;
 
         // Have the WP sign our messages.
-        let signatures = key_factory
-            .sign_multiple_with_existing_keys(
-                messages
-                    .iter()
-                    .map(|msg| msg.clone().into_bytes())
-                    .zip(keys.iter().map(|key| vec![key]))
-                    .collect_vec(),
-            )
-            .await
-            .map_err(|err| JwtError::Signing(Box::new(err)))?;
+        let messages_and_keys = messages
+            .iter()
+            .map(|msg| msg.clone().into_bytes())
+            .zip(keys.iter().map(|key| vec![key]))
+            .collect_vec();
+        let mut signatures = Vec::with_capacity(messages_and_keys.len());
+        for batch in messages_and_keys.chunks(concurrency_limit.get()) {
+            let batch_signatures = key_factory
+                .sign_multiple_with_existing_keys(batch.to_vec())
+                .await
+                .map_err(|err| JwtError::Signing(Box::new(err)))?;
+            signatures.extend(batch_signatures);
+        }
 
         let jwts = signatures
             .into_iter()
@@ -303,6 +316,111 @@ where
     }
 }
 
+/// The name of the JWS header parameter indicating whether the payload is Base64url encoded, see
+/// [RFC 7797](https://www.rfc-editor.org/rfc/rfc7797#section-3).
+const B64_HEADER_PARAMETER: &str = "b64";
+
+/// Header of a JWS with an unencoded payload, as specified in [RFC 7797](https://www.rfc-editor.org/rfc/rfc7797).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UnencodedPayloadHeader {
+    alg: Algorithm,
+    b64: Option<bool>,
+    #[serde(default)]
+    crit: Vec<String>,
+}
+
+/// JWS with a detached and unencoded payload, as specified in [RFC 7797](https://www.rfc-editor.org/rfc/rfc7797).
+///
+/// Its compact serialization is of the form `header..signature`, i.e. the payload part is empty. The signature is
+/// computed over the Base64url encoded header, a `.` and the raw bytes of the payload. This means that the payload has
+/// to be transmitted separately, but also that it does not need to be Base64url encoded for transmission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedJws<T>(pub String, PhantomData<T>);
+impl<T, S: Into<String>> From<S> for DetachedJws<T> {
+    fn from(val: S) -> Self {
+        DetachedJws(val.into(), PhantomData)
+    }
+}
+
+impl<T> DetachedJws<T> {
+    fn signing_input(encoded_header: &str, payload: &[u8]) -> Vec<u8> {
+        [encoded_header.as_bytes(), b".", payload].concat()
+    }
+
+    /// Sign the `payload`, which should contain the JSON serialization of `T`, into a [`DetachedJws`].
+    pub async fn sign(payload: &[u8], privkey: &impl EcdsaKey) -> Result<DetachedJws<T>> {
+        let header = UnencodedPayloadHeader {
+            alg: Algorithm::ES256,
+            b64: Some(false),
+            crit: vec![B64_HEADER_PARAMETER.to_string()],
+        };
+        let encoded_header = BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?);
+
+        let signature = privkey
+            .try_sign(&Self::signing_input(&encoded_header, payload))
+            .await
+            .map_err(|err| JwtError::Signing(Box::new(err)))?;
+        let encoded_signature = BASE64_URL_SAFE_NO_PAD.encode(signature.to_vec());
+
+        Ok([encoded_header, String::new(), encoded_signature].join(".").into())
+    }
+}
+
+impl<T> DetachedJws<T>
+where
+    T: DeserializeOwned,
+{
+    /// Verify the JWS against the detached `payload`, and parse and return the payload.
+    pub fn parse_and_verify(&self, payload: &[u8], pubkey: &EcdsaDecodingKey) -> Result<T> {
+        let parts = self.0.split('.').collect_vec();
+        if parts.len() != 3 {
+            return Err(JwtError::UnexpectedNumberOfParts(parts.len()));
+        }
+        let (encoded_header, encoded_payload, encoded_signature) = (parts[0], parts[1], parts[2]);
+
+        if !encoded_payload.is_empty() {
+            return Err(JwtError::PayloadNotDetached);
+        }
+
+        let header: UnencodedPayloadHeader = serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(encoded_header)?)?;
+        if header.alg != Algorithm::ES256 {
+            return Err(JwtError::Validation(ErrorKind::InvalidAlgorithm.into()));
+        }
+
+        // The `b64` parameter has to be marked as critical, so that implementations that do not understand it
+        // will reject the JWS instead of interpreting the payload as being Base64url encoded.
+        if header.b64 != Some(false) || header.crit != [B64_HEADER_PARAMETER] {
+            return Err(JwtError::PayloadNotUnencoded);
+        }
+
+        let is_valid = jsonwebtoken::crypto::verify(
+            encoded_signature,
+            &Self::signing_input(encoded_header, payload),
+            &pubkey.0,
+            Algorithm::ES256,
+        )
+        .map_err(JwtError::Validation)?;
+        if !is_valid {
+            return Err(JwtError::Validation(ErrorKind::InvalidSignature.into()));
+        }
+
+        let claims = serde_json::from_slice(payload)?;
+
+        Ok(claims)
+    }
+}
+
+impl<T> Serialize for DetachedJws<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        String::serialize(&self.0, serializer)
+    }
+}
+impl<'de, T> Deserialize<'de> for DetachedJws<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(DetachedJws::from)
+    }
+}
+
 pub fn validations() -> Validation {
     let mut validation_options = Validation::new(Algorithm::ES256);
 
@@ -748,6 +866,64 @@ mod tests {
         serde_json::from_slice(&bts).unwrap()
     }
 
+    #[tokio::test]
+    async fn test_detached_jws_sign_and_verify() {
+        let private_key = SigningKey::random(&mut OsRng);
+        let t = ToyMessage::default();
+        let payload = serde_json::to_vec(&t).unwrap();
+
+        let jws = DetachedJws::<ToyMessage>::sign(&payload, &private_key).await.unwrap();
+
+        // the JWS does not contain the payload
+        assert!(jws.0.contains(".."));
+        let header: HashMap<String, serde_json::Value> = part(0, &jws.0);
+        assert_eq!(header.get("b64"), Some(&serde_json::Value::Bool(false)));
+
+        // the JWS can be verified against the payload and parsed back into an identical value
+        let parsed = jws
+            .parse_and_verify(&payload, &private_key.verifying_key().into())
+            .unwrap();
+        assert_eq!(t, parsed);
+
+        // verification fails against a different payload
+        let other_payload = serde_json::to_vec(&ToyMessage {
+            number: 43,
+            ..Default::default()
+        })
+        .unwrap();
+        let error = jws
+            .parse_and_verify(&other_payload, &private_key.verifying_key().into())
+            .unwrap_err();
+        assert_matches!(error, JwtError::Validation(_));
+
+        // verification fails against a different key
+        let other_key = SigningKey::random(&mut OsRng);
+        let error = jws
+            .parse_and_verify(&payload, &other_key.verifying_key().into())
+            .unwrap_err();
+        assert_matches!(error, JwtError::Validation(_));
+    }
+
+    #[tokio::test]
+    async fn test_detached_jws_rejects_attached_jwt() {
+        let private_key = SigningKey::random(&mut OsRng);
+        let t = ToyMessage::default();
+
+        // a normal JWT contains its payload, so it cannot be used as a detached JWS
+        let jwt = Jwt::sign(&t, &header(), &private_key).await.unwrap();
+        let error = DetachedJws::<ToyMessage>::from(jwt.0.clone())
+            .parse_and_verify(&serde_json::to_vec(&t).unwrap(), &private_key.verifying_key().into())
+            .unwrap_err();
+        assert_matches!(error, JwtError::PayloadNotDetached);
+
+        // removing the payload is not enough, as the header does not specify an unencoded payload
+        let parts = jwt.0.split('.').collect_vec();
+        let error = DetachedJws::<ToyMessage>::from(format!("{}..{}", parts[0], parts[2]))
+            .parse_and_verify(&serde_json::to_vec(&t).unwrap(), &private_key.verifying_key().into())
+            .unwrap_err();
+        assert_matches!(error, JwtError::PayloadNotUnencoded);
+    }
+
     #[test]
     fn jwk_p256_key_conversion() {
         let private_key = SigningKey::random(&mut OsRng);
@@ -783,7 +959,10 @@ mod tests {
             })
             .collect();
 
-        let jwts = Jwt::sign_bulk(keys_and_messages, key_factory).await.unwrap();
+        // Sign in batches of at most 3 keys, so that the last batch is smaller than the others.
+        let jwts = Jwt::sign_bulk(keys_and_messages, key_factory, NonZeroUsize::new(3).unwrap())
+            .await
+            .unwrap();
 
         // Verify JWTs. (futures::stream supports async for_each closures.)
         futures::stream::iter(jwts)
@@ -2,10 +2,12 @@ pub mod account;
 pub mod apple;
 pub mod built_info;
 pub mod config;
+pub mod format;
 pub mod generator;
 pub mod http_error;
 pub mod jwt;
 pub mod keys;
+pub mod redact;
 pub mod reqwest;
 pub mod spawn;
 pub mod trust_anchor;
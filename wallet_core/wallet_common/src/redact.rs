@@ -0,0 +1,46 @@
+use std::fmt;
+use std::fmt::Debug;
+
+/// The placeholder that is printed instead of a redacted value.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Wrapper type of which the [`Debug`] implementation prints a placeholder instead of the wrapped value. This should be
+/// used for personal data such as attribute values, so that these do not end up in logs. When the `log_sensitive`
+/// feature is enabled the wrapped value is printed instead, which should only ever be done during development.
+///
+/// Note that `DataElementValue` is a re-export of `ciborium::Value`, the [`Debug`] implementation of which cannot be
+/// changed. Attribute values are therefore redacted by the types containing them, such as `Entry`,
+/// `IssuerSignedItem` and `DocumentDisclosedAttributes` (and through those `ProposedAttributes` and
+/// `DisclosedAttributes`). Types that stay unredacted are a bare `DataElementValue` and the `DeviceSignedItems` map,
+/// which the wallet never populates.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Redacted<T>(pub T);
+
+impl<T> Debug for Redacted<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if cfg!(feature = "log_sensitive") {
+            self.0.fmt(f)
+        } else {
+            f.write_str(REDACTED)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_debug() {
+        let output = format!("{:?}", Redacted("Willeke Liselotte"));
+
+        if cfg!(feature = "log_sensitive") {
+            assert_eq!(output, "\"Willeke Liselotte\"");
+        } else {
+            assert_eq!(output, REDACTED);
+        }
+    }
+}
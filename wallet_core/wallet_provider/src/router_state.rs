@@ -78,7 +78,7 @@ impl<GC> RouterState<GC> {
 
         let account_server = AccountServer::new(
             settings.instruction_challenge_timeout,
-            "account_server".into(),
+            settings.certificate_issuer,
             (&certificate_signing_pubkey).into(),
             settings.pin_pubkey_encryption_key_identifier,
             settings.pin_public_disclosure_protection_key_identifier,
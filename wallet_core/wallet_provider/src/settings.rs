@@ -31,6 +31,7 @@ pub struct Settings {
     pub pin_public_disclosure_protection_key_identifier: String,
     pub wte_signing_key_identifier: String,
     pub wte_issuer_identifier: String,
+    pub certificate_issuer: String,
     pub database: Database,
     pub webserver: Webserver,
     pub tls_config: Option<TlsServerConfig>,
@@ -112,6 +113,7 @@ impl Settings {
             )?
             .set_default("wte_signing_key_identifier", "wte_signing_key")?
             .set_default("wte_issuer_identifier", "wte-issuer.example.com")?
+            .set_default("certificate_issuer", "account_server")?
             .set_default("webserver.ip", "0.0.0.0")?
             .set_default("webserver.port", 3000)?
             .set_default("pin_policy.rounds", 4)?
@@ -97,6 +97,7 @@ pub fn create_issuance_router<A, K, S, W>(
     wallet_client_ids: Vec<String>,
     wte_issuer_pubkey: VerifyingKey,
     wte_tracker: W,
+    accept_single_holder_keys: bool,
 ) -> anyhow::Result<Router>
 where
     A: AttributeService + Send + Sync + 'static,
@@ -114,6 +115,7 @@ where
             wallet_client_ids,
             wte_issuer_pubkey,
             wte_tracker,
+            accept_single_holder_keys,
         ),
     });
 
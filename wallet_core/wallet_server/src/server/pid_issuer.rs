@@ -26,6 +26,7 @@ where
         settings.issuer.wallet_client_ids,
         settings.issuer.wte_issuer_pubkey.0,
         wte_tracker,
+        settings.issuer.accept_single_holder_keys,
     )?;
 
     listen_wallet_only(
@@ -36,6 +36,7 @@ where
         settings.issuer.wallet_client_ids,
         settings.issuer.wte_issuer_pubkey.0,
         wte_tracker,
+        settings.issuer.accept_single_holder_keys,
     )?;
     let (wallet_disclosure_router, requester_router) = verifier::create_routers(
         settings.urls,
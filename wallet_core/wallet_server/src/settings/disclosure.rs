@@ -9,6 +9,7 @@ use serde::Deserialize;
 use serde_with::hex::Hex;
 use serde_with::serde_as;
 
+use openid4vc::openid4vp::VpResponseMode;
 use openid4vc::verifier::SessionTypeReturnUrl;
 use openid4vc::verifier::UseCase;
 use openid4vc::verifier::UseCases;
@@ -37,6 +38,13 @@ pub struct EhpemeralIdSecret(Vec<u8>);
 pub struct VerifierUseCase {
     #[serde(default)]
     pub session_type_return_url: SessionTypeReturnUrl,
+    /// The ages for which `age_over_NN` attributes should be derived from a disclosed `birth_date`.
+    #[serde(default)]
+    pub derive_age_over: Vec<u8>,
+    /// The response mode in which the Authorization Response is accepted, either `direct_post.jwt` (the default) or
+    /// `direct_post`.
+    #[serde(default)]
+    pub response_mode: VpResponseMode,
     #[serde(flatten)]
     pub key_pair: KeyPair,
 }
@@ -63,7 +71,11 @@ impl TryFrom<VerifierUseCase> for UseCase {
     type Error = anyhow::Error;
 
     fn try_from(value: VerifierUseCase) -> Result<Self, Self::Error> {
-        let use_case = UseCase::try_new(value.key_pair.try_into_mdoc_key_pair()?, value.session_type_return_url)?;
+        let use_case = UseCase {
+            derive_age_over: value.derive_age_over,
+            response_mode: value.response_mode,
+            ..UseCase::try_new(value.key_pair.try_into_mdoc_key_pair()?, value.session_type_return_url)?
+        };
 
         Ok(use_case)
     }
@@ -43,6 +43,11 @@ pub struct Issuer {
     pub valid_days: u64,
 
     pub copy_count: NonZeroU8,
+
+    /// Whether to accept wallets binding all copies of a credential to a single key. Note that this makes the copies
+    /// linkable to each other when disclosed, so this should only be enabled if this trade-off is accepted.
+    #[serde(default)]
+    pub accept_single_holder_keys: bool,
 }
 
 fn deserialize_type_metadata<'de, D>(deserializer: D) -> Result<Vec<TypeMetadata>, D::Error>
@@ -30,6 +30,7 @@ use openid4vc::openid4vp::WalletRequest;
 use openid4vc::return_url::ReturnUrlTemplate;
 use openid4vc::server_state::SessionStore;
 use openid4vc::server_state::SessionToken;
+use openid4vc::verifier::DisclosureConformance;
 use openid4vc::verifier::DisclosureData;
 use openid4vc::verifier::SessionType;
 use openid4vc::verifier::StatusResponse;
@@ -121,6 +122,10 @@ where
     let requester_router = Router::new()
         .route("/", post(start::<S>))
         .route("/{session_token}/disclosed_attributes", get(disclosed_attributes::<S>))
+        .route(
+            "/{session_token}/disclosure_conformance",
+            get(disclosure_conformance::<S>),
+        )
         .with_state(application_state);
 
     Ok((
@@ -284,3 +289,20 @@ where
 
     Ok(Json(disclosed_attributes))
 }
+
+async fn disclosure_conformance<S>(
+    State(state): State<Arc<ApplicationState<S>>>,
+    Path(session_token): Path<SessionToken>,
+    Query(params): Query<DisclosedAttributesParams>,
+) -> Result<Json<DisclosureConformance>, HttpJsonError<VerificationErrorCode>>
+where
+    S: SessionStore<DisclosureData>,
+{
+    let conformance = state
+        .verifier
+        .disclosure_conformance(&session_token, params.nonce)
+        .await
+        .inspect_err(|error| warn!("fetching disclosure conformance failed: {error}"))?;
+
+    Ok(Json(conformance))
+}
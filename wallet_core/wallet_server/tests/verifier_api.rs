@@ -50,11 +50,13 @@ use nl_wallet_mdoc::ItemsRequest;
 use openid4vc::disclosure_session::DisclosureSession;
 use openid4vc::disclosure_session::DisclosureUriSource;
 use openid4vc::disclosure_session::HttpVpMessageClient;
+use openid4vc::openid4vp::VpResponseMode;
 use openid4vc::server_state::MemorySessionStore;
 use openid4vc::server_state::SessionStore;
 use openid4vc::server_state::SessionStoreTimeouts;
 use openid4vc::server_state::SessionToken;
 use openid4vc::server_state::CLEANUP_INTERVAL_SECONDS;
+use openid4vc::verifier::DisclosureConformance;
 use openid4vc::verifier::DisclosureData;
 use openid4vc::verifier::SessionType;
 use openid4vc::verifier::SessionTypeReturnUrl;
@@ -136,6 +138,7 @@ fn fake_issuer_settings() -> Issuer {
         wte_issuer_pubkey: (*SigningKey::random(&mut OsRng).verifying_key()).into(),
         valid_days: 1,
         copy_count: NonZeroU8::new(1).unwrap(),
+        accept_single_holder_keys: false,
     }
 }
 
@@ -170,6 +173,8 @@ fn wallet_server_settings() -> (Settings, KeyPair<SigningKey>, TrustAnchor<'stat
         USECASE_NAME.to_string(),
         VerifierUseCase {
             session_type_return_url: SessionTypeReturnUrl::SameDevice,
+            derive_age_over: vec![],
+            response_mode: VpResponseMode::default(),
             key_pair: usecase_keypair.into(),
         },
     )])
@@ -633,11 +638,18 @@ async fn test_disclosure_cancel() {
     let response = client.get(disclosed_attributes_url).send().await.unwrap();
 
     let error_body = test_http_json_error_body(response, StatusCode::BAD_REQUEST, "session_state").await;
-    itertools::assert_equal(error_body.extra.keys(), ["session_status"]);
+    itertools::assert_equal(
+        error_body.extra.keys().sorted(),
+        ["session_status", "session_status_reason"],
+    );
     assert_eq!(
         error_body.extra.get("session_status").unwrap(),
         &serde_json::Value::from("CANCELLED")
     );
+    assert_eq!(
+        error_body.extra.get("session_status_reason").unwrap(),
+        &serde_json::Value::from("relying_party")
+    );
 }
 
 async fn test_disclosure_expired<S, F, Fut>(
@@ -689,9 +701,18 @@ async fn test_disclosure_expired<S, F, Fut>(
     );
 
     // Fetching the disclosed attributes should still return 400, since the session did not succeed.
+    // The response body should include that the session expired because of the session timeout.
     let response = client.get(disclosed_attributes_url.clone()).send().await.unwrap();
 
-    test_http_json_error_body(response, StatusCode::BAD_REQUEST, "session_state").await;
+    let error_body = test_http_json_error_body(response, StatusCode::BAD_REQUEST, "session_state").await;
+    assert_eq!(
+        error_body.extra.get("session_status").unwrap(),
+        &serde_json::Value::from("EXPIRED")
+    );
+    assert_eq!(
+        error_body.extra.get("session_status_reason").unwrap(),
+        &serde_json::Value::from("session_timeout")
+    );
 
     // Advance the clock again so that the expired session will be purged.
     *mock_time.write() = expiry_time + timeouts.failed_deletion + Duration::from_millis(1);
@@ -1005,6 +1026,27 @@ async fn test_disclosed_attributes_without_nonce() {
     check_example_disclosed_attributes(&disclosed_attributes);
 }
 
+#[tokio::test]
+async fn test_disclosure_conformance() {
+    let (client, session_token, internal_url, _) = perform_full_disclosure(SessionType::CrossDevice).await;
+
+    // Check if the disclosure conformance endpoint returns a 200 for the session.
+    let disclosure_conformance_url = internal_url.join(&format!(
+        "disclosure/sessions/{}/disclosure_conformance",
+        session_token.as_ref()
+    ));
+
+    let response = client.get(disclosure_conformance_url).send().await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The wallet should have disclosed exactly the requested attributes.
+    let conformance = response.json::<DisclosureConformance>().await.unwrap();
+
+    assert!(conformance.requested_but_missing.is_empty());
+    assert!(conformance.disclosed_but_unrequested.is_empty());
+}
+
 #[tokio::test]
 async fn test_disclosed_attributes_with_nonce() {
     let (client, session_token, internal_url, return_url) = perform_full_disclosure(SessionType::SameDevice).await;
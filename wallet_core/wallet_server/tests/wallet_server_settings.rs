@@ -6,6 +6,7 @@ use nl_wallet_mdoc::server_keys::generate::Ca;
 use nl_wallet_mdoc::server_keys::KeyPair;
 use nl_wallet_mdoc::utils::reader_auth::ReaderRegistration;
 use nl_wallet_mdoc::utils::x509::CertificateError;
+use openid4vc::openid4vp::VpResponseMode;
 use openid4vc::verifier::SessionTypeReturnUrl;
 use wallet_server::settings::CertificateVerificationError;
 use wallet_server::settings::Settings;
@@ -14,6 +15,8 @@ use wallet_server::settings::VerifierUseCase;
 fn to_use_case(key_pair: KeyPair) -> VerifierUseCase {
     VerifierUseCase {
         session_type_return_url: SessionTypeReturnUrl::Both,
+        derive_age_over: vec![],
+        response_mode: VpResponseMode::default(),
         key_pair: key_pair.into(),
     }
 }